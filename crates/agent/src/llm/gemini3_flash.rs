@@ -7,15 +7,6 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::str::FromStr;
 use std::time::Duration;
-use tokio::sync::OnceCell;
-
-static GEMINI_LLM: OnceCell<GeminiBackend> = OnceCell::const_new();
-
-pub async fn llm() -> &'static GeminiBackend {
-    GEMINI_LLM
-        .get_or_init(|| async { GeminiBackend::new() })
-        .await
-}
 
 #[derive(Debug, Deserialize)]
 struct GeminiResponse {
@@ -160,7 +151,7 @@ mod tests {
     // 这里仅演示如何调用。
     #[tokio::test]
     async fn test_gemini_backend_implementation() {
-        let backend = llm().await;
+        let backend = GeminiBackend::new();
 
         let system = "你是一个精通Rust的助手，回答要简洁。";
         let user = "如何声明一个不可变变量？";
@@ -6,15 +6,6 @@ use reqwest::header;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::time::Duration;
-use tokio::sync::OnceCell;
-
-static QWEN_LLM: OnceCell<QwenBackend> = OnceCell::const_new();
-
-pub async fn llm() -> &'static QwenBackend {
-    QWEN_LLM
-        .get_or_init(|| async { QwenBackend::new() })
-        .await
-}
 
 // ==========================================
 // 1. 定义响应结构体 (OpenAI 兼容格式)
@@ -67,10 +58,7 @@ impl QwenBackend {
         // 标记为敏感信息，日志中不打印
         auth_header_val.set_sensitive(true);
 
-        headers.insert(
-            header::AUTHORIZATION,
-            auth_header_val,
-        );
+        headers.insert(header::AUTHORIZATION, auth_header_val);
         headers.insert(
             header::CONTENT_TYPE,
             header::HeaderValue::from_static("application/json"),
@@ -156,7 +144,7 @@ mod tests {
     #[tokio::test]
     async fn test_qwen_backend_implementation() {
         // 确保你的 .env 文件中有 DASHSCOPE_API_KEY
-        let backend = llm().await;
+        let backend = QwenBackend::new();
 
         let system = "你是一个助手。";
         let user = "你好，请介绍一下你自己。";
@@ -172,4 +160,4 @@ mod tests {
             }
         }
     }
-}
\ No newline at end of file
+}
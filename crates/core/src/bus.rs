@@ -0,0 +1,478 @@
+use crate::event::{Envelope, Event, Topic};
+use crate::time::Clock;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::pin::pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use tokio::sync::Notify;
+
+// =========================================================================
+// 1. 订阅配置
+// =========================================================================
+
+/// 订阅者队列写满时的处理策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BackpressurePolicy {
+    /// 丢弃最新事件 (适合行情这类“只关心最新”的消费者)
+    DropNewest,
+    /// 丢弃队列中最旧的事件，为新事件腾出位置
+    #[default]
+    DropOldest,
+    /// 阻塞发布者直到队列有空位 (适合订单/成交等不可丢失的事件)
+    Block,
+}
+
+/// 主题过滤器
+#[derive(Debug, Clone, Default)]
+pub enum TopicFilter {
+    #[default]
+    All,
+    Only(HashSet<Topic>),
+}
+
+impl TopicFilter {
+    pub fn topics(topics: impl IntoIterator<Item = Topic>) -> Self {
+        TopicFilter::Only(topics.into_iter().collect())
+    }
+
+    pub fn matches(&self, topic: Topic) -> bool {
+        match self {
+            TopicFilter::All => true,
+            TopicFilter::Only(set) => set.contains(&topic),
+        }
+    }
+}
+
+/// 订阅参数
+#[derive(Debug, Clone)]
+pub struct SubscribeOptions {
+    /// 订阅者名称 (用于指标和日志)
+    pub name: String,
+    pub filter: TopicFilter,
+    /// 队列容量
+    pub capacity: usize,
+    pub policy: BackpressurePolicy,
+}
+
+impl SubscribeOptions {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            filter: TopicFilter::All,
+            capacity: 1024,
+            policy: BackpressurePolicy::default(),
+        }
+    }
+
+    pub fn topics(mut self, topics: impl IntoIterator<Item = Topic>) -> Self {
+        self.filter = TopicFilter::topics(topics);
+        self
+    }
+
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    pub fn policy(mut self, policy: BackpressurePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
+
+// =========================================================================
+// 2. 订阅者队列
+// =========================================================================
+
+/// 有界队列 + 两个 Notify，实现三种背压策略
+struct SubscriberQueue {
+    name: String,
+    filter: TopicFilter,
+    policy: BackpressurePolicy,
+    capacity: usize,
+    buf: Mutex<VecDeque<Arc<Envelope>>>,
+    not_empty: Notify,
+    not_full: Notify,
+    closed: AtomicBool,
+    delivered: AtomicU64,
+    dropped: AtomicU64,
+    max_depth: AtomicU64,
+}
+
+impl SubscriberQueue {
+    fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+    }
+
+    fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        self.not_empty.notify_waiters();
+        self.not_full.notify_waiters();
+    }
+
+    /// 非阻塞入队；队列已满时返回 Err(envelope)
+    fn try_push(&self, envelope: Arc<Envelope>) -> std::result::Result<(), Arc<Envelope>> {
+        let mut buf = self.buf.lock().unwrap();
+        if buf.len() >= self.capacity {
+            return Err(envelope);
+        }
+        buf.push_back(envelope);
+        self.max_depth
+            .fetch_max(buf.len() as u64, Ordering::Relaxed);
+        drop(buf);
+        self.delivered.fetch_add(1, Ordering::Relaxed);
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    async fn push(&self, envelope: Arc<Envelope>) {
+        match self.policy {
+            BackpressurePolicy::DropNewest => {
+                if self.try_push(envelope).is_err() {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            BackpressurePolicy::DropOldest => {
+                let mut buf = self.buf.lock().unwrap();
+                if buf.len() >= self.capacity {
+                    buf.pop_front();
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+                buf.push_back(envelope);
+                self.max_depth
+                    .fetch_max(buf.len() as u64, Ordering::Relaxed);
+                drop(buf);
+                self.delivered.fetch_add(1, Ordering::Relaxed);
+                self.not_empty.notify_one();
+            }
+            BackpressurePolicy::Block => {
+                let mut pending = envelope;
+                loop {
+                    // 同 recv：先 enable 再检查，避免错过 close() 的 notify_waiters
+                    let mut notified = pin!(self.not_full.notified());
+                    notified.as_mut().enable();
+                    if self.is_closed() {
+                        return;
+                    }
+                    match self.try_push(pending) {
+                        Ok(()) => return,
+                        Err(back) => pending = back,
+                    }
+                    // notify_one 在没有等待者时会保存一个许可，消费者出队后的通知不会丢失
+                    notified.await;
+                }
+            }
+        }
+    }
+
+    fn try_pop(&self) -> Option<Arc<Envelope>> {
+        let item = self.buf.lock().unwrap().pop_front();
+        if item.is_some() {
+            self.not_full.notify_one();
+        }
+        item
+    }
+
+    fn stats(&self) -> SubscriberStats {
+        let buf = self.buf.lock().unwrap();
+        let lag_ms = buf
+            .front()
+            .map(|oldest| (Clock::now_ms() - oldest.timestamp_ms).max(0))
+            .unwrap_or(0);
+        SubscriberStats {
+            name: self.name.clone(),
+            policy: self.policy,
+            capacity: self.capacity,
+            depth: buf.len(),
+            max_depth: self.max_depth.load(Ordering::Relaxed) as usize,
+            delivered: self.delivered.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            lag_ms,
+        }
+    }
+}
+
+/// 订阅者运行指标
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscriberStats {
+    pub name: String,
+    pub policy: BackpressurePolicy,
+    pub capacity: usize,
+    /// 当前积压事件数
+    pub depth: usize,
+    /// 历史最高积压
+    pub max_depth: usize,
+    /// 已入队事件数
+    pub delivered: u64,
+    /// 因背压被丢弃的事件数
+    pub dropped: u64,
+    /// 积压延迟：队首 (最旧) 事件距今的毫秒数
+    pub lag_ms: i64,
+}
+
+/// 订阅句柄
+///
+/// Drop 时自动退订，总线在下一次发布时清理该队列。
+pub struct Subscriber {
+    queue: Arc<SubscriberQueue>,
+}
+
+impl Subscriber {
+    pub fn name(&self) -> &str {
+        &self.queue.name
+    }
+
+    /// 等待下一条事件；总线关闭且队列耗尽时返回 None
+    pub async fn recv(&self) -> Option<Arc<Envelope>> {
+        loop {
+            // 先登记等待再检查状态：close() 的 notify_waiters 不保存许可，
+            // 检查之后才创建 Notified 会错过唤醒而永久挂起
+            let mut notified = pin!(self.queue.not_empty.notified());
+            notified.as_mut().enable();
+            if let Some(envelope) = self.queue.try_pop() {
+                return Some(envelope);
+            }
+            if self.queue.is_closed() {
+                return None;
+            }
+            notified.await;
+        }
+    }
+
+    /// 非阻塞获取
+    pub fn try_recv(&self) -> Option<Arc<Envelope>> {
+        self.queue.try_pop()
+    }
+
+    pub fn stats(&self) -> SubscriberStats {
+        self.queue.stats()
+    }
+}
+
+impl Drop for Subscriber {
+    fn drop(&mut self) {
+        self.queue.close();
+    }
+}
+
+// =========================================================================
+// 3. 录制 (Recording Sink)
+// =========================================================================
+
+/// 事件旁路输出 (录制 / 桥接)
+///
+/// 每条发布的事件都会先交给所有 Sink，再分发给订阅者。
+pub trait EventSink: Send + Sync {
+    fn record(&self, envelope: &Envelope) -> Result<()>;
+
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// JSON Lines 录制器：每行一条 `Envelope`，用于确定性回放
+pub struct JsonlRecorder {
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl JsonlRecorder {
+    /// 以追加模式打开录制文件 (不存在则创建)
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open event record file: {:?}", path))?;
+        Ok(Self {
+            writer: Mutex::new(BufWriter::new(file)),
+        })
+    }
+}
+
+impl EventSink for JsonlRecorder {
+    fn record(&self, envelope: &Envelope) -> Result<()> {
+        let mut writer = self.writer.lock().unwrap();
+        serde_json::to_writer(&mut *writer, envelope)?;
+        writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.writer.lock().unwrap().flush()?;
+        Ok(())
+    }
+}
+
+/// 读取录制文件，按 `seq` 排序返回
+pub fn load_recording(path: impl AsRef<Path>) -> Result<Vec<Envelope>> {
+    let path = path.as_ref();
+    let file = File::open(path)
+        .with_context(|| format!("Failed to open event record file: {:?}", path))?;
+    let mut envelopes = Vec::new();
+    for (line_no, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let envelope: Envelope = serde_json::from_str(&line)
+            .with_context(|| format!("Invalid event record at line {}", line_no + 1))?;
+        envelopes.push(envelope);
+    }
+    envelopes.sort_by_key(|e| e.seq);
+    Ok(envelopes)
+}
+
+// =========================================================================
+// 4. 事件总线
+// =========================================================================
+
+struct BusInner {
+    seq: AtomicU64,
+    subscribers: RwLock<Vec<Arc<SubscriberQueue>>>,
+    sinks: RwLock<Vec<Arc<dyn EventSink>>>,
+    closed: AtomicBool,
+}
+
+/// 进程内类型化事件总线
+///
+/// * 按主题过滤：每个订阅者只接收自己关心的主题
+/// * 独立背压：每个订阅者有自己的有界队列和背压策略，慢消费者不会拖垮其他消费者 (Block 策略除外)
+/// * 可观测：`stats()` 返回每个订阅者的积压、丢弃和延迟
+/// * 可录制：挂载 `JsonlRecorder` 后可用 `replay` 确定性回放
+#[derive(Clone)]
+pub struct EventBus {
+    inner: Arc<BusInner>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(BusInner {
+                seq: AtomicU64::new(0),
+                subscribers: RwLock::new(Vec::new()),
+                sinks: RwLock::new(Vec::new()),
+                closed: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    /// 挂载旁路输出 (录制器 / 跨进程桥接)
+    pub fn add_sink(&self, sink: Arc<dyn EventSink>) {
+        self.inner.sinks.write().unwrap().push(sink);
+    }
+
+    /// 注册订阅者
+    pub fn subscribe(&self, options: SubscribeOptions) -> Subscriber {
+        let queue = Arc::new(SubscriberQueue {
+            name: options.name,
+            filter: options.filter,
+            policy: options.policy,
+            capacity: options.capacity.max(1),
+            buf: Mutex::new(VecDeque::with_capacity(options.capacity.min(4096))),
+            not_empty: Notify::new(),
+            not_full: Notify::new(),
+            closed: AtomicBool::new(self.inner.closed.load(Ordering::Acquire)),
+            delivered: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+            max_depth: AtomicU64::new(0),
+        });
+        self.inner.subscribers.write().unwrap().push(queue.clone());
+        Subscriber { queue }
+    }
+
+    /// 发布事件，返回分配的序号
+    pub async fn publish(&self, source: impl Into<String>, event: Event) -> u64 {
+        let seq = self.inner.seq.fetch_add(1, Ordering::SeqCst) + 1;
+        self.dispatch(Envelope::new(seq, source, event)).await;
+        seq
+    }
+
+    /// 原样发布一个已有的信封 (回放 / 跨进程桥接使用，保留原始序号与时间戳)
+    pub async fn publish_envelope(&self, envelope: Envelope) {
+        self.inner.seq.fetch_max(envelope.seq, Ordering::SeqCst);
+        self.dispatch(envelope).await;
+    }
+
+    async fn dispatch(&self, envelope: Envelope) {
+        if self.inner.closed.load(Ordering::Acquire) {
+            return;
+        }
+
+        let sinks: Vec<Arc<dyn EventSink>> = self.inner.sinks.read().unwrap().clone();
+        for sink in &sinks {
+            // 录制失败不应影响交易主流程，这里只能吞掉错误；调用方可通过 flush() 暴露 IO 问题
+            let _ = sink.record(&envelope);
+        }
+
+        let topic = envelope.topic();
+        let targets: Vec<Arc<SubscriberQueue>> = {
+            let mut subscribers = self.inner.subscribers.write().unwrap();
+            subscribers.retain(|q| !q.is_closed());
+            subscribers
+                .iter()
+                .filter(|q| q.filter.matches(topic))
+                .cloned()
+                .collect()
+        };
+
+        let envelope = Arc::new(envelope);
+        for queue in targets {
+            queue.push(envelope.clone()).await;
+        }
+    }
+
+    /// 当前所有订阅者的运行指标
+    pub fn stats(&self) -> Vec<SubscriberStats> {
+        self.inner
+            .subscribers
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|q| !q.is_closed())
+            .map(|q| q.stats())
+            .collect()
+    }
+
+    /// 刷新所有 Sink
+    pub fn flush(&self) -> Result<()> {
+        for sink in self.inner.sinks.read().unwrap().iter() {
+            sink.flush()?;
+        }
+        Ok(())
+    }
+
+    /// 关闭总线：不再接受新事件，订阅者消费完积压后 `recv()` 返回 None
+    pub fn close(&self) -> Result<()> {
+        self.inner.closed.store(true, Ordering::Release);
+        for queue in self.inner.subscribers.read().unwrap().iter() {
+            queue.close();
+        }
+        self.flush()
+    }
+
+    /// 确定性回放：按序号重放录制的事件，并把全局时钟拨到事件发生时间
+    pub async fn replay(&self, envelopes: Vec<Envelope>) -> usize {
+        let count = envelopes.len();
+        for envelope in envelopes {
+            Clock::set_mock_time(envelope.timestamp_ms);
+            self.publish_envelope(envelope).await;
+        }
+        count
+    }
+}
@@ -0,0 +1,180 @@
+use crate::enums::Exchange;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::collections::BTreeSet;
+
+// =========================================================================
+// 交易日历
+// =========================================================================
+
+/// NYSE / Nasdaq 临时休市日 (不在常规节假日规则内)
+const US_EQUITY_SPECIAL_CLOSURES: &[(i32, u32, u32)] = &[
+    (2001, 9, 11), // 9·11
+    (2001, 9, 12),
+    (2001, 9, 13),
+    (2001, 9, 14),
+    (2004, 6, 11),  // 里根国葬
+    (2007, 1, 2),   // 福特国葬
+    (2012, 10, 29), // 飓风桑迪
+    (2012, 10, 30),
+    (2018, 12, 5), // 老布什国葬
+    (2025, 1, 9),  // 卡特国葬
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CalendarKind {
+    /// 7x24 (加密货币)
+    AlwaysOpen,
+    /// 美股: 周末 + NYSE 节假日休市
+    UsEquity,
+}
+
+/// 交易日历：判断某天是否开市，用于缺失交易日检测和回测
+#[derive(Debug, Clone)]
+pub struct TradingCalendar {
+    kind: CalendarKind,
+    /// 额外休市日 (规则之外的临时休市)
+    extra_holidays: BTreeSet<NaiveDate>,
+}
+
+impl TradingCalendar {
+    pub fn always_open() -> Self {
+        Self {
+            kind: CalendarKind::AlwaysOpen,
+            extra_holidays: BTreeSet::new(),
+        }
+    }
+
+    pub fn us_equity() -> Self {
+        Self {
+            kind: CalendarKind::UsEquity,
+            extra_holidays: US_EQUITY_SPECIAL_CLOSURES
+                .iter()
+                .filter_map(|(y, m, d)| NaiveDate::from_ymd_opt(*y, *m, *d))
+                .collect(),
+        }
+    }
+
+    /// 按交易所选择默认日历
+    pub fn for_exchange(exchange: Exchange) -> Self {
+        match exchange {
+            Exchange::Nasdaq | Exchange::Nyse => Self::us_equity(),
+            Exchange::Binance | Exchange::Okx | Exchange::Bybit | Exchange::Coinbase => {
+                Self::always_open()
+            }
+        }
+    }
+
+    /// 追加临时休市日
+    pub fn with_holidays(mut self, days: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.extra_holidays.extend(days);
+        self
+    }
+
+    pub fn is_trading_day(&self, date: NaiveDate) -> bool {
+        if self.extra_holidays.contains(&date) {
+            return false;
+        }
+        match self.kind {
+            CalendarKind::AlwaysOpen => true,
+            CalendarKind::UsEquity => {
+                !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+                    && !us_equity_holidays(date.year()).contains(&date)
+            }
+        }
+    }
+
+    /// [start, end] 之间的全部交易日
+    pub fn trading_days(&self, start: NaiveDate, end: NaiveDate) -> Vec<NaiveDate> {
+        start
+            .iter_days()
+            .take_while(|d| *d <= end)
+            .filter(|d| self.is_trading_day(*d))
+            .collect()
+    }
+
+    /// 严格晚于 `date` 的下一个交易日
+    pub fn next_trading_day(&self, date: NaiveDate) -> NaiveDate {
+        let mut next = date + Duration::days(1);
+        while !self.is_trading_day(next) {
+            next += Duration::days(1);
+        }
+        next
+    }
+}
+
+/// NYSE 常规节假日 (按规则推算，含周末顺延)
+///
+/// 元旦落在周六时不在前一年 12-31 补休 (NYSE 规则)。
+pub fn us_equity_holidays(year: i32) -> Vec<NaiveDate> {
+    let ymd = |m, d| NaiveDate::from_ymd_opt(year, m, d).expect("valid date");
+    let mut days = Vec::with_capacity(10);
+
+    let new_year = ymd(1, 1);
+    match new_year.weekday() {
+        Weekday::Sat => {}
+        Weekday::Sun => days.push(new_year + Duration::days(1)),
+        _ => days.push(new_year),
+    }
+    if year >= 1998 {
+        days.push(nth_weekday(year, 1, Weekday::Mon, 3)); // 马丁·路德·金纪念日
+    }
+    days.push(nth_weekday(year, 2, Weekday::Mon, 3)); // 总统日
+    days.push(easter_sunday(year) - Duration::days(2)); // 耶稣受难日
+    days.push(last_weekday(year, 5, Weekday::Mon)); // 阵亡将士纪念日
+    if year >= 2022 {
+        days.push(observed(ymd(6, 19))); // 六月节
+    }
+    days.push(observed(ymd(7, 4))); // 独立日
+    days.push(nth_weekday(year, 9, Weekday::Mon, 1)); // 劳动节
+    days.push(nth_weekday(year, 11, Weekday::Thu, 4)); // 感恩节
+    days.push(observed(ymd(12, 25))); // 圣诞节
+    days
+}
+
+/// 周六提前到周五，周日顺延到周一
+fn observed(date: NaiveDate) -> NaiveDate {
+    match date.weekday() {
+        Weekday::Sat => date - Duration::days(1),
+        Weekday::Sun => date + Duration::days(1),
+        _ => date,
+    }
+}
+
+/// 某月第 n 个星期几
+fn nth_weekday(year: i32, month: u32, weekday: Weekday, n: u8) -> NaiveDate {
+    NaiveDate::from_weekday_of_month_opt(year, month, weekday, n).expect("valid weekday of month")
+}
+
+/// 某月最后一个星期几
+fn last_weekday(year: i32, month: u32, weekday: Weekday) -> NaiveDate {
+    let first_next = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)
+    }
+    .expect("valid date");
+    let mut date = first_next - Duration::days(1);
+    while date.weekday() != weekday {
+        date -= Duration::days(1);
+    }
+    date
+}
+
+/// 复活节 (格里高利历，匿名算法)
+fn easter_sunday(year: i32) -> NaiveDate {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32).expect("valid easter date")
+}
@@ -96,6 +96,60 @@ pub enum StrategyStatus {
     Error,
 }
 
+/// 交易信号生命周期
+///
+/// Generated -> Submitted -> PartiallyExecuted -> Executed；
+/// 风控 / 定量拒绝 (含订单发出后被运行时风控拦截) 进入 RiskRejected，超过 TTL 仍未完全成交进入 Expired。
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, Display, EnumString,
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum SignalStatus {
+    #[default]
+    Generated,
+    RiskRejected,
+    Submitted,
+    PartiallyExecuted,
+    Executed,
+    Expired,
+}
+
+/// 策略状态变更的发起方 (审计用)
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, Display, EnumString,
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum StatusActor {
+    /// 人工操作 (CLI / 管理界面)
+    User,
+    /// 风控触发 (熔断 / 超限)
+    Risk,
+    /// Agent 决策
+    Agent,
+    /// 引擎自身 (启动 / 停机 / 异常)
+    #[default]
+    System,
+}
+
+impl OrderStatus {
+    /// 订单是否仍挂在交易所 (可撤)
+    pub fn is_open(&self) -> bool {
+        matches!(
+            self,
+            OrderStatus::Created
+                | OrderStatus::Pending
+                | OrderStatus::New
+                | OrderStatus::PartiallyFilled
+        )
+    }
+    /// 订单是否已进入终态
+    pub fn is_terminal(&self) -> bool {
+        !self.is_open()
+    }
+}
+
 impl StrategyStatus {
     pub fn is_active(&self) -> bool {
         matches!(self, StrategyStatus::Running | StrategyStatus::Initializing)
@@ -106,6 +160,47 @@ impl StrategyStatus {
     pub fn is_finished(&self) -> bool {
         matches!(self, StrategyStatus::Stopped | StrategyStatus::Error)
     }
+    /// 生命周期是否允许 `self -> next`
+    ///
+    /// Created -> Initializing -> Running <-> Paused -> Stopping -> Stopped，
+    /// 已停止 / 出错的策略必须重新经过 Initializing 才能运行；任何活动状态都可能进入 Error。
+    pub fn can_transition_to(&self, next: StrategyStatus) -> bool {
+        use StrategyStatus::*;
+        matches!(
+            (*self, next),
+            (Created, Initializing | Stopped | Error)
+                | (Initializing, Running | Paused | Stopping | Stopped | Error)
+                | (Running, Paused | Stopping | Stopped | Error)
+                | (Paused, Running | Stopping | Stopped | Error)
+                | (Stopping, Stopped | Error)
+                | (Stopped, Initializing)
+                | (Error, Initializing | Stopped)
+        )
+    }
+}
+
+impl SignalStatus {
+    /// 信号是否已结束 (不会再产生订单或成交)
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            SignalStatus::RiskRejected | SignalStatus::Executed | SignalStatus::Expired
+        )
+    }
+
+    /// 生命周期是否允许 `self -> next` (部分成交可以继续累计成交)
+    pub fn can_transition_to(&self, next: SignalStatus) -> bool {
+        use SignalStatus::*;
+        matches!(
+            (*self, next),
+            (Generated, RiskRejected | Submitted | Expired)
+                | (
+                    Submitted,
+                    RiskRejected | PartiallyExecuted | Executed | Expired
+                )
+                | (PartiallyExecuted, PartiallyExecuted | Executed | Expired)
+        )
+    }
 }
 
 #[derive(
@@ -123,6 +218,20 @@ pub enum BarPeriod {
     D1,
 }
 
+impl BarPeriod {
+    /// 单根 K 线覆盖的时长
+    pub fn duration(&self) -> chrono::Duration {
+        match self {
+            BarPeriod::M1 => chrono::Duration::minutes(1),
+            BarPeriod::M5 => chrono::Duration::minutes(5),
+            BarPeriod::M15 => chrono::Duration::minutes(15),
+            BarPeriod::H1 => chrono::Duration::hours(1),
+            BarPeriod::H4 => chrono::Duration::hours(4),
+            BarPeriod::D1 => chrono::Duration::days(1),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display, EnumString)]
 #[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
 pub enum Exchange {
@@ -134,6 +243,19 @@ pub enum Exchange {
     Nyse,
 }
 
+/// 公司行动类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display, EnumString)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum CorporateActionType {
+    /// 拆股 / 合股
+    Split,
+    /// 现金分红
+    CashDividend,
+    /// 代码变更
+    SymbolChange,
+}
+
 // =========================================================================
 // 统一应用宏
 // =========================================================================
@@ -142,5 +264,8 @@ impl_mysql_string_type!(Side);
 impl_mysql_string_type!(OrderType);
 impl_mysql_string_type!(OrderStatus);
 impl_mysql_string_type!(StrategyStatus);
+impl_mysql_string_type!(StatusActor);
+impl_mysql_string_type!(SignalStatus);
 impl_mysql_string_type!(BarPeriod);
 impl_mysql_string_type!(Exchange);
+impl_mysql_string_type!(CorporateActionType);
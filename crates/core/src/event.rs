@@ -0,0 +1,169 @@
+use crate::market::MarketBar;
+use crate::oms::{Fill, Order};
+use crate::strategy::Signal;
+use crate::time::Clock;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use strum_macros::{Display, EnumString};
+
+// =========================================================================
+// Topic (事件主题)
+// =========================================================================
+
+/// 事件主题，订阅者按主题过滤自己关心的事件
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum Topic {
+    MarketData,
+    Order,
+    Fill,
+    Signal,
+    Risk,
+    Agent,
+    Timer,
+}
+
+impl Topic {
+    pub const ALL: [Topic; 7] = [
+        Topic::MarketData,
+        Topic::Order,
+        Topic::Fill,
+        Topic::Signal,
+        Topic::Risk,
+        Topic::Agent,
+        Topic::Timer,
+    ];
+}
+
+// =========================================================================
+// Event (事件负载)
+// =========================================================================
+
+/// 风控事件级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum RiskLevel {
+    Info,
+    Warning,
+    /// 触发拦截 (信号/订单被拒绝)
+    Reject,
+    /// 触发熔断 (策略暂停/强平)
+    Halt,
+}
+
+/// 风控事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskEvent {
+    pub level: RiskLevel,
+    /// 触发的规则名称 (e.g. "max_position")
+    pub rule: String,
+    pub strategy_uuid: Option<String>,
+    /// 被拦截的订单 UUID (订单级规则触发时填写，OMS 据此把订单标记为 Rejected)
+    #[serde(default)]
+    pub order_uuid: Option<String>,
+    pub message: String,
+}
+
+/// 撤单请求 (发布在 `Topic::Order` 上，由 OMS 撤销仍在挂单中的订单)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelRequest {
+    pub order_uuid: String,
+    pub reason: String,
+}
+
+/// Agent 决策事件 (慢路径产出，交由风控/执行消费)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentDecision {
+    /// 产生决策的 Agent 名称
+    pub agent: String,
+    /// 链路追踪 ID (对应 `AgentContext.trace_id`)
+    pub trace_id: String,
+    /// 决策内容 (结构化 JSON)
+    pub decision: Value,
+}
+
+/// 定时器事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimerEvent {
+    pub name: String,
+    pub fired_at: DateTime<Utc>,
+}
+
+/// 进程内事件
+///
+/// 所有子系统 (Feed / Strategy / Risk / Execution / Agent) 之间只通过事件通信。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "payload", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Event {
+    MarketData(MarketBar),
+    Order(Order),
+    CancelOrder(CancelRequest),
+    Fill(Fill),
+    Signal(Signal),
+    Risk(RiskEvent),
+    AgentDecision(AgentDecision),
+    Timer(TimerEvent),
+}
+
+impl Event {
+    /// 事件所属主题
+    pub fn topic(&self) -> Topic {
+        match self {
+            Event::MarketData(_) => Topic::MarketData,
+            Event::Order(_) | Event::CancelOrder(_) => Topic::Order,
+            Event::Fill(_) => Topic::Fill,
+            Event::Signal(_) => Topic::Signal,
+            Event::Risk(_) => Topic::Risk,
+            Event::AgentDecision(_) => Topic::Agent,
+            Event::Timer(_) => Topic::Timer,
+        }
+    }
+}
+
+// =========================================================================
+// Envelope (事件信封)
+// =========================================================================
+
+/// 事件信封：在事件外包装序号、时间戳和来源
+///
+/// `seq` 由总线分配，在单个总线实例内严格递增，回放时按 `seq` 顺序重放即可复现事件顺序。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Envelope {
+    pub seq: u64,
+    /// 发布时间 (毫秒)，取自 `Clock`，回测模式下为模拟时间
+    pub timestamp_ms: i64,
+    /// 发布者 (e.g. "feed", "strategy:grid_v1")
+    pub source: String,
+    pub event: Event,
+}
+
+impl Envelope {
+    pub fn new(seq: u64, source: impl Into<String>, event: Event) -> Self {
+        Self {
+            seq,
+            timestamp_ms: Clock::now_ms(),
+            source: source.into(),
+            event,
+        }
+    }
+
+    pub fn topic(&self) -> Topic {
+        self.event.topic()
+    }
+}
@@ -1,5 +1,8 @@
 pub mod account;
+pub mod bus;
+pub mod calendar;
 pub mod enums;
+pub mod event;
 pub mod market;
 pub mod oms;
 pub mod primitive;
@@ -9,7 +12,10 @@ pub mod validate;
 
 // 导出让外部使用
 pub use account::*;
+pub use bus::*;
+pub use calendar::*;
 pub use enums::*;
+pub use event::*;
 pub use oms::*;
 pub use primitive::*;
 pub use strategy::*;
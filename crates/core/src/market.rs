@@ -1,4 +1,4 @@
-use crate::enums::{BarPeriod, Exchange};
+use crate::enums::{BarPeriod, CorporateActionType, Exchange, Side};
 use crate::primitive::{CurrencyPair, Price, Quantity};
 use chrono::{DateTime, NaiveDate, Utc}; // 引入 NaiveDate 处理数据库的 DATE 类型
 use rust_decimal::Decimal;
@@ -98,3 +98,201 @@ impl MarketBar {
         })
     }
 }
+
+/// 日内 K 线 (Intraday Bar)
+///
+/// 字段与 [`MarketBar`] 一致，但起止时间精确到毫秒。
+/// 对应时序库 (TimescaleDB) 表: `market_bar`，主要存 M1，高周期由连续聚合生成。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntradayBar {
+    pub exchange: Exchange,
+    pub symbol: CurrencyPair,
+    pub bar_period: BarPeriod,
+    /// 交易条件类型 (默认 21 盘中交易)
+    pub trade_type: u8,
+    pub open: Price,
+    pub high: Price,
+    pub low: Price,
+    pub close: Price,
+    pub volume: Quantity,
+    pub amount: Option<Decimal>,
+    /// K 线开始时间 (含)
+    pub start_time: DateTime<Utc>,
+    /// K 线结束时间 (不含)
+    pub end_time: DateTime<Utc>,
+}
+
+impl IntradayBar {
+    /// 按日期粒度转换为 [`MarketBar`] (日线及以上周期使用)
+    pub fn to_market_bar(&self) -> MarketBar {
+        MarketBar {
+            id: 0,
+            exchange: self.exchange,
+            symbol: self.symbol.clone(),
+            bar_period: self.bar_period,
+            trade_type: self.trade_type,
+            open: self.open,
+            high: self.high,
+            low: self.low,
+            close: self.close,
+            volume: self.volume,
+            amount: self.amount,
+            start_time: self.start_time.date_naive(),
+            end_time: self.end_time.date_naive(),
+            gmt_create: Utc::now(),
+        }
+    }
+}
+
+impl From<&MarketBar> for IntradayBar {
+    /// 日期粒度的 K 线按当天 00:00 UTC 对齐
+    fn from(bar: &MarketBar) -> Self {
+        let midnight = |d: NaiveDate| d.and_time(chrono::NaiveTime::MIN).and_utc();
+        Self {
+            exchange: bar.exchange,
+            symbol: bar.symbol.clone(),
+            bar_period: bar.bar_period,
+            trade_type: bar.trade_type,
+            open: bar.open,
+            high: bar.high,
+            low: bar.low,
+            close: bar.close,
+            volume: bar.volume,
+            amount: bar.amount,
+            start_time: midnight(bar.start_time),
+            end_time: midnight(bar.end_time),
+        }
+    }
+}
+
+/// 盘口快照 (Tick)
+///
+/// 对应时序库表: `market_tick`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketTick {
+    pub exchange: Exchange,
+    pub symbol: CurrencyPair,
+    /// 交易所时间戳
+    pub time: DateTime<Utc>,
+    pub bid_price: Price,
+    pub bid_size: Quantity,
+    pub ask_price: Price,
+    pub ask_size: Quantity,
+    /// 最新成交价 (部分交易所的快照不带)
+    pub last_price: Option<Price>,
+}
+
+/// 逐笔成交 (Trade Print)
+///
+/// 对应时序库表: `market_trade`，(exchange, symbol, trade_id, time) 唯一，重复写入会被忽略。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketTrade {
+    pub exchange: Exchange,
+    pub symbol: CurrencyPair,
+    /// 交易所成交 ID
+    pub trade_id: String,
+    pub time: DateTime<Utc>,
+    pub price: Price,
+    pub quantity: Quantity,
+    /// 主动方 (taker side)，未知时为 None
+    pub side: Option<Side>,
+}
+
+/// 公司行动实体 (Corporate Action)
+///
+/// 对应数据库表: `corporate_action`。原始 K 线不做修改，复权价格由公司行动在读取时计算。
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CorporateAction {
+    #[sqlx(rename = "id")]
+    pub id: i64,
+
+    pub exchange: Exchange,
+
+    /// 行动发生时的标的代码 (代码变更时为旧代码)
+    pub symbol: CurrencyPair,
+
+    #[sqlx(rename = "action_type")]
+    pub action_type: CorporateActionType,
+
+    /// 除权除息日 / 生效日：当天及之后的价格已反映该行动
+    pub ex_date: NaiveDate,
+
+    /// 拆股比例：每 1 股旧股变为 ratio 股 (4:1 拆股为 4，1:10 合股为 0.1)
+    pub ratio: Option<Decimal>,
+
+    /// 每股现金分红 (与价格同币种)
+    pub cash_amount: Option<Decimal>,
+
+    /// 代码变更后的新代码
+    pub new_symbol: Option<CurrencyPair>,
+
+    /// 入库时间
+    pub gmt_create: DateTime<Utc>,
+}
+
+impl CorporateAction {
+    fn base(
+        exchange: Exchange,
+        symbol: &str,
+        action_type: CorporateActionType,
+        ex_date: NaiveDate,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            id: 0,
+            exchange,
+            symbol: CurrencyPair::from_str(symbol)?,
+            action_type,
+            ex_date,
+            ratio: None,
+            cash_amount: None,
+            new_symbol: None,
+            gmt_create: Utc::now(),
+        })
+    }
+
+    /// 拆股 / 合股
+    pub fn split(
+        exchange: Exchange,
+        symbol: &str,
+        ex_date: NaiveDate,
+        ratio: Decimal,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            ratio > Decimal::ZERO,
+            "Split ratio must be positive: {}",
+            ratio
+        );
+        let mut action = Self::base(exchange, symbol, CorporateActionType::Split, ex_date)?;
+        action.ratio = Some(ratio);
+        Ok(action)
+    }
+
+    /// 现金分红
+    pub fn cash_dividend(
+        exchange: Exchange,
+        symbol: &str,
+        ex_date: NaiveDate,
+        amount: Decimal,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            amount > Decimal::ZERO,
+            "Dividend must be positive: {}",
+            amount
+        );
+        let mut action = Self::base(exchange, symbol, CorporateActionType::CashDividend, ex_date)?;
+        action.cash_amount = Some(amount);
+        Ok(action)
+    }
+
+    /// 代码变更: `symbol` 从 `ex_date` 起改为 `new_symbol`
+    pub fn symbol_change(
+        exchange: Exchange,
+        symbol: &str,
+        ex_date: NaiveDate,
+        new_symbol: &str,
+    ) -> anyhow::Result<Self> {
+        let mut action = Self::base(exchange, symbol, CorporateActionType::SymbolChange, ex_date)?;
+        action.new_symbol = Some(CurrencyPair::from_str(new_symbol)?);
+        Ok(action)
+    }
+}
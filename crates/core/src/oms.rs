@@ -162,3 +162,96 @@ impl Order {
         }
     }
 }
+
+/// 成交回报实体 (Fill / Trade Execution)
+///
+/// 对应数据库表: `fill`
+///
+/// 一张订单可能被拆成多笔成交（部分成交）。`Order` 只记录累计成交量和均价，
+/// 而 `Fill` 记录每一笔真实成交的价格、数量、手续费和时间，是 TCA（交易成本分析）的原始数据。
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Fill {
+    /// 数据库物理主键 (自增 ID)
+    #[sqlx(rename = "id")]
+    #[serde(skip)]
+    pub id: i64,
+
+    /// 成交业务唯一标识 (UUID)
+    #[sqlx(rename = "fill_uuid")]
+    pub uuid: String,
+
+    /// 所属订单 UUID (对应 `Order.uuid`)
+    pub order_uuid: String,
+
+    /// 归属策略 UUID (冗余字段，方便按策略聚合)
+    pub strategy_uuid: Option<String>,
+
+    /// 交易所返回的成交 ID
+    pub exchange_trade_id: Option<String>,
+
+    /// 交易所 (执行场所 / Venue)
+    pub exchange: Exchange,
+
+    /// 交易标的
+    pub symbol: CurrencyPair,
+
+    /// 买卖方向
+    pub side: Side,
+
+    /// 成交价格
+    pub price: Price,
+
+    /// 成交数量
+    pub quantity: Quantity,
+
+    /// 本笔成交手续费 (按计价币种折算)
+    pub fee: Decimal,
+
+    /// 手续费币种 (e.g. "USDT", "BNB")
+    pub fee_currency: Option<String>,
+
+    /// 是否为 Maker 成交
+    pub is_maker: bool,
+
+    /// 交易所撮合时间
+    pub trade_time: DateTime<Utc>,
+
+    /// 入库时间
+    pub gmt_create: DateTime<Utc>,
+}
+
+impl Fill {
+    /// 根据订单创建一笔成交记录
+    ///
+    /// 方向、标的、交易所和策略归属均从订单继承，保证成交与订单一致。
+    pub fn new(
+        order: &Order,
+        price: Price,
+        quantity: Quantity,
+        fee: Decimal,
+        trade_time: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: 0,
+            uuid: Uuid::new_v4().to_string(),
+            order_uuid: order.uuid.clone(),
+            strategy_uuid: order.strategy_uuid.clone(),
+            exchange_trade_id: None,
+            exchange: order.exchange,
+            symbol: order.symbol.clone(),
+            side: order.side,
+            price,
+            quantity,
+            fee,
+            fee_currency: None,
+            is_maker: false,
+            trade_time,
+            gmt_create: Utc::now(),
+        }
+    }
+
+    /// 成交名义金额 (price * quantity)
+    pub fn notional(&self) -> Decimal {
+        self.price * self.quantity
+    }
+}
@@ -1,7 +1,8 @@
 use crate::enums::Side;
 use crate::enums::StrategyStatus; // 引入 StrategyStatus
+use crate::enums::{SignalStatus, StatusActor};
 use crate::primitive::{CurrencyPair, Price, Quantity};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sqlx::FromRow;
@@ -59,10 +60,36 @@ pub struct StrategyState {
     /// 策略运行时动态数据 (JSON)
     pub state_data: Value,
 
+    /// 状态版本号：同一策略每次保存递增 (历史见 `strategy_state_version`)
+    #[sqlx(default)]
+    #[serde(default)]
+    pub version: i64,
+
+    /// `state_data` 的结构版本，由策略代码定义，加载时据此升级旧状态
+    #[sqlx(default)]
+    #[serde(default = "default_schema_version")]
+    pub schema_version: i32,
+
     pub gmt_create: DateTime<Utc>,
     pub gmt_modified: DateTime<Utc>,
 }
 
+fn default_schema_version() -> i32 {
+    1
+}
+
+/// 策略状态结构升级钩子
+///
+/// 策略修改状态 JSON 结构时递增 `current_version`，并在 `upgrade` 中把
+/// 旧版本逐级转换为新版本 (v1 -> v2 -> ... -> current)。
+pub trait StateMigration: Send + Sync {
+    /// 当前代码使用的结构版本
+    fn current_version(&self) -> i32;
+
+    /// 把 `from_version` 版本的状态升级到 `from_version + 1`
+    fn upgrade(&self, from_version: i32, state: Value) -> anyhow::Result<Value>;
+}
+
 /// 策略生成的交易信号 (Signal)
 ///
 /// 对应数据库表: `signals`
@@ -90,16 +117,82 @@ pub struct Signal {
     /// 建议成交价格 (限价)
     pub price: Option<Price>,
 
-    /// 建议成交数量
+    /// 建议成交数量 (为空时由定量模块决定)，提交后为实际委托数量
     pub quantity: Option<Quantity>,
 
     /// 信号触发原因
     pub reason: String,
 
+    /// 生命周期状态
+    #[sqlx(default)]
+    #[serde(default)]
+    pub status: SignalStatus,
+
+    /// 被拒绝 / 过期的原因
+    #[sqlx(default)]
+    #[serde(default)]
+    pub status_reason: Option<String>,
+
+    /// 过期时间 (None 表示不过期)，到期仍未完全成交的信号进入 Expired
+    #[sqlx(default)]
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+
+    /// 由该信号产生的订单 (`Order.uuid`)
+    #[sqlx(default, json)]
+    #[serde(default)]
+    pub order_uuids: Vec<String>,
+
+    /// 累计成交数量
+    #[sqlx(default)]
+    #[serde(default)]
+    pub filled_quantity: Quantity,
+
+    /// 提交订单的时间
+    #[sqlx(default)]
+    #[serde(default)]
+    pub submitted_at: Option<DateTime<Utc>>,
+
+    /// 第一笔成交时间 (信号 -> 成交延迟 = first_fill_at - gmt_create)
+    #[sqlx(default)]
+    #[serde(default)]
+    pub first_fill_at: Option<DateTime<Utc>>,
+
+    /// 完全成交时间
+    #[sqlx(default)]
+    #[serde(default)]
+    pub executed_at: Option<DateTime<Utc>>,
+
     pub gmt_create: DateTime<Utc>,
     pub gmt_modified: DateTime<Utc>,
 }
 
+/// 策略状态变更审计记录
+///
+/// 对应数据库表: `strategy_status_audit`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct StrategyStatusChange {
+    #[sqlx(rename = "id")]
+    pub id: i64,
+
+    /// 关联的策略业务 UUID
+    pub strategy_uuid: String,
+
+    /// 变更前状态
+    pub from_status: StrategyStatus,
+
+    /// 变更后状态
+    pub to_status: StrategyStatus,
+
+    /// 变更原因 (同时写入 `Strategy.reason`)
+    pub reason: String,
+
+    /// 发起方
+    pub actor: StatusActor,
+
+    pub gmt_create: DateTime<Utc>,
+}
+
 // =========================================================================
 // 实现部分 (impl)
 // =========================================================================
@@ -158,6 +251,14 @@ impl Signal {
             price: Some(price),
             quantity: Some(quantity),
             reason: reason.into(),
+            status: SignalStatus::Generated,
+            status_reason: None,
+            expires_at: None,
+            order_uuids: Vec::new(),
+            filled_quantity: Quantity::ZERO,
+            submitted_at: None,
+            first_fill_at: None,
+            executed_at: None,
             gmt_create: now,
             gmt_modified: now,
         }
@@ -184,10 +285,100 @@ impl Signal {
             price: None,
             quantity: Some(quantity),
             reason: reason.into(),
+            status: SignalStatus::Generated,
+            status_reason: None,
+            expires_at: None,
+            order_uuids: Vec::new(),
+            filled_quantity: Quantity::ZERO,
+            submitted_at: None,
+            first_fill_at: None,
+            executed_at: None,
             gmt_create: now,
             gmt_modified: now,
         }
     }
+
+    /// 设置存活时间 (从信号产生时刻算起)
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.expires_at = Some(self.gmt_create + ttl);
+        self
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|t| now >= t)
+    }
+
+    /// 尚未成交的数量 (未定量时为 None)
+    pub fn remaining_quantity(&self) -> Option<Quantity> {
+        self.quantity.map(|q| q - self.filled_quantity)
+    }
+
+    /// 信号 -> 首笔成交延迟
+    pub fn fill_latency(&self) -> Option<Duration> {
+        self.first_fill_at.map(|t| t - self.gmt_create)
+    }
+
+    fn transition(&mut self, next: SignalStatus, now: DateTime<Utc>) -> anyhow::Result<()> {
+        if !self.status.can_transition_to(next) {
+            anyhow::bail!(
+                "Invalid status transition for signal {}: {} -> {}",
+                self.uuid,
+                self.status,
+                next
+            );
+        }
+        self.status = next;
+        self.gmt_modified = now;
+        Ok(())
+    }
+
+    /// 风控 / 定量拒绝
+    pub fn reject(&mut self, reason: impl Into<String>) -> anyhow::Result<()> {
+        self.transition(SignalStatus::RiskRejected, Utc::now())?;
+        self.status_reason = Some(reason.into());
+        Ok(())
+    }
+
+    /// 已生成订单：记录订单号，`quantity` 改为实际委托数量 (成交进度以此为准)
+    pub fn submit(
+        &mut self,
+        order_uuid: impl Into<String>,
+        quantity: Quantity,
+    ) -> anyhow::Result<()> {
+        let now = Utc::now();
+        self.transition(SignalStatus::Submitted, now)?;
+        self.order_uuids.push(order_uuid.into());
+        self.quantity = Some(quantity);
+        self.submitted_at = Some(now);
+        Ok(())
+    }
+
+    /// 累计一笔成交，返回是否已完全成交
+    pub fn record_fill(
+        &mut self,
+        quantity: Quantity,
+        trade_time: DateTime<Utc>,
+    ) -> anyhow::Result<bool> {
+        self.filled_quantity += quantity;
+        self.first_fill_at.get_or_insert(trade_time);
+        let done = self
+            .remaining_quantity()
+            .is_some_and(|q| q <= Quantity::ZERO);
+        if done {
+            self.transition(SignalStatus::Executed, Utc::now())?;
+            self.executed_at = Some(trade_time);
+        } else {
+            self.transition(SignalStatus::PartiallyExecuted, Utc::now())?;
+        }
+        Ok(done)
+    }
+
+    /// 超过 TTL 仍未完全成交 (已成交部分保留在 `filled_quantity`)
+    pub fn expire(&mut self, now: DateTime<Utc>) -> anyhow::Result<()> {
+        self.transition(SignalStatus::Expired, now)?;
+        self.status_reason = Some(format!("expired with {} filled", self.filled_quantity));
+        Ok(())
+    }
 }
 
 impl StrategyState {
@@ -197,8 +388,38 @@ impl StrategyState {
             id: 0,
             uuid: strategy_uuid,
             state_data,
+            version: 0,
+            schema_version: default_schema_version(),
             gmt_create: now,
             gmt_modified: now,
         }
     }
+
+    /// 用迁移钩子把状态升级到当前结构版本
+    ///
+    /// 状态版本比代码新 (例如代码回滚) 时报错，避免旧代码误读新结构。
+    pub fn migrate(mut self, migration: &dyn StateMigration) -> anyhow::Result<Self> {
+        let target = migration.current_version();
+        if self.schema_version > target {
+            anyhow::bail!(
+                "State v{} of strategy {} has schema {} newer than supported {}",
+                self.version,
+                self.uuid,
+                self.schema_version,
+                target
+            );
+        }
+        while self.schema_version < target {
+            self.state_data = migration
+                .upgrade(self.schema_version, self.state_data)
+                .map_err(|e| {
+                    e.context(format!(
+                        "Failed to upgrade state of strategy {} from schema {}",
+                        self.uuid, self.schema_version
+                    ))
+                })?;
+            self.schema_version += 1;
+        }
+        Ok(self)
+    }
 }
@@ -0,0 +1,196 @@
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use chrono::Utc;
+    use quant_core::bus::{
+        load_recording, BackpressurePolicy, EventBus, JsonlRecorder, SubscribeOptions,
+    };
+    use quant_core::event::{Event, RiskEvent, RiskLevel, TimerEvent, Topic};
+    use quant_core::time::Clock;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    fn timer(name: &str) -> Event {
+        Event::Timer(TimerEvent {
+            name: name.to_string(),
+            fired_at: Utc::now(),
+        })
+    }
+
+    fn risk(message: &str) -> Event {
+        Event::Risk(RiskEvent {
+            level: RiskLevel::Warning,
+            rule: "test_rule".to_string(),
+            strategy_uuid: None,
+            order_uuid: None,
+            message: message.to_string(),
+        })
+    }
+
+    // =========================================================================
+    // 1. 主题过滤
+    // =========================================================================
+    #[tokio::test]
+    async fn test_topic_filtering() {
+        let bus = EventBus::new();
+        let timers = bus.subscribe(SubscribeOptions::new("timers").topics([Topic::Timer]));
+        let everything = bus.subscribe(SubscribeOptions::new("all"));
+
+        bus.publish("test", timer("t1")).await;
+        bus.publish("test", risk("r1")).await;
+
+        let first = timers
+            .try_recv()
+            .expect("timer subscriber should receive timer");
+        assert_eq!(first.topic(), Topic::Timer);
+        assert!(
+            timers.try_recv().is_none(),
+            "risk event must be filtered out"
+        );
+
+        assert_eq!(everything.stats().depth, 2);
+        assert_eq!(everything.try_recv().unwrap().seq, 1);
+        assert_eq!(everything.try_recv().unwrap().seq, 2);
+    }
+
+    // =========================================================================
+    // 2. 背压策略
+    // =========================================================================
+    #[tokio::test]
+    async fn test_drop_newest_and_drop_oldest() {
+        let bus = EventBus::new();
+        let newest = bus.subscribe(
+            SubscribeOptions::new("drop_newest")
+                .capacity(2)
+                .policy(BackpressurePolicy::DropNewest),
+        );
+        let oldest = bus.subscribe(
+            SubscribeOptions::new("drop_oldest")
+                .capacity(2)
+                .policy(BackpressurePolicy::DropOldest),
+        );
+
+        for i in 0..5 {
+            bus.publish("test", timer(&format!("t{}", i))).await;
+        }
+
+        // DropNewest 保留最早的两条 (seq 1, 2)
+        assert_eq!(newest.stats().dropped, 3);
+        assert_eq!(newest.try_recv().unwrap().seq, 1);
+        assert_eq!(newest.try_recv().unwrap().seq, 2);
+
+        // DropOldest 保留最新的两条 (seq 4, 5)
+        let stats = oldest.stats();
+        assert_eq!(stats.dropped, 3);
+        assert_eq!(stats.max_depth, 2);
+        assert_eq!(oldest.try_recv().unwrap().seq, 4);
+        assert_eq!(oldest.try_recv().unwrap().seq, 5);
+    }
+
+    #[tokio::test]
+    async fn test_block_policy_waits_for_consumer() {
+        let bus = EventBus::new();
+        let slow = bus.subscribe(
+            SubscribeOptions::new("slow")
+                .capacity(1)
+                .policy(BackpressurePolicy::Block),
+        );
+
+        bus.publish("test", timer("t1")).await;
+
+        // 队列已满，第二次发布应当阻塞
+        let blocked =
+            tokio::time::timeout(Duration::from_millis(50), bus.publish("test", timer("t2"))).await;
+        assert!(blocked.is_err(), "publish should block while queue is full");
+
+        let publisher = {
+            let bus = bus.clone();
+            tokio::spawn(async move { bus.publish("test", timer("t3")).await })
+        };
+        assert_eq!(slow.recv().await.unwrap().seq, 1);
+        let seq = tokio::time::timeout(Duration::from_secs(1), publisher)
+            .await
+            .expect("publisher should be released after consumer drains")
+            .unwrap();
+        assert_eq!(slow.recv().await.unwrap().seq, seq);
+        assert_eq!(slow.stats().dropped, 0);
+    }
+
+    // =========================================================================
+    // 3. 退订与关闭
+    // =========================================================================
+    #[tokio::test]
+    async fn test_unsubscribe_and_close() {
+        let bus = EventBus::new();
+        let keep = bus.subscribe(SubscribeOptions::new("keep"));
+        {
+            let _temp = bus.subscribe(SubscribeOptions::new("temp"));
+            assert_eq!(bus.stats().len(), 2);
+        }
+        bus.publish("test", timer("t1")).await;
+        assert_eq!(bus.stats().len(), 1, "dropped subscriber should be pruned");
+
+        bus.close().unwrap();
+        bus.publish("test", timer("after_close")).await;
+
+        // 关闭前的积压仍可消费，之后返回 None
+        assert!(keep.recv().await.is_some());
+        assert!(keep.recv().await.is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_close_wakes_waiting_receiver() {
+        // 反复让 close 与 recv 竞争，recv 必须总能返回 None 而不是挂起
+        for _ in 0..200 {
+            let bus = EventBus::new();
+            let sub = bus.subscribe(SubscribeOptions::new("waiter"));
+            let waiter = tokio::spawn(async move { sub.recv().await.is_none() });
+            tokio::task::yield_now().await;
+            bus.close().unwrap();
+            let closed = tokio::time::timeout(Duration::from_secs(1), waiter)
+                .await
+                .expect("recv should be woken by close")
+                .unwrap();
+            assert!(closed);
+        }
+    }
+
+    // =========================================================================
+    // 4. 录制与确定性回放
+    // =========================================================================
+    #[tokio::test]
+    async fn test_record_and_replay() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("photon_events_{}.jsonl", Uuid::new_v4()));
+
+        let bus = EventBus::new();
+        bus.add_sink(Arc::new(JsonlRecorder::open(&path)?));
+        bus.publish("feed", timer("t1")).await;
+        bus.publish("risk", risk("r1")).await;
+        bus.publish("feed", timer("t2")).await;
+        bus.flush()?;
+
+        let recorded = load_recording(&path)?;
+        assert_eq!(recorded.len(), 3);
+        assert_eq!(recorded[1].source, "risk");
+
+        let replay_bus = EventBus::new();
+        let sub = replay_bus.subscribe(SubscribeOptions::new("replay"));
+        let count = replay_bus.replay(recorded.clone()).await;
+        Clock::reset();
+        assert_eq!(count, 3);
+
+        for original in &recorded {
+            let replayed = sub.try_recv().unwrap();
+            assert_eq!(replayed.seq, original.seq);
+            assert_eq!(replayed.timestamp_ms, original.timestamp_ms);
+            assert_eq!(replayed.topic(), original.topic());
+        }
+
+        // 回放后继续发布，序号应接续录制的最大序号
+        assert_eq!(replay_bus.publish("test", timer("t4")).await, 4);
+
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+}
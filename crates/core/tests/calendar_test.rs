@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use quant_core::calendar::{us_equity_holidays, TradingCalendar};
+    use quant_core::enums::Exchange;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_us_equity_holidays_2024() {
+        let holidays = us_equity_holidays(2024);
+        let expected = [
+            date(2024, 1, 1),
+            date(2024, 1, 15),
+            date(2024, 2, 19),
+            date(2024, 3, 29), // 耶稣受难日
+            date(2024, 5, 27),
+            date(2024, 6, 19),
+            date(2024, 7, 4),
+            date(2024, 9, 2),
+            date(2024, 11, 28),
+            date(2024, 12, 25),
+        ];
+        assert_eq!(holidays, expected);
+    }
+
+    #[test]
+    fn test_observed_rules() {
+        // 2022-01-01 周六: 不补休
+        assert!(!us_equity_holidays(2022).contains(&date(2021, 12, 31)));
+        // 2022-12-25 周日 -> 12-26 休市
+        assert!(us_equity_holidays(2022).contains(&date(2022, 12, 26)));
+        // 2021-07-04 周日 -> 07-05 休市
+        assert!(us_equity_holidays(2021).contains(&date(2021, 7, 5)));
+        // 六月节 2022 年起
+        assert!(!us_equity_holidays(2021).contains(&date(2021, 6, 18)));
+    }
+
+    #[test]
+    fn test_trading_days() {
+        let us = TradingCalendar::for_exchange(Exchange::Nyse);
+        // 2024-12-23 .. 2024-12-29: 周一到周五，圣诞休市
+        let days = us.trading_days(date(2024, 12, 23), date(2024, 12, 29));
+        assert_eq!(
+            days,
+            vec![
+                date(2024, 12, 23),
+                date(2024, 12, 24),
+                date(2024, 12, 26),
+                date(2024, 12, 27)
+            ]
+        );
+        assert_eq!(us.next_trading_day(date(2024, 12, 24)), date(2024, 12, 26));
+        assert!(!us.is_trading_day(date(2012, 10, 29))); // 飓风桑迪
+
+        let crypto = TradingCalendar::for_exchange(Exchange::Binance);
+        assert_eq!(
+            crypto
+                .trading_days(date(2024, 12, 23), date(2024, 12, 29))
+                .len(),
+            7
+        );
+        let custom = crypto.with_holidays([date(2024, 12, 25)]);
+        assert!(!custom.is_trading_day(date(2024, 12, 25)));
+    }
+}
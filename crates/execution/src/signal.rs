@@ -0,0 +1,394 @@
+//! 信号 -> 订单管道 (Signal Pipeline)
+//!
+//! `SignalProcessor` 把策略 / Agent 产生的 `Signal` 定量并转换为 `Order`，再根据成交回报推进
+//! 信号生命周期，所有状态变化都写回 `signal` 表：
+//!
+//! * **Generated** -> 定量 / 事前检查不通过 -> **RiskRejected**
+//! * **Submitted** -> 订单被运行时风控拦截 -> **RiskRejected**
+//! * **Generated** -> 生成订单 -> **Submitted** -> 成交 -> **PartiallyExecuted** -> **Executed**
+//! * 超过 TTL 仍未完全成交 -> **Expired** (剩余订单由调用方撤销)
+//!
+//! `SignalStats` 基于信号记录按策略统计命中率和信号 -> 成交延迟。
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use quant_core::enums::{Exchange, SignalStatus};
+use quant_core::oms::{Fill, Order};
+use quant_core::primitive::Quantity;
+use quant_core::strategy::Signal;
+use quant_storage::store::StrategyStore;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+// =========================================================================
+// 1. 扩展点：定量与事前检查
+// =========================================================================
+
+/// 定量：决定一个信号实际交易多少
+pub trait OrderSizer: Send + Sync {
+    /// 返回 None 或非正数表示不交易，返回错误 (如缺少行情) 同样拒绝信号
+    fn size(&self, signal: &Signal) -> Result<Option<Quantity>>;
+}
+
+/// 默认定量：直接使用信号自带的数量
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SignalQuantity;
+
+impl OrderSizer for SignalQuantity {
+    fn size(&self, signal: &Signal) -> Result<Option<Quantity>> {
+        Ok(signal.quantity)
+    }
+}
+
+/// 事前检查：订单生成后、提交前调用
+pub trait PreTradeCheck: Send + Sync {
+    /// 规则名称 (写入拒绝原因)
+    fn name(&self) -> &str;
+
+    /// 通过返回 Ok，拒绝返回原因
+    fn check(&self, signal: &Signal, order: &Order) -> std::result::Result<(), String>;
+}
+
+// =========================================================================
+// 2. 处理器
+// =========================================================================
+
+/// 单个信号的处理结果
+#[derive(Debug, Clone)]
+pub enum SignalOutcome {
+    /// 已生成订单，调用方负责发送
+    Submitted(Box<Order>),
+    /// 定量或事前检查拒绝
+    Rejected(String),
+    /// 处理前已过期
+    Expired,
+}
+
+/// 跟踪中的信号 (已提交、等待成交)
+#[derive(Default)]
+struct Tracked {
+    signals: HashMap<String, Signal>,
+    /// 订单 UUID -> 信号 UUID
+    by_order: HashMap<String, String>,
+}
+
+impl Tracked {
+    fn insert(&mut self, signal: Signal) {
+        for order_uuid in &signal.order_uuids {
+            self.by_order
+                .insert(order_uuid.clone(), signal.uuid.clone());
+        }
+        self.signals.insert(signal.uuid.clone(), signal);
+    }
+
+    fn remove(&mut self, signal: &Signal) {
+        for order_uuid in &signal.order_uuids {
+            self.by_order.remove(order_uuid);
+        }
+        self.signals.remove(&signal.uuid);
+    }
+}
+
+/// 信号处理器
+pub struct SignalProcessor {
+    store: Arc<dyn StrategyStore>,
+    /// 未配置路由的策略下单到该交易所
+    default_exchange: Exchange,
+    /// 策略 UUID -> 交易所
+    routes: HashMap<String, Exchange>,
+    /// 信号未设置 `expires_at` 时使用的 TTL (None 表示不过期)
+    default_ttl: Option<Duration>,
+    sizer: Box<dyn OrderSizer>,
+    checks: Vec<Box<dyn PreTradeCheck>>,
+    tracked: Mutex<Tracked>,
+}
+
+impl SignalProcessor {
+    pub fn new(store: Arc<dyn StrategyStore>, default_exchange: Exchange) -> Self {
+        Self {
+            store,
+            default_exchange,
+            routes: HashMap::new(),
+            default_ttl: None,
+            sizer: Box::new(SignalQuantity),
+            checks: Vec::new(),
+            tracked: Mutex::new(Tracked::default()),
+        }
+    }
+
+    /// 指定某个策略的下单交易所
+    pub fn with_route(mut self, strategy_uuid: impl Into<String>, exchange: Exchange) -> Self {
+        self.routes.insert(strategy_uuid.into(), exchange);
+        self
+    }
+
+    pub fn with_default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = Some(ttl);
+        self
+    }
+
+    pub fn with_sizer(mut self, sizer: impl OrderSizer + 'static) -> Self {
+        self.sizer = Box::new(sizer);
+        self
+    }
+
+    pub fn with_check(mut self, check: impl PreTradeCheck + 'static) -> Self {
+        self.checks.push(Box::new(check));
+        self
+    }
+
+    /// 正在等待成交的信号数
+    pub fn tracked(&self) -> usize {
+        self.tracked.lock().unwrap().signals.len()
+    }
+
+    /// 重启后恢复：已提交的信号继续跟踪成交，返回尚未处理的 (Generated) 信号
+    pub async fn recover(&self) -> Result<Vec<Signal>> {
+        let open = self.store.find_open_signals().await?;
+        let mut pending = Vec::new();
+        let mut tracked = self.tracked.lock().unwrap();
+        for signal in open {
+            if signal.status == SignalStatus::Generated {
+                pending.push(signal);
+            } else {
+                tracked.insert(signal);
+            }
+        }
+        Ok(pending)
+    }
+
+    /// 处理一个新信号：落库 -> 过期检查 -> 定量 -> 生成订单 -> 事前检查 -> 提交
+    pub async fn process(&self, mut signal: Signal) -> Result<SignalOutcome> {
+        if signal.expires_at.is_none() {
+            if let Some(ttl) = self.default_ttl {
+                signal = signal.with_ttl(ttl);
+            }
+        }
+        // 策略可能已自行落库 (save_signal)，这里只补录
+        if self
+            .store
+            .find_signal(Uuid::from_str(&signal.uuid)?)
+            .await?
+            .is_none()
+        {
+            self.store.save_signal(&signal).await?;
+        }
+
+        if signal.is_expired(Utc::now()) {
+            signal.expire(Utc::now())?;
+            self.store.update_signal(&signal).await?;
+            return Ok(SignalOutcome::Expired);
+        }
+
+        let quantity = match self.sizer.size(&signal) {
+            Ok(Some(q)) if q > Quantity::ZERO => q,
+            Ok(_) => return self.reject(signal, "sizer returned no quantity").await,
+            Err(e) => return self.reject(signal, format!("sizer: {}", e)).await,
+        };
+        let order = self.build_order(&signal, quantity);
+        for check in &self.checks {
+            if let Err(reason) = check.check(&signal, &order) {
+                return self
+                    .reject(signal, format!("{}: {}", check.name(), reason))
+                    .await;
+            }
+        }
+
+        signal.submit(order.uuid.clone(), quantity)?;
+        self.store.update_signal(&signal).await?;
+        self.tracked.lock().unwrap().insert(signal);
+        Ok(SignalOutcome::Submitted(Box::new(order)))
+    }
+
+    /// 成交回报：推进对应信号的成交进度，返回更新后的信号 (非本管道产生的订单返回 None)
+    pub async fn on_fill(&self, fill: &Fill) -> Result<Option<Signal>> {
+        let signal = {
+            let mut tracked = self.tracked.lock().unwrap();
+            let Some(signal_uuid) = tracked.by_order.get(&fill.order_uuid).cloned() else {
+                return Ok(None);
+            };
+            let Some(signal) = tracked.signals.get_mut(&signal_uuid) else {
+                return Ok(None);
+            };
+            signal.record_fill(fill.quantity, fill.trade_time)?;
+            let signal = signal.clone();
+            if signal.status.is_terminal() {
+                tracked.remove(&signal);
+            }
+            signal
+        };
+        self.store.update_signal(&signal).await?;
+        Ok(Some(signal))
+    }
+
+    /// 订单被运行时风控拦截：把未成交的父信号置为 RiskRejected (非本管道产生的订单返回 None)
+    pub async fn on_order_rejected(
+        &self,
+        order_uuid: &str,
+        reason: impl Into<String>,
+    ) -> Result<Option<Signal>> {
+        let signal = {
+            let mut tracked = self.tracked.lock().unwrap();
+            let Some(signal_uuid) = tracked.by_order.get(order_uuid).cloned() else {
+                return Ok(None);
+            };
+            let Some(mut signal) = tracked.signals.get(&signal_uuid).cloned() else {
+                return Ok(None);
+            };
+            signal.reject(reason)?;
+            tracked.remove(&signal);
+            signal
+        };
+        self.store.update_signal(&signal).await?;
+        Ok(Some(signal))
+    }
+
+    /// 把到期的跟踪信号置为 Expired，返回这些信号 (调用方撤销其未完结订单)
+    pub async fn expire_due(&self, now: DateTime<Utc>) -> Result<Vec<Signal>> {
+        let expired: Vec<Signal> = {
+            let mut tracked = self.tracked.lock().unwrap();
+            let due: Vec<Signal> = tracked
+                .signals
+                .values()
+                .filter(|s| s.is_expired(now))
+                .cloned()
+                .collect();
+            for signal in &due {
+                tracked.remove(signal);
+            }
+            due
+        };
+        let mut result = Vec::with_capacity(expired.len());
+        for mut signal in expired {
+            signal.expire(now)?;
+            self.store.update_signal(&signal).await?;
+            result.push(signal);
+        }
+        Ok(result)
+    }
+
+    async fn reject(&self, mut signal: Signal, reason: impl Into<String>) -> Result<SignalOutcome> {
+        let reason = reason.into();
+        signal.reject(reason.clone())?;
+        self.store.update_signal(&signal).await?;
+        Ok(SignalOutcome::Rejected(reason))
+    }
+
+    /// 有价格的信号生成限价单，否则生成市价单
+    fn build_order(&self, signal: &Signal, quantity: Quantity) -> Order {
+        let exchange = self
+            .routes
+            .get(&signal.strategy_uuid)
+            .copied()
+            .unwrap_or(self.default_exchange);
+        let strategy_uuid = Some(signal.strategy_uuid.clone());
+        let symbol = signal.symbol.to_string();
+        match signal.price {
+            Some(price) => Order::new_limit(
+                symbol,
+                exchange,
+                strategy_uuid,
+                signal.side,
+                price,
+                quantity,
+            ),
+            None => Order::new_market(symbol, exchange, strategy_uuid, signal.side, quantity),
+        }
+    }
+}
+
+// =========================================================================
+// 3. 统计
+// =========================================================================
+
+/// 单个策略的信号执行统计
+///
+/// 延迟均以毫秒计：成交延迟 = 首笔成交 - 信号产生，执行延迟 = 完全成交 - 信号产生。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SignalStats {
+    pub strategy_uuid: String,
+    pub total: usize,
+    /// 尚未结束 (Generated / Submitted / PartiallyExecuted)
+    pub pending: usize,
+    pub rejected: usize,
+    pub expired: usize,
+    pub executed: usize,
+    /// 提交过订单的信号数
+    pub submitted: usize,
+    /// 有成交 (含部分成交) 的信号数
+    pub filled: usize,
+    /// 命中率 = filled / submitted
+    pub hit_rate: Option<Decimal>,
+    pub avg_fill_latency_ms: Option<i64>,
+    pub median_fill_latency_ms: Option<i64>,
+    pub max_fill_latency_ms: Option<i64>,
+    pub avg_execution_latency_ms: Option<i64>,
+}
+
+impl SignalStats {
+    /// 按策略汇总 (结果按策略 UUID 排序)
+    pub fn by_strategy(signals: &[Signal]) -> Vec<SignalStats> {
+        let mut groups: BTreeMap<&str, Vec<&Signal>> = BTreeMap::new();
+        for signal in signals {
+            groups
+                .entry(signal.strategy_uuid.as_str())
+                .or_default()
+                .push(signal);
+        }
+        groups
+            .into_iter()
+            .map(|(strategy_uuid, signals)| Self::summarize(strategy_uuid, &signals))
+            .collect()
+    }
+
+    fn summarize(strategy_uuid: &str, signals: &[&Signal]) -> SignalStats {
+        let mut stats = SignalStats {
+            strategy_uuid: strategy_uuid.to_string(),
+            total: signals.len(),
+            ..SignalStats::default()
+        };
+        let mut fill_latencies = Vec::new();
+        let mut execution_latencies = Vec::new();
+        for signal in signals {
+            match signal.status {
+                SignalStatus::RiskRejected => stats.rejected += 1,
+                SignalStatus::Expired => stats.expired += 1,
+                SignalStatus::Executed => stats.executed += 1,
+                _ => stats.pending += 1,
+            }
+            if signal.submitted_at.is_some() {
+                stats.submitted += 1;
+            }
+            if signal.filled_quantity > Quantity::ZERO {
+                stats.filled += 1;
+            }
+            if let Some(latency) = signal.fill_latency() {
+                fill_latencies.push(latency.num_milliseconds());
+            }
+            if let Some(executed_at) = signal.executed_at {
+                execution_latencies.push((executed_at - signal.gmt_create).num_milliseconds());
+            }
+        }
+        if stats.submitted > 0 {
+            stats.hit_rate =
+                Some(Decimal::from(stats.filled as u64) / Decimal::from(stats.submitted as u64));
+        }
+        fill_latencies.sort_unstable();
+        stats.avg_fill_latency_ms = mean(&fill_latencies);
+        stats.median_fill_latency_ms = fill_latencies.get(fill_latencies.len() / 2).copied();
+        stats.max_fill_latency_ms = fill_latencies.last().copied();
+        stats.avg_execution_latency_ms = mean(&execution_latencies);
+        stats
+    }
+}
+
+fn mean(values: &[i64]) -> Option<i64> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().sum::<i64>() / values.len() as i64)
+}
@@ -0,0 +1,143 @@
+//! 信号定量 (Signal Sizing)
+//!
+//! `PortfolioSizer` 把 `quant_strategy::sizing` 接入信号管道：策略 / Agent 只给出方向
+//! (`Signal.quantity` 为空) 时，按账户净值、现有持仓和最近 K 线把信号方向转换为交易数量。
+//!
+//! * 信号自带数量时原样使用
+//! * 价格取信号限价，没有则取该标的最近一根 K 线收盘价
+//! * 目标持仓与当前持仓方向相反的部分不交易 (买入信号不会变成卖单)
+//! * 配置了资金分配 (`set_allocations`) 的策略以分配资金代替账户净值，持仓仍按账户合计
+
+use crate::signal::OrderSizer;
+use anyhow::{bail, Result};
+use quant_core::account::{Asset, Position};
+use quant_core::market::MarketBar;
+use quant_core::primitive::{CurrencyPair, Quantity};
+use quant_core::strategy::Signal;
+use quant_strategy::sizing::{AccountSnapshot, Allocation, PositionSizer, View};
+use rust_decimal::Decimal;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, RwLock};
+
+/// 定量所需的账户与行情状态
+#[derive(Debug, Default)]
+struct SizingState {
+    assets: Vec<Asset>,
+    positions: Vec<Position>,
+    /// 标的 -> 最近 K 线 (按时间升序)
+    bars: HashMap<String, VecDeque<MarketBar>>,
+    /// 策略 UUID -> 分配资金
+    budgets: HashMap<String, Decimal>,
+}
+
+/// 基于账户和行情的信号定量器
+///
+/// 克隆共享同一份状态：交给 `SignalProcessor::with_sizer` 后，调用方仍可通过
+/// 保留的克隆更新账户和 K 线。
+#[derive(Clone)]
+pub struct PortfolioSizer {
+    quote: String,
+    default: PositionSizer,
+    /// 策略 UUID -> 专用定量器
+    strategies: HashMap<String, PositionSizer>,
+    /// 每个标的保留的 K 线数
+    history: usize,
+    state: Arc<RwLock<SizingState>>,
+}
+
+impl PortfolioSizer {
+    pub fn new(quote: impl Into<String>, sizer: PositionSizer) -> Self {
+        Self {
+            quote: quote.into().to_uppercase(),
+            history: sizer.method.required_bars().max(1),
+            default: sizer,
+            strategies: HashMap::new(),
+            state: Arc::new(RwLock::new(SizingState::default())),
+        }
+    }
+
+    /// 指定某个策略使用的定量器
+    pub fn with_strategy(mut self, strategy_uuid: impl Into<String>, sizer: PositionSizer) -> Self {
+        self.history = self.history.max(sizer.method.required_bars());
+        self.strategies.insert(strategy_uuid.into(), sizer);
+        self
+    }
+
+    /// 替换账户余额和持仓
+    pub fn set_account(&self, assets: Vec<Asset>, positions: Vec<Position>) {
+        let mut state = self.state.write().unwrap();
+        state.assets = assets;
+        state.positions = positions;
+    }
+
+    /// 替换策略资金分配
+    pub fn set_allocations(&self, allocations: &[Allocation]) {
+        self.state.write().unwrap().budgets = allocations
+            .iter()
+            .map(|a| (a.strategy_uuid.clone(), a.capital))
+            .collect();
+    }
+
+    /// 追加一根 K 线 (同一时间的 K 线覆盖旧值)
+    pub fn on_bar(&self, bar: &MarketBar) {
+        let mut state = self.state.write().unwrap();
+        let bars = state.bars.entry(bar.symbol.to_string()).or_default();
+        if bars.back().is_some_and(|b| b.start_time == bar.start_time) {
+            bars.pop_back();
+        }
+        bars.push_back(bar.clone());
+        while bars.len() > self.history {
+            bars.pop_front();
+        }
+    }
+
+    /// 当前账户快照 (非计价币种按 `BASE/QUOTE` 最近收盘价估值)
+    pub fn snapshot(&self) -> Result<AccountSnapshot> {
+        let state = self.state.read().unwrap();
+        self.snapshot_of(&state)
+    }
+
+    fn snapshot_of(&self, state: &SizingState) -> Result<AccountSnapshot> {
+        let prices: HashMap<String, Decimal> = state
+            .bars
+            .iter()
+            .filter_map(|(symbol, bars)| {
+                let pair: CurrencyPair = symbol.parse().ok()?;
+                let close = bars.back()?.close.0;
+                (pair.quote == self.quote).then_some((pair.base, close))
+            })
+            .collect();
+        AccountSnapshot::from_account(&state.assets, &state.positions, &self.quote, &prices)
+    }
+}
+
+impl OrderSizer for PortfolioSizer {
+    fn size(&self, signal: &Signal) -> Result<Option<Quantity>> {
+        if signal.quantity.is_some() {
+            return Ok(signal.quantity);
+        }
+        let symbol = signal.symbol.to_string();
+        let state = self.state.read().unwrap();
+        let bars: Vec<MarketBar> = state
+            .bars
+            .get(&symbol)
+            .map(|b| b.iter().cloned().collect())
+            .unwrap_or_default();
+        let Some(price) = signal.price.map(|p| p.0).or(bars.last().map(|b| b.close.0)) else {
+            bail!("no price for {}", symbol);
+        };
+        let mut account = self.snapshot_of(&state)?;
+        if let Some(capital) = state.budgets.get(&signal.strategy_uuid) {
+            account = account.with_budget(*capital);
+        }
+        let sizer = self
+            .strategies
+            .get(&signal.strategy_uuid)
+            .unwrap_or(&self.default);
+        let view = View::new(symbol, signal.side);
+        Ok(match sizer.order_for(&account, &view, price, &bars)? {
+            Some((side, quantity)) if side == signal.side => Some(quantity),
+            _ => None,
+        })
+    }
+}
@@ -0,0 +1,562 @@
+//! 交易成本分析 (Transaction Cost Analysis, TCA)
+//!
+//! 基于订单 (`Order`)、逐笔成交 (`Fill`) 和订单前后的 K 线数据，评估每一笔订单的执行质量，
+//! 并按策略 / 标的 / 交易所 (Venue) 三个维度汇总。
+//!
+//! 指标口径 (所有 bps 均以“成本为正”表示，即数值越大执行越差):
+//! * **决策时间**: `Order.gmt_create`
+//! * **到达价 (Arrival Price)**: 决策日 K 线的开盘价；若决策日无 K 线 (休市)，取之前最近一根 K 线的收盘价
+//! * **到达价滑点**: `side * (成交均价 - 到达价) / 到达价`
+//! * **执行落差 (Implementation Shortfall)**: 已成交部分的执行成本 + 未成交部分的机会成本 + 手续费
+//!   (机会成本以决策日收盘价计)
+//! * **有效价差 (Effective Spread)**: `2 * side * (成交价 - 中间价) / 中间价`，中间价取成交日 K 线的 (high + low) / 2
+//! * **费用拖累 (Fee Drag)**: `手续费 / 成交额`
+//! * **成交率 (Fill Rate)**: `已成交数量 / 委托数量`
+
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use quant_core::enums::{BarPeriod, Exchange, Side};
+use quant_core::market::MarketBar;
+use quant_core::oms::{Fill, Order};
+use quant_core::primitive::CurrencyPair;
+use quant_storage::repository::fill_repo::FillRepository;
+use quant_storage::repository::market_repo::MarketDataRepository;
+use quant_storage::repository::order_repo::OrderRepository;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
+
+const BPS: Decimal = dec!(10000);
+
+// =========================================================================
+// 1. 报告结构
+// =========================================================================
+
+/// 单笔订单的 TCA 结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderTca {
+    pub order_uuid: String,
+    pub strategy_uuid: Option<String>,
+    pub exchange: Exchange,
+    /// 交易标的 ("BTC/USDT")，以字符串形式输出，便于 CSV 扁平化
+    pub symbol: String,
+    pub side: Side,
+
+    /// 决策时间 (= `Order.gmt_create`)
+    pub decision_time: DateTime<Utc>,
+
+    pub order_quantity: Decimal,
+    pub filled_quantity: Decimal,
+
+    /// 成交率 [0, 1]
+    pub fill_rate: Decimal,
+
+    /// 到达价 (缺少行情时为 None)
+    pub arrival_price: Option<Decimal>,
+
+    /// 成交均价 (未成交时为 None)
+    pub average_fill_price: Option<Decimal>,
+
+    /// 决策日收盘价 (用于计算未成交部分的机会成本)
+    pub close_price: Option<Decimal>,
+
+    /// 成交额 (计价币种)
+    pub filled_notional: Decimal,
+
+    /// 手续费合计 (计价币种)
+    pub fees: Decimal,
+
+    pub arrival_slippage_bps: Option<Decimal>,
+
+    /// 执行落差金额 (计价币种)
+    pub implementation_shortfall: Option<Decimal>,
+
+    /// 执行落差 bps (相对决策名义金额 `到达价 * 委托数量`)
+    pub implementation_shortfall_bps: Option<Decimal>,
+
+    pub effective_spread_bps: Option<Decimal>,
+
+    pub fee_drag_bps: Option<Decimal>,
+}
+
+/// 汇总维度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TcaDimension {
+    Strategy,
+    Symbol,
+    Venue,
+}
+
+impl TcaDimension {
+    fn key_of(&self, order: &OrderTca) -> String {
+        match self {
+            // 无策略归属的订单 (人工单 / 强平单) 统一归为 MANUAL
+            TcaDimension::Strategy => order
+                .strategy_uuid
+                .clone()
+                .unwrap_or_else(|| "MANUAL".to_string()),
+            TcaDimension::Symbol => format!("{}:{}", order.exchange, order.symbol),
+            TcaDimension::Venue => order.exchange.to_string(),
+        }
+    }
+}
+
+/// 某一维度下一个分组的汇总结果
+///
+/// bps 指标按成交额加权；执行落差按决策名义金额加权。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TcaSummary {
+    pub dimension: TcaDimension,
+    pub key: String,
+    pub order_count: usize,
+    pub filled_order_count: usize,
+
+    /// 决策名义金额合计 (`到达价 * 委托数量`)
+    pub intended_notional: Decimal,
+    pub filled_notional: Decimal,
+    pub fees: Decimal,
+
+    /// 按名义金额计算的成交率
+    pub fill_rate: Option<Decimal>,
+    pub arrival_slippage_bps: Option<Decimal>,
+    pub implementation_shortfall: Decimal,
+    pub implementation_shortfall_bps: Option<Decimal>,
+    pub effective_spread_bps: Option<Decimal>,
+    pub fee_drag_bps: Option<Decimal>,
+}
+
+/// TCA 报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TcaReport {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub generated_at: DateTime<Utc>,
+    pub orders: Vec<OrderTca>,
+    pub by_strategy: Vec<TcaSummary>,
+    pub by_symbol: Vec<TcaSummary>,
+    pub by_venue: Vec<TcaSummary>,
+}
+
+impl TcaReport {
+    /// 导出为 JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// 导出逐笔订单明细 CSV
+    pub fn write_orders_csv<W: Write>(&self, writer: W) -> Result<()> {
+        let mut wtr = csv::Writer::from_writer(writer);
+        for order in &self.orders {
+            wtr.serialize(order)?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    /// 导出汇总 CSV (三个维度写在同一个文件中，以 `dimension` 列区分)
+    pub fn write_summary_csv<W: Write>(&self, writer: W) -> Result<()> {
+        let mut wtr = csv::Writer::from_writer(writer);
+        for summary in self
+            .by_strategy
+            .iter()
+            .chain(&self.by_symbol)
+            .chain(&self.by_venue)
+        {
+            wtr.serialize(summary)?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+}
+
+// =========================================================================
+// 2. 行情索引
+// =========================================================================
+
+/// 按 (交易所, 标的) 索引的 K 线，用于查找决策时点前后的行情
+#[derive(Debug, Default)]
+pub struct BarIndex {
+    bars: HashMap<(Exchange, CurrencyPair), BTreeMap<NaiveDate, MarketBar>>,
+}
+
+impl BarIndex {
+    pub fn new(bars: impl IntoIterator<Item = MarketBar>) -> Self {
+        let mut index = Self::default();
+        for bar in bars {
+            index.insert(bar);
+        }
+        index
+    }
+
+    pub fn insert(&mut self, bar: MarketBar) {
+        self.bars
+            .entry((bar.exchange, bar.symbol.clone()))
+            .or_default()
+            .insert(bar.start_time, bar);
+    }
+
+    /// 指定日期的 K 线
+    pub fn bar_on(
+        &self,
+        exchange: Exchange,
+        symbol: &CurrencyPair,
+        date: NaiveDate,
+    ) -> Option<&MarketBar> {
+        self.bars.get(&(exchange, symbol.clone()))?.get(&date)
+    }
+
+    /// 严格早于指定日期的最近一根 K 线
+    pub fn last_before(
+        &self,
+        exchange: Exchange,
+        symbol: &CurrencyPair,
+        date: NaiveDate,
+    ) -> Option<&MarketBar> {
+        self.bars
+            .get(&(exchange, symbol.clone()))?
+            .range(..date)
+            .next_back()
+            .map(|(_, bar)| bar)
+    }
+
+    /// 到达价：决策日开盘价，决策日无行情时取之前最近的收盘价
+    pub fn arrival_price(
+        &self,
+        exchange: Exchange,
+        symbol: &CurrencyPair,
+        at: DateTime<Utc>,
+    ) -> Option<Decimal> {
+        let date = at.date_naive();
+        self.bar_on(exchange, symbol, date)
+            .map(|bar| bar.open.0)
+            .or_else(|| {
+                self.last_before(exchange, symbol, date)
+                    .map(|bar| bar.close.0)
+            })
+    }
+
+    /// 决策日收盘价，决策日无行情时取之前最近的收盘价
+    pub fn close_price(
+        &self,
+        exchange: Exchange,
+        symbol: &CurrencyPair,
+        at: DateTime<Utc>,
+    ) -> Option<Decimal> {
+        let date = at.date_naive();
+        self.bar_on(exchange, symbol, date)
+            .or_else(|| self.last_before(exchange, symbol, date))
+            .map(|bar| bar.close.0)
+    }
+
+    /// 成交时点的中间价近似：当日 (high + low) / 2
+    pub fn mid_price(
+        &self,
+        exchange: Exchange,
+        symbol: &CurrencyPair,
+        at: DateTime<Utc>,
+    ) -> Option<Decimal> {
+        self.bar_on(exchange, symbol, at.date_naive())
+            .map(|bar| (bar.high.0 + bar.low.0) / dec!(2))
+    }
+}
+
+// =========================================================================
+// 3. 分析器
+// =========================================================================
+
+/// TCA 分析器配置
+///
+/// `market_bar` 以日期为粒度 (`start_time` 为 DATE)，基准价固定使用日线。
+#[derive(Debug, Clone)]
+pub struct TcaConfig {
+    /// K 线交易条件类型 (21 = 盘中交易)
+    pub trade_type: u8,
+}
+
+impl Default for TcaConfig {
+    fn default() -> Self {
+        Self { trade_type: 21 }
+    }
+}
+
+pub struct TcaAnalyzer {
+    config: TcaConfig,
+}
+
+impl TcaAnalyzer {
+    pub fn new(config: TcaConfig) -> Self {
+        Self { config }
+    }
+
+    /// 从数据库加载 [start, end) 内的订单、成交与行情并生成报告
+    pub async fn run(
+        &self,
+        orders: &OrderRepository,
+        fills: &FillRepository,
+        market: &MarketDataRepository,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<TcaReport> {
+        let order_list = orders.find_by_range(start, end).await?;
+        // 订单在区间内创建，但成交可能发生在区间结束之后，因此成交按订单批量加载
+        let order_uuids: Vec<String> = order_list.iter().map(|o| o.uuid.clone()).collect();
+        let fill_list = fills.find_by_orders(&order_uuids).await?;
+
+        // 行情窗口向前多取 7 天，保证休市日下单也能找到之前的收盘价
+        let bar_start = start.date_naive() - chrono::Duration::days(7);
+        let bar_end = end.date_naive();
+        let mut index = BarIndex::default();
+        let instruments: HashSet<(Exchange, CurrencyPair)> = order_list
+            .iter()
+            .map(|o| (o.exchange, o.symbol.clone()))
+            .collect();
+        for (exchange, symbol) in instruments {
+            let bars = market
+                .find_bars_by_range(
+                    &exchange.to_string(),
+                    &symbol.to_string(),
+                    BarPeriod::D1,
+                    self.config.trade_type,
+                    bar_start,
+                    bar_end,
+                )
+                .await?;
+            for bar in bars {
+                index.insert(bar);
+            }
+        }
+
+        Ok(self.analyze(&order_list, &fill_list, &index, start, end))
+    }
+
+    /// 纯计算入口：对给定的订单、成交和行情生成报告
+    pub fn analyze(
+        &self,
+        orders: &[Order],
+        fills: &[Fill],
+        bars: &BarIndex,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> TcaReport {
+        let mut fills_by_order: HashMap<&str, Vec<&Fill>> = HashMap::new();
+        for fill in fills {
+            fills_by_order
+                .entry(fill.order_uuid.as_str())
+                .or_default()
+                .push(fill);
+        }
+
+        let order_tcas: Vec<OrderTca> = orders
+            .iter()
+            .filter(|o| o.gmt_create >= start && o.gmt_create < end)
+            .map(|o| {
+                let order_fills = fills_by_order
+                    .get(o.uuid.as_str())
+                    .map(Vec::as_slice)
+                    .unwrap_or(&[]);
+                analyze_order(o, order_fills, bars)
+            })
+            .collect();
+
+        TcaReport {
+            start,
+            end,
+            generated_at: Utc::now(),
+            by_strategy: summarize(&order_tcas, TcaDimension::Strategy),
+            by_symbol: summarize(&order_tcas, TcaDimension::Symbol),
+            by_venue: summarize(&order_tcas, TcaDimension::Venue),
+            orders: order_tcas,
+        }
+    }
+}
+
+impl Default for TcaAnalyzer {
+    fn default() -> Self {
+        Self::new(TcaConfig::default())
+    }
+}
+
+fn side_sign(side: Side) -> Decimal {
+    match side {
+        Side::Buy => Decimal::ONE,
+        Side::Sell => Decimal::NEGATIVE_ONE,
+    }
+}
+
+fn ratio_bps(numerator: Decimal, denominator: Decimal) -> Option<Decimal> {
+    if denominator.is_zero() {
+        None
+    } else {
+        Some((numerator / denominator * BPS).round_dp(4))
+    }
+}
+
+fn analyze_order(order: &Order, fills: &[&Fill], bars: &BarIndex) -> OrderTca {
+    let sign = side_sign(order.side);
+    let arrival = bars.arrival_price(order.exchange, &order.symbol, order.gmt_create);
+    let close = bars.close_price(order.exchange, &order.symbol, order.gmt_create);
+
+    // 1. 成交汇总：优先使用逐笔成交；没有成交明细时回退到订单上的累计字段
+    let (filled_qty, filled_notional, fees) = if fills.is_empty() {
+        let qty = order.filled_quantity.0;
+        let notional = order.average_price.map(|p| p.0 * qty).unwrap_or_default();
+        (qty, notional, order.fee.unwrap_or_default())
+    } else {
+        fills.iter().fold(
+            (Decimal::ZERO, Decimal::ZERO, Decimal::ZERO),
+            |(q, n, f), fill| (q + fill.quantity.0, n + fill.notional(), f + fill.fee),
+        )
+    };
+    let avg_fill = if filled_qty.is_zero() {
+        None
+    } else {
+        Some(filled_notional / filled_qty)
+    };
+
+    let order_qty = order.quantity.0;
+    let fill_rate = if order_qty.is_zero() {
+        Decimal::ZERO
+    } else {
+        (filled_qty / order_qty).min(Decimal::ONE)
+    };
+
+    // 2. 到达价滑点
+    let arrival_slippage_bps = match (avg_fill, arrival) {
+        (Some(avg), Some(arr)) => ratio_bps(sign * (avg - arr), arr),
+        _ => None,
+    };
+
+    // 3. 执行落差 = 执行成本 + 机会成本 + 手续费
+    let (implementation_shortfall, implementation_shortfall_bps) = match arrival {
+        Some(arr) => {
+            let execution_cost = sign * (filled_notional - arr * filled_qty);
+            let unfilled = (order_qty - filled_qty).max(Decimal::ZERO);
+            let opportunity_cost = sign * (close.unwrap_or(arr) - arr) * unfilled;
+            let shortfall = execution_cost + opportunity_cost + fees;
+            (Some(shortfall), ratio_bps(shortfall, arr * order_qty))
+        }
+        None => (None, None),
+    };
+
+    // 4. 有效价差：按成交额加权的逐笔 2 * side * (成交价 - 中间价) / 中间价
+    let effective_spread_bps = if fills.is_empty() {
+        match (
+            avg_fill,
+            bars.mid_price(order.exchange, &order.symbol, order.gmt_modified),
+        ) {
+            (Some(avg), Some(mid)) => ratio_bps(dec!(2) * sign * (avg - mid), mid),
+            _ => None,
+        }
+    } else {
+        let mut weighted = Decimal::ZERO;
+        let mut weight = Decimal::ZERO;
+        for fill in fills {
+            if let Some(mid) = bars.mid_price(fill.exchange, &fill.symbol, fill.trade_time) {
+                if !mid.is_zero() {
+                    weighted += dec!(2) * sign * (fill.price.0 - mid) / mid * fill.notional();
+                    weight += fill.notional();
+                }
+            }
+        }
+        ratio_bps(weighted, weight)
+    };
+
+    let fee_drag_bps = ratio_bps(fees, filled_notional);
+
+    OrderTca {
+        order_uuid: order.uuid.clone(),
+        strategy_uuid: order.strategy_uuid.clone(),
+        exchange: order.exchange,
+        symbol: order.symbol.to_string(),
+        side: order.side,
+        decision_time: order.gmt_create,
+        order_quantity: order_qty,
+        filled_quantity: filled_qty,
+        fill_rate,
+        arrival_price: arrival,
+        average_fill_price: avg_fill,
+        close_price: close,
+        filled_notional,
+        fees,
+        arrival_slippage_bps,
+        implementation_shortfall,
+        implementation_shortfall_bps,
+        effective_spread_bps,
+        fee_drag_bps,
+    }
+}
+
+/// 按维度汇总订单级结果
+pub fn summarize(orders: &[OrderTca], dimension: TcaDimension) -> Vec<TcaSummary> {
+    let mut groups: BTreeMap<String, Vec<&OrderTca>> = BTreeMap::new();
+    for order in orders {
+        groups
+            .entry(dimension.key_of(order))
+            .or_default()
+            .push(order);
+    }
+
+    groups
+        .into_iter()
+        .map(|(key, members)| {
+            let mut intended = Decimal::ZERO;
+            let mut filled_at_arrival = Decimal::ZERO;
+            let mut filled_notional = Decimal::ZERO;
+            let mut fees = Decimal::ZERO;
+            let mut shortfall = Decimal::ZERO;
+            let mut slippage_weighted = Decimal::ZERO;
+            let mut slippage_weight = Decimal::ZERO;
+            let mut spread_weighted = Decimal::ZERO;
+            let mut spread_weight = Decimal::ZERO;
+
+            for o in &members {
+                if let Some(arr) = o.arrival_price {
+                    intended += arr * o.order_quantity;
+                    filled_at_arrival += arr * o.filled_quantity;
+                }
+                filled_notional += o.filled_notional;
+                fees += o.fees;
+                shortfall += o.implementation_shortfall.unwrap_or_default();
+                if let Some(bps) = o.arrival_slippage_bps {
+                    slippage_weighted += bps * o.filled_notional;
+                    slippage_weight += o.filled_notional;
+                }
+                if let Some(bps) = o.effective_spread_bps {
+                    spread_weighted += bps * o.filled_notional;
+                    spread_weight += o.filled_notional;
+                }
+            }
+
+            let weighted_avg = |sum: Decimal, weight: Decimal| {
+                if weight.is_zero() {
+                    None
+                } else {
+                    Some((sum / weight).round_dp(4))
+                }
+            };
+
+            TcaSummary {
+                dimension,
+                key,
+                order_count: members.len(),
+                filled_order_count: members
+                    .iter()
+                    .filter(|o| !o.filled_quantity.is_zero())
+                    .count(),
+                intended_notional: intended,
+                filled_notional,
+                fees,
+                // 按到达价折算的成交金额 / 决策名义金额，避免不同标的的数量单位无法相加
+                fill_rate: weighted_avg(filled_at_arrival, intended),
+                arrival_slippage_bps: weighted_avg(slippage_weighted, slippage_weight),
+                implementation_shortfall: shortfall,
+                implementation_shortfall_bps: ratio_bps(shortfall, intended),
+                effective_spread_bps: weighted_avg(spread_weighted, spread_weight),
+                fee_drag_bps: ratio_bps(fees, filled_notional),
+            }
+        })
+        .collect()
+}
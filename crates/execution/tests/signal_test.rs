@@ -0,0 +1,326 @@
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use chrono::{Duration, Utc};
+    use quant_core::enums::{Exchange, OrderType, Side, SignalStatus};
+    use quant_core::oms::{Fill, Order};
+    use quant_core::primitive::{Price, Quantity};
+    use quant_core::strategy::Signal;
+    use quant_execution::signal::{
+        OrderSizer, PreTradeCheck, SignalOutcome, SignalProcessor, SignalStats,
+    };
+    use quant_storage::store::{MemoryStrategyStore, StrategyStore};
+    use rust_decimal_macros::dec;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    const STRATEGY: &str = "6f1c3a52-0d3e-4b8e-9a51-0c8f1f7f2a10";
+
+    fn setup() -> (Arc<MemoryStrategyStore>, SignalProcessor) {
+        let store = Arc::new(MemoryStrategyStore::new());
+        let processor = SignalProcessor::new(store.clone(), Exchange::Binance)
+            .with_route(STRATEGY, Exchange::Okx);
+        (store, processor)
+    }
+
+    fn submitted(outcome: SignalOutcome) -> Order {
+        match outcome {
+            SignalOutcome::Submitted(order) => *order,
+            other => panic!("expected Submitted, got {:?}", other),
+        }
+    }
+
+    /// 固定数量定量器
+    struct Fixed(Quantity);
+
+    impl OrderSizer for Fixed {
+        fn size(&self, _signal: &Signal) -> Result<Option<Quantity>> {
+            Ok(Some(self.0))
+        }
+    }
+
+    /// 单笔数量上限检查
+    struct MaxQuantity(Quantity);
+
+    impl PreTradeCheck for MaxQuantity {
+        fn name(&self) -> &str {
+            "max_quantity"
+        }
+
+        fn check(&self, _signal: &Signal, order: &Order) -> Result<(), String> {
+            if order.quantity > self.0 {
+                return Err(format!("{} > {}", order.quantity, self.0));
+            }
+            Ok(())
+        }
+    }
+
+    // =========================================================================
+    // 1. 生命周期
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_signal_to_partial_and_full_execution() -> Result<()> {
+        let (store, processor) = setup();
+        let signal = Signal::new_limit(
+            STRATEGY.to_string(),
+            "BTC/USDT",
+            Side::Buy,
+            Price(dec!(30000)),
+            Quantity(dec!(2)),
+            "breakout",
+        );
+        let uuid = Uuid::from_str(&signal.uuid)?;
+
+        let order = submitted(processor.process(signal).await?);
+        assert_eq!(order.exchange, Exchange::Okx);
+        assert_eq!(order.order_type, OrderType::Limit);
+        assert_eq!(order.strategy_uuid.as_deref(), Some(STRATEGY));
+        assert_eq!(processor.tracked(), 1);
+
+        let stored = store.find_signal(uuid).await?.unwrap();
+        assert_eq!(stored.status, SignalStatus::Submitted);
+        assert_eq!(stored.order_uuids, vec![order.uuid.clone()]);
+
+        let first = Fill::new(
+            &order,
+            Price(dec!(30000)),
+            Quantity(dec!(0.5)),
+            dec!(0),
+            Utc::now(),
+        );
+        let partial = processor.on_fill(&first).await?.unwrap();
+        assert_eq!(partial.status, SignalStatus::PartiallyExecuted);
+        assert_eq!(partial.remaining_quantity(), Some(Quantity(dec!(1.5))));
+
+        let rest = Fill::new(
+            &order,
+            Price(dec!(30010)),
+            Quantity(dec!(1.5)),
+            dec!(0),
+            Utc::now(),
+        );
+        let done = processor.on_fill(&rest).await?.unwrap();
+        assert_eq!(done.status, SignalStatus::Executed);
+        assert!(done.executed_at.is_some());
+        assert_eq!(processor.tracked(), 0);
+        assert_eq!(
+            store.find_signal(uuid).await?.unwrap().status,
+            SignalStatus::Executed
+        );
+
+        // 不属于任何信号的成交被忽略
+        let manual = Order::new_market(
+            "BTC/USDT",
+            Exchange::Binance,
+            None,
+            Side::Sell,
+            Quantity(dec!(1)),
+        );
+        let fill = Fill::new(
+            &manual,
+            Price(dec!(1)),
+            Quantity(dec!(1)),
+            dec!(0),
+            Utc::now(),
+        );
+        assert!(processor.on_fill(&fill).await?.is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sizing_and_pre_trade_rejection() -> Result<()> {
+        let (store, processor) = setup();
+        let processor = processor
+            .with_sizer(Fixed(Quantity(dec!(3))))
+            .with_check(MaxQuantity(Quantity(dec!(5))));
+
+        // 未定量的信号由定量器决定数量，并回填到信号
+        let mut unsized_signal = Signal::new_market(
+            STRATEGY.to_string(),
+            "ETH/USDT",
+            Side::Sell,
+            Quantity(dec!(1)),
+            "mean reversion",
+        );
+        unsized_signal.quantity = None;
+        let uuid = Uuid::from_str(&unsized_signal.uuid)?;
+        let order = submitted(processor.process(unsized_signal).await?);
+        assert_eq!(order.order_type, OrderType::Market);
+        assert_eq!(order.quantity, Quantity(dec!(3)));
+        assert_eq!(
+            store.find_signal(uuid).await?.unwrap().quantity,
+            Some(Quantity(dec!(3)))
+        );
+
+        let (store, processor) = setup();
+        let processor = processor
+            .with_sizer(Fixed(Quantity(dec!(10))))
+            .with_check(MaxQuantity(Quantity(dec!(5))));
+        let signal = Signal::new_market(
+            STRATEGY.to_string(),
+            "ETH/USDT",
+            Side::Buy,
+            Quantity(dec!(1)),
+            "too big",
+        );
+        let uuid = Uuid::from_str(&signal.uuid)?;
+        match processor.process(signal).await? {
+            SignalOutcome::Rejected(reason) => assert!(reason.starts_with("max_quantity")),
+            other => panic!("expected Rejected, got {:?}", other),
+        }
+        let stored = store.find_signal(uuid).await?.unwrap();
+        assert_eq!(stored.status, SignalStatus::RiskRejected);
+        assert!(stored.order_uuids.is_empty());
+        assert_eq!(processor.tracked(), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_runtime_risk_rejection() -> Result<()> {
+        let (store, processor) = setup();
+        let signal = Signal::new_market(
+            STRATEGY.to_string(),
+            "BTC/USDT",
+            Side::Buy,
+            Quantity(dec!(1)),
+            "breakout",
+        );
+        let uuid = Uuid::from_str(&signal.uuid)?;
+        let order = submitted(processor.process(signal).await?);
+
+        // 不属于任何信号的订单被忽略
+        assert!(processor
+            .on_order_rejected("unknown", "max_open_orders")
+            .await?
+            .is_none());
+
+        let rejected = processor
+            .on_order_rejected(&order.uuid, "max_open_orders: 3 open orders")
+            .await?
+            .unwrap();
+        assert_eq!(rejected.status, SignalStatus::RiskRejected);
+        assert_eq!(processor.tracked(), 0);
+        let stored = store.find_signal(uuid).await?.unwrap();
+        assert_eq!(stored.status, SignalStatus::RiskRejected);
+        assert_eq!(
+            stored.status_reason.as_deref(),
+            Some("max_open_orders: 3 open orders")
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ttl_expiry_and_recovery() -> Result<()> {
+        let (store, processor) = setup();
+        let processor = processor.with_default_ttl(Duration::seconds(30));
+
+        // 已过期的信号不会下单
+        let stale = Signal::new_market(
+            STRATEGY.to_string(),
+            "BTC/USDT",
+            Side::Buy,
+            Quantity(dec!(1)),
+            "late",
+        )
+        .with_ttl(Duration::seconds(-1));
+        assert!(matches!(
+            processor.process(stale).await?,
+            SignalOutcome::Expired
+        ));
+
+        let signal = Signal::new_market(
+            STRATEGY.to_string(),
+            "BTC/USDT",
+            Side::Buy,
+            Quantity(dec!(1)),
+            "momentum",
+        );
+        let uuid = Uuid::from_str(&signal.uuid)?;
+        submitted(processor.process(signal).await?);
+        assert!(processor.expire_due(Utc::now()).await?.is_empty());
+
+        // 重启后从存储恢复跟踪
+        let restarted = SignalProcessor::new(store.clone(), Exchange::Binance);
+        assert!(restarted.recover().await?.is_empty());
+        assert_eq!(restarted.tracked(), 1);
+
+        let expired = restarted
+            .expire_due(Utc::now() + Duration::seconds(31))
+            .await?;
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].status, SignalStatus::Expired);
+        assert_eq!(restarted.tracked(), 0);
+        assert_eq!(
+            store.find_signal(uuid).await?.unwrap().status,
+            SignalStatus::Expired
+        );
+        assert!(store.find_open_signals().await?.is_empty());
+        Ok(())
+    }
+
+    // =========================================================================
+    // 2. 统计
+    // =========================================================================
+
+    #[test]
+    fn test_signal_stats() -> Result<()> {
+        let t0 = Utc::now();
+        let make = |latency_ms: Option<i64>, executed: bool| -> Result<Signal> {
+            let mut signal = Signal::new_market(
+                STRATEGY.to_string(),
+                "BTC/USDT",
+                Side::Buy,
+                Quantity(dec!(2)),
+                "",
+            );
+            signal.gmt_create = t0;
+            signal.submit(Uuid::new_v4().to_string(), Quantity(dec!(2)))?;
+            if let Some(ms) = latency_ms {
+                let qty = if executed { dec!(2) } else { dec!(1) };
+                signal.record_fill(Quantity(qty), t0 + Duration::milliseconds(ms))?;
+            }
+            Ok(signal)
+        };
+
+        let mut rejected = Signal::new_market(
+            "other".to_string(),
+            "BTC/USDT",
+            Side::Buy,
+            Quantity(dec!(1)),
+            "",
+        );
+        rejected.reject("limit")?;
+        let mut expired = make(None, false)?;
+        expired.expire(t0)?;
+
+        let signals = vec![
+            make(Some(100), true)?,
+            make(Some(300), true)?,
+            make(Some(200), false)?,
+            expired,
+            rejected,
+        ];
+        let stats = SignalStats::by_strategy(&signals);
+        assert_eq!(stats.len(), 2);
+
+        let main = stats.iter().find(|s| s.strategy_uuid == STRATEGY).unwrap();
+        assert_eq!(main.total, 4);
+        assert_eq!(main.executed, 2);
+        assert_eq!(main.expired, 1);
+        assert_eq!(main.pending, 1);
+        assert_eq!(main.submitted, 4);
+        assert_eq!(main.filled, 3);
+        assert_eq!(main.hit_rate, Some(dec!(0.75)));
+        assert_eq!(main.avg_fill_latency_ms, Some(200));
+        assert_eq!(main.median_fill_latency_ms, Some(200));
+        assert_eq!(main.max_fill_latency_ms, Some(300));
+        assert_eq!(main.avg_execution_latency_ms, Some(200));
+
+        let other = stats.iter().find(|s| s.strategy_uuid == "other").unwrap();
+        assert_eq!(other.rejected, 1);
+        assert_eq!(other.hit_rate, None);
+        Ok(())
+    }
+}
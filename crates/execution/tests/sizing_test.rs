@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use chrono::NaiveDate;
+    use quant_core::account::Asset;
+    use quant_core::enums::{BarPeriod, Exchange, Side, SignalStatus};
+    use quant_core::market::MarketBar;
+    use quant_core::primitive::{Price, Quantity};
+    use quant_core::strategy::Signal;
+    use quant_execution::signal::{OrderSizer, SignalOutcome, SignalProcessor};
+    use quant_execution::sizing::PortfolioSizer;
+    use quant_storage::store::{MemoryStrategyStore, StrategyStore};
+    use quant_strategy::sizing::{Allocation, PositionSizer, SizingMethod};
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    const STRATEGY: &str = "6f1c3a52-0d3e-4b8e-9a51-0c8f1f7f2a10";
+
+    fn bar(symbol: &str, day: u32, close: Decimal) -> MarketBar {
+        MarketBar::new(
+            Exchange::Binance,
+            symbol,
+            BarPeriod::D1,
+            21,
+            Price(close),
+            Price(close),
+            Price(close),
+            Price(close),
+            Quantity(dec!(1)),
+            NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+        )
+        .expect("Failed to create mock bar")
+    }
+
+    fn asset(currency: &str, free: Decimal) -> Asset {
+        let mut asset = Asset::new("main", Exchange::Binance, currency);
+        asset.free = free;
+        asset
+    }
+
+    /// 方向信号 (不带数量)
+    fn view(side: Side) -> Signal {
+        let mut signal = Signal::new_market(
+            STRATEGY.to_string(),
+            "BTC/USDT",
+            side,
+            Quantity(dec!(1)),
+            "view",
+        );
+        signal.quantity = None;
+        signal
+    }
+
+    fn sizer() -> PortfolioSizer {
+        let sizer = PortfolioSizer::new(
+            "USDT",
+            PositionSizer::new(SizingMethod::FixedFractional {
+                fraction: dec!(0.1),
+            }),
+        );
+        sizer.set_account(vec![asset("USDT", dec!(10000))], Vec::new());
+        sizer
+    }
+
+    #[test]
+    fn test_portfolio_sizer_uses_nav_positions_and_budgets() -> Result<()> {
+        let sizer = sizer();
+
+        // 没有价格无法定量
+        assert!(sizer.size(&view(Side::Buy)).is_err());
+
+        // 净值 10000 * 10% / 20000 = 0.05
+        sizer.on_bar(&bar("BTC/USDT", 1, dec!(21000)));
+        sizer.on_bar(&bar("BTC/USDT", 1, dec!(20000)));
+        assert_eq!(sizer.size(&view(Side::Buy))?, Some(Quantity(dec!(0.05))));
+        assert_eq!(sizer.snapshot()?.nav, dec!(10000));
+
+        // 信号自带数量或限价时优先使用
+        let explicit = Signal::new_market(
+            STRATEGY.to_string(),
+            "BTC/USDT",
+            Side::Buy,
+            Quantity(dec!(3)),
+            "",
+        );
+        assert_eq!(sizer.size(&explicit)?, Some(Quantity(dec!(3))));
+        let mut limit = view(Side::Buy);
+        limit.price = Some(Price(dec!(10000)));
+        assert_eq!(sizer.size(&limit)?, Some(Quantity(dec!(0.1))));
+
+        // 现货余额计入净值和持仓：已持有 0.5 BTC，目标 (10000 + 10000) * 10% / 20000 = 0.1
+        sizer.set_account(
+            vec![asset("USDT", dec!(10000)), asset("BTC", dec!(0.5))],
+            Vec::new(),
+        );
+        assert_eq!(sizer.snapshot()?.nav, dec!(20000));
+        assert_eq!(sizer.size(&view(Side::Buy))?, None);
+        assert_eq!(sizer.size(&view(Side::Sell))?, Some(Quantity(dec!(0.6))));
+
+        // 资金分配后按分配资金定量
+        sizer.set_account(vec![asset("USDT", dec!(10000))], Vec::new());
+        sizer.set_allocations(&[Allocation {
+            strategy_uuid: STRATEGY.to_string(),
+            weight: 0.5,
+            capital: dec!(5000),
+            volatility: 0.01,
+            risk_contribution: 1.0,
+        }]);
+        assert_eq!(sizer.size(&view(Side::Buy))?, Some(Quantity(dec!(0.025))));
+
+        // 策略专用定量器
+        let custom = sizer.clone().with_strategy(
+            STRATEGY,
+            PositionSizer::new(SizingMethod::FixedNotional {
+                notional: dec!(2000),
+            }),
+        );
+        assert_eq!(custom.size(&view(Side::Buy))?, Some(Quantity(dec!(0.1))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_processor_sizes_directional_signals() -> Result<()> {
+        let store = Arc::new(MemoryStrategyStore::new());
+        let sizer = sizer();
+        let processor =
+            SignalProcessor::new(store.clone(), Exchange::Binance).with_sizer(sizer.clone());
+
+        // 缺少行情：拒绝而不是报错
+        let signal = view(Side::Buy);
+        let uuid = Uuid::from_str(&signal.uuid)?;
+        match processor.process(signal).await? {
+            SignalOutcome::Rejected(reason) => assert!(reason.starts_with("sizer:")),
+            other => panic!("expected Rejected, got {:?}", other),
+        }
+        assert_eq!(
+            store.find_signal(uuid).await?.unwrap().status,
+            SignalStatus::RiskRejected
+        );
+
+        sizer.on_bar(&bar("BTC/USDT", 2, dec!(25000)));
+        let signal = view(Side::Buy);
+        let uuid = Uuid::from_str(&signal.uuid)?;
+        match processor.process(signal).await? {
+            SignalOutcome::Submitted(order) => assert_eq!(order.quantity, Quantity(dec!(0.04))),
+            other => panic!("expected Submitted, got {:?}", other),
+        }
+        assert_eq!(
+            store.find_signal(uuid).await?.unwrap().quantity,
+            Some(Quantity(dec!(0.04)))
+        );
+        Ok(())
+    }
+}
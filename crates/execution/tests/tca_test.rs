@@ -0,0 +1,206 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use quant_core::enums::{BarPeriod, Exchange, OrderStatus, Side};
+    use quant_core::market::MarketBar;
+    use quant_core::oms::{Fill, Order};
+    use quant_core::primitive::{Price, Quantity};
+    use quant_execution::tca::{BarIndex, TcaAnalyzer, TcaDimension};
+    use rust_decimal_macros::dec;
+
+    // =========================================================================
+    // 1. Mock 数据
+    // =========================================================================
+
+    fn mock_bar(exchange: Exchange, symbol: &str, date: NaiveDate, ohlc: [f64; 4]) -> MarketBar {
+        MarketBar::new(
+            exchange,
+            symbol,
+            BarPeriod::D1,
+            21,
+            Price::from_f64(ohlc[0]),
+            Price::from_f64(ohlc[1]),
+            Price::from_f64(ohlc[2]),
+            Price::from_f64(ohlc[3]),
+            Quantity(dec!(1000)),
+            date,
+        )
+        .expect("Failed to create mock bar")
+    }
+
+    fn bar_index() -> BarIndex {
+        BarIndex::new(vec![
+            mock_bar(
+                Exchange::Binance,
+                "BTC/USDT",
+                NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+                [100.0, 110.0, 90.0, 105.0],
+            ),
+            // ETH 在 01-06 没有 K 线，到达价应回退到 01-05 的收盘价
+            mock_bar(
+                Exchange::Okx,
+                "ETH/USDT",
+                NaiveDate::from_ymd_opt(2024, 1, 5).unwrap(),
+                [48.0, 52.0, 47.0, 50.0],
+            ),
+        ])
+    }
+
+    /// 买单：委托 1.0，两笔成交共 0.8
+    fn partially_filled_buy() -> (Order, Vec<Fill>) {
+        let mut order = Order::new_limit(
+            "BTC/USDT",
+            Exchange::Binance,
+            Some("strategy-a".to_string()),
+            Side::Buy,
+            Price(dec!(103)),
+            Quantity(dec!(1.0)),
+        );
+        order.gmt_create = Utc.with_ymd_and_hms(2024, 1, 2, 10, 0, 0).unwrap();
+        order.status = OrderStatus::PartiallyFilled;
+
+        let t1 = Utc.with_ymd_and_hms(2024, 1, 2, 10, 1, 0).unwrap();
+        let t2 = Utc.with_ymd_and_hms(2024, 1, 2, 10, 5, 0).unwrap();
+        let fills = vec![
+            Fill::new(
+                &order,
+                Price(dec!(101)),
+                Quantity(dec!(0.5)),
+                dec!(0.05),
+                t1,
+            ),
+            Fill::new(
+                &order,
+                Price(dec!(102)),
+                Quantity(dec!(0.3)),
+                dec!(0.03),
+                t2,
+            ),
+        ];
+        (order, fills)
+    }
+
+    /// 卖单：没有逐笔成交，只有订单上的累计成交字段
+    fn filled_sell_without_fills() -> Order {
+        let mut order = Order::new_market(
+            "ETH/USDT",
+            Exchange::Okx,
+            None,
+            Side::Sell,
+            Quantity(dec!(2)),
+        );
+        order.gmt_create = Utc.with_ymd_and_hms(2024, 1, 6, 3, 0, 0).unwrap();
+        order.status = OrderStatus::Filled;
+        order.filled_quantity = Quantity(dec!(2));
+        order.average_price = Some(Price(dec!(49)));
+        order.fee = Some(dec!(0.1));
+        order
+    }
+
+    fn window() -> (chrono::DateTime<Utc>, chrono::DateTime<Utc>) {
+        (
+            Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 1, 31, 0, 0, 0).unwrap(),
+        )
+    }
+
+    // =========================================================================
+    // 2. 测试用例
+    // =========================================================================
+
+    #[test]
+    fn test_order_level_metrics() {
+        let (buy, fills) = partially_filled_buy();
+        let sell = filled_sell_without_fills();
+        let (start, end) = window();
+
+        let report =
+            TcaAnalyzer::default().analyze(&[buy.clone(), sell], &fills, &bar_index(), start, end);
+        assert_eq!(report.orders.len(), 2);
+
+        let buy_tca = report
+            .orders
+            .iter()
+            .find(|o| o.order_uuid == buy.uuid)
+            .unwrap();
+        assert_eq!(buy_tca.arrival_price, Some(dec!(100)));
+        assert_eq!(buy_tca.filled_quantity, dec!(0.8));
+        assert_eq!(buy_tca.fill_rate, dec!(0.8));
+        assert_eq!(buy_tca.average_fill_price, Some(dec!(101.375)));
+        assert_eq!(buy_tca.arrival_slippage_bps, Some(dec!(137.5)));
+        // 执行成本 1.1 + 机会成本 0.2 * (105 - 100) + 手续费 0.08
+        assert_eq!(buy_tca.implementation_shortfall, Some(dec!(2.18)));
+        assert_eq!(buy_tca.implementation_shortfall_bps, Some(dec!(218)));
+        assert_eq!(
+            buy_tca.effective_spread_bps.map(|v| v.round_dp(2)),
+            Some(dec!(275.46))
+        );
+        assert_eq!(
+            buy_tca.fee_drag_bps.map(|v| v.round_dp(2)),
+            Some(dec!(9.86))
+        );
+
+        // 卖出价低于到达价 => 正成本
+        let sell_tca = report.orders.iter().find(|o| o.side == Side::Sell).unwrap();
+        assert_eq!(sell_tca.arrival_price, Some(dec!(50)));
+        assert_eq!(sell_tca.arrival_slippage_bps, Some(dec!(200)));
+        assert_eq!(sell_tca.fill_rate, dec!(1));
+    }
+
+    #[test]
+    fn test_rollups_by_dimension() {
+        let (buy, fills) = partially_filled_buy();
+        let sell = filled_sell_without_fills();
+        let (start, end) = window();
+
+        let report = TcaAnalyzer::default().analyze(&[buy, sell], &fills, &bar_index(), start, end);
+
+        let venues: Vec<&str> = report.by_venue.iter().map(|s| s.key.as_str()).collect();
+        assert_eq!(venues, vec!["BINANCE", "OKX"]);
+
+        let strategies: Vec<&str> = report.by_strategy.iter().map(|s| s.key.as_str()).collect();
+        assert_eq!(strategies, vec!["MANUAL", "strategy-a"]);
+        assert!(report
+            .by_symbol
+            .iter()
+            .all(|s| s.dimension == TcaDimension::Symbol && s.order_count == 1));
+
+        let binance = &report.by_venue[0];
+        assert_eq!(binance.intended_notional, dec!(100));
+        assert_eq!(binance.fill_rate, Some(dec!(0.8)));
+        assert_eq!(binance.implementation_shortfall_bps, Some(dec!(218)));
+    }
+
+    #[test]
+    fn test_orders_outside_window_are_ignored() {
+        let (buy, fills) = partially_filled_buy();
+        let start = Utc.with_ymd_and_hms(2024, 1, 3, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 1, 31, 0, 0, 0).unwrap();
+
+        let report = TcaAnalyzer::default().analyze(&[buy], &fills, &bar_index(), start, end);
+        assert!(report.orders.is_empty());
+        assert!(report.by_strategy.is_empty());
+    }
+
+    #[test]
+    fn test_json_and_csv_export() -> anyhow::Result<()> {
+        let (buy, fills) = partially_filled_buy();
+        let (start, end) = window();
+        let report = TcaAnalyzer::default().analyze(&[buy], &fills, &bar_index(), start, end);
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()?)?;
+        assert_eq!(json["orders"].as_array().unwrap().len(), 1);
+
+        let mut orders_csv = Vec::new();
+        report.write_orders_csv(&mut orders_csv)?;
+        let orders_csv = String::from_utf8(orders_csv)?;
+        assert!(orders_csv.starts_with("order_uuid,"));
+        assert_eq!(orders_csv.lines().count(), 2);
+
+        let mut summary_csv = Vec::new();
+        report.write_summary_csv(&mut summary_csv)?;
+        // 表头 + 策略/标的/交易所各一行
+        assert_eq!(String::from_utf8(summary_csv)?.lines().count(), 4);
+        Ok(())
+    }
+}
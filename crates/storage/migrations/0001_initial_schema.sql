@@ -0,0 +1,149 @@
+-- Photon 基础表结构 (MySQL 8.0+)
+-- 价格 / 数量统一 DECIMAL(30, 8)，时间统一 DATETIME(3) UTC
+
+-- K 线
+CREATE TABLE IF NOT EXISTS `market_bar` (
+    `id`           BIGINT          NOT NULL AUTO_INCREMENT,
+    `exchange`     VARCHAR(32)     NOT NULL,
+    `symbol`       VARCHAR(64)     NOT NULL COMMENT 'BASE/QUOTE',
+    `bar_period`   VARCHAR(16)     NOT NULL,
+    `type`         TINYINT UNSIGNED NOT NULL DEFAULT 21 COMMENT '交易条件类型 (21 = 盘中交易)',
+    `open`         DECIMAL(30, 8)  NOT NULL,
+    `high`         DECIMAL(30, 8)  NOT NULL,
+    `low`          DECIMAL(30, 8)  NOT NULL,
+    `close`        DECIMAL(30, 8)  NOT NULL,
+    `volume`       DECIMAL(30, 8)  NOT NULL,
+    `amount`       DECIMAL(30, 8)  NULL,
+    `start_time`   DATE            NOT NULL,
+    `end_time`     DATE            NOT NULL,
+    `gmt_create`   DATETIME(3)     NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `uk_market_bar` (`exchange`, `symbol`, `bar_period`, `start_time`, `type`)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COMMENT = 'K 线 (原始价格，不复权)';
+
+-- 订单
+CREATE TABLE IF NOT EXISTS `order` (
+    `id`                BIGINT         NOT NULL AUTO_INCREMENT,
+    `order_uuid`        VARCHAR(64)    NOT NULL,
+    `strategy_uuid`     VARCHAR(64)    NULL,
+    `exchange_order_id` VARCHAR(128)   NULL,
+    `symbol`            VARCHAR(64)    NOT NULL,
+    `exchange`          VARCHAR(32)    NOT NULL,
+    `side`              VARCHAR(16)    NOT NULL,
+    `order_type`        VARCHAR(32)    NOT NULL,
+    `status`            VARCHAR(32)    NOT NULL,
+    `price`             DECIMAL(30, 8) NULL,
+    `quantity`          DECIMAL(30, 8) NOT NULL,
+    `filled_quantity`   DECIMAL(30, 8) NOT NULL DEFAULT 0,
+    `average_price`     DECIMAL(30, 8) NULL,
+    `fee`               DECIMAL(30, 8) NULL,
+    `gmt_create`        DATETIME(3)    NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    `gmt_modified`      DATETIME(3)    NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `uk_order_uuid` (`order_uuid`),
+    KEY `idx_order_strategy` (`strategy_uuid`, `gmt_create`),
+    KEY `idx_order_status` (`status`),
+    KEY `idx_order_gmt_create` (`gmt_create`)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COMMENT = '订单';
+
+-- 成交
+CREATE TABLE IF NOT EXISTS `fill` (
+    `id`                BIGINT         NOT NULL AUTO_INCREMENT,
+    `fill_uuid`         VARCHAR(64)    NOT NULL,
+    `order_uuid`        VARCHAR(64)    NOT NULL,
+    `strategy_uuid`     VARCHAR(64)    NULL,
+    `exchange_trade_id` VARCHAR(128)   NULL,
+    `exchange`          VARCHAR(32)    NOT NULL,
+    `symbol`            VARCHAR(64)    NOT NULL,
+    `side`              VARCHAR(16)    NOT NULL,
+    `price`             DECIMAL(30, 8) NOT NULL,
+    `quantity`          DECIMAL(30, 8) NOT NULL,
+    `fee`               DECIMAL(30, 8) NOT NULL DEFAULT 0,
+    `fee_currency`      VARCHAR(16)    NULL,
+    `is_maker`          TINYINT(1)     NOT NULL DEFAULT 0,
+    `trade_time`        DATETIME(3)    NOT NULL,
+    `gmt_create`        DATETIME(3)    NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `uk_fill_uuid` (`fill_uuid`),
+    KEY `idx_fill_order` (`order_uuid`, `trade_time`),
+    KEY `idx_fill_trade_time` (`trade_time`)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COMMENT = '成交明细';
+
+-- 资产
+CREATE TABLE IF NOT EXISTS `asset` (
+    `id`           BIGINT         NOT NULL AUTO_INCREMENT,
+    `uuid`         VARCHAR(64)    NOT NULL,
+    `account_name` VARCHAR(64)    NOT NULL,
+    `exchange`     VARCHAR(32)    NOT NULL,
+    `currency`     VARCHAR(16)    NOT NULL,
+    `free`         DECIMAL(30, 8) NOT NULL DEFAULT 0,
+    `frozen`       DECIMAL(30, 8) NOT NULL DEFAULT 0,
+    `borrowed`     DECIMAL(30, 8) NOT NULL DEFAULT 0,
+    `gmt_create`   DATETIME(3)    NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    `gmt_modified` DATETIME(3)    NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `uk_asset_uuid` (`uuid`),
+    UNIQUE KEY `uk_asset_account` (`account_name`, `exchange`, `currency`)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COMMENT = '账户资产';
+
+-- 持仓
+CREATE TABLE IF NOT EXISTS `position` (
+    `id`             BIGINT         NOT NULL AUTO_INCREMENT,
+    `uuid`           VARCHAR(64)    NOT NULL,
+    `account_name`   VARCHAR(64)    NOT NULL,
+    `exchange`       VARCHAR(32)    NOT NULL,
+    `symbol`         VARCHAR(64)    NOT NULL,
+    `side`           VARCHAR(16)    NOT NULL,
+    `quantity`       DECIMAL(30, 8) NOT NULL DEFAULT 0,
+    `entry_price`    DECIMAL(30, 8) NULL,
+    `unrealized_pnl` DECIMAL(30, 8) NULL,
+    `leverage`       DECIMAL(10, 2) NOT NULL DEFAULT 1,
+    `gmt_create`     DATETIME(3)    NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    `gmt_modified`   DATETIME(3)    NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `uk_position_uuid` (`uuid`),
+    UNIQUE KEY `uk_position_account` (`account_name`, `exchange`, `symbol`, `side`)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COMMENT = '账户持仓';
+
+-- 策略实例
+CREATE TABLE IF NOT EXISTS `strategy` (
+    `id`           BIGINT       NOT NULL AUTO_INCREMENT,
+    `uuid`         VARCHAR(64)  NOT NULL,
+    `name`         VARCHAR(128) NOT NULL,
+    `class_name`   VARCHAR(128) NOT NULL,
+    `status`       VARCHAR(32)  NOT NULL,
+    `config`       JSON         NOT NULL,
+    `gmt_create`   DATETIME(3)  NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    `gmt_modified` DATETIME(3)  NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `uk_strategy_uuid` (`uuid`),
+    KEY `idx_strategy_status` (`status`)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COMMENT = '策略实例';
+
+-- 策略运行时状态 (每个策略一行)
+CREATE TABLE IF NOT EXISTS `strategy_state` (
+    `id`            BIGINT      NOT NULL AUTO_INCREMENT,
+    `strategy_uuid` VARCHAR(64) NOT NULL,
+    `state_data`    JSON        NOT NULL,
+    `gmt_create`    DATETIME(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    `gmt_modified`  DATETIME(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `uk_strategy_state` (`strategy_uuid`)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COMMENT = '策略运行时状态';
+
+-- 交易信号
+CREATE TABLE IF NOT EXISTS `signal` (
+    `id`            BIGINT         NOT NULL AUTO_INCREMENT,
+    `uuid`          VARCHAR(64)    NOT NULL,
+    `strategy_uuid` VARCHAR(64)    NOT NULL,
+    `symbol`        VARCHAR(64)    NOT NULL,
+    `side`          VARCHAR(16)    NOT NULL,
+    `price`         DECIMAL(30, 8) NULL,
+    `quantity`      DECIMAL(30, 8) NULL,
+    `reason`        VARCHAR(1024)  NOT NULL DEFAULT '',
+    `gmt_create`    DATETIME(3)    NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    `gmt_modified`  DATETIME(3)    NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `uk_signal_uuid` (`uuid`),
+    KEY `idx_signal_strategy` (`strategy_uuid`, `gmt_create`)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COMMENT = '策略交易信号';
@@ -0,0 +1,42 @@
+-- K 线数据质量: 扫描结果 + 隔离表
+
+-- 每条序列最近一次扫描结果
+CREATE TABLE IF NOT EXISTS `market_bar_quality` (
+    `id`            BIGINT           NOT NULL AUTO_INCREMENT,
+    `exchange`      VARCHAR(32)      NOT NULL,
+    `symbol`        VARCHAR(64)      NOT NULL,
+    `bar_period`    VARCHAR(16)      NOT NULL,
+    `type`          TINYINT UNSIGNED NOT NULL,
+    `bar_count`     BIGINT           NOT NULL DEFAULT 0,
+    `first_date`    DATE             NULL,
+    `last_date`     DATE             NULL,
+    `error_count`   BIGINT           NOT NULL DEFAULT 0,
+    `warning_count` BIGINT           NOT NULL DEFAULT 0,
+    `anomalies`     MEDIUMTEXT       NOT NULL COMMENT '异常明细 (JSON 数组)',
+    `scanned_at`    DATETIME(3)      NOT NULL,
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `uk_market_bar_quality` (`exchange`, `symbol`, `bar_period`, `type`),
+    KEY `idx_market_bar_quality_errors` (`error_count`)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COMMENT = 'K 线质量扫描结果';
+
+-- 被隔离的 K 线 (从 market_bar 移出)
+CREATE TABLE IF NOT EXISTS `market_bar_quarantine` (
+    `id`         BIGINT           NOT NULL AUTO_INCREMENT,
+    `bar_id`     BIGINT           NOT NULL COMMENT '原 market_bar.id',
+    `exchange`   VARCHAR(32)      NOT NULL,
+    `symbol`     VARCHAR(64)      NOT NULL,
+    `bar_period` VARCHAR(16)      NOT NULL,
+    `type`       TINYINT UNSIGNED NOT NULL,
+    `open`       DECIMAL(30, 8)   NOT NULL,
+    `high`       DECIMAL(30, 8)   NOT NULL,
+    `low`        DECIMAL(30, 8)   NOT NULL,
+    `close`      DECIMAL(30, 8)   NOT NULL,
+    `volume`     DECIMAL(30, 8)   NOT NULL,
+    `amount`     DECIMAL(30, 8)   NULL,
+    `start_time` DATE             NOT NULL,
+    `end_time`   DATE             NOT NULL,
+    `reason`     VARCHAR(1024)    NOT NULL,
+    `gmt_create` DATETIME(3)      NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    PRIMARY KEY (`id`),
+    KEY `idx_market_bar_quarantine_series` (`exchange`, `symbol`, `bar_period`, `type`, `start_time`)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COMMENT = '隔离的异常 K 线';
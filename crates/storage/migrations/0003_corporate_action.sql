@@ -0,0 +1,16 @@
+-- 公司行动 (拆股 / 现金分红 / 代码变更)，复权在读取时计算
+
+CREATE TABLE IF NOT EXISTS `corporate_action` (
+    `id`          BIGINT         NOT NULL AUTO_INCREMENT,
+    `exchange`    VARCHAR(32)    NOT NULL,
+    `symbol`      VARCHAR(64)    NOT NULL COMMENT '行动发生时的代码 (代码变更时为旧代码)',
+    `action_type` VARCHAR(32)    NOT NULL COMMENT 'SPLIT / CASH_DIVIDEND / SYMBOL_CHANGE',
+    `ex_date`     DATE           NOT NULL,
+    `ratio`       DECIMAL(30, 8) NULL COMMENT '拆股比例 (4 = 1 拆 4)',
+    `cash_amount` DECIMAL(30, 8) NULL COMMENT '每股现金分红',
+    `new_symbol`  VARCHAR(64)    NULL,
+    `gmt_create`  DATETIME(3)    NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `uk_corporate_action` (`exchange`, `symbol`, `action_type`, `ex_date`),
+    KEY `idx_corporate_action_new_symbol` (`exchange`, `new_symbol`)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COMMENT = '公司行动';
@@ -0,0 +1,23 @@
+-- 策略状态版本化：每次保存追加一个快照，strategy_state 只保存当前版本
+
+ALTER TABLE `strategy_state`
+    ADD COLUMN `version`        BIGINT NOT NULL DEFAULT 0 COMMENT '当前状态版本号' AFTER `state_data`,
+    ADD COLUMN `schema_version` INT    NOT NULL DEFAULT 1 COMMENT 'state_data 结构版本' AFTER `version`;
+
+CREATE TABLE IF NOT EXISTS `strategy_state_version` (
+    `id`             BIGINT      NOT NULL AUTO_INCREMENT,
+    `strategy_uuid`  VARCHAR(64) NOT NULL,
+    `version`        BIGINT      NOT NULL COMMENT '同一策略内单调递增',
+    `schema_version` INT         NOT NULL DEFAULT 1,
+    `state_data`     JSON        NOT NULL,
+    `gmt_create`     DATETIME(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `uk_strategy_state_version` (`strategy_uuid`, `version`)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COMMENT = '策略状态历史版本';
+
+-- 已有状态作为各策略的第 1 个版本
+UPDATE `strategy_state` SET `version` = 1;
+
+INSERT INTO `strategy_state_version` (strategy_uuid, version, schema_version, state_data, gmt_create)
+SELECT strategy_uuid, 1, 1, state_data, gmt_modified
+FROM `strategy_state`;
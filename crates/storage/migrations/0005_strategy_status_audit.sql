@@ -0,0 +1,16 @@
+-- 策略状态审计：记录每次生命周期变更的原因与发起方
+
+ALTER TABLE `strategy`
+    ADD COLUMN `reason` VARCHAR(512) NOT NULL DEFAULT '' COMMENT '最近一次状态变更原因' AFTER `status`;
+
+CREATE TABLE IF NOT EXISTS `strategy_status_audit` (
+    `id`            BIGINT       NOT NULL AUTO_INCREMENT,
+    `strategy_uuid` VARCHAR(64)  NOT NULL,
+    `from_status`   VARCHAR(32)  NOT NULL,
+    `to_status`     VARCHAR(32)  NOT NULL,
+    `reason`        VARCHAR(512) NOT NULL DEFAULT '',
+    `actor`         VARCHAR(16)  NOT NULL COMMENT 'USER / RISK / AGENT / SYSTEM',
+    `gmt_create`    DATETIME(3)  NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    PRIMARY KEY (`id`),
+    KEY `idx_status_audit_strategy` (`strategy_uuid`, `gmt_create`)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COMMENT = '策略状态变更审计';
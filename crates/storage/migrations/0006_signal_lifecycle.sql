@@ -0,0 +1,12 @@
+-- 信号生命周期：状态、TTL、产生的订单以及成交进度 (用于信号 -> 成交延迟和命中率统计)
+
+ALTER TABLE `signal`
+    ADD COLUMN `status`          VARCHAR(32)    NOT NULL DEFAULT 'GENERATED' AFTER `reason`,
+    ADD COLUMN `status_reason`   VARCHAR(512)   NULL COMMENT '拒绝 / 过期原因' AFTER `status`,
+    ADD COLUMN `expires_at`      DATETIME(3)    NULL AFTER `status_reason`,
+    ADD COLUMN `order_uuids`     JSON           NOT NULL DEFAULT (JSON_ARRAY()) COMMENT '由该信号产生的订单' AFTER `expires_at`,
+    ADD COLUMN `filled_quantity` DECIMAL(30, 8) NOT NULL DEFAULT 0 AFTER `order_uuids`,
+    ADD COLUMN `submitted_at`    DATETIME(3)    NULL AFTER `filled_quantity`,
+    ADD COLUMN `first_fill_at`   DATETIME(3)    NULL AFTER `submitted_at`,
+    ADD COLUMN `executed_at`     DATETIME(3)    NULL AFTER `first_fill_at`,
+    ADD KEY `idx_signal_status` (`status`);
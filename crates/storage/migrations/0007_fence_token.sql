@@ -0,0 +1,7 @@
+-- 主节点 fencing：记录最近一次写入该行的主节点任期 (fencing token)，旧任期的写入被拒绝
+
+ALTER TABLE `order`
+    ADD COLUMN `fence_token` BIGINT UNSIGNED NULL COMMENT '最近一次写入的 fencing token' AFTER `fee`;
+
+ALTER TABLE `strategy`
+    ADD COLUMN `fence_token` BIGINT UNSIGNED NULL COMMENT '最近一次写入的 fencing token' AFTER `reason`;
@@ -0,0 +1,188 @@
+-- Photon 高频行情表结构 (PostgreSQL 14+ / TimescaleDB 2.9+)
+-- 价格 / 数量统一 NUMERIC(30, 8)，时间统一 TIMESTAMPTZ (UTC)
+--
+-- 与 MySQL 的 market_bar 不同，这里的 start_time / end_time 精确到毫秒，用于 Tick / 成交 / 分钟线。
+-- 高周期 (M5 ~ D1) 由 M1 连续聚合得到，不需要另外写入。
+
+CREATE EXTENSION IF NOT EXISTS timescaledb;
+
+-- =========================================================================
+-- 1. K 线 (任意周期，主要存 M1)
+-- =========================================================================
+CREATE TABLE IF NOT EXISTS market_bar (
+    exchange     VARCHAR(32)     NOT NULL,
+    symbol       VARCHAR(64)     NOT NULL,
+    bar_period   VARCHAR(16)     NOT NULL,
+    "type"       SMALLINT        NOT NULL DEFAULT 21,
+    open         NUMERIC(30, 8)  NOT NULL,
+    high         NUMERIC(30, 8)  NOT NULL,
+    low          NUMERIC(30, 8)  NOT NULL,
+    close        NUMERIC(30, 8)  NOT NULL,
+    volume       NUMERIC(30, 8)  NOT NULL,
+    amount       NUMERIC(30, 8)  NULL,
+    start_time   TIMESTAMPTZ     NOT NULL,
+    end_time     TIMESTAMPTZ     NOT NULL,
+    gmt_create   TIMESTAMPTZ     NOT NULL DEFAULT now(),
+    PRIMARY KEY (exchange, symbol, bar_period, "type", start_time)
+);
+SELECT create_hypertable('market_bar', 'start_time',
+    chunk_time_interval => INTERVAL '7 days', if_not_exists => TRUE);
+
+-- =========================================================================
+-- 2. Tick (盘口快照)
+-- =========================================================================
+CREATE TABLE IF NOT EXISTS market_tick (
+    exchange     VARCHAR(32)     NOT NULL,
+    symbol       VARCHAR(64)     NOT NULL,
+    "time"       TIMESTAMPTZ     NOT NULL,
+    bid_price    NUMERIC(30, 8)  NOT NULL,
+    bid_size     NUMERIC(30, 8)  NOT NULL,
+    ask_price    NUMERIC(30, 8)  NOT NULL,
+    ask_size     NUMERIC(30, 8)  NOT NULL,
+    last_price   NUMERIC(30, 8)  NULL
+);
+SELECT create_hypertable('market_tick', 'time',
+    chunk_time_interval => INTERVAL '1 day', if_not_exists => TRUE);
+CREATE INDEX IF NOT EXISTS idx_market_tick_symbol_time ON market_tick (exchange, symbol, "time" DESC);
+
+-- =========================================================================
+-- 3. 逐笔成交
+-- =========================================================================
+CREATE TABLE IF NOT EXISTS market_trade (
+    exchange     VARCHAR(32)     NOT NULL,
+    symbol       VARCHAR(64)     NOT NULL,
+    trade_id     VARCHAR(64)     NOT NULL,
+    "time"       TIMESTAMPTZ     NOT NULL,
+    price        NUMERIC(30, 8)  NOT NULL,
+    quantity     NUMERIC(30, 8)  NOT NULL,
+    side         VARCHAR(16)     NULL,
+    PRIMARY KEY (exchange, symbol, trade_id, "time")
+);
+SELECT create_hypertable('market_trade', 'time',
+    chunk_time_interval => INTERVAL '1 day', if_not_exists => TRUE);
+
+-- =========================================================================
+-- 4. 压缩 (按标的分段，超过期限的 chunk 转为列存)
+-- =========================================================================
+ALTER TABLE market_bar SET (
+    timescaledb.compress,
+    timescaledb.compress_segmentby = 'exchange, symbol, bar_period, "type"',
+    timescaledb.compress_orderby = 'start_time DESC'
+);
+SELECT add_compression_policy('market_bar', INTERVAL '30 days', if_not_exists => TRUE);
+
+ALTER TABLE market_tick SET (
+    timescaledb.compress,
+    timescaledb.compress_segmentby = 'exchange, symbol',
+    timescaledb.compress_orderby = '"time" DESC'
+);
+SELECT add_compression_policy('market_tick', INTERVAL '3 days', if_not_exists => TRUE);
+
+ALTER TABLE market_trade SET (
+    timescaledb.compress,
+    timescaledb.compress_segmentby = 'exchange, symbol',
+    timescaledb.compress_orderby = '"time" DESC'
+);
+SELECT add_compression_policy('market_trade', INTERVAL '3 days', if_not_exists => TRUE);
+
+-- =========================================================================
+-- 5. 连续聚合: M1 -> M5 / M15 / H1 / H4 / D1
+-- =========================================================================
+-- WITH NO DATA 允许在迁移事务内创建；历史数据由刷新策略 (或手动 refresh) 补齐。
+-- materialized_only = false: 查询时实时合并尚未物化的最新 M1。
+
+CREATE MATERIALIZED VIEW IF NOT EXISTS market_bar_m5
+WITH (timescaledb.continuous, timescaledb.materialized_only = false) AS
+SELECT exchange, symbol, "type",
+       time_bucket(INTERVAL '5 minutes', start_time) AS bucket,
+       first(open, start_time) AS open,
+       max(high) AS high,
+       min(low) AS low,
+       last(close, start_time) AS close,
+       sum(volume) AS volume,
+       sum(amount) AS amount,
+       max(end_time) AS end_time
+FROM market_bar
+WHERE bar_period = 'M1'
+GROUP BY exchange, symbol, "type", bucket
+WITH NO DATA;
+
+CREATE MATERIALIZED VIEW IF NOT EXISTS market_bar_m15
+WITH (timescaledb.continuous, timescaledb.materialized_only = false) AS
+SELECT exchange, symbol, "type",
+       time_bucket(INTERVAL '15 minutes', start_time) AS bucket,
+       first(open, start_time) AS open,
+       max(high) AS high,
+       min(low) AS low,
+       last(close, start_time) AS close,
+       sum(volume) AS volume,
+       sum(amount) AS amount,
+       max(end_time) AS end_time
+FROM market_bar
+WHERE bar_period = 'M1'
+GROUP BY exchange, symbol, "type", bucket
+WITH NO DATA;
+
+CREATE MATERIALIZED VIEW IF NOT EXISTS market_bar_h1
+WITH (timescaledb.continuous, timescaledb.materialized_only = false) AS
+SELECT exchange, symbol, "type",
+       time_bucket(INTERVAL '1 hour', start_time) AS bucket,
+       first(open, start_time) AS open,
+       max(high) AS high,
+       min(low) AS low,
+       last(close, start_time) AS close,
+       sum(volume) AS volume,
+       sum(amount) AS amount,
+       max(end_time) AS end_time
+FROM market_bar
+WHERE bar_period = 'M1'
+GROUP BY exchange, symbol, "type", bucket
+WITH NO DATA;
+
+CREATE MATERIALIZED VIEW IF NOT EXISTS market_bar_h4
+WITH (timescaledb.continuous, timescaledb.materialized_only = false) AS
+SELECT exchange, symbol, "type",
+       time_bucket(INTERVAL '4 hours', start_time) AS bucket,
+       first(open, start_time) AS open,
+       max(high) AS high,
+       min(low) AS low,
+       last(close, start_time) AS close,
+       sum(volume) AS volume,
+       sum(amount) AS amount,
+       max(end_time) AS end_time
+FROM market_bar
+WHERE bar_period = 'M1'
+GROUP BY exchange, symbol, "type", bucket
+WITH NO DATA;
+
+CREATE MATERIALIZED VIEW IF NOT EXISTS market_bar_d1
+WITH (timescaledb.continuous, timescaledb.materialized_only = false) AS
+SELECT exchange, symbol, "type",
+       time_bucket(INTERVAL '1 day', start_time) AS bucket,
+       first(open, start_time) AS open,
+       max(high) AS high,
+       min(low) AS low,
+       last(close, start_time) AS close,
+       sum(volume) AS volume,
+       sum(amount) AS amount,
+       max(end_time) AS end_time
+FROM market_bar
+WHERE bar_period = 'M1'
+GROUP BY exchange, symbol, "type", bucket
+WITH NO DATA;
+
+SELECT add_continuous_aggregate_policy('market_bar_m5',
+    start_offset => INTERVAL '1 day', end_offset => INTERVAL '5 minutes',
+    schedule_interval => INTERVAL '5 minutes', if_not_exists => TRUE);
+SELECT add_continuous_aggregate_policy('market_bar_m15',
+    start_offset => INTERVAL '1 day', end_offset => INTERVAL '15 minutes',
+    schedule_interval => INTERVAL '15 minutes', if_not_exists => TRUE);
+SELECT add_continuous_aggregate_policy('market_bar_h1',
+    start_offset => INTERVAL '3 days', end_offset => INTERVAL '1 hour',
+    schedule_interval => INTERVAL '1 hour', if_not_exists => TRUE);
+SELECT add_continuous_aggregate_policy('market_bar_h4',
+    start_offset => INTERVAL '7 days', end_offset => INTERVAL '4 hours',
+    schedule_interval => INTERVAL '1 hour', if_not_exists => TRUE);
+SELECT add_continuous_aggregate_policy('market_bar_d1',
+    start_offset => INTERVAL '30 days', end_offset => INTERVAL '1 day',
+    schedule_interval => INTERVAL '1 day', if_not_exists => TRUE);
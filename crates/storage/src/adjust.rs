@@ -0,0 +1,227 @@
+use crate::repository::corporate_action_repo::CorporateActionRepository;
+use crate::repository::market_repo::BarSeriesKey;
+use crate::store::MarketDataStore;
+use anyhow::Result;
+use chrono::NaiveDate;
+use quant_core::enums::CorporateActionType;
+use quant_core::market::{CorporateAction, MarketBar};
+use quant_core::primitive::{CurrencyPair, Price, Quantity};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+// =========================================================================
+// 1. 复权方式
+// =========================================================================
+
+/// 复权方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AdjustMode {
+    /// 不复权 (原始价格)
+    None,
+    /// 向历史回溯调整 (前复权)：最新价格不变，除权日之前的价格按因子缩放
+    #[default]
+    Backward,
+    /// 向未来调整 (后复权)：最早价格不变，除权日之后的价格按因子放大
+    Forward,
+}
+
+/// 价格精度 (与 market_bar 的 DECIMAL 精度一致)
+const PRICE_DP: u32 = 8;
+
+/// 代码变更最多向前追溯的层数
+const MAX_RENAME_DEPTH: usize = 8;
+
+/// 单个除权事件的调整因子
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdjustmentEvent {
+    pub ex_date: NaiveDate,
+    /// 除权日之前的价格乘以该因子即与除权后可比
+    pub price_factor: Decimal,
+    /// 除权日之前的成交量乘以该因子 (只有拆股影响成交量)
+    pub volume_factor: Decimal,
+}
+
+// =========================================================================
+// 2. 纯函数复权
+// =========================================================================
+
+/// 由公司行动和原始 K 线计算除权事件
+///
+/// - 拆股 ratio: 价格因子 1/ratio，成交量因子 ratio
+/// - 现金分红 D: 价格因子 (C - D) / C，C 为除息日前最后一根 K 线的收盘价；
+///   找不到前收盘价或 D >= C 时忽略该分红
+/// - 代码变更不影响价格
+pub fn adjustment_events(bars: &[MarketBar], actions: &[CorporateAction]) -> Vec<AdjustmentEvent> {
+    let mut events: Vec<AdjustmentEvent> = actions
+        .iter()
+        .filter_map(|action| match action.action_type {
+            CorporateActionType::Split => {
+                let ratio = action.ratio.filter(|r| *r > Decimal::ZERO)?;
+                Some(AdjustmentEvent {
+                    ex_date: action.ex_date,
+                    price_factor: Decimal::ONE / ratio,
+                    volume_factor: ratio,
+                })
+            }
+            CorporateActionType::CashDividend => {
+                let amount = action.cash_amount.filter(|d| *d > Decimal::ZERO)?;
+                let prev_close = bars
+                    .iter()
+                    .filter(|b| b.start_time < action.ex_date)
+                    .max_by_key(|b| b.start_time)?
+                    .close
+                    .0;
+                (prev_close > amount).then(|| AdjustmentEvent {
+                    ex_date: action.ex_date,
+                    price_factor: (prev_close - amount) / prev_close,
+                    volume_factor: Decimal::ONE,
+                })
+            }
+            CorporateActionType::SymbolChange => None,
+        })
+        .collect();
+    events.sort_by_key(|e| e.ex_date);
+    events
+}
+
+/// 按复权方式调整 K 线 (返回新序列，原始数据不变)
+pub fn adjust_bars(
+    bars: &[MarketBar],
+    actions: &[CorporateAction],
+    mode: AdjustMode,
+) -> Vec<MarketBar> {
+    if mode == AdjustMode::None {
+        return bars.to_vec();
+    }
+    let events = adjustment_events(bars, actions);
+    bars.iter()
+        .map(|bar| {
+            let (price_mult, volume_mult) = cumulative_factor(&events, bar.start_time, mode);
+            let mut adjusted = bar.clone();
+            adjusted.open = Price((bar.open.0 * price_mult).round_dp(PRICE_DP));
+            adjusted.high = Price((bar.high.0 * price_mult).round_dp(PRICE_DP));
+            adjusted.low = Price((bar.low.0 * price_mult).round_dp(PRICE_DP));
+            adjusted.close = Price((bar.close.0 * price_mult).round_dp(PRICE_DP));
+            adjusted.volume = Quantity((bar.volume.0 * volume_mult).round_dp(PRICE_DP));
+            adjusted
+        })
+        .collect()
+}
+
+/// 某日的累计 (价格, 成交量) 乘数
+fn cumulative_factor(
+    events: &[AdjustmentEvent],
+    date: NaiveDate,
+    mode: AdjustMode,
+) -> (Decimal, Decimal) {
+    let mut price = Decimal::ONE;
+    let mut volume = Decimal::ONE;
+    for event in events {
+        match mode {
+            AdjustMode::Backward if date < event.ex_date => {
+                price *= event.price_factor;
+                volume *= event.volume_factor;
+            }
+            AdjustMode::Forward if date >= event.ex_date => {
+                price /= event.price_factor;
+                volume /= event.volume_factor;
+            }
+            _ => {}
+        }
+    }
+    (price, volume)
+}
+
+// =========================================================================
+// 3. 复权行情服务 (读库 + 代码变更拼接 + 复权)
+// =========================================================================
+
+pub struct AdjustedBarService<'a> {
+    market: &'a dyn MarketDataStore,
+    /// None 表示没有公司行动数据 (e.g. 本地 Parquet 离线回测)，K 线原样返回
+    actions: Option<&'a CorporateActionRepository>,
+}
+
+impl<'a> AdjustedBarService<'a> {
+    pub fn new(market: &'a dyn MarketDataStore, actions: &'a CorporateActionRepository) -> Self {
+        Self {
+            market,
+            actions: Some(actions),
+        }
+    }
+
+    /// 不做复权 / 改名拼接，只按日期窗口读取原始 K 线
+    pub fn without_actions(market: &'a dyn MarketDataStore) -> Self {
+        Self {
+            market,
+            actions: None,
+        }
+    }
+
+    /// 查询 [start, end] 的复权 K 线
+    ///
+    /// 标的曾改名时，旧代码在改名前的 K 线会拼接进来并标记为当前代码。
+    /// 后复权的基准是整条序列最早的一根 K 线，而不是 `start`。
+    pub async fn load(
+        &self,
+        key: &BarSeriesKey,
+        start: NaiveDate,
+        end: NaiveDate,
+        mode: AdjustMode,
+    ) -> Result<Vec<MarketBar>> {
+        let exchange = key.exchange.to_string();
+        let current = CurrencyPair::from_str(&key.symbol)?;
+
+        let mut bars = self.market.find_series_bars(key).await?;
+        let Some(repo) = self.actions else {
+            bars.retain(|b| b.start_time >= start && b.start_time <= end);
+            bars.sort_by_key(|b| b.start_time);
+            return Ok(bars);
+        };
+        let mut actions = repo.find_by_symbol(&exchange, &key.symbol).await?;
+
+        // 沿代码变更向前追溯: 旧代码只取改名日之前的数据
+        let mut symbol = key.symbol.clone();
+        let mut cutoff: Option<NaiveDate> = None;
+        for _ in 0..MAX_RENAME_DEPTH {
+            let renames = repo.find_renamed_to(&exchange, &symbol).await?;
+            let Some(rename) = renames
+                .into_iter()
+                .find(|r| cutoff.is_none_or(|c| r.ex_date <= c))
+            else {
+                break;
+            };
+            let old_key = BarSeriesKey {
+                symbol: rename.symbol.to_string(),
+                ..key.clone()
+            };
+            let old_bars = self.market.find_series_bars(&old_key).await?;
+            bars.extend(
+                old_bars
+                    .into_iter()
+                    .filter(|b| b.start_time < rename.ex_date)
+                    .map(|mut b| {
+                        b.symbol = current.clone();
+                        b
+                    }),
+            );
+            actions.extend(
+                repo.find_by_symbol(&exchange, &old_key.symbol)
+                    .await?
+                    .into_iter()
+                    .filter(|a| a.ex_date < rename.ex_date),
+            );
+            symbol = old_key.symbol;
+            cutoff = Some(rename.ex_date);
+        }
+        bars.sort_by_key(|b| b.start_time);
+
+        let adjusted = adjust_bars(&bars, &actions, mode);
+        Ok(adjusted
+            .into_iter()
+            .filter(|b| b.start_time >= start && b.start_time <= end)
+            .collect())
+    }
+}
@@ -0,0 +1,35 @@
+use anyhow::Result;
+use clap::Parser;
+use quant_storage::import::{self, ImportArgs};
+use quant_storage::Storage;
+use tracing_subscriber::EnvFilter;
+
+// =========================================================================
+// K 线导入工具
+// =========================================================================
+//
+// cargo run -p quant-storage --bin bar-import -- data/1d --timezone America/New_York \
+//     --checkpoint data/1d.checkpoint.json --rejects data/1d.rejects.csv
+
+/// 把 CSV / Parquet K 线批量导入 market_bar 表
+#[derive(Debug, Parser)]
+#[command(name = "bar-import", version)]
+struct Cli {
+    #[command(flatten)]
+    args: ImportArgs,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    dotenvy::dotenv().ok();
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env().add_directive(tracing::Level::INFO.into()))
+        .with_target(false)
+        .compact()
+        .init();
+
+    let storage = Storage::from_env().await?;
+    import::run(&storage, &cli.args).await?;
+    Ok(())
+}
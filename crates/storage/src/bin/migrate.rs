@@ -0,0 +1,34 @@
+use anyhow::Result;
+use clap::Parser;
+use quant_storage::migrate::{self, MigrateArgs};
+use quant_storage::Storage;
+use tracing_subscriber::EnvFilter;
+
+// =========================================================================
+// 数据库迁移工具
+// =========================================================================
+//
+// cargo run -p quant-storage --bin migrate            # 执行未执行的迁移
+// cargo run -p quant-storage --bin migrate -- --status
+
+/// 对 DATABASE_URL 执行 crates/storage/migrations 下的迁移
+#[derive(Debug, Parser)]
+#[command(name = "migrate", version)]
+struct Cli {
+    #[command(flatten)]
+    args: MigrateArgs,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    dotenvy::dotenv().ok();
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env().add_directive(tracing::Level::INFO.into()))
+        .with_target(false)
+        .compact()
+        .init();
+
+    let storage = Storage::from_env().await?;
+    migrate::run(&storage, &cli.args).await
+}
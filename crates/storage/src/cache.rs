@@ -0,0 +1,628 @@
+use crate::lock::{FencedOut, Fencing};
+use crate::redis::RedisService;
+use crate::store::{day_start, AccountStore, OrderStore};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use quant_core::account::{Asset, Position};
+use quant_core::enums::{BarPeriod, OrderStatus};
+use quant_core::market::{MarketBar, MarketTick};
+use quant_core::oms::Order;
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::warn;
+use uuid::Uuid;
+
+// =========================================================================
+// Redis 热点缓存
+// =========================================================================
+//
+// MySQL 仍是唯一的事实来源，Redis 只保存策略 / Agent 高频读取的最新状态:
+//   {prefix}:quote:{exchange}:{symbol}                        最新报价 (Hash: t / v)
+//   {prefix}:bar:{exchange}:{symbol}:{period}:{type}          最新 K 线 (Hash: t / v)
+//   {prefix}:assets:{account}     field = {exchange}:{currency}         余额快照
+//   {prefix}:positions:{account}  field = {exchange}:{symbol}:{side}    持仓快照
+//   {prefix}:orders:{strategy}    field = order_uuid                    未完结订单
+//
+// 值统一用 JSON 编码 (与事件录制格式一致)，便于 redis-cli 直接排查。
+// 设置了 `Fencing` 时，账户快照和订单的写入在 Lua 中先比较锁的 fencing 计数器，
+// 旧任期的写入返回 `FencedOut`；行情不受主节点身份约束，不做校验。
+
+/// 快照标记字段：Hash 中存在该字段才说明是完整快照 (空账户也能命中缓存)
+const SNAPSHOT_FIELD: &str = "__snapshot";
+
+/// 没有策略归属的订单 (手工单) 归入这个分组
+pub const MANUAL_STRATEGY: &str = "manual";
+
+/// 只接受不比缓存更旧的数据 (乱序到达 / 回补历史时不会覆盖最新值)
+///
+/// KEYS[1] = key, ARGV[1] = 时间戳 (毫秒), ARGV[2] = JSON, ARGV[3] = TTL (秒，0 为不过期)
+const SET_LATEST_SCRIPT: &str = r#"
+    local current = redis.call("HGET", KEYS[1], "t")
+    if current and tonumber(current) > tonumber(ARGV[1]) then
+        return 0
+    end
+    redis.call("HSET", KEYS[1], "t", ARGV[1], "v", ARGV[2])
+    if tonumber(ARGV[3]) > 0 then
+        redis.call("EXPIRE", KEYS[1], ARGV[3])
+    end
+    return 1
+"#;
+
+/// 计数器没有超过自己的 token 时才写入 Hash
+///
+/// KEYS[1] = fencing 计数器, KEYS[2] = Hash, ARGV[1] = fencing token,
+/// ARGV[2] = SET / DEL / REPLACE / DROP, ARGV[3] = TTL (秒，0 为不过期), ARGV[4..] = field (/ value)
+const FENCED_HASH_SCRIPT: &str = r#"
+    local current = redis.call("GET", KEYS[1])
+    if current and tonumber(current) > tonumber(ARGV[1]) then
+        return 0
+    end
+    local mode = ARGV[2]
+    if mode == "REPLACE" or mode == "DROP" then
+        redis.call("DEL", KEYS[2])
+    end
+    if mode == "DEL" then
+        for i = 4, #ARGV do
+            redis.call("HDEL", KEYS[2], ARGV[i])
+        end
+    elseif #ARGV >= 5 then
+        redis.call("HSET", KEYS[2], unpack(ARGV, 4))
+        if tonumber(ARGV[3]) > 0 then
+            redis.call("EXPIRE", KEYS[2], ARGV[3])
+        end
+    end
+    return 1
+"#;
+
+/// 受 Fencing 保护的 Hash 写入
+enum HashWrite<'a> {
+    Set {
+        field: &'a str,
+        value: String,
+        ttl_secs: u64,
+    },
+    Del {
+        field: &'a str,
+    },
+    /// 整体替换 (空列表等同于删除)
+    Replace {
+        entries: Vec<(String, String)>,
+        ttl_secs: u64,
+    },
+    Drop,
+}
+
+/// 热点缓存配置 (引擎配置中的 `[storage.cache]`)
+///
+/// TTL 单位为秒，0 表示不过期。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Key 前缀，多套环境共用一个 Redis 时用来隔离
+    pub key_prefix: String,
+    /// 最新报价：行情断流后尽快失效，避免策略拿陈旧价格下单
+    pub quote_ttl_secs: u64,
+    pub bar_ttl_secs: u64,
+    /// 余额 / 持仓快照
+    pub account_ttl_secs: u64,
+    /// 未完结订单 (每次写入都会续期)
+    pub order_ttl_secs: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            key_prefix: "photon".to_string(),
+            quote_ttl_secs: 30,
+            bar_ttl_secs: 86_400,
+            account_ttl_secs: 3_600,
+            order_ttl_secs: 86_400,
+        }
+    }
+}
+
+fn ttl(seconds: u64) -> Option<u64> {
+    (seconds > 0).then_some(seconds)
+}
+
+fn encode<T: Serialize>(value: &T) -> Result<String> {
+    serde_json::to_string(value).context("Failed to encode cache value")
+}
+
+fn decode<T: DeserializeOwned>(key: &str, raw: &str) -> Result<T> {
+    serde_json::from_str(raw).with_context(|| format!("Corrupted cache value at {}", key))
+}
+
+/// 类型化的 Redis 热点缓存 (行情 / 账户 / 订单)
+#[derive(Clone)]
+pub struct HotCache {
+    redis: RedisService,
+    config: CacheConfig,
+    fencing: Fencing,
+}
+
+impl HotCache {
+    pub fn new(redis: RedisService, config: CacheConfig) -> Self {
+        Self {
+            redis,
+            config,
+            fencing: Fencing::default(),
+        }
+    }
+
+    /// 账户快照 / 订单写入按当前任期的 Fence 校验
+    pub fn with_fencing(mut self, fencing: Fencing) -> Self {
+        self.fencing = fencing;
+        self
+    }
+
+    pub fn config(&self) -> &CacheConfig {
+        &self.config
+    }
+
+    /// 写 Hash：没有 Fence 时直接写，有 Fence 时在 Lua 中校验后再写
+    async fn write_hash(&self, key: &str, write: HashWrite<'_>) -> Result<()> {
+        let Some(fence) = self.fencing.current() else {
+            return match write {
+                HashWrite::Set {
+                    field,
+                    value,
+                    ttl_secs,
+                } => self.redis.hset(key, field, &value, ttl(ttl_secs)).await,
+                HashWrite::Del { field } => self.redis.hdel(key, field).await,
+                HashWrite::Replace { entries, ttl_secs } => {
+                    self.redis.hreplace(key, &entries, ttl(ttl_secs)).await
+                }
+                HashWrite::Drop => self.redis.delete(key).await,
+            };
+        };
+        let (mode, ttl_secs, mut args) = match write {
+            HashWrite::Set {
+                field,
+                value,
+                ttl_secs,
+            } => ("SET", ttl_secs, vec![field.to_string(), value]),
+            HashWrite::Del { field } => ("DEL", 0, vec![field.to_string()]),
+            HashWrite::Replace { entries, ttl_secs } => (
+                "REPLACE",
+                ttl_secs,
+                entries.into_iter().flat_map(|(f, v)| [f, v]).collect(),
+            ),
+            HashWrite::Drop => ("DROP", 0, Vec::new()),
+        };
+        let mut argv = vec![
+            fence.token.to_string(),
+            mode.to_string(),
+            ttl_secs.to_string(),
+        ];
+        argv.append(&mut args);
+        let argv: Vec<&str> = argv.iter().map(String::as_str).collect();
+        let written: i32 = self
+            .redis
+            .exec_lua_script(FENCED_HASH_SCRIPT, &[&fence.key, key], &argv)
+            .await?;
+        if written == 0 {
+            return Err(FencedOut {
+                resource: key.to_string(),
+                token: fence.token,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    fn key(&self, parts: &[&str]) -> String {
+        let mut key = self.config.key_prefix.clone();
+        for part in parts {
+            key.push(':');
+            key.push_str(part);
+        }
+        key
+    }
+
+    async fn set_latest<T: Serialize>(
+        &self,
+        key: &str,
+        time: DateTime<Utc>,
+        value: &T,
+        ttl_secs: u64,
+    ) -> Result<bool> {
+        let time = time.timestamp_millis().to_string();
+        let value = encode(value)?;
+        let ttl_secs = ttl_secs.to_string();
+        let written: i32 = self
+            .redis
+            .exec_lua_script(SET_LATEST_SCRIPT, &[key], &[&time, &value, &ttl_secs])
+            .await?;
+        Ok(written == 1)
+    }
+
+    async fn get_latest<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        match self.redis.hget(key, "v").await? {
+            Some(raw) => decode(key, &raw).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// 读取快照 Hash；没有快照标记时返回 None (调用方应回源数据库)
+    async fn get_snapshot<T: DeserializeOwned>(&self, key: &str) -> Result<Option<Vec<T>>> {
+        let mut entries = self.redis.hget_all(key).await?;
+        if entries.remove(SNAPSHOT_FIELD).is_none() {
+            return Ok(None);
+        }
+        entries
+            .values()
+            .map(|raw| decode(key, raw))
+            .collect::<Result<Vec<T>>>()
+            .map(Some)
+    }
+
+    async fn set_snapshot<'a, T: Serialize + 'a>(
+        &self,
+        key: &str,
+        items: impl Iterator<Item = (String, &'a T)>,
+        ttl_secs: u64,
+    ) -> Result<()> {
+        let mut entries = vec![(SNAPSHOT_FIELD.to_string(), Utc::now().to_rfc3339())];
+        for (field, item) in items {
+            entries.push((field, encode(item)?));
+        }
+        self.write_hash(key, HashWrite::Replace { entries, ttl_secs })
+            .await
+    }
+
+    // =========================================================================
+    // 1. 行情
+    // =========================================================================
+
+    /// 写入最新报价，返回是否生效 (比缓存旧的报价会被忽略)
+    pub async fn set_quote(&self, tick: &MarketTick) -> Result<bool> {
+        let key = self.key(&[
+            "quote",
+            &tick.exchange.to_string(),
+            &tick.symbol.to_string(),
+        ]);
+        self.set_latest(&key, tick.time, tick, self.config.quote_ttl_secs)
+            .await
+    }
+
+    pub async fn latest_quote(&self, exchange: &str, symbol: &str) -> Result<Option<MarketTick>> {
+        self.get_latest(&self.key(&["quote", exchange, symbol]))
+            .await
+    }
+
+    /// 写入最新 K 线，返回是否生效 (回补历史时不会覆盖更新的 K 线)
+    pub async fn set_bar(&self, bar: &MarketBar) -> Result<bool> {
+        let key = self.bar_key(
+            &bar.exchange.to_string(),
+            &bar.symbol.to_string(),
+            bar.bar_period,
+            bar.trade_type,
+        );
+        self.set_latest(
+            &key,
+            day_start(bar.start_time),
+            bar,
+            self.config.bar_ttl_secs,
+        )
+        .await
+    }
+
+    pub async fn latest_bar(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        bar_period: BarPeriod,
+        trade_type: u8,
+    ) -> Result<Option<MarketBar>> {
+        self.get_latest(&self.bar_key(exchange, symbol, bar_period, trade_type))
+            .await
+    }
+
+    fn bar_key(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        bar_period: BarPeriod,
+        trade_type: u8,
+    ) -> String {
+        self.key(&[
+            "bar",
+            exchange,
+            symbol,
+            &bar_period.to_string(),
+            &trade_type.to_string(),
+        ])
+    }
+
+    // =========================================================================
+    // 2. 账户 (整账户快照)
+    // =========================================================================
+
+    pub async fn set_assets(&self, account_name: &str, assets: &[Asset]) -> Result<()> {
+        let items = assets
+            .iter()
+            .map(|a| (format!("{}:{}", a.exchange, a.currency), a));
+        self.set_snapshot(
+            &self.key(&["assets", account_name]),
+            items,
+            self.config.account_ttl_secs,
+        )
+        .await
+    }
+
+    /// 账户余额快照 (None = 未缓存)
+    pub async fn assets(&self, account_name: &str) -> Result<Option<Vec<Asset>>> {
+        self.get_snapshot(&self.key(&["assets", account_name]))
+            .await
+    }
+
+    pub async fn set_positions(&self, account_name: &str, positions: &[Position]) -> Result<()> {
+        let items = positions
+            .iter()
+            .map(|p| (format!("{}:{}:{}", p.exchange, p.symbol, p.side), p));
+        self.set_snapshot(
+            &self.key(&["positions", account_name]),
+            items,
+            self.config.account_ttl_secs,
+        )
+        .await
+    }
+
+    /// 账户持仓快照 (None = 未缓存)
+    pub async fn positions(&self, account_name: &str) -> Result<Option<Vec<Position>>> {
+        self.get_snapshot(&self.key(&["positions", account_name]))
+            .await
+    }
+
+    /// 丢弃账户快照，下次读取时回源
+    pub async fn invalidate_account(&self, account_name: &str) -> Result<()> {
+        self.write_hash(&self.key(&["assets", account_name]), HashWrite::Drop)
+            .await?;
+        self.write_hash(&self.key(&["positions", account_name]), HashWrite::Drop)
+            .await
+    }
+
+    // =========================================================================
+    // 3. 未完结订单 (按策略分组)
+    // =========================================================================
+
+    fn orders_key(&self, strategy_uuid: Option<&str>) -> String {
+        self.key(&["orders", strategy_uuid.unwrap_or(MANUAL_STRATEGY)])
+    }
+
+    /// 跟踪订单最新状态：未终结则写入，终结 (成交 / 撤单 / 拒绝) 则移除
+    pub async fn put_order(&self, order: &Order) -> Result<()> {
+        let key = self.orders_key(order.strategy_uuid.as_deref());
+        let write = if order.status.is_open() {
+            HashWrite::Set {
+                field: &order.uuid,
+                value: encode(order)?,
+                ttl_secs: self.config.order_ttl_secs,
+            }
+        } else {
+            HashWrite::Del { field: &order.uuid }
+        };
+        self.write_hash(&key, write).await
+    }
+
+    /// 用数据库中的未完结订单重建某个策略的缓存 (OMS 启动时调用)
+    pub async fn set_open_orders(
+        &self,
+        strategy_uuid: Option<&str>,
+        orders: &[Order],
+    ) -> Result<()> {
+        let entries = orders
+            .iter()
+            .filter(|o| o.status.is_open())
+            .map(|o| Ok((o.uuid.clone(), encode(o)?)))
+            .collect::<Result<Vec<_>>>()?;
+        self.write_hash(
+            &self.orders_key(strategy_uuid),
+            HashWrite::Replace {
+                entries,
+                ttl_secs: self.config.order_ttl_secs,
+            },
+        )
+        .await
+    }
+
+    /// 某策略的未完结订单 (按创建时间正序；`None` 为手工单)
+    pub async fn open_orders(&self, strategy_uuid: Option<&str>) -> Result<Vec<Order>> {
+        let key = self.orders_key(strategy_uuid);
+        let mut orders = self
+            .redis
+            .hget_all(&key)
+            .await?
+            .values()
+            .map(|raw| decode::<Order>(&key, raw))
+            .collect::<Result<Vec<_>>>()?;
+        orders.sort_by_key(|o| o.gmt_create);
+        Ok(orders)
+    }
+
+    /// 用一批未完结订单 (通常来自数据库) 按策略整体覆盖缓存，返回涉及的策略数
+    pub async fn replace_open_orders(&self, orders: &[Order]) -> Result<usize> {
+        let mut by_strategy: HashMap<Option<&str>, Vec<Order>> = HashMap::new();
+        for order in orders {
+            by_strategy
+                .entry(order.strategy_uuid.as_deref())
+                .or_default()
+                .push(order.clone());
+        }
+        for (strategy_uuid, orders) in &by_strategy {
+            self.set_open_orders(*strategy_uuid, orders).await?;
+        }
+        Ok(by_strategy.len())
+    }
+}
+
+// =========================================================================
+// 写穿 (Write-through) 存储包装
+// =========================================================================
+//
+// 包装任意后端：写操作先落库再刷新缓存，读操作优先命中缓存。
+// 缓存异常只记录告警，不影响数据库读写结果。
+
+/// 带热点缓存的订单存储 (写入后同步未完结订单缓存)
+pub struct CachedOrderStore {
+    inner: Arc<dyn OrderStore>,
+    cache: HotCache,
+}
+
+impl CachedOrderStore {
+    pub fn new(inner: Arc<dyn OrderStore>, cache: HotCache) -> Self {
+        Self { inner, cache }
+    }
+
+    async fn refresh(&self, order_uuid: Uuid) -> Result<()> {
+        if let Some(order) = self.inner.find_by_uuid(order_uuid).await? {
+            self.cache.put_order(&order).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl OrderStore for CachedOrderStore {
+    async fn insert(&self, order: &Order) -> Result<u64> {
+        let rows = self.inner.insert(order).await?;
+        if let Err(e) = self.cache.put_order(order).await {
+            warn!("Failed to cache order {}: {:?}", order.uuid, e);
+        }
+        Ok(rows)
+    }
+
+    async fn find_by_uuid(&self, order_uuid: Uuid) -> Result<Option<Order>> {
+        self.inner.find_by_uuid(order_uuid).await
+    }
+
+    async fn find_by_strategy(&self, strategy_uuid: Uuid) -> Result<Vec<Order>> {
+        self.inner.find_by_strategy(strategy_uuid).await
+    }
+
+    async fn find_by_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Order>> {
+        self.inner.find_by_range(start, end).await
+    }
+
+    async fn find_open_orders(&self) -> Result<Vec<Order>> {
+        self.inner.find_open_orders().await
+    }
+
+    async fn update_status(
+        &self,
+        order_uuid: Uuid,
+        status: OrderStatus,
+        exchange_order_id: Option<String>,
+        filled_qty: Decimal,
+        avg_price: Option<Decimal>,
+        fee: Option<Decimal>,
+    ) -> Result<()> {
+        self.inner
+            .update_status(
+                order_uuid,
+                status,
+                exchange_order_id,
+                filled_qty,
+                avg_price,
+                fee,
+            )
+            .await?;
+        if let Err(e) = self.refresh(order_uuid).await {
+            warn!("Failed to cache order {}: {:?}", order_uuid, e);
+        }
+        Ok(())
+    }
+}
+
+/// 缓存读取结果：异常按未命中处理 (回源数据库)
+fn cache_hit<T>(account_name: &str, hit: Result<Option<Vec<T>>>) -> Option<Vec<T>> {
+    match hit {
+        Ok(found) => found,
+        Err(e) => {
+            warn!("Cache read failed for {}: {:?}", account_name, e);
+            None
+        }
+    }
+}
+
+/// 带热点缓存的账户存储 (写入后重建整个账户快照，读取优先命中快照)
+pub struct CachedAccountStore {
+    inner: Arc<dyn AccountStore>,
+    cache: HotCache,
+}
+
+impl CachedAccountStore {
+    pub fn new(inner: Arc<dyn AccountStore>, cache: HotCache) -> Self {
+        Self { inner, cache }
+    }
+
+    async fn refresh_assets(&self, account_name: &str) -> Result<Vec<Asset>> {
+        let assets = self.inner.find_assets_by_account(account_name).await?;
+        if let Err(e) = self.cache.set_assets(account_name, &assets).await {
+            warn!("Failed to cache assets of {}: {:?}", account_name, e);
+        }
+        Ok(assets)
+    }
+
+    async fn refresh_positions(&self, account_name: &str) -> Result<Vec<Position>> {
+        let positions = self.inner.find_positions_by_account(account_name).await?;
+        if let Err(e) = self.cache.set_positions(account_name, &positions).await {
+            warn!("Failed to cache positions of {}: {:?}", account_name, e);
+        }
+        Ok(positions)
+    }
+}
+
+#[async_trait]
+impl AccountStore for CachedAccountStore {
+    async fn upsert_asset(&self, asset: &Asset) -> Result<u64> {
+        let rows = self.inner.upsert_asset(asset).await?;
+        self.refresh_assets(&asset.account_name).await?;
+        Ok(rows)
+    }
+
+    async fn find_assets_by_account(&self, account_name: &str) -> Result<Vec<Asset>> {
+        match cache_hit(account_name, self.cache.assets(account_name).await) {
+            Some(assets) => Ok(assets),
+            None => self.refresh_assets(account_name).await,
+        }
+    }
+
+    async fn find_asset(
+        &self,
+        account_name: &str,
+        exchange: &str,
+        currency: &str,
+    ) -> Result<Option<Asset>> {
+        let assets = self.find_assets_by_account(account_name).await?;
+        Ok(assets
+            .into_iter()
+            .find(|a| a.exchange.to_string() == exchange && a.currency == currency))
+    }
+
+    async fn upsert_position(&self, pos: &Position) -> Result<u64> {
+        let rows = self.inner.upsert_position(pos).await?;
+        self.refresh_positions(&pos.account_name).await?;
+        Ok(rows)
+    }
+
+    async fn find_positions_by_account(&self, account_name: &str) -> Result<Vec<Position>> {
+        match cache_hit(account_name, self.cache.positions(account_name).await) {
+            Some(positions) => Ok(positions),
+            None => self.refresh_positions(account_name).await,
+        }
+    }
+
+    async fn clear_positions(&self, account_name: &str) -> Result<()> {
+        self.inner.clear_positions(account_name).await?;
+        if let Err(e) = self.cache.set_positions(account_name, &[]).await {
+            warn!("Failed to cache positions of {}: {:?}", account_name, e);
+        }
+        Ok(())
+    }
+}
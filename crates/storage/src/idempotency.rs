@@ -0,0 +1,162 @@
+use crate::redis::RedisService;
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::marker::PhantomData;
+use std::time::Duration;
+use uuid::Uuid;
+
+// =========================================================================
+// 幂等键 (防止多个服务 / 重试重复下单)
+// =========================================================================
+//
+// Redis 中的 Key: {prefix}:idem:{idempotency_key}  (HASH)
+//   state    PENDING / COMPLETED
+//   owner    占用者 token，只有占用者能写入结果或放弃
+//   outcome  完成后的结果 (JSON)
+//
+// 流程：`begin` 占位 (PENDING，短 TTL，占用者崩溃后自动过期)
+//      -> 执行请求 -> `complete` 记录结果 (COMPLETED，长 TTL)
+//      请求在发出前失败时 `abandon`，允许后续重试。
+// 其他调用方在占位期间看到 InFlight，完成后直接拿到记录的结果。
+
+/// KEYS[1] = 幂等键, ARGV[1] = 占用者 token, ARGV[2] = 占位 TTL (毫秒)
+const BEGIN_SCRIPT: &str = r#"
+    if redis.call("EXISTS", KEYS[1]) == 1 then
+        return redis.call("HMGET", KEYS[1], "state", "outcome")
+    end
+    redis.call("HSET", KEYS[1], "state", "PENDING", "owner", ARGV[1])
+    redis.call("PEXPIRE", KEYS[1], ARGV[2])
+    return {"STARTED", false}
+"#;
+
+/// KEYS[1] = 幂等键, ARGV[1] = 占用者 token, ARGV[2] = 结果 JSON, ARGV[3] = 结果 TTL (毫秒)
+const COMPLETE_SCRIPT: &str = r#"
+    if redis.call("HGET", KEYS[1], "owner") ~= ARGV[1]
+        or redis.call("HGET", KEYS[1], "state") ~= "PENDING" then
+        return 0
+    end
+    redis.call("HSET", KEYS[1], "state", "COMPLETED", "outcome", ARGV[2])
+    redis.call("PEXPIRE", KEYS[1], ARGV[3])
+    return 1
+"#;
+
+/// KEYS[1] = 幂等键, ARGV[1] = 占用者 token
+const ABANDON_SCRIPT: &str = r#"
+    if redis.call("HGET", KEYS[1], "owner") == ARGV[1]
+        and redis.call("HGET", KEYS[1], "state") == "PENDING" then
+        return redis.call("DEL", KEYS[1])
+    end
+    return 0
+"#;
+
+/// 占位凭证：只有持有者能 `complete` / `abandon`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdempotencyClaim {
+    pub key: String,
+    token: String,
+}
+
+/// `begin` 的结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum Idempotency<T> {
+    /// 首次出现，调用方获得占位，应执行请求并记录结果
+    Started(IdempotencyClaim),
+    /// 其他调用方正在执行同一请求
+    InFlight,
+    /// 已执行过，直接返回记录的结果
+    Completed(T),
+}
+
+/// 幂等键存储 (结果类型 `T` 需要可 JSON 序列化)
+#[derive(Clone)]
+pub struct IdempotencyStore<T> {
+    redis: RedisService,
+    key_prefix: String,
+    /// 结果保留时长：在此期间重复请求都返回同一结果
+    ttl: Duration,
+    /// 占位时长：应覆盖一次请求的最长耗时
+    pending_ttl: Duration,
+    _outcome: PhantomData<fn() -> T>,
+}
+
+impl<T: Serialize + DeserializeOwned> IdempotencyStore<T> {
+    pub fn new(redis: RedisService, ttl: Duration) -> Self {
+        Self {
+            redis,
+            key_prefix: "photon".to_string(),
+            ttl,
+            pending_ttl: Duration::from_secs(30),
+            _outcome: PhantomData,
+        }
+    }
+
+    /// Key 前缀，多套环境共用一个 Redis 时用来隔离
+    pub fn with_prefix(mut self, key_prefix: impl Into<String>) -> Self {
+        self.key_prefix = key_prefix.into();
+        self
+    }
+
+    pub fn with_pending_ttl(mut self, pending_ttl: Duration) -> Self {
+        self.pending_ttl = pending_ttl;
+        self
+    }
+
+    fn redis_key(&self, key: &str) -> String {
+        format!("{}:idem:{}", self.key_prefix, key)
+    }
+
+    /// 原子地检查并占位
+    pub async fn begin(&self, key: &str) -> Result<Idempotency<T>> {
+        let redis_key = self.redis_key(key);
+        let token = Uuid::new_v4().to_string();
+        let pending_ms = self.pending_ttl.as_millis().to_string();
+        let (state, outcome): (Option<String>, Option<String>) = self
+            .redis
+            .exec_lua_script(BEGIN_SCRIPT, &[&redis_key], &[&token, &pending_ms])
+            .await?;
+        match (state.as_deref(), outcome) {
+            (Some("STARTED"), _) => Ok(Idempotency::Started(IdempotencyClaim {
+                key: key.to_string(),
+                token,
+            })),
+            (Some("COMPLETED"), Some(raw)) => serde_json::from_str(&raw)
+                .map(Idempotency::Completed)
+                .with_context(|| format!("Corrupted idempotency outcome at {}", redis_key)),
+            // 占位中，或恰好在 EXISTS 与 HMGET 之间过期 (state 为空)
+            _ => Ok(Idempotency::InFlight),
+        }
+    }
+
+    /// 记录结果；占位已过期 / 被别人接管时返回 false
+    pub async fn complete(&self, claim: &IdempotencyClaim, outcome: &T) -> Result<bool> {
+        let outcome =
+            serde_json::to_string(outcome).context("Failed to encode idempotency outcome")?;
+        let ttl_ms = self.ttl.as_millis().to_string();
+        if ttl_ms == "0" {
+            bail!("Idempotency ttl must be > 0");
+        }
+        let written: i32 = self
+            .redis
+            .exec_lua_script(
+                COMPLETE_SCRIPT,
+                &[&self.redis_key(&claim.key)],
+                &[&claim.token, &outcome, &ttl_ms],
+            )
+            .await?;
+        Ok(written == 1)
+    }
+
+    /// 放弃占位 (请求未发出就失败)，之后同一个键可以重新 `begin`
+    pub async fn abandon(&self, claim: &IdempotencyClaim) -> Result<bool> {
+        let deleted: i32 = self
+            .redis
+            .exec_lua_script(
+                ABANDON_SCRIPT,
+                &[&self.redis_key(&claim.key)],
+                &[&claim.token],
+            )
+            .await?;
+        Ok(deleted == 1)
+    }
+}
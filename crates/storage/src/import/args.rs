@@ -0,0 +1,128 @@
+use super::{import_path, ColumnMapping, ExchangeMap, ImportOptions, ImportSummary};
+use crate::quality::QualityScanner;
+use crate::Storage;
+use anyhow::{bail, Result};
+use chrono_tz::Tz;
+use clap::Args;
+use quant_core::{BarPeriod, Exchange};
+use std::path::PathBuf;
+use std::str::FromStr;
+use tracing::{info, warn};
+
+// =========================================================================
+// 命令行参数 (bar-import 与 quant-engine import 共用)
+// =========================================================================
+
+#[derive(Debug, Clone, Args)]
+pub struct ImportArgs {
+    /// CSV / Parquet 文件或目录
+    pub path: PathBuf,
+    /// 源文件中只有基础资产代码时补上的计价货币
+    #[arg(long, default_value = "USD")]
+    pub quote: String,
+    #[arg(long, default_value = "D1", value_parser = parse_period)]
+    pub period: BarPeriod,
+    /// 列映射 JSON 文件 (字段同 ColumnMapping，缺省字段取默认列名)
+    #[arg(long)]
+    pub columns: Option<PathBuf>,
+    /// 覆盖单列映射，可重复，例如 `--column time=datetime --column type=`
+    #[arg(long = "column", value_name = "FIELD=COLUMN")]
+    pub column_overrides: Vec<String>,
+    /// 追加交易所代码映射，可重复，例如 `--exchange-map ARCX=NYSE`
+    #[arg(long = "exchange-map", value_name = "CODE=EXCHANGE")]
+    pub exchange_map: Vec<String>,
+    /// 源文件没有 exchange 列时使用的交易所
+    #[arg(long, value_parser = parse_exchange)]
+    pub exchange: Option<Exchange>,
+    /// 源文件没有 type 列时使用的交易条件类型
+    #[arg(long, default_value_t = 21)]
+    pub trade_type: u8,
+    /// 交易所时区 (IANA 名称，例如 America/New_York)
+    #[arg(long)]
+    pub timezone: Option<Tz>,
+    /// 每条 INSERT 的行数
+    #[arg(long, default_value_t = 500)]
+    pub batch_size: usize,
+    /// 断点续传进度文件
+    #[arg(long)]
+    pub checkpoint: Option<PathBuf>,
+    /// 被拒绝行明细输出 (CSV)
+    #[arg(long)]
+    pub rejects: Option<PathBuf>,
+    /// 导入后不做数据质量扫描
+    #[arg(long)]
+    pub no_scan: bool,
+}
+
+impl ImportArgs {
+    pub fn to_options(&self) -> Result<ImportOptions> {
+        let mut columns = match &self.columns {
+            Some(path) => ColumnMapping::from_file(path)?,
+            None => ColumnMapping::default(),
+        };
+        for spec in &self.column_overrides {
+            columns.set(spec)?;
+        }
+        let mut exchanges = ExchangeMap::default();
+        for spec in &self.exchange_map {
+            exchanges.add_spec(spec)?;
+        }
+        if self.batch_size == 0 {
+            bail!("--batch-size must be greater than 0");
+        }
+        Ok(ImportOptions {
+            quote: self.quote.clone(),
+            bar_period: self.period,
+            columns,
+            exchanges,
+            default_exchange: self.exchange,
+            default_trade_type: self.trade_type,
+            timezone: self.timezone,
+            batch_size: self.batch_size,
+            checkpoint: self.checkpoint.clone(),
+        })
+    }
+}
+
+/// 命令行入口: 导入 -> 导出被拒绝行 -> 对写入过的序列做数据质量扫描
+pub async fn run(storage: &Storage, args: &ImportArgs) -> Result<ImportSummary> {
+    let options = args.to_options()?;
+    info!("📥 Importing bars from {:?}", args.path);
+    let summary = import_path(storage.market_store(), &args.path, &options).await?;
+
+    if let Some(path) = &args.rejects {
+        summary.write_rejects_csv(std::fs::File::create(path)?)?;
+    }
+    info!(
+        "✅ Import finished: {} files ({} already done), {} inserted, {} updated, {} rejected",
+        summary.files,
+        summary.skipped_files,
+        summary.inserted,
+        summary.updated,
+        summary.rejected.len()
+    );
+
+    if storage.timescale().is_some() {
+        info!("Skipping quality scan: only supported on the MySQL market backend");
+    } else if !args.no_scan && !summary.series.is_empty() {
+        let scanner = QualityScanner::new(storage.market(), storage.quality());
+        let reports = scanner.scan_all(&summary.series).await?;
+        let dirty = reports.iter().filter(|r| !r.is_clean()).count();
+        if dirty > 0 {
+            warn!(
+                "⚠️ {} of {} imported series have data errors, see `quant-engine quality`",
+                dirty,
+                reports.len()
+            );
+        }
+    }
+    Ok(summary)
+}
+
+fn parse_exchange(s: &str) -> Result<Exchange, String> {
+    Exchange::from_str(&s.to_uppercase()).map_err(|_| format!("unknown exchange: {}", s))
+}
+
+fn parse_period(s: &str) -> Result<BarPeriod, String> {
+    BarPeriod::from_str(&s.to_uppercase()).map_err(|_| format!("unknown bar period: {}", s))
+}
@@ -0,0 +1,66 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+// =========================================================================
+// 断点续传
+// =========================================================================
+
+/// 单个文件的导入进度
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileProgress {
+    /// 文件大小，变化说明文件被替换过，需要从头导入
+    pub len: u64,
+    /// 已提交的记录数 (按文件内记录顺序，含被拒绝的行)
+    pub records_done: usize,
+    pub completed: bool,
+}
+
+/// 导入进度文件 (JSON)，每提交一批就落盘一次
+///
+/// 导入中断后用同一个 checkpoint 重跑: 已完成的文件整体跳过，未完成的文件从断点继续。
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Checkpoint {
+    files: BTreeMap<String, FileProgress>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl Checkpoint {
+    /// 加载进度文件，不存在时返回空进度
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut checkpoint: Self = if path.exists() {
+            let text = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read checkpoint {:?}", path))?;
+            serde_json::from_str(&text)
+                .with_context(|| format!("Corrupted checkpoint {:?}", path))?
+        } else {
+            Self::default()
+        };
+        checkpoint.path = path.to_path_buf();
+        Ok(checkpoint)
+    }
+
+    /// 查询文件进度；文件大小不一致时视为新文件
+    pub fn progress(&self, file: &Path, len: u64) -> Option<&FileProgress> {
+        self.files
+            .get(&Self::key(file))
+            .filter(|progress| progress.len == len)
+    }
+
+    /// 更新进度并落盘 (先写临时文件再 rename，避免中断时写出半个 JSON)
+    pub fn record(&mut self, file: &Path, progress: FileProgress) -> Result<()> {
+        self.files.insert(Self::key(file), progress);
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write checkpoint {:?}", tmp))?;
+        std::fs::rename(&tmp, &self.path)
+            .with_context(|| format!("Failed to write checkpoint {:?}", self.path))?;
+        Ok(())
+    }
+
+    fn key(file: &Path) -> String {
+        file.to_string_lossy().into_owned()
+    }
+}
@@ -0,0 +1,244 @@
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use quant_core::Exchange;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+// =========================================================================
+// 1. 列映射
+// =========================================================================
+
+/// 源文件列名 -> K 线字段的映射
+///
+/// 可选列为 `None` 时使用 [`super::ImportOptions`] 中的默认值
+/// (exchange -> default_exchange，symbol -> 文件名，type -> default_trade_type)。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColumnMapping {
+    pub exchange: Option<String>,
+    pub symbol: Option<String>,
+    pub open: String,
+    pub high: String,
+    pub low: String,
+    pub close: String,
+    pub volume: String,
+    pub amount: Option<String>,
+    /// K 线开始时间列
+    pub time: String,
+    pub trade_type: Option<String>,
+}
+
+impl Default for ColumnMapping {
+    /// 掘金导出格式: exchange,symbol,open,high,low,close,amount,volume,bob,eob,type
+    fn default() -> Self {
+        Self {
+            exchange: Some("exchange".to_string()),
+            symbol: Some("symbol".to_string()),
+            open: "open".to_string(),
+            high: "high".to_string(),
+            low: "low".to_string(),
+            close: "close".to_string(),
+            volume: "volume".to_string(),
+            amount: Some("amount".to_string()),
+            time: "bob".to_string(),
+            trade_type: Some("type".to_string()),
+        }
+    }
+}
+
+impl ColumnMapping {
+    /// 从 JSON 文件加载 (缺省字段取默认列名)
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read column mapping {:?}", path))?;
+        serde_json::from_str(&text).with_context(|| format!("Invalid column mapping {:?}", path))
+    }
+
+    /// 覆盖单个字段，格式 `field=column`；可选字段写 `field=` 表示不使用该列
+    pub fn set(&mut self, spec: &str) -> Result<()> {
+        let (field, column) = spec
+            .split_once('=')
+            .ok_or_else(|| anyhow!("Invalid column spec '{}', expected FIELD=COLUMN", spec))?;
+        let column = column.trim().to_string();
+        let optional = (!column.is_empty()).then(|| column.clone());
+        match field.trim() {
+            "exchange" => self.exchange = optional,
+            "symbol" => self.symbol = optional,
+            "amount" => self.amount = optional,
+            "type" | "trade_type" => self.trade_type = optional,
+            required => {
+                if column.is_empty() {
+                    bail!("Column '{}' is required", required);
+                }
+                match required {
+                    "open" => self.open = column,
+                    "high" => self.high = column,
+                    "low" => self.low = column,
+                    "close" => self.close = column,
+                    "volume" => self.volume = column,
+                    "time" => self.time = column,
+                    other => bail!("Unknown bar field '{}'", other),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 按表头解析出各字段的列下标 (表头大小写不敏感)
+    pub(crate) fn resolve(&self, headers: &[String]) -> Result<ColumnIndex> {
+        let find = |name: &str| {
+            headers
+                .iter()
+                .position(|h| h.eq_ignore_ascii_case(name))
+                .ok_or_else(|| anyhow!("Missing column '{}' (headers: {:?})", name, headers))
+        };
+        let find_opt = |name: &Option<String>| name.as_deref().map(find).transpose();
+        Ok(ColumnIndex {
+            exchange: find_opt(&self.exchange)?,
+            symbol: find_opt(&self.symbol)?,
+            open: find(&self.open)?,
+            high: find(&self.high)?,
+            low: find(&self.low)?,
+            close: find(&self.close)?,
+            volume: find(&self.volume)?,
+            amount: find_opt(&self.amount)?,
+            time: find(&self.time)?,
+            trade_type: find_opt(&self.trade_type)?,
+        })
+    }
+}
+
+/// 解析后的列下标
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ColumnIndex {
+    pub exchange: Option<usize>,
+    pub symbol: Option<usize>,
+    pub open: usize,
+    pub high: usize,
+    pub low: usize,
+    pub close: usize,
+    pub volume: usize,
+    pub amount: Option<usize>,
+    pub time: usize,
+    pub trade_type: Option<usize>,
+}
+
+// =========================================================================
+// 2. 交易所代码映射
+// =========================================================================
+
+/// 交易所代码 (MIC 等) -> [`Exchange`]
+///
+/// 查找顺序: 显式映射 -> 枚举名 (大小写不敏感)。都找不到时报错，不做兜底。
+#[derive(Debug, Clone)]
+pub struct ExchangeMap {
+    codes: HashMap<String, Exchange>,
+}
+
+impl Default for ExchangeMap {
+    fn default() -> Self {
+        let mut codes = HashMap::new();
+        codes.insert("XNAS".to_string(), Exchange::Nasdaq);
+        codes.insert("XNYS".to_string(), Exchange::Nyse);
+        Self { codes }
+    }
+}
+
+impl ExchangeMap {
+    pub fn insert(&mut self, code: &str, exchange: Exchange) {
+        self.codes.insert(code.trim().to_uppercase(), exchange);
+    }
+
+    /// 解析 `CODE=EXCHANGE` 形式的映射，例如 `ARCX=NYSE`
+    pub fn add_spec(&mut self, spec: &str) -> Result<()> {
+        let (code, name) = spec
+            .split_once('=')
+            .ok_or_else(|| anyhow!("Invalid exchange spec '{}', expected CODE=EXCHANGE", spec))?;
+        let exchange = Exchange::from_str(&name.trim().to_uppercase())
+            .map_err(|_| anyhow!("Unknown exchange '{}' in spec '{}'", name, spec))?;
+        self.insert(code, exchange);
+        Ok(())
+    }
+
+    pub fn resolve(&self, code: &str) -> Result<Exchange> {
+        let key = code.trim().to_uppercase();
+        if let Some(exchange) = self.codes.get(&key) {
+            return Ok(*exchange);
+        }
+        Exchange::from_str(&key).map_err(|_| {
+            anyhow!(
+                "Unknown exchange code '{}' (add a mapping with --exchange-map {}=<EXCHANGE>)",
+                code,
+                key
+            )
+        })
+    }
+}
+
+// =========================================================================
+// 3. 时间解析
+// =========================================================================
+
+const OFFSET_FORMATS: &[&str] = &[
+    "%Y-%m-%d %H:%M:%S%:z",
+    "%Y-%m-%d %H:%M:%S%z",
+    "%Y-%m-%d %H:%M:%S%.f%:z",
+    "%Y-%m-%dT%H:%M:%S%.f%:z",
+];
+
+const NAIVE_FORMATS: &[&str] = &[
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S%.f",
+];
+
+/// 把时间字段解析为交易日
+///
+/// - 带偏移 (如 `2015-01-02 00:00:00-05:00`): 指定 `timezone` 时先换算到该时区再取日期，
+///   否则直接取字符串本身的本地日期
+/// - Unix 时间戳 (秒 / 毫秒): 按 UTC 解释，再换算到 `timezone`
+/// - 不带偏移的时间 / 纯日期: 视为已经是交易所本地时间，直接取日期
+pub fn parse_trade_date(raw: &str, timezone: Option<Tz>) -> Result<NaiveDate> {
+    let raw = raw.trim();
+
+    if !raw.is_empty() && raw.bytes().all(|b| b.is_ascii_digit()) && raw.len() >= 10 {
+        let value: i64 = raw.parse()?;
+        let utc = match raw.len() {
+            10 => Utc.timestamp_opt(value, 0).single(),
+            13 => Utc.timestamp_millis_opt(value).single(),
+            _ => None,
+        }
+        .ok_or_else(|| anyhow!("Invalid unix timestamp '{}'", raw))?;
+        return Ok(match timezone {
+            Some(tz) => utc.with_timezone(&tz).date_naive(),
+            None => utc.date_naive(),
+        });
+    }
+
+    let with_offset = DateTime::parse_from_rfc3339(raw).ok().or_else(|| {
+        OFFSET_FORMATS
+            .iter()
+            .find_map(|fmt| DateTime::<FixedOffset>::parse_from_str(raw, fmt).ok())
+    });
+    if let Some(dt) = with_offset {
+        return Ok(match timezone {
+            Some(tz) => dt.with_timezone(&tz).date_naive(),
+            None => dt.date_naive(),
+        });
+    }
+
+    if let Some(dt) = NAIVE_FORMATS
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(raw, fmt).ok())
+    {
+        return Ok(dt.date());
+    }
+
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(raw, "%Y%m%d"))
+        .map_err(|_| anyhow!("Unrecognized time format '{}'", raw))
+}
@@ -0,0 +1,405 @@
+use crate::repository::market_repo::BarSeriesKey;
+use crate::store::MarketDataStore;
+use anyhow::{anyhow, bail, Context, Result};
+use chrono_tz::Tz;
+use quant_core::market::MarketBar;
+use quant_core::{BarPeriod, CurrencyPair, Exchange, Price, Quantity};
+use rust_decimal::Decimal;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tracing::{info, warn};
+use walkdir::WalkDir;
+
+mod args;
+mod checkpoint;
+mod mapping;
+mod source;
+
+pub use args::{run, ImportArgs};
+pub use checkpoint::{Checkpoint, FileProgress};
+pub use mapping::{parse_trade_date, ColumnMapping, ExchangeMap};
+pub use source::{require_format, SourceFormat};
+
+use mapping::ColumnIndex;
+use source::read_table;
+
+// =========================================================================
+// 1. 导入参数与结果
+// =========================================================================
+
+// CSV 示例 (默认列映射):
+// exchange,symbol,open,high,low,close,amount,volume,bob,eob,type
+// XNAS,AAL,54.28,54.6,53.07,53.91,579582022.1,10756705,2015-01-02 00:00:00-05:00,2015-01-02 00:00:00-05:00,21
+
+/// 导入参数
+#[derive(Debug, Clone)]
+pub struct ImportOptions {
+    /// 源文件里只有基础资产代码时补上的计价货币 (e.g. "USD")
+    pub quote: String,
+    pub bar_period: BarPeriod,
+    pub columns: ColumnMapping,
+    pub exchanges: ExchangeMap,
+    /// 没有 exchange 列时使用
+    pub default_exchange: Option<Exchange>,
+    /// 没有 type 列时使用 (21 = 盘中交易)
+    pub default_trade_type: u8,
+    /// 交易所时区，带偏移的时间先换算到该时区再取交易日
+    pub timezone: Option<Tz>,
+    /// 每条 INSERT 的行数
+    pub batch_size: usize,
+    /// 断点续传进度文件
+    pub checkpoint: Option<PathBuf>,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            quote: "USD".to_string(),
+            bar_period: BarPeriod::D1,
+            columns: ColumnMapping::default(),
+            exchanges: ExchangeMap::default(),
+            default_exchange: None,
+            default_trade_type: 21,
+            timezone: None,
+            batch_size: 500,
+            checkpoint: None,
+        }
+    }
+}
+
+/// 被拒绝的行
+#[derive(Debug, Clone)]
+pub struct RejectedRow {
+    pub file: PathBuf,
+    pub line: usize,
+    pub reason: String,
+}
+
+/// 单个文件的解析结果
+#[derive(Debug, Default)]
+pub struct ParsedFile {
+    /// (记录序号, K 线)，记录序号用于断点续传
+    pub bars: Vec<(usize, MarketBar)>,
+    pub rejected: Vec<(usize, RejectedRow)>,
+    /// 文件总记录数
+    pub records: usize,
+}
+
+/// 导入结果汇总
+#[derive(Debug, Clone, Default)]
+pub struct ImportSummary {
+    /// 本次处理的文件数
+    pub files: usize,
+    /// checkpoint 中已完成、整体跳过的文件数
+    pub skipped_files: usize,
+    pub inserted: u64,
+    pub updated: u64,
+    pub rejected: Vec<RejectedRow>,
+    /// 本次写入过的 K 线序列 (导入后做数据质量扫描)
+    pub series: Vec<BarSeriesKey>,
+}
+
+impl ImportSummary {
+    pub fn rows_written(&self) -> u64 {
+        self.inserted + self.updated
+    }
+
+    /// 导出被拒绝行明细 (file,line,reason)
+    pub fn write_rejects_csv<W: Write>(&self, writer: W) -> Result<()> {
+        let mut wtr = csv::Writer::from_writer(writer);
+        wtr.write_record(["file", "line", "reason"])?;
+        for row in &self.rejected {
+            wtr.write_record([
+                row.file.to_string_lossy().as_ref(),
+                &row.line.to_string(),
+                &row.reason,
+            ])?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+}
+
+// =========================================================================
+// 2. 解析
+// =========================================================================
+
+/// 列出待导入文件 (单个文件或目录递归，按路径排序保证断点续传顺序稳定)
+pub fn collect_files(path: &Path) -> Result<Vec<(PathBuf, SourceFormat)>> {
+    if path.is_file() {
+        return Ok(vec![(path.to_path_buf(), require_format(path)?)]);
+    }
+    if !path.is_dir() {
+        bail!("Import path {:?} does not exist", path);
+    }
+    let files = WalkDir::new(path)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| SourceFormat::detect(e.path()).map(|f| (e.into_path(), f)))
+        .collect();
+    Ok(files)
+}
+
+/// 解析单个文件
+///
+/// 数值 / 时间 / OHLC 不合法的行记为拒绝；缺列和未知交易所代码属于配置错误，直接返回 Err。
+pub fn parse_file(
+    path: &Path,
+    format: SourceFormat,
+    options: &ImportOptions,
+) -> Result<ParsedFile> {
+    let table = read_table(path, format)?;
+    let columns = options
+        .columns
+        .resolve(&table.headers)
+        .with_context(|| format!("Column mapping does not match {:?}", path))?;
+    if columns.exchange.is_none() && options.default_exchange.is_none() {
+        bail!(
+            "No exchange column in {:?} and no default exchange configured",
+            path
+        );
+    }
+
+    let mut parsed = ParsedFile {
+        records: table.records.len(),
+        ..Default::default()
+    };
+    for (index, (line, record)) in table.records.into_iter().enumerate() {
+        let values = match record {
+            Ok(values) => values,
+            Err(e) => {
+                parsed.rejected.push((index, reject(path, line, e)));
+                continue;
+            }
+        };
+        let exchange = match (columns.exchange, options.default_exchange) {
+            (Some(i), _) => match field(&values, i) {
+                Ok(code) => options
+                    .exchanges
+                    .resolve(code)
+                    .with_context(|| format!("{:?} line {}", path, line))?,
+                Err(e) => {
+                    parsed.rejected.push((index, reject(path, line, e)));
+                    continue;
+                }
+            },
+            (None, Some(exchange)) => exchange,
+            (None, None) => unreachable!("checked above"),
+        };
+        match build_bar(path, &values, &columns, exchange, options) {
+            Ok(bar) => parsed.bars.push((index, bar)),
+            Err(e) => parsed.rejected.push((index, reject(path, line, e))),
+        }
+    }
+    Ok(parsed)
+}
+
+fn reject(path: &Path, line: usize, e: anyhow::Error) -> RejectedRow {
+    RejectedRow {
+        file: path.to_path_buf(),
+        line,
+        reason: format!("{:#}", e),
+    }
+}
+
+fn field(values: &[String], index: usize) -> Result<&str> {
+    values.get(index).map(String::as_str).ok_or_else(|| {
+        anyhow!(
+            "Row has {} fields, missing column #{}",
+            values.len(),
+            index + 1
+        )
+    })
+}
+
+fn decimal(values: &[String], index: usize, name: &str) -> Result<Decimal> {
+    let raw = field(values, index)?;
+    Decimal::from_str(raw)
+        .or_else(|_| Decimal::from_scientific(raw))
+        .map_err(|_| anyhow!("Invalid {} '{}'", name, raw))
+}
+
+fn build_bar(
+    path: &Path,
+    values: &[String],
+    columns: &ColumnIndex,
+    exchange: Exchange,
+    options: &ImportOptions,
+) -> Result<MarketBar> {
+    let raw_symbol = match columns.symbol {
+        Some(i) => field(values, i)?.to_string(),
+        None => path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .ok_or_else(|| anyhow!("Cannot derive symbol from file name"))?,
+    };
+    if raw_symbol.is_empty() {
+        bail!("Empty symbol");
+    }
+    let symbol = if raw_symbol.contains('/') {
+        raw_symbol
+    } else {
+        CurrencyPair::new(&raw_symbol, &options.quote).to_string()
+    };
+
+    let open = decimal(values, columns.open, "open")?;
+    let high = decimal(values, columns.high, "high")?;
+    let low = decimal(values, columns.low, "low")?;
+    let close = decimal(values, columns.close, "close")?;
+    let volume = decimal(values, columns.volume, "volume")?;
+    if low <= Decimal::ZERO || high < open.max(close) || low > open.min(close) {
+        bail!(
+            "Inconsistent OHLC (open={}, high={}, low={}, close={})",
+            open,
+            high,
+            low,
+            close
+        );
+    }
+    if volume < Decimal::ZERO {
+        bail!("Negative volume {}", volume);
+    }
+
+    let trade_type = match columns.trade_type {
+        Some(i) => {
+            let raw = field(values, i)?;
+            raw.parse::<u8>()
+                .map_err(|_| anyhow!("Invalid type '{}'", raw))?
+        }
+        None => options.default_trade_type,
+    };
+    let date = parse_trade_date(field(values, columns.time)?, options.timezone)?;
+
+    let mut bar = MarketBar::new(
+        exchange,
+        symbol,
+        options.bar_period,
+        trade_type,
+        Price(open),
+        Price(high),
+        Price(low),
+        Price(close),
+        Quantity(volume),
+        date,
+    )?;
+    if let Some(i) = columns.amount {
+        let raw = field(values, i)?;
+        if !raw.is_empty() {
+            bar.amount = Some(decimal(values, i, "amount")?);
+        }
+    }
+    Ok(bar)
+}
+
+// =========================================================================
+// 3. 写库
+// =========================================================================
+
+/// 导入单个文件或整个目录 (递归查找 .csv / .parquet)
+///
+/// 每个文件按 `batch_size` 分批 upsert，每批提交后更新 checkpoint；
+/// 中断后用同一 checkpoint 重跑即可从断点继续。
+pub async fn import_path(
+    repo: &dyn MarketDataStore,
+    path: &Path,
+    options: &ImportOptions,
+) -> Result<ImportSummary> {
+    let batch_size = options.batch_size.max(1);
+    let mut checkpoint = options
+        .checkpoint
+        .as_deref()
+        .map(Checkpoint::load)
+        .transpose()?;
+    let mut summary = ImportSummary::default();
+
+    for (file, format) in collect_files(path)? {
+        let len = std::fs::metadata(&file)?.len();
+        let resume_from = match checkpoint.as_ref().and_then(|c| c.progress(&file, len)) {
+            Some(progress) if progress.completed => {
+                summary.skipped_files += 1;
+                continue;
+            }
+            Some(progress) => progress.records_done,
+            None => 0,
+        };
+        if resume_from > 0 {
+            info!("  -> Resuming {:?} from record {}", file, resume_from);
+        }
+
+        let parsed = parse_file(&file, format, options)?;
+        let (mut inserted, mut updated) = (0, 0);
+        let mut rejected = parsed
+            .rejected
+            .into_iter()
+            .filter(|(i, _)| *i >= resume_from)
+            .peekable();
+        let pending: Vec<_> = parsed
+            .bars
+            .into_iter()
+            .filter(|(i, _)| *i >= resume_from)
+            .collect();
+
+        for (_, bar) in &pending {
+            let key = BarSeriesKey {
+                exchange: bar.exchange,
+                symbol: bar.symbol.to_string(),
+                bar_period: bar.bar_period,
+                trade_type: bar.trade_type,
+            };
+            if !summary.series.contains(&key) {
+                summary.series.push(key);
+            }
+        }
+        for chunk in pending.chunks(batch_size) {
+            let bars: Vec<MarketBar> = chunk.iter().map(|(_, bar)| bar.clone()).collect();
+            let counts = repo
+                .save_batch(&bars)
+                .await
+                .with_context(|| format!("Failed to save batch from {:?}", file))?;
+            inserted += counts.inserted;
+            updated += counts.updated;
+
+            let next = chunk.last().map(|(i, _)| i + 1).unwrap_or(resume_from);
+            while let Some((_, row)) = rejected.next_if(|(i, _)| *i < next) {
+                warn!("Rejected {:?} line {}: {}", row.file, row.line, row.reason);
+                summary.rejected.push(row);
+            }
+            if let Some(checkpoint) = checkpoint.as_mut() {
+                checkpoint.record(
+                    &file,
+                    FileProgress {
+                        len,
+                        records_done: next,
+                        completed: false,
+                    },
+                )?;
+            }
+        }
+        for (_, row) in rejected {
+            warn!("Rejected {:?} line {}: {}", row.file, row.line, row.reason);
+            summary.rejected.push(row);
+        }
+        if let Some(checkpoint) = checkpoint.as_mut() {
+            checkpoint.record(
+                &file,
+                FileProgress {
+                    len,
+                    records_done: parsed.records,
+                    completed: true,
+                },
+            )?;
+        }
+
+        info!(
+            "  -> {:?}: {} inserted, {} updated",
+            file, inserted, updated
+        );
+        summary.files += 1;
+        summary.inserted += inserted;
+        summary.updated += updated;
+    }
+    Ok(summary)
+}
@@ -0,0 +1,149 @@
+use anyhow::{bail, Context, Result};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+// =========================================================================
+// 源文件读取 (CSV / Parquet -> 字符串表)
+// =========================================================================
+
+/// 一条原始记录: 文件内行号 (CSV 含表头，从 2 开始；Parquet 从 1 开始) + 字段值
+pub(crate) type RawRecord = (usize, Result<Vec<String>>);
+
+/// 读取后的原始表格，字段统一为字符串，交给列映射解析
+pub(crate) struct RawTable {
+    pub headers: Vec<String>,
+    pub records: Vec<RawRecord>,
+}
+
+/// 支持的源文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceFormat {
+    Csv,
+    Parquet,
+}
+
+impl SourceFormat {
+    /// 按扩展名识别 (未启用 `parquet` feature 时读取 .parquet 会报错)
+    pub fn detect(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "csv" => Some(Self::Csv),
+            "parquet" => Some(Self::Parquet),
+            _ => None,
+        }
+    }
+}
+
+pub(crate) fn read_table(path: &Path, format: SourceFormat) -> Result<RawTable> {
+    match format {
+        SourceFormat::Csv => read_csv(path),
+        SourceFormat::Parquet => read_parquet(path),
+    }
+}
+
+fn read_csv(path: &Path) -> Result<RawTable> {
+    let mut file = File::open(path).with_context(|| format!("Failed to open {:?}", path))?;
+
+    // 跳过 UTF-8 BOM
+    let mut bom = [0u8; 3];
+    let bytes_read = file.read(&mut bom)?;
+    if bytes_read != 3 || bom != [0xEF, 0xBB, 0xBF] {
+        file.seek(SeekFrom::Start(0))?;
+    }
+
+    let mut rdr = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(file);
+
+    let headers = rdr
+        .headers()
+        .with_context(|| format!("Failed to read header of {:?}", path))?
+        .iter()
+        .map(str::to_string)
+        .collect();
+
+    let records = rdr
+        .records()
+        .enumerate()
+        .map(|(i, record)| {
+            let line = record
+                .as_ref()
+                .ok()
+                .and_then(|r| r.position())
+                .map(|p| p.line() as usize)
+                .unwrap_or(i + 2);
+            let values = record
+                .map(|r| r.iter().map(str::to_string).collect())
+                .map_err(anyhow::Error::from);
+            (line, values)
+        })
+        .collect();
+
+    Ok(RawTable { headers, records })
+}
+
+#[cfg(feature = "parquet")]
+fn read_parquet(path: &Path) -> Result<RawTable> {
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::Field;
+
+    let file = File::open(path).with_context(|| format!("Failed to open {:?}", path))?;
+    let reader = SerializedFileReader::new(file)
+        .with_context(|| format!("Failed to read parquet {:?}", path))?;
+
+    let headers: Vec<String> = reader
+        .metadata()
+        .file_metadata()
+        .schema_descr()
+        .columns()
+        .iter()
+        .map(|c| c.name().to_string())
+        .collect();
+
+    let field_to_string = |field: &Field| match field {
+        Field::Null => String::new(),
+        Field::Str(s) => s.clone(),
+        Field::Date(days) => (chrono::NaiveDate::default() + chrono::Duration::days(*days as i64))
+            .format("%Y-%m-%d")
+            .to_string(),
+        Field::TimestampMillis(ms) => ms.to_string(),
+        Field::TimestampMicros(us) => (us / 1_000).to_string(),
+        other => other.to_string(),
+    };
+
+    let records = reader
+        .get_row_iter(None)?
+        .enumerate()
+        .map(|(i, row)| {
+            let values = row.map_err(anyhow::Error::from).map(|row| {
+                row.get_column_iter()
+                    .map(|(_, field)| field_to_string(field))
+                    .collect()
+            });
+            (i + 1, values)
+        })
+        .collect();
+
+    Ok(RawTable { headers, records })
+}
+
+#[cfg(not(feature = "parquet"))]
+fn read_parquet(path: &Path) -> Result<RawTable> {
+    bail!(
+        "Parquet support is disabled, rebuild quant-storage with `--features parquet` ({:?})",
+        path
+    )
+}
+
+/// 校验源文件格式，供 CLI 在导入单个文件时给出明确报错
+pub fn require_format(path: &Path) -> Result<SourceFormat> {
+    match SourceFormat::detect(path) {
+        Some(format) => Ok(format),
+        None => bail!(
+            "Unsupported file type {:?} (expected .csv or .parquet)",
+            path
+        ),
+    }
+}
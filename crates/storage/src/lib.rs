@@ -1,14 +1,205 @@
+use crate::cache::{CachedAccountStore, CachedOrderStore, HotCache};
+use crate::lock::Fencing;
+use crate::redis::RedisService;
+use crate::repository::account_repo::AccountRepository;
+use crate::repository::common::{self, MarketBackend, StorageConfig};
+use crate::repository::corporate_action_repo::CorporateActionRepository;
+use crate::repository::fill_repo::FillRepository;
+use crate::repository::market_repo::MarketDataRepository;
+use crate::repository::order_repo::OrderRepository;
+use crate::repository::quality_repo::QualityRepository;
+use crate::repository::strategy_repo::{StateRetention, StrategyRepository};
+use crate::store::{
+    timescale, AccountStore, FillStore, MarketDataStore, OrderStore, TimescaleMarketStore,
+};
+use anyhow::Result;
 use sqlx::MySqlPool;
+use std::sync::Arc;
 
 // 声明子模块
+pub mod adjust;
+pub mod cache;
+pub mod idempotency;
+pub mod import;
+pub mod lock;
+pub mod migrate;
+pub mod quality;
+pub mod ratelimit;
 pub mod redis;
 pub mod repository;
+pub mod store;
+pub mod stream;
 
+/// 存储上下文：持有连接池和全部仓储层，由调用方显式传递
+///
+/// 克隆开销很小 (内部都是连接池句柄)；不同的 `Storage` 可以指向不同的数据库，
+/// 便于测试和多租户场景并存。
 #[derive(Clone)]
-pub struct Storage {}
+pub struct Storage {
+    pool: MySqlPool,
+    redis: Option<RedisService>,
+    /// 配置了 Redis 时存在，订单 / 账户读写经过热点缓存
+    cache: Option<HotCache>,
+    /// 配置了 TimescaleDB 行情后端时存在，K 线读写改走时序库
+    timescale: Option<TimescaleMarketStore>,
+    market: MarketDataRepository,
+    orders: OrderRepository,
+    fills: FillRepository,
+    accounts: AccountRepository,
+    strategies: StrategyRepository,
+    quality: QualityRepository,
+    corporate_actions: CorporateActionRepository,
+}
 
 impl Storage {
     pub fn new(pool: MySqlPool) -> Self {
-        Self {}
+        Self {
+            market: MarketDataRepository::new(pool.clone()),
+            orders: OrderRepository::new(pool.clone()),
+            fills: FillRepository::new(pool.clone()),
+            accounts: AccountRepository::new(pool.clone()),
+            strategies: StrategyRepository::new(pool.clone()),
+            quality: QualityRepository::new(pool.clone()),
+            corporate_actions: CorporateActionRepository::new(pool.clone()),
+            redis: None,
+            cache: None,
+            timescale: None,
+            pool,
+        }
+    }
+
+    /// 按配置连接 MySQL (以及配置了 redis_url 时的 Redis、选择时序库后端时的 TimescaleDB)
+    pub async fn connect(config: &StorageConfig) -> Result<Self> {
+        let pool = common::connect(config).await?;
+        let mut storage = Self::new(pool).with_state_retention(config.state_retention.clone());
+        if let Some(url) = config.redis_url.as_deref().filter(|u| !u.is_empty()) {
+            let redis = RedisService::new(url)?;
+            storage = storage
+                .with_cache(HotCache::new(redis.clone(), config.cache.clone()))
+                .with_redis(redis);
+        }
+        if config.market_backend == MarketBackend::Timescale {
+            let pool = timescale::connect(config).await?;
+            storage = storage.with_timescale(TimescaleMarketStore::new(pool));
+        }
+        Ok(storage)
+    }
+
+    /// 只用环境变量 (DATABASE_URL / REDIS_URL) 连接
+    pub async fn from_env() -> Result<Self> {
+        Self::connect(&StorageConfig::from_env()).await
+    }
+
+    pub fn with_redis(mut self, redis: RedisService) -> Self {
+        self.redis = Some(redis);
+        self
+    }
+
+    pub fn with_cache(mut self, cache: HotCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// 策略状态历史版本的保留策略
+    pub fn with_state_retention(mut self, retention: StateRetention) -> Self {
+        self.strategies = self.strategies.with_retention(retention);
+        self
+    }
+
+    /// 高可用部署：订单 / 策略 / 热点缓存的写入按主节点任期的 Fence 校验
+    pub fn with_fencing(mut self, fencing: Fencing) -> Self {
+        self.orders = self.orders.with_fencing(fencing.clone());
+        self.strategies = self.strategies.with_fencing(fencing.clone());
+        self.cache = self.cache.map(|cache| cache.with_fencing(fencing));
+        self
+    }
+
+    pub fn with_timescale(mut self, timescale: TimescaleMarketStore) -> Self {
+        self.timescale = Some(timescale);
+        self
+    }
+
+    pub fn pool(&self) -> &MySqlPool {
+        &self.pool
+    }
+
+    pub fn redis(&self) -> Option<&RedisService> {
+        self.redis.as_ref()
+    }
+
+    pub fn cache(&self) -> Option<&HotCache> {
+        self.cache.as_ref()
+    }
+
+    pub fn timescale(&self) -> Option<&TimescaleMarketStore> {
+        self.timescale.as_ref()
+    }
+
+    /// MySQL K 线仓储 (数据质量修复 / 隔离只支持 MySQL)
+    pub fn market(&self) -> &MarketDataRepository {
+        &self.market
+    }
+
+    /// 按配置选择的 K 线存储后端 (回测 / 导入使用)
+    pub fn market_store(&self) -> &dyn MarketDataStore {
+        match &self.timescale {
+            Some(timescale) => timescale,
+            None => &self.market,
+        }
+    }
+
+    pub fn orders(&self) -> &OrderRepository {
+        &self.orders
+    }
+
+    /// 订单存储 (配置了 Redis 时写穿热点缓存)
+    pub fn order_store(&self) -> Arc<dyn OrderStore> {
+        let orders: Arc<dyn OrderStore> = Arc::new(self.orders.clone());
+        match &self.cache {
+            Some(cache) => Arc::new(CachedOrderStore::new(orders, cache.clone())),
+            None => orders,
+        }
+    }
+
+    pub fn fills(&self) -> &FillRepository {
+        &self.fills
+    }
+
+    /// 成交存储 (OMS 记录成交回报)
+    pub fn fill_store(&self) -> Arc<dyn FillStore> {
+        Arc::new(self.fills.clone())
+    }
+
+    pub fn accounts(&self) -> &AccountRepository {
+        &self.accounts
+    }
+
+    /// 账户存储 (配置了 Redis 时余额 / 持仓优先读缓存)
+    pub fn account_store(&self) -> Arc<dyn AccountStore> {
+        let accounts: Arc<dyn AccountStore> = Arc::new(self.accounts.clone());
+        match &self.cache {
+            Some(cache) => Arc::new(CachedAccountStore::new(accounts, cache.clone())),
+            None => accounts,
+        }
+    }
+
+    pub fn strategies(&self) -> &StrategyRepository {
+        &self.strategies
+    }
+
+    pub fn quality(&self) -> &QualityRepository {
+        &self.quality
+    }
+
+    pub fn corporate_actions(&self) -> &CorporateActionRepository {
+        &self.corporate_actions
+    }
+
+    /// 关闭连接池 (等待进行中的查询结束)
+    pub async fn close(&self) {
+        self.pool.close().await;
+        if let Some(timescale) = &self.timescale {
+            timescale.pool().close().await;
+        }
     }
 }
@@ -0,0 +1,356 @@
+use crate::redis::RedisService;
+use anyhow::Result;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, timeout};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+// =========================================================================
+// 分布式锁 (租约 + 看门狗续期 + Fencing Token)
+// =========================================================================
+//
+// Redis 中的 Key:
+//   {key}        当前持有者的 token (带 PX 过期)，过期即视为释放
+//   {key}:fence  单调递增计数器，每次成功加锁 INCR 一次，作为 Fencing Token
+//
+// 看门狗按 `renew_interval` 续期，每次续期最多等待 `renew_interval`。租约从发出请求时起算，
+// 本地在 `上次续期成功 + ttl - renew_interval` 就把锁标记为丢失 (`is_held() == false`，
+// `lost()` 返回)，比 Redis 中的 Key 过期至少早一个续期间隔，留出让持有者停手的余量；
+// 发现锁已被别人持有时立即标记丢失。
+//
+// 持锁方在写入前应检查 `is_held()`，由上面的安全余量保证旧持有者先于新持有者停手；
+// 余量兜不住的情况 (进程暂停、网络延迟) 由 Fencing token 兜底：把 `fence()` 放进共享的
+// `Fencing` 后，受保护的写入都会带上它:
+//   - 热点缓存 (订单 / 账户快照) 在 Lua 中比较 {key}:fence，计数器已前进则拒绝写入
+//   - 数据库的订单状态、策略状态 / 运行状态写入以 fence_token 列为条件，不接受更旧的 token
+// 被拒绝的写入返回 `FencedOut` 错误。
+
+/// 加锁并分配 fencing token
+///
+/// KEYS[1] = 锁, KEYS[2] = 计数器, ARGV[1] = 持有者 token, ARGV[2] = TTL (毫秒)
+const ACQUIRE_SCRIPT: &str = r#"
+    if redis.call("SET", KEYS[1], ARGV[1], "NX", "PX", ARGV[2]) then
+        return redis.call("INCR", KEYS[2])
+    end
+    return 0
+"#;
+
+/// 仍由自己持有时续期
+const RENEW_SCRIPT: &str = r#"
+    if redis.call("GET", KEYS[1]) == ARGV[1] then
+        return redis.call("PEXPIRE", KEYS[1], ARGV[2])
+    end
+    return 0
+"#;
+
+/// 仍由自己持有时删除
+const RELEASE_SCRIPT: &str = r#"
+    if redis.call("GET", KEYS[1]) == ARGV[1] then
+        return redis.call("DEL", KEYS[1])
+    end
+    return 0
+"#;
+
+/// 某一任持有者的 Fencing Token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fence {
+    /// 计数器 Key (`{key}:fence`)
+    pub key: String,
+    pub token: u64,
+}
+
+impl Fence {
+    /// 资源上记录的 token 比本任期新时拒绝写入 (相等视为同一任期的重复写入)
+    pub fn check(&self, resource: &str, stored: Option<u64>) -> Result<(), FencedOut> {
+        match stored {
+            Some(stored) if stored > self.token => Err(FencedOut {
+                resource: resource.to_string(),
+                token: self.token,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// 当前任期的 Fence，在选主器和存储层之间共享 (未持锁时为空，写入不加条件)
+///
+/// 失去租约后不清空：旧任期的迟到写入仍带着旧 token，会被新任期拒绝。
+#[derive(Debug, Clone, Default)]
+pub struct Fencing(Arc<RwLock<Option<Fence>>>);
+
+impl Fencing {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&self, fence: Fence) {
+        *self.0.write().unwrap() = Some(fence);
+    }
+
+    pub fn current(&self) -> Option<Fence> {
+        self.0.read().unwrap().clone()
+    }
+}
+
+/// 写入被拒绝：资源已被更新任期的持有者写过
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FencedOut {
+    pub resource: String,
+    pub token: u64,
+}
+
+impl fmt::Display for FencedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Write to {} refused: fencing token {} is stale",
+            self.resource, self.token
+        )
+    }
+}
+
+impl std::error::Error for FencedOut {}
+
+/// 锁参数
+#[derive(Debug, Clone)]
+pub struct LockOptions {
+    /// 租约时长：持有者失联后最多这么久锁会自动释放
+    pub ttl: Duration,
+    /// 看门狗续期间隔 (应明显小于 ttl，默认 ttl / 3)
+    pub renew_interval: Duration,
+    /// `acquire` 的最长等待时间
+    pub wait_timeout: Duration,
+    /// `acquire` 的重试间隔
+    pub retry_interval: Duration,
+}
+
+impl Default for LockOptions {
+    fn default() -> Self {
+        Self::with_ttl(Duration::from_secs(10))
+    }
+}
+
+impl LockOptions {
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            renew_interval: ttl / 3,
+            wait_timeout: Duration::ZERO,
+            retry_interval: Duration::from_millis(100),
+        }
+    }
+
+    pub fn wait(mut self, wait_timeout: Duration) -> Self {
+        self.wait_timeout = wait_timeout;
+        self
+    }
+}
+
+/// 分布式锁守卫
+///
+/// 持有期间后台看门狗自动续期；`release` 或 Drop 时释放 (Drop 在后台异步释放)。
+pub struct DistributedLock {
+    redis: RedisService,
+    key: String,
+    token: String,
+    fencing_token: u64,
+    held: watch::Receiver<bool>,
+    watchdog: Option<JoinHandle<()>>,
+}
+
+impl DistributedLock {
+    fn fence_key(key: &str) -> String {
+        format!("{}:fence", key)
+    }
+
+    /// 尝试加锁一次，锁已被占用时返回 None
+    pub async fn try_acquire(
+        redis: &RedisService,
+        key: &str,
+        options: &LockOptions,
+    ) -> Result<Option<Self>> {
+        let token = Uuid::new_v4().to_string();
+        let ttl_ms = options.ttl.as_millis().to_string();
+        let fence_key = Self::fence_key(key);
+        // 租约从发出请求时起算 (Redis 可能在响应到达前就已开始计时)
+        let requested_at = Instant::now();
+        let fencing_token: u64 = redis
+            .exec_lua_script(ACQUIRE_SCRIPT, &[key, &fence_key], &[&token, &ttl_ms])
+            .await?;
+        if fencing_token == 0 {
+            return Ok(None);
+        }
+        debug!("🔒 Acquired {} (fencing token {})", key, fencing_token);
+
+        let (tx, held) = watch::channel(true);
+        let watchdog = tokio::spawn(Self::watchdog(
+            redis.clone(),
+            key.to_string(),
+            token.clone(),
+            options.clone(),
+            requested_at,
+            tx,
+        ));
+        Ok(Some(Self {
+            redis: redis.clone(),
+            key: key.to_string(),
+            token,
+            fencing_token,
+            held,
+            watchdog: Some(watchdog),
+        }))
+    }
+
+    /// 在 `options.wait_timeout` 内反复尝试加锁，超时返回 None
+    pub async fn acquire(
+        redis: &RedisService,
+        key: &str,
+        options: &LockOptions,
+    ) -> Result<Option<Self>> {
+        let start = Instant::now();
+        loop {
+            if let Some(lock) = Self::try_acquire(redis, key, options).await? {
+                return Ok(Some(lock));
+            }
+            if start.elapsed() >= options.wait_timeout {
+                return Ok(None);
+            }
+            sleep(options.retry_interval).await;
+        }
+    }
+
+    /// 定期续期；锁被别人拿走，或到安全期限 (`ttl - renew_interval`) 仍未续期成功时标记丢失并退出
+    async fn watchdog(
+        redis: RedisService,
+        key: String,
+        token: String,
+        options: LockOptions,
+        mut last_renewed: Instant,
+        held: watch::Sender<bool>,
+    ) {
+        let ttl_ms = options.ttl.as_millis().to_string();
+        let safe_lease = options.ttl.saturating_sub(options.renew_interval);
+        loop {
+            let deadline = last_renewed + safe_lease;
+            let next = (Instant::now() + options.renew_interval).min(deadline);
+            sleep(next.saturating_duration_since(Instant::now())).await;
+            let attempt = Instant::now();
+            if attempt >= deadline {
+                warn!(
+                    "⚠️ Lock {} lease is about to expire, treating it as lost",
+                    key
+                );
+                break;
+            }
+            // 单次续期不能超过续期间隔，也不能越过安全期限
+            let limit = options
+                .renew_interval
+                .min(deadline.saturating_duration_since(attempt));
+            let renewed = timeout(
+                limit,
+                redis.exec_lua_script::<i32>(RENEW_SCRIPT, &[&key], &[&token, &ttl_ms]),
+            )
+            .await;
+            match renewed {
+                Ok(Ok(1)) => last_renewed = attempt,
+                Ok(Ok(_)) => {
+                    warn!("⚠️ Lock {} was taken over, lease lost", key);
+                    break;
+                }
+                Ok(Err(e)) => warn!("Failed to renew lock {}: {:?}", key, e),
+                Err(_) => warn!("Renewing lock {} timed out after {:?}", key, limit),
+            }
+        }
+        let _ = held.send(false);
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// 持有者标识 (每次加锁随机生成)
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Fencing Token：同一把锁每次成功加锁严格递增 (标识持有者任期)
+    pub fn fencing_token(&self) -> u64 {
+        self.fencing_token
+    }
+
+    /// 本任期的 Fence，放进 `Fencing` 后受保护的写入会据此校验
+    pub fn fence(&self) -> Fence {
+        Fence {
+            key: Self::fence_key(&self.key),
+            token: self.fencing_token,
+        }
+    }
+
+    /// 看门狗是否仍认为自己持有锁 (本地判断，不访问 Redis)；写入受保护资源前应先检查
+    pub fn is_held(&self) -> bool {
+        *self.held.borrow()
+    }
+
+    /// 订阅持有状态 (看门狗判定丢失后变为 false)，供拿不到锁本身的组件在写入前检查
+    pub fn watch_held(&self) -> watch::Receiver<bool> {
+        self.held.clone()
+    }
+
+    /// 等待直到租约丢失 (锁被接管 / 续期失败)
+    pub async fn lost(&self) {
+        let mut held = self.held.clone();
+        // 发送端退出 (看门狗结束) 同样视为丢失
+        let _ = held.wait_for(|held| !*held).await;
+    }
+
+    /// 到 Redis 确认自己仍是最新的持有者 (锁值和计数器都没有变)
+    pub async fn is_current(&self) -> Result<bool> {
+        let owner = self.redis.get(&self.key).await?;
+        let fence = self.redis.get(&Self::fence_key(&self.key)).await?;
+        Ok(owner.as_deref() == Some(self.token.as_str())
+            && fence.as_deref() == Some(self.fencing_token.to_string().as_str()))
+    }
+
+    /// 释放锁，返回是否真正删除 (租约已过期 / 被接管时为 false)
+    pub async fn release(mut self) -> Result<bool> {
+        if let Some(watchdog) = self.watchdog.take() {
+            watchdog.abort();
+        }
+        let released: i32 = self
+            .redis
+            .exec_lua_script(RELEASE_SCRIPT, &[&self.key], &[&self.token])
+            .await?;
+        debug!(
+            "🔓 Released {} (fencing token {})",
+            self.key, self.fencing_token
+        );
+        Ok(released == 1)
+    }
+}
+
+impl Drop for DistributedLock {
+    fn drop(&mut self) {
+        let Some(watchdog) = self.watchdog.take() else {
+            return; // 已经 release
+        };
+        watchdog.abort();
+        // 没有运行时 (进程退出中) 时只能等租约自然过期
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            let redis = self.redis.clone();
+            let key = std::mem::take(&mut self.key);
+            let token = std::mem::take(&mut self.token);
+            handle.spawn(async move {
+                let released: Result<i32> = redis
+                    .exec_lua_script(RELEASE_SCRIPT, &[&key], &[&token])
+                    .await;
+                if let Err(e) = released {
+                    warn!("Failed to release lock {} on drop: {:?}", key, e);
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use crate::store;
+use crate::Storage;
+use anyhow::{Context, Result};
+use sqlx::migrate::{Migrate, Migrator};
+use sqlx::MySqlPool;
+use std::collections::HashMap;
+use tracing::info;
+
+// =========================================================================
+// 数据库迁移 (crates/storage/migrations，编译期嵌入)
+// =========================================================================
+//
+// 新增迁移: 在 migrations/ 下追加 `{版本号}_{描述}.sql`，不要修改已发布的文件
+// (sqlx 会校验已执行迁移的 checksum)。
+
+/// 嵌入的全部迁移
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// 单个迁移的执行状态
+#[derive(Debug, Clone)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    pub applied: bool,
+    /// 已执行但 checksum 与嵌入文件不一致 (迁移文件被改过)
+    pub checksum_mismatch: bool,
+}
+
+/// 执行全部未执行的迁移
+pub async fn run_migrations(pool: &MySqlPool) -> Result<()> {
+    MIGRATOR
+        .run(pool)
+        .await
+        .context("Failed to run database migrations")?;
+    Ok(())
+}
+
+/// 查询每个迁移的执行状态
+pub async fn status(pool: &MySqlPool) -> Result<Vec<MigrationStatus>> {
+    let mut conn = pool.acquire().await?;
+    collect_status(&MIGRATOR, &mut *conn).await
+}
+
+/// 对比嵌入的迁移和库中已执行的记录 (MySQL / TimescaleDB 共用)
+pub(crate) async fn collect_status<C: Migrate + ?Sized>(
+    migrator: &Migrator,
+    conn: &mut C,
+) -> Result<Vec<MigrationStatus>> {
+    conn.ensure_migrations_table().await?;
+    let applied: HashMap<i64, Vec<u8>> = conn
+        .list_applied_migrations()
+        .await?
+        .into_iter()
+        .map(|m| (m.version, m.checksum.into_owned()))
+        .collect();
+
+    Ok(migrator
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+        .map(|m| {
+            let checksum = applied.get(&m.version);
+            MigrationStatus {
+                version: m.version,
+                description: m.description.to_string(),
+                applied: checksum.is_some(),
+                checksum_mismatch: checksum.is_some_and(|c| c.as_slice() != m.checksum.as_ref()),
+            }
+        })
+        .collect())
+}
+
+// =========================================================================
+// 命令行 (quant-engine migrate / bin/migrate 共用)
+// =========================================================================
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct MigrateArgs {
+    /// 只打印迁移状态，不执行
+    #[arg(long)]
+    pub status: bool,
+}
+
+/// 执行迁移 (或只打印状态)；配置了 TimescaleDB 时一并处理
+pub async fn run(storage: &Storage, args: &MigrateArgs) -> Result<()> {
+    let pool = storage.pool();
+    if !args.status {
+        run_migrations(pool).await?;
+    }
+    print_status("MySQL", &status(pool).await?);
+
+    if let Some(timescale) = storage.timescale() {
+        if !args.status {
+            store::timescale::run_migrations(timescale.pool()).await?;
+        }
+        print_status(
+            "TimescaleDB",
+            &store::timescale::status(timescale.pool()).await?,
+        );
+    }
+    Ok(())
+}
+
+fn print_status(database: &str, statuses: &[MigrationStatus]) {
+    for s in statuses {
+        let mark = match (s.applied, s.checksum_mismatch) {
+            (true, false) => "✅",
+            (true, true) => "⚠️ checksum mismatch",
+            (false, _) => "⏳ pending",
+        };
+        info!("{:>4} {:<32} {}", s.version, s.description, mark);
+    }
+    let pending = statuses.iter().filter(|s| !s.applied).count();
+    info!(
+        "🗄️ {}: {} migrations, {} applied, {} pending",
+        database,
+        statuses.len(),
+        statuses.len() - pending,
+        pending
+    );
+}
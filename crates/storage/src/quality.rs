@@ -0,0 +1,359 @@
+use crate::repository::market_repo::{BarSeriesKey, MarketDataRepository};
+use crate::repository::quality_repo::QualityRepository;
+use anyhow::Result;
+use chrono::NaiveDate;
+use quant_core::calendar::TradingCalendar;
+use quant_core::market::MarketBar;
+use quant_core::{BarPeriod, Price};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tracing::{info, warn};
+
+// =========================================================================
+// 1. 异常类型
+// =========================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Severity {
+    /// 可疑但可能真实 (停牌、暴涨暴跌)
+    Warning,
+    /// 数据本身不合法，回测默认拒绝
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AnomalyKind {
+    HighBelowLow,
+    /// open 不在 [low, high]
+    OpenOutOfRange,
+    /// close 不在 [low, high]
+    CloseOutOfRange,
+    NonPositivePrice,
+    NegativeVolume,
+    /// 同一天出现多根 K 线
+    DuplicateDay,
+    /// 日历上的交易日没有 K 线
+    MissingDay,
+    /// 日历上的休市日有 K 线
+    NonTradingDay,
+    /// 收盘价相对前一根的涨跌幅超过阈值
+    SuspiciousJump {
+        change: f64,
+    },
+}
+
+impl AnomalyKind {
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::HighBelowLow
+            | Self::OpenOutOfRange
+            | Self::CloseOutOfRange
+            | Self::NonPositivePrice
+            | Self::NegativeVolume
+            | Self::DuplicateDay => Severity::Error,
+            Self::MissingDay | Self::NonTradingDay | Self::SuspiciousJump { .. } => {
+                Severity::Warning
+            }
+        }
+    }
+
+    /// 只需重排 OHLC 就能修复 (价格本身为正)
+    pub fn is_repairable(&self) -> bool {
+        matches!(
+            self,
+            Self::HighBelowLow | Self::OpenOutOfRange | Self::CloseOutOfRange
+        )
+    }
+}
+
+/// 一条异常记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Anomaly {
+    pub date: NaiveDate,
+    /// 对应的 market_bar.id (MissingDay 没有)
+    pub bar_id: Option<i64>,
+    #[serde(flatten)]
+    pub kind: AnomalyKind,
+}
+
+// =========================================================================
+// 2. 扫描规则与报告
+// =========================================================================
+
+/// 扫描规则
+#[derive(Debug, Clone)]
+pub struct QualityRules {
+    /// 相邻收盘价涨跌幅阈值 (0.5 = ±50%)
+    pub max_jump: f64,
+    /// 是否对照交易日历检测缺失交易日 (只对日线生效)
+    pub check_gaps: bool,
+    /// 为 None 时按交易所选择默认日历
+    pub calendar: Option<TradingCalendar>,
+}
+
+impl Default for QualityRules {
+    fn default() -> Self {
+        Self {
+            max_jump: 0.5,
+            check_gaps: true,
+            calendar: None,
+        }
+    }
+}
+
+/// 单条序列的扫描结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeriesReport {
+    pub key: BarSeriesKey,
+    pub bars: usize,
+    pub first_date: Option<NaiveDate>,
+    pub last_date: Option<NaiveDate>,
+    pub anomalies: Vec<Anomaly>,
+}
+
+impl SeriesReport {
+    pub fn count(&self, severity: Severity) -> usize {
+        self.anomalies
+            .iter()
+            .filter(|a| a.kind.severity() == severity)
+            .count()
+    }
+
+    pub fn errors(&self) -> usize {
+        self.count(Severity::Error)
+    }
+
+    pub fn warnings(&self) -> usize {
+        self.count(Severity::Warning)
+    }
+
+    pub fn first_error(&self) -> Option<&Anomaly> {
+        self.anomalies
+            .iter()
+            .find(|a| a.kind.severity() == Severity::Error)
+    }
+
+    /// 没有 Error 级别异常
+    pub fn is_clean(&self) -> bool {
+        self.errors() == 0
+    }
+}
+
+/// 扫描到异常后的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QualityAction {
+    /// 只报告
+    #[default]
+    Report,
+    /// 可修复的 OHLC 问题就地修正，其余 Error 移入隔离表
+    Repair,
+    /// 所有 Error 级别的 K 线移入隔离表
+    Quarantine,
+}
+
+// =========================================================================
+// 3. 纯函数扫描
+// =========================================================================
+
+/// 扫描一条序列 (bars 需属于同一序列，顺序不限)
+pub fn scan_series(key: &BarSeriesKey, bars: &[MarketBar], rules: &QualityRules) -> SeriesReport {
+    let mut sorted: Vec<&MarketBar> = bars.iter().collect();
+    sorted.sort_by_key(|b| b.start_time);
+
+    let calendar = rules
+        .calendar
+        .clone()
+        .unwrap_or_else(|| TradingCalendar::for_exchange(key.exchange));
+    let mut anomalies = Vec::new();
+    let push = |anomalies: &mut Vec<Anomaly>, bar: &MarketBar, kind| {
+        anomalies.push(Anomaly {
+            date: bar.start_time,
+            bar_id: (bar.id > 0).then_some(bar.id),
+            kind,
+        })
+    };
+
+    let mut seen = HashSet::with_capacity(sorted.len());
+    let mut prev_close: Option<Decimal> = None;
+    for bar in &sorted {
+        let (open, high, low, close) = (bar.open.0, bar.high.0, bar.low.0, bar.close.0);
+        if [open, high, low, close].iter().any(|p| *p <= Decimal::ZERO) {
+            push(&mut anomalies, bar, AnomalyKind::NonPositivePrice);
+        } else if high < low {
+            push(&mut anomalies, bar, AnomalyKind::HighBelowLow);
+        } else {
+            if open < low || open > high {
+                push(&mut anomalies, bar, AnomalyKind::OpenOutOfRange);
+            }
+            if close < low || close > high {
+                push(&mut anomalies, bar, AnomalyKind::CloseOutOfRange);
+            }
+        }
+        if bar.volume.0 < Decimal::ZERO {
+            push(&mut anomalies, bar, AnomalyKind::NegativeVolume);
+        }
+        if !seen.insert(bar.start_time) {
+            push(&mut anomalies, bar, AnomalyKind::DuplicateDay);
+        }
+        if key.bar_period == BarPeriod::D1 && !calendar.is_trading_day(bar.start_time) {
+            push(&mut anomalies, bar, AnomalyKind::NonTradingDay);
+        }
+
+        if let Some(prev) = prev_close.filter(|p| *p > Decimal::ZERO) {
+            let change = ((close - prev) / prev).to_f64().unwrap_or(0.0);
+            if change.abs() > rules.max_jump {
+                push(&mut anomalies, bar, AnomalyKind::SuspiciousJump { change });
+            }
+        }
+        if close > Decimal::ZERO {
+            prev_close = Some(close);
+        }
+    }
+
+    let first_date = sorted.first().map(|b| b.start_time);
+    let last_date = sorted.last().map(|b| b.start_time);
+    if rules.check_gaps && key.bar_period == BarPeriod::D1 {
+        if let (Some(first), Some(last)) = (first_date, last_date) {
+            for date in calendar.trading_days(first, last) {
+                if !seen.contains(&date) {
+                    anomalies.push(Anomaly {
+                        date,
+                        bar_id: None,
+                        kind: AnomalyKind::MissingDay,
+                    });
+                }
+            }
+        }
+    }
+    anomalies.sort_by_key(|a| a.date);
+
+    SeriesReport {
+        key: key.clone(),
+        bars: sorted.len(),
+        first_date,
+        last_date,
+        anomalies,
+    }
+}
+
+/// 修复 OHLC: high / low 取四个价格的最大 / 最小值
+///
+/// 只处理价格均为正的 K 线，否则返回 None。
+pub fn repair_bar(bar: &MarketBar) -> Option<MarketBar> {
+    let prices = [bar.open.0, bar.high.0, bar.low.0, bar.close.0];
+    if prices.iter().any(|p| *p <= Decimal::ZERO) {
+        return None;
+    }
+    let mut fixed = bar.clone();
+    fixed.high = Price(prices.iter().copied().max()?);
+    fixed.low = Price(prices.iter().copied().min()?);
+    Some(fixed)
+}
+
+// =========================================================================
+// 4. 扫描器 (读库 -> 扫描 -> 修复/隔离 -> 保存结果)
+// =========================================================================
+
+pub struct QualityScanner<'a> {
+    market: &'a MarketDataRepository,
+    quality: &'a QualityRepository,
+    rules: QualityRules,
+    action: QualityAction,
+}
+
+impl<'a> QualityScanner<'a> {
+    pub fn new(market: &'a MarketDataRepository, quality: &'a QualityRepository) -> Self {
+        Self {
+            market,
+            quality,
+            rules: QualityRules::default(),
+            action: QualityAction::Report,
+        }
+    }
+
+    pub fn with_rules(mut self, rules: QualityRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    pub fn with_action(mut self, action: QualityAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// 扫描一条序列；执行修复/隔离后会重新扫描，保存的是处理后的结果
+    pub async fn scan(&self, key: &BarSeriesKey) -> Result<SeriesReport> {
+        let bars = self.market.find_series_bars(key).await?;
+        let mut report = scan_series(key, &bars, &self.rules);
+
+        if self.action != QualityAction::Report && !report.is_clean() {
+            let fixed = self.apply_action(&bars, &report).await?;
+            if fixed > 0 {
+                let bars = self.market.find_series_bars(key).await?;
+                report = scan_series(key, &bars, &self.rules);
+            }
+        }
+
+        self.quality.save(&report).await?;
+        if report.is_clean() {
+            info!(
+                "🧹 {}: {} bars, {} warnings",
+                key,
+                report.bars,
+                report.warnings()
+            );
+        } else {
+            warn!(
+                "🧹 {}: {} bars, {} errors, {} warnings",
+                key,
+                report.bars,
+                report.errors(),
+                report.warnings()
+            );
+        }
+        Ok(report)
+    }
+
+    /// 扫描多条序列
+    pub async fn scan_all(&self, keys: &[BarSeriesKey]) -> Result<Vec<SeriesReport>> {
+        let mut reports = Vec::with_capacity(keys.len());
+        for key in keys {
+            reports.push(self.scan(key).await?);
+        }
+        Ok(reports)
+    }
+
+    async fn apply_action(&self, bars: &[MarketBar], report: &SeriesReport) -> Result<usize> {
+        let mut handled = HashSet::new();
+        for anomaly in &report.anomalies {
+            let Some(bar_id) = anomaly.bar_id else {
+                continue;
+            };
+            if anomaly.kind.severity() != Severity::Error || !handled.insert(bar_id) {
+                continue;
+            }
+            let Some(bar) = bars.iter().find(|b| b.id == bar_id) else {
+                continue;
+            };
+
+            let repaired = match self.action {
+                QualityAction::Repair if anomaly.kind.is_repairable() => repair_bar(bar),
+                _ => None,
+            };
+            match repaired {
+                Some(fixed) => {
+                    self.market.update_ohlc(&fixed).await?;
+                }
+                None => {
+                    let reason = serde_json::to_string(&anomaly.kind)?;
+                    self.market.quarantine(bar_id, &reason).await?;
+                }
+            }
+        }
+        Ok(handled.len())
+    }
+}
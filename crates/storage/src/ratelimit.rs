@@ -0,0 +1,217 @@
+use crate::redis::RedisService;
+use anyhow::{bail, Result};
+use quant_core::enums::Exchange;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+use uuid::Uuid;
+
+// =========================================================================
+// 分布式限流 (多个服务共享同一份交易所 API 配额)
+// =========================================================================
+//
+// Redis 中的 Key: {prefix}:ratelimit:{exchange}:{endpoint}
+//   滑动窗口  ZSET，member = 请求标识，score = 请求时间 (毫秒)
+//   令牌桶    HASH，tokens = 剩余令牌，ts = 上次补充时间 (毫秒)
+//
+// 时间统一取 Redis 服务端 TIME，不同主机的时钟偏差不会影响配额。
+// 脚本返回 {是否放行, 剩余额度, 建议等待毫秒}。
+
+/// KEYS[1] = 窗口, ARGV[1] = 窗口长度 (毫秒), ARGV[2] = 上限, ARGV[3] = 本次消耗, ARGV[4] = 请求标识
+const SLIDING_WINDOW_SCRIPT: &str = r#"
+    redis.replicate_commands()
+    local time = redis.call("TIME")
+    local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+    local window = tonumber(ARGV[1])
+    local limit = tonumber(ARGV[2])
+    local cost = tonumber(ARGV[3])
+
+    redis.call("ZREMRANGEBYSCORE", KEYS[1], "-inf", now - window)
+    local used = redis.call("ZCARD", KEYS[1])
+    if used + cost <= limit then
+        for i = 1, cost do
+            redis.call("ZADD", KEYS[1], now, ARGV[4] .. ":" .. i)
+        end
+        redis.call("PEXPIRE", KEYS[1], window)
+        return {1, limit - used - cost, 0}
+    end
+    -- 需要等最早的 (used + cost - limit) 个请求滑出窗口
+    local oldest = redis.call("ZRANGE", KEYS[1], used + cost - limit - 1, used + cost - limit - 1, "WITHSCORES")
+    return {0, limit - used, tonumber(oldest[2]) + window - now}
+"#;
+
+/// KEYS[1] = 桶, ARGV[1] = 容量, ARGV[2] = 每秒补充令牌数, ARGV[3] = 本次消耗
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+    redis.replicate_commands()
+    local time = redis.call("TIME")
+    local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+    local capacity = tonumber(ARGV[1])
+    local rate = tonumber(ARGV[2])
+    local cost = tonumber(ARGV[3])
+
+    local state = redis.call("HMGET", KEYS[1], "tokens", "ts")
+    local tokens = tonumber(state[1]) or capacity
+    local ts = tonumber(state[2]) or now
+    tokens = math.min(capacity, tokens + math.max(0, now - ts) * rate / 1000)
+
+    local allowed, wait = 0, 0
+    if tokens >= cost then
+        tokens = tokens - cost
+        allowed = 1
+    else
+        wait = math.ceil((cost - tokens) * 1000 / rate)
+    end
+    redis.call("HSET", KEYS[1], "tokens", tostring(tokens), "ts", now)
+    -- 桶补满后状态等价于不存在，过期即可
+    redis.call("PEXPIRE", KEYS[1], math.ceil(capacity * 1000 / rate) + 1000)
+    return {allowed, math.floor(tokens), wait}
+"#;
+
+/// 限流策略
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RateLimitPolicy {
+    /// 任意 `window_ms` 毫秒内最多 `limit` 次 (对应交易所 "每分钟 N 次" 类规则)
+    SlidingWindow { limit: u64, window_ms: u64 },
+    /// 令牌桶：允许 `capacity` 的突发，长期速率为每秒 `refill_per_sec`
+    TokenBucket { capacity: u64, refill_per_sec: f64 },
+}
+
+impl RateLimitPolicy {
+    /// 单次请求允许的最大消耗
+    fn max_cost(&self) -> u64 {
+        match self {
+            RateLimitPolicy::SlidingWindow { limit, .. } => *limit,
+            RateLimitPolicy::TokenBucket { capacity, .. } => *capacity,
+        }
+    }
+}
+
+/// 一次限流判定
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    /// 放行后 (或当前) 剩余的额度
+    pub remaining: u64,
+    /// 被拒绝时，额度恢复到足够本次消耗的预计等待时间
+    pub retry_after: Duration,
+}
+
+/// 按 交易所 / 接口 分桶的分布式限流器
+#[derive(Clone)]
+pub struct RateLimiter {
+    redis: RedisService,
+    key_prefix: String,
+    policy: RateLimitPolicy,
+}
+
+impl RateLimiter {
+    pub fn new(redis: RedisService, policy: RateLimitPolicy) -> Self {
+        Self {
+            redis,
+            key_prefix: "photon".to_string(),
+            policy,
+        }
+    }
+
+    /// Key 前缀，多套环境共用一个 Redis 时用来隔离
+    pub fn with_prefix(mut self, key_prefix: impl Into<String>) -> Self {
+        self.key_prefix = key_prefix.into();
+        self
+    }
+
+    pub fn policy(&self) -> &RateLimitPolicy {
+        &self.policy
+    }
+
+    fn key(&self, exchange: Exchange, endpoint: &str) -> String {
+        format!("{}:ratelimit:{}:{}", self.key_prefix, exchange, endpoint)
+    }
+
+    /// 尝试消耗 `cost` 个额度，不等待
+    pub async fn try_acquire(
+        &self,
+        exchange: Exchange,
+        endpoint: &str,
+        cost: u64,
+    ) -> Result<RateLimitDecision> {
+        if cost == 0 || cost > self.policy.max_cost() {
+            bail!(
+                "Rate limit cost {} for {}:{} must be in 1..={}",
+                cost,
+                exchange,
+                endpoint,
+                self.policy.max_cost()
+            );
+        }
+        if let RateLimitPolicy::TokenBucket { refill_per_sec, .. } = self.policy {
+            if refill_per_sec <= 0.0 {
+                bail!("Token bucket refill_per_sec must be > 0");
+            }
+        }
+        let key = self.key(exchange, endpoint);
+        let cost = cost.to_string();
+        let (allowed, remaining, retry_after_ms): (i64, i64, i64) = match self.policy {
+            RateLimitPolicy::SlidingWindow { limit, window_ms } => {
+                let request_id = Uuid::new_v4().to_string();
+                self.redis
+                    .exec_lua_script(
+                        SLIDING_WINDOW_SCRIPT,
+                        &[&key],
+                        &[
+                            &window_ms.to_string(),
+                            &limit.to_string(),
+                            &cost,
+                            &request_id,
+                        ],
+                    )
+                    .await?
+            }
+            RateLimitPolicy::TokenBucket {
+                capacity,
+                refill_per_sec,
+            } => {
+                self.redis
+                    .exec_lua_script(
+                        TOKEN_BUCKET_SCRIPT,
+                        &[&key],
+                        &[&capacity.to_string(), &refill_per_sec.to_string(), &cost],
+                    )
+                    .await?
+            }
+        };
+        Ok(RateLimitDecision {
+            allowed: allowed == 1,
+            remaining: remaining.max(0) as u64,
+            retry_after: Duration::from_millis(retry_after_ms.max(0) as u64),
+        })
+    }
+
+    /// 在 `timeout` 内按建议等待时间重试，超时仍未放行返回 false
+    pub async fn acquire(
+        &self,
+        exchange: Exchange,
+        endpoint: &str,
+        cost: u64,
+        timeout: Duration,
+    ) -> Result<bool> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let decision = self.try_acquire(exchange, endpoint, cost).await?;
+            if decision.allowed {
+                return Ok(true);
+            }
+            let now = Instant::now();
+            if now + decision.retry_after > deadline {
+                return Ok(false);
+            }
+            // 至少等 1ms，避免 retry_after 为 0 时空转
+            sleep(decision.retry_after.max(Duration::from_millis(1))).await;
+        }
+    }
+
+    /// 清空某个接口的限流状态 (测试 / 运维手动重置)
+    pub async fn reset(&self, exchange: Exchange, endpoint: &str) -> Result<()> {
+        self.redis.delete(&self.key(exchange, endpoint)).await
+    }
+}
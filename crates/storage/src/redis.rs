@@ -1,26 +1,50 @@
 use anyhow::{Context, Result};
 use deadpool_redis::{Config, Pool, Runtime};
-use dotenvy::dotenv;
+use redis::streams::{StreamMaxlen, StreamReadOptions, StreamReadReply};
 use redis::{AsyncCommands, FromRedisValue, Script};
+use std::collections::HashMap;
 use std::fmt::Debug;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
-use tokio::sync::OnceCell;
 use tokio::time::sleep;
 
-static REDIS_SERVICE: OnceCell<RedisService> = OnceCell::const_new();
+/// Stream 中的一条消息
+#[derive(Debug, Clone)]
+pub struct StreamEntry {
+    pub id: String,
+    pub fields: HashMap<String, String>,
+}
 
-pub async fn service() -> &'static RedisService {
-    REDIS_SERVICE
-        .get_or_init(|| async {
-            dotenv().ok();
-            let redis_url = std::env::var("REDIS_URL").expect("REDIS_URL missing");
-            RedisService::new(redis_url.as_str()).expect("Failed to initialize RedisService")
+impl StreamEntry {
+    fn from_id(entry: redis::streams::StreamId) -> Result<Self> {
+        let fields = entry
+            .map
+            .iter()
+            .map(|(k, v)| Ok((k.clone(), String::from_redis_value(v)?)))
+            .collect::<Result<HashMap<_, _>>>()?;
+        Ok(Self {
+            id: entry.id,
+            fields,
         })
-        .await
+    }
 }
+
+/// 消费组中已投递未确认的消息
+#[derive(Debug, Clone)]
+pub struct PendingEntry {
+    pub id: String,
+    pub consumer: String,
+    /// 距上次投递的毫秒数
+    pub idle_ms: u64,
+    /// 累计投递次数
+    pub deliveries: u64,
+}
+
 #[derive(Clone)]
 pub struct RedisService {
     pool: Pool,
+    /// 脚本内容 -> 预先计算好 SHA1 的脚本 (进程内常量脚本数量很少，不做淘汰)
+    scripts: Arc<RwLock<HashMap<String, Script>>>,
 }
 
 impl RedisService {
@@ -32,7 +56,10 @@ impl RedisService {
             .create_pool(Some(Runtime::Tokio1))
             .context("Failed to create Redis pool")?;
 
-        Ok(Self { pool })
+        Ok(Self {
+            pool,
+            scripts: Arc::new(RwLock::new(HashMap::new())),
+        })
     }
 
     async fn get_connection(&self) -> Result<deadpool_redis::Connection> {
@@ -67,8 +94,72 @@ impl RedisService {
         Ok(())
     }
 
+    // ========================================================================
+    //  Hash 操作
+    // ========================================================================
+
+    /// 写入 Hash 的单个字段 (可选同时刷新整个 Key 的过期时间)
+    pub async fn hset(
+        &self,
+        key: &str,
+        field: &str,
+        value: &str,
+        expire_seconds: Option<u64>,
+    ) -> Result<()> {
+        let mut conn = self.get_connection().await?;
+        let mut pipe = redis::pipe();
+        pipe.atomic().hset(key, field, value).ignore();
+        if let Some(seconds) = expire_seconds {
+            pipe.expire(key, seconds as i64).ignore();
+        }
+        let _: () = pipe.query_async(&mut conn).await?;
+        Ok(())
+    }
+
+    /// 用 `entries` 整体替换一个 Hash (MULTI 中先删后写，读者不会看到半截数据)
+    pub async fn hreplace(
+        &self,
+        key: &str,
+        entries: &[(String, String)],
+        expire_seconds: Option<u64>,
+    ) -> Result<()> {
+        let mut conn = self.get_connection().await?;
+        let mut pipe = redis::pipe();
+        pipe.atomic().del(key).ignore();
+        if !entries.is_empty() {
+            pipe.hset_multiple(key, entries).ignore();
+            if let Some(seconds) = expire_seconds {
+                pipe.expire(key, seconds as i64).ignore();
+            }
+        }
+        let _: () = pipe.query_async(&mut conn).await?;
+        Ok(())
+    }
+
+    pub async fn hget(&self, key: &str, field: &str) -> Result<Option<String>> {
+        let mut conn = self.get_connection().await?;
+        let result: Option<String> = conn.hget(key, field).await?;
+        Ok(result)
+    }
+
+    /// 读取整个 Hash (Key 不存在时为空)
+    pub async fn hget_all(&self, key: &str) -> Result<HashMap<String, String>> {
+        let mut conn = self.get_connection().await?;
+        let result: HashMap<String, String> = conn.hgetall(key).await?;
+        Ok(result)
+    }
+
+    pub async fn hdel(&self, key: &str, field: &str) -> Result<()> {
+        let mut conn = self.get_connection().await?;
+        let _: () = conn.hdel(key, field).await?;
+        Ok(())
+    }
+
     /// 【核心功能】执行 Lua 脚本 (原子性/事务性)
     ///
+    /// 脚本按内容缓存 SHA1，执行时只发送 EVALSHA；
+    /// 服务端没有该脚本 (首次执行 / Redis 重启 / SCRIPT FLUSH) 时自动 SCRIPT LOAD 后重试。
+    ///
     /// # 参数
     /// * `script_content`: Lua 脚本代码
     /// * `keys`: 涉及到的 Key 列表 (Lua 中用 KEYS[1], KEYS[2]...)
@@ -83,36 +174,161 @@ impl RedisService {
         T: FromRedisValue + Debug,
     {
         let mut conn = self.get_connection().await?;
+        let script = self.script(script_content);
 
-        // 构建 EVAL 命令
-        // 格式: EVAL script numkeys key1 key2 ... arg1 arg2 ...
-        let mut cmd = redis::cmd("EVAL");
-
-        // 1. 脚本内容
-        cmd.arg(script_content);
-
-        // 2. Key 的数量 (Redis 协议要求必须传这个)
-        cmd.arg(keys.len());
-
-        // 3. 注入所有 Keys
+        // 构建 EVALSHA 调用
+        // 格式: EVALSHA sha1 numkeys key1 key2 ... arg1 arg2 ...
+        let mut invocation = script.prepare_invoke();
         for key in keys {
-            cmd.arg(*key);
+            invocation.key(*key);
         }
-
-        // 4. 注入所有 Args
         for arg in args {
-            cmd.arg(*arg);
+            invocation.arg(*arg);
         }
 
-        // 5. 执行
-        let result: T = cmd
-            .query_async(&mut conn)
+        let result: T = invocation
+            .invoke_async(&mut conn)
             .await
             .context(format!("Failed to execute lua script: {}", script_content))?;
 
         Ok(result)
     }
 
+    /// 取出 (或首次创建) 脚本对象，SHA1 只计算一次
+    fn script(&self, script_content: &str) -> Script {
+        if let Some(script) = self.scripts.read().unwrap().get(script_content) {
+            return script.clone();
+        }
+        self.scripts
+            .write()
+            .unwrap()
+            .entry(script_content.to_string())
+            .or_insert_with(|| Script::new(script_content))
+            .clone()
+    }
+
+    // ========================================================================
+    //  Streams (跨进程消息)
+    // ========================================================================
+
+    /// 追加一条消息 (按 `max_len` 近似裁剪)，返回消息 ID
+    pub async fn xadd(
+        &self,
+        stream: &str,
+        fields: &[(&str, &str)],
+        max_len: Option<usize>,
+    ) -> Result<String> {
+        let mut conn = self.get_connection().await?;
+        let id: String = match max_len {
+            Some(len) => {
+                conn.xadd_maxlen(stream, StreamMaxlen::Approx(len), "*", fields)
+                    .await?
+            }
+            None => conn.xadd(stream, "*", fields).await?,
+        };
+        Ok(id)
+    }
+
+    /// 创建消费组 (流不存在时一并创建)，返回是否新建；组已存在不算错误
+    pub async fn xgroup_create(&self, stream: &str, group: &str, start_id: &str) -> Result<bool> {
+        let mut conn = self.get_connection().await?;
+        let created: redis::RedisResult<()> =
+            conn.xgroup_create_mkstream(stream, group, start_id).await;
+        match created {
+            Ok(()) => Ok(true),
+            Err(e) if e.code() == Some("BUSYGROUP") => Ok(false),
+            Err(e) => Err(e).context("Failed to create consumer group"),
+        }
+    }
+
+    /// 以消费组身份读取新消息 (最多阻塞 `block_ms` 毫秒)
+    pub async fn xread_group(
+        &self,
+        stream: &str,
+        group: &str,
+        consumer: &str,
+        count: usize,
+        block_ms: u64,
+    ) -> Result<Vec<StreamEntry>> {
+        let mut conn = self.get_connection().await?;
+        let options = StreamReadOptions::default()
+            .group(group, consumer)
+            .count(count)
+            .block(block_ms as usize);
+        let reply: StreamReadReply = conn.xread_options(&[stream], &[">"], &options).await?;
+        reply
+            .keys
+            .into_iter()
+            .flat_map(|key| key.ids)
+            .map(StreamEntry::from_id)
+            .collect()
+    }
+
+    /// 确认消息已处理，返回确认条数
+    pub async fn xack(&self, stream: &str, group: &str, ids: &[String]) -> Result<u64> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        let mut conn = self.get_connection().await?;
+        let acked: u64 = conn.xack(stream, group, ids).await?;
+        Ok(acked)
+    }
+
+    /// 按 ID 范围读取 (不经过消费组，用于回放)
+    pub async fn xrange(
+        &self,
+        stream: &str,
+        start: &str,
+        end: &str,
+        count: usize,
+    ) -> Result<Vec<StreamEntry>> {
+        let mut conn = self.get_connection().await?;
+        let reply: redis::streams::StreamRangeReply =
+            conn.xrange_count(stream, start, end, count).await?;
+        reply.ids.into_iter().map(StreamEntry::from_id).collect()
+    }
+
+    /// 消费组中最早的 `count` 条未确认消息
+    pub async fn xpending(
+        &self,
+        stream: &str,
+        group: &str,
+        count: usize,
+    ) -> Result<Vec<PendingEntry>> {
+        let mut conn = self.get_connection().await?;
+        let reply: redis::streams::StreamPendingCountReply =
+            conn.xpending_count(stream, group, "-", "+", count).await?;
+        Ok(reply
+            .ids
+            .into_iter()
+            .map(|p| PendingEntry {
+                id: p.id,
+                consumer: p.consumer,
+                idle_ms: p.last_delivered_ms as u64,
+                deliveries: p.times_delivered as u64,
+            })
+            .collect())
+    }
+
+    /// 把空闲超过 `min_idle_ms` 的消息转给 `consumer` (接管崩溃消费者的消息)
+    pub async fn xclaim(
+        &self,
+        stream: &str,
+        group: &str,
+        consumer: &str,
+        min_idle_ms: u64,
+        ids: &[String],
+    ) -> Result<Vec<StreamEntry>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut conn = self.get_connection().await?;
+        let reply: redis::streams::StreamClaimReply = conn
+            .xclaim(stream, group, consumer, min_idle_ms as usize, ids)
+            .await?;
+        reply.ids.into_iter().map(StreamEntry::from_id).collect()
+    }
+
     // ========================================================================
     //  分布式锁 (Distributed Lock)
     // ========================================================================
@@ -1,19 +1,61 @@
-use sqlx::MySqlPool;
-use tokio::sync::OnceCell;
+use sqlx::{MySql, MySqlPool, QueryBuilder};
 
 // 1. 引入 Core 定义的实体和错误
-use crate::repository::common;
+use crate::repository::common::batch::{execute_batches, BatchOptions, BatchReport};
 use anyhow::Result;
 use quant_core::account::{Asset, Position};
 
-static ACCOUNT_POOL: OnceCell<AccountRepository> = OnceCell::const_new();
-pub async fn repository() -> &'static AccountRepository {
-    ACCOUNT_POOL
-        .get_or_init(|| async {
-            let pool = common::get_db_pool().await;
-            AccountRepository::new(pool.clone())
-        })
-        .await
+/// 多行 upsert，列和更新字段与 [`AccountRepository::upsert_asset`] 保持一致
+fn build_asset_upsert(assets: &[Asset]) -> QueryBuilder<'_, MySql> {
+    let mut qb = QueryBuilder::<MySql>::new(
+        "INSERT INTO asset (\
+            uuid, account_name, exchange, currency, \
+            free, frozen, borrowed) ",
+    );
+    qb.push_values(assets, |mut b, asset| {
+        b.push_bind(&asset.uuid)
+            .push_bind(&asset.account_name)
+            .push_bind(asset.exchange.to_string())
+            .push_bind(&asset.currency)
+            .push_bind(asset.free)
+            .push_bind(asset.frozen)
+            .push_bind(asset.borrowed);
+    });
+    qb.push(
+        " ON DUPLICATE KEY UPDATE \
+            free = VALUES(free), \
+            frozen = VALUES(frozen), \
+            borrowed = VALUES(borrowed)",
+    );
+    qb
+}
+
+/// 多行 upsert，列和更新字段与 [`AccountRepository::upsert_position`] 保持一致
+fn build_position_upsert(positions: &[Position]) -> QueryBuilder<'_, MySql> {
+    let mut qb = QueryBuilder::<MySql>::new(
+        "INSERT INTO `position` (\
+            uuid, account_name, exchange, symbol, side, \
+            quantity, entry_price, unrealized_pnl, leverage) ",
+    );
+    qb.push_values(positions, |mut b, pos| {
+        b.push_bind(&pos.uuid)
+            .push_bind(&pos.account_name)
+            .push_bind(pos.exchange)
+            .push_bind(&pos.symbol)
+            .push_bind(pos.side.to_string())
+            .push_bind(pos.quantity)
+            .push_bind(pos.entry_price)
+            .push_bind(pos.unrealized_pnl)
+            .push_bind(pos.leverage);
+    });
+    qb.push(
+        " ON DUPLICATE KEY UPDATE \
+            quantity = VALUES(quantity), \
+            entry_price = VALUES(entry_price), \
+            unrealized_pnl = VALUES(unrealized_pnl), \
+            leverage = VALUES(leverage)",
+    );
+    qb
 }
 
 #[derive(Clone)]
@@ -52,6 +94,15 @@ impl AccountRepository {
         Ok(result.rows_affected())
     }
 
+    /// 按批同步资产余额 (Upsert)，返回每批的 rows_affected
+    pub async fn upsert_assets(
+        &self,
+        assets: &[Asset],
+        options: &BatchOptions,
+    ) -> Result<BatchReport> {
+        execute_batches(&self.pool, assets, 7, options, build_asset_upsert).await
+    }
+
     /// 查询某账户下的所有资产余额
     pub async fn find_assets_by_account(&self, account_name: &str) -> Result<Vec<Asset>> {
         let assets = sqlx::query_as::<_, Asset>(
@@ -132,6 +183,15 @@ impl AccountRepository {
         Ok(result.rows_affected())
     }
 
+    /// 按批同步持仓 (Upsert)，返回每批的 rows_affected
+    pub async fn upsert_positions(
+        &self,
+        positions: &[Position],
+        options: &BatchOptions,
+    ) -> Result<BatchReport> {
+        execute_batches(&self.pool, positions, 9, options, build_position_upsert).await
+    }
+
     /// 查询某账户下的所有持仓
     pub async fn find_positions_by_account(&self, account_name: &str) -> Result<Vec<Position>> {
         let positions = sqlx::query_as::<_, Position>(
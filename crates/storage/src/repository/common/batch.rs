@@ -0,0 +1,98 @@
+use anyhow::{bail, Result};
+use sqlx::{MySql, MySqlPool, QueryBuilder};
+
+// =========================================================================
+// 批量写入
+// =========================================================================
+
+/// MySQL 单条语句的占位符上限
+const MAX_PLACEHOLDERS: usize = 65_535;
+
+/// 批量写入参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchOptions {
+    /// 每条多行 INSERT 的行数
+    pub batch_size: usize,
+    /// 所有批次放在同一个事务里：任一批失败则整体回滚
+    pub transactional: bool,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            batch_size: 500,
+            transactional: false,
+        }
+    }
+}
+
+impl BatchOptions {
+    pub fn new(batch_size: usize) -> Self {
+        Self {
+            batch_size,
+            ..Default::default()
+        }
+    }
+
+    pub fn transactional(mut self) -> Self {
+        self.transactional = true;
+        self
+    }
+}
+
+/// 批量写入结果：每批的 rows_affected
+///
+/// 计数口径与对应的单行写入方法一致 (upsert: 新插入计 1，命中已有行计 1 或 2)。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchReport {
+    pub batches: Vec<u64>,
+}
+
+impl BatchReport {
+    pub fn total(&self) -> u64 {
+        self.batches.iter().sum()
+    }
+}
+
+/// 按批执行多行写入
+///
+/// `build` 负责为一批数据生成完整语句 (通常是 `push_values` + 可选的 ON DUPLICATE KEY UPDATE)，
+/// `columns` 为每行占位符数量，用于校验批大小不超过 MySQL 上限。
+pub(crate) async fn execute_batches<T>(
+    pool: &MySqlPool,
+    items: &[T],
+    columns: usize,
+    options: &BatchOptions,
+    build: for<'a> fn(&'a [T]) -> QueryBuilder<'a, MySql>,
+) -> Result<BatchReport> {
+    if options.batch_size == 0 {
+        bail!("batch_size must be greater than 0");
+    }
+    if options.batch_size * columns > MAX_PLACEHOLDERS {
+        bail!(
+            "batch_size {} exceeds MySQL placeholder limit ({} columns per row)",
+            options.batch_size,
+            columns
+        );
+    }
+
+    let mut report = BatchReport::default();
+    if items.is_empty() {
+        return Ok(report);
+    }
+
+    if options.transactional {
+        let mut tx = pool.begin().await?;
+        for chunk in items.chunks(options.batch_size) {
+            let result = build(chunk).build().execute(&mut *tx).await?;
+            report.batches.push(result.rows_affected());
+        }
+        tx.commit().await?;
+    } else {
+        for chunk in items.chunks(options.batch_size) {
+            let result = build(chunk).build().execute(pool).await?;
+            report.batches.push(result.rows_affected());
+        }
+    }
+    Ok(report)
+}
@@ -1,66 +1,133 @@
-use anyhow::Context;
-use anyhow::Result;
+use crate::cache::CacheConfig;
+use crate::repository::strategy_repo::StateRetention;
+use anyhow::{bail, Context, Result};
 use dotenvy::dotenv;
+use serde::{Deserialize, Serialize};
 use sqlx::mysql::{MySqlPoolOptions, MySqlRow};
 use sqlx::MySqlPool;
 use std::time::Duration;
-use tokio::sync::OnceCell;
 use tracing::info;
 
-static DB_POOL: OnceCell<MySqlPool> = OnceCell::const_new();
-pub async fn get_db_pool() -> &'static MySqlPool {
-    DB_POOL
-        .get_or_init(|| async { get_real_pool().await })
-        .await
+pub mod batch;
+
+pub use batch::{BatchOptions, BatchReport};
+
+/// K 线存储后端
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MarketBackend {
+    /// MySQL `market_bar` (日线级别)
+    #[default]
+    Mysql,
+    /// TimescaleDB (Tick / 逐笔成交 / 分钟线，高周期由连续聚合生成)
+    Timescale,
 }
 
-pub async fn get_real_pool() -> MySqlPool {
-    dotenv().ok();
-    let url = std::env::var("DATABASE_URL").expect("DATABASE_URL missing");
-    let pool = MySqlPoolOptions::new()
-        // 最大连接数：根据你的并发量设置。
-        // 量化系统通常并发不高但响应要求快，20-50 足够。
-        // 如果太多，MySQL 服务端可能会拒绝连接 (Too many connections)。
-        .max_connections(20)
+/// 存储配置 (MySQL 连接池 + 可选 Redis / TimescaleDB)
+///
+/// 引擎配置中对应 `[storage]` 段；`database_url` 为空时回退到环境变量 `DATABASE_URL`，
+/// `redis_url` 为空时回退到 `REDIS_URL` (都没有则不启用 Redis)，
+/// `timescale_url` 为空时回退到 `TIMESCALE_URL`。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    pub database_url: String,
+    /// 最大连接数：量化系统通常并发不高但响应要求快，20-50 足够。
+    /// 如果太多，MySQL 服务端可能会拒绝连接 (Too many connections)。
+    pub max_connections: u32,
+    /// 最小空闲连接数：保持一定数量的连接“热着”，避免有新请求时还要花时间握手。
+    pub min_connections: u32,
+    /// 获取连接超时 (秒)：拿不到连接说明数据库挂了或者太忙，直接报错，不要死等。
+    pub acquire_timeout_secs: u64,
+    /// 空闲超时 (秒)：连接长时间没人用就关掉省资源。
+    pub idle_timeout_secs: u64,
+    pub redis_url: Option<String>,
+    /// 行情 (K 线) 读写走哪个后端；订单 / 账户 / 策略始终在 MySQL
+    pub market_backend: MarketBackend,
+    /// TimescaleDB 连接串 (market_backend = TIMESCALE 时必需)
+    pub timescale_url: Option<String>,
+    /// Redis 热点缓存 (配置了 Redis 时启用)
+    pub cache: CacheConfig,
+    /// 策略状态历史版本的保留策略
+    pub state_retention: StateRetention,
+}
 
-        // 最小空闲连接数：
-        // 保持一定数量的连接“热着”，避免有新请求时还要花时间握手建立连接。
-        .min_connections(5)
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            database_url: String::new(),
+            max_connections: 20,
+            min_connections: 5,
+            acquire_timeout_secs: 3,
+            idle_timeout_secs: 600,
+            redis_url: None,
+            market_backend: MarketBackend::Mysql,
+            timescale_url: None,
+            cache: CacheConfig::default(),
+            state_retention: StateRetention::default(),
+        }
+    }
+}
 
-        // 获取连接超时：
-        // 如果 3 秒拿不到连接，说明数据库挂了或者太忙，直接报错，不要死等。
-        .acquire_timeout(Duration::from_secs(3))
+impl StorageConfig {
+    /// 只从环境变量 (含 .env) 读取连接串，其余取默认值
+    pub fn from_env() -> Self {
+        Self::default().with_env_fallback()
+    }
 
-        // 空闲超时：
-        // 如果一个连接 10 分钟没人用，就把它关掉省资源。
-        .idle_timeout(Duration::from_secs(600))
+    /// 未配置的连接串用环境变量补齐
+    pub fn with_env_fallback(mut self) -> Self {
+        dotenv().ok();
+        if self.database_url.is_empty() {
+            self.database_url = std::env::var("DATABASE_URL").unwrap_or_default();
+        }
+        if self.redis_url.as_deref().is_none_or(str::is_empty) {
+            self.redis_url = std::env::var("REDIS_URL").ok().filter(|u| !u.is_empty());
+        }
+        if self.timescale_url.as_deref().is_none_or(str::is_empty) {
+            self.timescale_url = std::env::var("TIMESCALE_URL")
+                .ok()
+                .filter(|u| !u.is_empty());
+        }
+        self
+    }
+}
 
-        // 2. 建立连接
-        .connect(url.as_str())
+/// 按配置创建 MySQL 连接池并 ping 一次
+pub async fn connect(config: &StorageConfig) -> Result<MySqlPool> {
+    if config.database_url.is_empty() {
+        bail!("Database URL missing: set [storage].database_url or DATABASE_URL");
+    }
+    let pool = MySqlPoolOptions::new()
+        .max_connections(config.max_connections)
+        .min_connections(config.min_connections)
+        .acquire_timeout(Duration::from_secs(config.acquire_timeout_secs))
+        .idle_timeout(Duration::from_secs(config.idle_timeout_secs))
+        .connect(&config.database_url)
         .await
-        .context("Failed to connect to MySQL database. Please check your .env configuration and ensure MySQL is running.")
-        .expect("Failed to connect to MySQL database.");
+        .context("Failed to connect to MySQL database. Please check your .env configuration and ensure MySQL is running.")?;
 
-    // 3. 验证连接是否真正可用
-    // 发送一个简单的 ping 确保网络通畅
+    // 验证连接是否真正可用
     sqlx::query("SELECT 1")
         .execute(&pool)
         .await
-        .context("Failed to execute ping query")
-        .expect("Ping query failed");
+        .context("Failed to execute ping query")?;
 
     info!("✅ Successfully connected to MySQL database pool.");
-    pool
+    Ok(pool)
+}
+
+/// 用 `DATABASE_URL` 创建连接池 (测试 / 命令行工具)
+pub async fn connect_from_env() -> Result<MySqlPool> {
+    connect(&StorageConfig::from_env()).await
 }
 
-pub async fn dml(sql: &str) -> anyhow::Result<u64> {
-    let pool = get_real_pool().await;
-    let result = sqlx::query(sql).execute(&pool).await?;
+pub async fn dml(pool: &MySqlPool, sql: &str) -> Result<u64> {
+    let result = sqlx::query(sql).execute(pool).await?;
     Ok(result.rows_affected())
 }
 
-pub async fn dql<'a>(sql: &str) -> anyhow::Result<Vec<MySqlRow>> {
-    let pool = get_real_pool().await;
-    let rows = sqlx::query(sql).fetch_all(&pool).await?;
+pub async fn dql(pool: &MySqlPool, sql: &str) -> Result<Vec<MySqlRow>> {
+    let rows = sqlx::query(sql).fetch_all(pool).await?;
     Ok(rows)
 }
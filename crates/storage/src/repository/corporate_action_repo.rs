@@ -0,0 +1,123 @@
+use anyhow::Result;
+use quant_core::enums::CorporateActionType;
+use quant_core::market::CorporateAction;
+use sqlx::MySqlPool;
+
+/// 公司行动仓储层 (拆股 / 分红 / 代码变更)
+///
+/// 唯一索引: (exchange, symbol, action_type, ex_date)
+#[derive(Clone)]
+pub struct CorporateActionRepository {
+    pool: MySqlPool,
+}
+
+impl CorporateActionRepository {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+
+    /// 保存公司行动 (Upsert)
+    pub async fn save(&self, action: &CorporateAction) -> Result<u64> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO corporate_action (
+                exchange, symbol, action_type, ex_date,
+                ratio, cash_amount, new_symbol
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                ratio = VALUES(ratio),
+                cash_amount = VALUES(cash_amount),
+                new_symbol = VALUES(new_symbol)
+            "#,
+            action.exchange,
+            action.symbol,
+            action.action_type.to_string(),
+            action.ex_date,
+            action.ratio,
+            action.cash_amount,
+            action.new_symbol.as_ref().map(|s| s.to_string())
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// 查询某标的的全部公司行动 (按除权日升序)
+    pub async fn find_by_symbol(
+        &self,
+        exchange: &str,
+        symbol: &str,
+    ) -> Result<Vec<CorporateAction>> {
+        let actions = sqlx::query_as::<_, CorporateAction>(
+            r#"
+            SELECT
+                id, exchange, symbol, action_type, ex_date,
+                ratio, cash_amount, new_symbol, gmt_create
+            FROM corporate_action
+            WHERE exchange = ? AND symbol = ?
+            ORDER BY ex_date ASC, id ASC
+            "#,
+        )
+        .bind(exchange)
+        .bind(symbol)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(actions)
+    }
+
+    /// 查询改名为 `new_symbol` 的代码变更记录 (用于向前追溯旧代码)
+    pub async fn find_renamed_to(
+        &self,
+        exchange: &str,
+        new_symbol: &str,
+    ) -> Result<Vec<CorporateAction>> {
+        let actions = sqlx::query_as::<_, CorporateAction>(
+            r#"
+            SELECT
+                id, exchange, symbol, action_type, ex_date,
+                ratio, cash_amount, new_symbol, gmt_create
+            FROM corporate_action
+            WHERE exchange = ? AND new_symbol = ? AND action_type = ?
+            ORDER BY ex_date DESC
+            "#,
+        )
+        .bind(exchange)
+        .bind(new_symbol)
+        .bind(CorporateActionType::SymbolChange.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(actions)
+    }
+
+    /// 查询某交易所的全部公司行动
+    pub async fn find_by_exchange(&self, exchange: &str) -> Result<Vec<CorporateAction>> {
+        let actions = sqlx::query_as::<_, CorporateAction>(
+            r#"
+            SELECT
+                id, exchange, symbol, action_type, ex_date,
+                ratio, cash_amount, new_symbol, gmt_create
+            FROM corporate_action
+            WHERE exchange = ?
+            ORDER BY symbol ASC, ex_date ASC
+            "#,
+        )
+        .bind(exchange)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(actions)
+    }
+
+    /// 删除一条公司行动
+    pub async fn delete(&self, id: i64) -> Result<u64> {
+        let result = sqlx::query!("DELETE FROM corporate_action WHERE id = ?", id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,121 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use quant_core::oms::Fill;
+use sqlx::{MySql, MySqlPool, QueryBuilder};
+use uuid::Uuid;
+
+/// 按订单批量查询时每条 IN 语句的订单数
+const ORDERS_PER_QUERY: usize = 1000;
+
+/// 成交仓储层
+/// 负责逐笔成交回报 (Fill) 的持久化，是 TCA 分析的数据来源
+#[derive(Clone)]
+pub struct FillRepository {
+    pool: MySqlPool,
+}
+
+impl FillRepository {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+
+    /// 记录一笔成交
+    pub async fn insert(&self, fill: &Fill) -> Result<u64> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO `fill` (
+                fill_uuid, order_uuid, strategy_uuid, exchange_trade_id,
+                exchange, symbol, side, price, quantity,
+                fee, fee_currency, is_maker, trade_time
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            fill.uuid.to_string(),
+            fill.order_uuid,
+            fill.strategy_uuid,
+            fill.exchange_trade_id,
+            fill.exchange,
+            fill.symbol,
+            fill.side.to_string(),
+            fill.price.0,
+            fill.quantity.0,
+            fill.fee,
+            fill.fee_currency,
+            fill.is_maker,
+            fill.trade_time
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// 查询某订单的全部成交 (按撮合时间升序)
+    pub async fn find_by_order(&self, order_uuid: Uuid) -> Result<Vec<Fill>> {
+        let fills = sqlx::query_as::<_, Fill>(
+            r#"
+            SELECT
+                id, fill_uuid, order_uuid, strategy_uuid, exchange_trade_id,
+                exchange, symbol, side, price, quantity,
+                fee, fee_currency, is_maker, trade_time, gmt_create
+            FROM `fill`
+            WHERE order_uuid = ?
+            ORDER BY trade_time ASC
+            "#,
+        )
+        .bind(order_uuid.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(fills)
+    }
+
+    /// 查询时间范围内的全部成交 [start, end)
+    pub async fn find_by_range(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Fill>> {
+        let fills = sqlx::query_as::<_, Fill>(
+            r#"
+            SELECT
+                id, fill_uuid, order_uuid, strategy_uuid, exchange_trade_id,
+                exchange, symbol, side, price, quantity,
+                fee, fee_currency, is_maker, trade_time, gmt_create
+            FROM `fill`
+            WHERE trade_time >= ? AND trade_time < ?
+            ORDER BY trade_time ASC
+            "#,
+        )
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(fills)
+    }
+
+    /// 查询一批订单的全部成交 (按撮合时间升序)，订单较多时分批 IN 查询
+    pub async fn find_by_orders(&self, order_uuids: &[String]) -> Result<Vec<Fill>> {
+        let mut fills = Vec::new();
+        for chunk in order_uuids.chunks(ORDERS_PER_QUERY) {
+            let mut qb = QueryBuilder::<MySql>::new(
+                r#"
+            SELECT
+                id, fill_uuid, order_uuid, strategy_uuid, exchange_trade_id,
+                exchange, symbol, side, price, quantity,
+                fee, fee_currency, is_maker, trade_time, gmt_create
+            FROM `fill`
+            WHERE order_uuid IN ("#,
+            );
+            let mut separated = qb.separated(", ");
+            for order_uuid in chunk {
+                separated.push_bind(order_uuid);
+            }
+            qb.push(") ORDER BY trade_time ASC");
+            fills.extend(qb.build_query_as::<Fill>().fetch_all(&self.pool).await?);
+        }
+        fills.sort_by_key(|f| f.trade_time);
+        Ok(fills)
+    }
+}
@@ -1,20 +1,87 @@
-use crate::repository::common;
+use crate::repository::common::batch::{execute_batches, BatchOptions, BatchReport};
 use anyhow::Result;
 use chrono::NaiveDate;
 use quant_core::market::MarketBar;
-use quant_core::BarPeriod;
-use sqlx::MySqlPool;
-use tokio::sync::OnceCell;
-
-static MARKET_DATA_POOL: OnceCell<MarketDataRepository> = OnceCell::const_new();
-
-pub async fn repository() -> &'static MarketDataRepository {
-    MARKET_DATA_POOL
-        .get_or_init(|| async {
-            let pool = common::get_db_pool().await;
-            MarketDataRepository::new(pool.clone())
-        })
-        .await
+use quant_core::{BarPeriod, Exchange};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, MySql, MySqlPool, QueryBuilder};
+use std::collections::HashSet;
+
+/// 批量写入结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UpsertCounts {
+    /// 新插入的行数
+    pub inserted: u64,
+    /// 命中唯一索引、被覆盖的行数
+    pub updated: u64,
+}
+
+/// 一条 K 线序列: 同一交易所 / 标的 / 周期 / 交易条件类型
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, FromRow)]
+pub struct BarSeriesKey {
+    pub exchange: Exchange,
+    pub symbol: String,
+    pub bar_period: BarPeriod,
+    #[sqlx(rename = "type")]
+    pub trade_type: u8,
+}
+
+impl std::fmt::Display for BarSeriesKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} type={}",
+            self.exchange, self.symbol, self.bar_period, self.trade_type
+        )
+    }
+}
+
+/// K 线唯一键: (exchange, symbol, bar_period, type, start_time)
+pub(crate) type BarKey = (String, String, String, u8, NaiveDate);
+
+pub(crate) fn bar_key(bar: &MarketBar) -> BarKey {
+    (
+        bar.exchange.to_string(),
+        bar.symbol.to_string(),
+        bar.bar_period.to_string(),
+        bar.trade_type,
+        bar.start_time,
+    )
+}
+
+/// 多行 upsert，列和更新字段与 [`MarketDataRepository::save`] 保持一致
+fn build_bar_upsert(bars: &[MarketBar]) -> QueryBuilder<'_, MySql> {
+    let mut qb = QueryBuilder::<MySql>::new(
+        "INSERT INTO `market_bar` (\
+            exchange, symbol, bar_period, `type`, \
+            open, high, low, close, volume, amount, \
+            start_time, end_time) ",
+    );
+    qb.push_values(bars, |mut b, bar| {
+        b.push_bind(bar.exchange)
+            .push_bind(&bar.symbol)
+            .push_bind(bar.bar_period.to_string())
+            .push_bind(bar.trade_type)
+            .push_bind(bar.open.0)
+            .push_bind(bar.high.0)
+            .push_bind(bar.low.0)
+            .push_bind(bar.close.0)
+            .push_bind(bar.volume.0)
+            .push_bind(bar.amount)
+            .push_bind(bar.start_time)
+            .push_bind(bar.end_time);
+    });
+    qb.push(
+        " ON DUPLICATE KEY UPDATE \
+            open = VALUES(open), \
+            high = VALUES(high), \
+            low = VALUES(low), \
+            close = VALUES(close), \
+            volume = VALUES(volume), \
+            amount = VALUES(amount), \
+            end_time = VALUES(end_time)",
+    );
+    qb
 }
 
 #[derive(Clone)]
@@ -68,6 +135,62 @@ impl MarketDataRepository {
         Ok(result.rows_affected())
     }
 
+    /// 按批保存 K 线 (Upsert)，返回每批的 rows_affected
+    pub async fn save_all(
+        &self,
+        bars: &[MarketBar],
+        options: &BatchOptions,
+    ) -> Result<BatchReport> {
+        execute_batches(&self.pool, bars, 12, options, build_bar_upsert).await
+    }
+
+    /// 保存一批 K 线并区分插入 / 覆盖 (单条多行 INSERT ... ON DUPLICATE KEY UPDATE)
+    ///
+    /// 连接开启了 CLIENT_FOUND_ROWS，rows_affected 无法区分 "新插入" 和 "值未变的已有行"，
+    /// 因此先在同一事务内查出已存在的唯一键，再执行 upsert。
+    /// 调用方负责控制批大小 (每行 12 个占位符)。
+    pub async fn save_batch(&self, bars: &[MarketBar]) -> Result<UpsertCounts> {
+        if bars.is_empty() {
+            return Ok(UpsertCounts::default());
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        let mut select = QueryBuilder::<MySql>::new(
+            "SELECT exchange, symbol, bar_period, `type`, start_time FROM market_bar \
+             WHERE (exchange, symbol, bar_period, `type`, start_time) IN ",
+        );
+        select.push_tuples(bars, |mut b, bar| {
+            b.push_bind(bar.exchange)
+                .push_bind(&bar.symbol)
+                .push_bind(bar.bar_period.to_string())
+                .push_bind(bar.trade_type)
+                .push_bind(bar.start_time);
+        });
+        let existing: HashSet<BarKey> = select
+            .build_query_as::<BarKey>()
+            .fetch_all(&mut *tx)
+            .await?
+            .into_iter()
+            .collect();
+
+        build_bar_upsert(bars).build().execute(&mut *tx).await?;
+        tx.commit().await?;
+
+        // 同一批内的重复键：第一次算插入，之后算覆盖
+        let mut seen = HashSet::with_capacity(bars.len());
+        let mut counts = UpsertCounts::default();
+        for bar in bars {
+            let key = bar_key(bar);
+            if existing.contains(&key) || !seen.insert(key) {
+                counts.updated += 1;
+            } else {
+                counts.inserted += 1;
+            }
+        }
+        Ok(counts)
+    }
+
     /// 查询最近的 N 天 K 线
     ///
     /// 参数 trade_type: 通常查询普通交易(21)
@@ -142,4 +265,109 @@ impl MarketDataRepository {
 
         Ok(bars)
     }
+
+    // =========================================================================
+    // 数据质量 (扫描 / 修复 / 隔离)
+    // =========================================================================
+
+    /// 列出库中全部 K 线序列，可按交易所 / 标的过滤
+    pub async fn find_series(
+        &self,
+        exchange: Option<&str>,
+        symbol: Option<&str>,
+    ) -> Result<Vec<BarSeriesKey>> {
+        let series = sqlx::query_as::<_, BarSeriesKey>(
+            r#"
+            SELECT DISTINCT exchange, symbol, bar_period, `type`
+            FROM market_bar
+            WHERE (? IS NULL OR exchange = ?)
+              AND (? IS NULL OR symbol = ?)
+            ORDER BY exchange, symbol, bar_period, `type`
+            "#,
+        )
+        .bind(exchange)
+        .bind(exchange)
+        .bind(symbol)
+        .bind(symbol)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(series)
+    }
+
+    /// 查询一条序列的全部 K 线 (按日期升序)
+    pub async fn find_series_bars(&self, key: &BarSeriesKey) -> Result<Vec<MarketBar>> {
+        let bars = sqlx::query_as::<_, MarketBar>(
+            r#"
+            SELECT 
+                id, exchange, symbol, bar_period, `type`,
+                open, high, low, close, volume, amount, 
+                start_time, end_time, gmt_create
+            FROM market_bar
+            WHERE exchange = ? 
+              AND symbol = ? 
+              AND bar_period = ?
+              AND `type` = ?
+            ORDER BY start_time ASC
+            "#,
+        )
+        .bind(key.exchange)
+        .bind(&key.symbol)
+        .bind(key.bar_period.to_string())
+        .bind(key.trade_type)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(bars)
+    }
+
+    /// 按主键修正 OHLC
+    pub async fn update_ohlc(&self, bar: &MarketBar) -> Result<u64> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE market_bar
+            SET open = ?, high = ?, low = ?, close = ?
+            WHERE id = ?
+            "#,
+            bar.open.0,
+            bar.high.0,
+            bar.low.0,
+            bar.close.0,
+            bar.id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// 把 K 线移入隔离表 `market_bar_quarantine` (保留原始数据和原因)，原表删除
+    pub async fn quarantine(&self, bar_id: i64, reason: &str) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO market_bar_quarantine (
+                bar_id, exchange, symbol, bar_period, `type`,
+                open, high, low, close, volume, amount,
+                start_time, end_time, reason
+            )
+            SELECT
+                id, exchange, symbol, bar_period, `type`,
+                open, high, low, close, volume, amount,
+                start_time, end_time, ?
+            FROM market_bar
+            WHERE id = ?
+            "#,
+            reason,
+            bar_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        let result = sqlx::query!("DELETE FROM market_bar WHERE id = ?", bar_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(result.rows_affected())
+    }
 }
@@ -1,5 +1,8 @@
 pub mod account_repo;
+pub mod common;
+pub mod corporate_action_repo;
+pub mod fill_repo;
 pub mod market_repo;
 pub mod order_repo;
+pub mod quality_repo;
 pub mod strategy_repo;
-pub mod common;
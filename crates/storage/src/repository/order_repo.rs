@@ -1,32 +1,57 @@
-use crate::repository::common;
+use crate::lock::{Fence, Fencing};
+use crate::repository::common::batch::{execute_batches, BatchOptions, BatchReport};
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use quant_core::enums::OrderStatus;
 use quant_core::oms::Order;
-use sqlx::MySqlPool;
-use tokio::sync::OnceCell;
+use sqlx::{MySql, MySqlPool, QueryBuilder};
 use uuid::Uuid;
 
-static ORDER_POOL: OnceCell<OrderRepository> = OnceCell::const_new();
-
-/// **获取订单数据仓储层实例**
-pub async fn repository() -> &'static OrderRepository {
-    ORDER_POOL
-        .get_or_init(|| async {
-            let pool = common::get_db_pool().await;
-            OrderRepository::new(pool.clone())
-        })
-        .await
+/// 多行 INSERT，列与 [`OrderRepository::insert`] 保持一致
+fn build_order_insert(orders: &[Order]) -> QueryBuilder<'_, MySql> {
+    let mut qb = QueryBuilder::<MySql>::new(
+        "INSERT INTO `order` (\
+            order_uuid, strategy_uuid, exchange_order_id, \
+            symbol, exchange, side, order_type, status, \
+            price, quantity, filled_quantity, average_price, fee) ",
+    );
+    qb.push_values(orders, |mut b, order| {
+        b.push_bind(&order.uuid)
+            .push_bind(&order.strategy_uuid)
+            .push_bind(&order.exchange_order_id)
+            .push_bind(&order.symbol)
+            .push_bind(order.exchange)
+            .push_bind(order.side.to_string())
+            .push_bind(order.order_type.to_string())
+            .push_bind(order.status.to_string())
+            .push_bind(order.price.map(|p| p.0))
+            .push_bind(order.quantity.0)
+            .push_bind(order.filled_quantity.0)
+            .push_bind(order.average_price.map(|p| p.0))
+            .push_bind(order.fee);
+    });
+    qb
 }
 
 // ⚠️ 修复：添加 Clone
 #[derive(Clone)]
 pub struct OrderRepository {
     pool: MySqlPool,
+    fencing: Fencing,
 }
 
 impl OrderRepository {
     pub fn new(pool: MySqlPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            fencing: Fencing::default(),
+        }
+    }
+
+    /// 订单状态更新以 `fence_token` 列为条件，拒绝旧任期的写入
+    pub fn with_fencing(mut self, fencing: Fencing) -> Self {
+        self.fencing = fencing;
+        self
     }
 
     // insert 方法保持不变，因为它用的是 query! 宏来检查 SQL 语法，这很好
@@ -61,6 +86,15 @@ impl OrderRepository {
         Ok(result.rows_affected())
     }
 
+    /// 按批插入订单，返回每批的 rows_affected
+    pub async fn insert_all(
+        &self,
+        orders: &[Order],
+        options: &BatchOptions,
+    ) -> Result<BatchReport> {
+        execute_batches(&self.pool, orders, 13, options, build_order_insert).await
+    }
+
     /// ⚠️ 修复：改用 sqlx::query_as (函数版)
     pub async fn find_by_uuid(&self, order_uuid: Uuid) -> Result<Option<Order>> {
         let order = sqlx::query_as::<_, Order>(
@@ -102,7 +136,53 @@ impl OrderRepository {
         Ok(orders)
     }
 
-    // update_status 保持不变
+    /// 查询创建时间落在 [start, end) 内的订单 (TCA / 报表使用)
+    pub async fn find_by_range(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Order>> {
+        let orders = sqlx::query_as::<_, Order>(
+            r#"
+            SELECT
+                id, order_uuid, strategy_uuid, exchange_order_id,
+                symbol, exchange, side, order_type, status,
+                price, quantity, filled_quantity, average_price, fee,
+                gmt_create, gmt_modified
+            FROM `order`
+            WHERE gmt_create >= ? AND gmt_create < ?
+            ORDER BY gmt_create ASC
+            "#,
+        )
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(orders)
+    }
+
+    /// 查询所有未终结的订单 (停机撤单 / 重启对账使用)
+    pub async fn find_open_orders(&self) -> Result<Vec<Order>> {
+        let orders = sqlx::query_as::<_, Order>(
+            r#"
+            SELECT
+                id, order_uuid, strategy_uuid, exchange_order_id,
+                symbol, exchange, side, order_type, status,
+                price, quantity, filled_quantity, average_price, fee,
+                gmt_create, gmt_modified
+            FROM `order`
+            WHERE status IN ('CREATED', 'PENDING', 'NEW', 'PARTIALLY_FILLED')
+            ORDER BY gmt_create ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(orders)
+    }
+
+    /// 更新订单状态；设置了 Fence 时只在行上的 token 不比本任期新时写入
     pub async fn update_status(
         &self,
         order_uuid: Uuid,
@@ -112,6 +192,19 @@ impl OrderRepository {
         avg_price: Option<rust_decimal::Decimal>,
         fee: Option<rust_decimal::Decimal>,
     ) -> Result<()> {
+        if let Some(fence) = self.fencing.current() {
+            return self
+                .update_status_fenced(
+                    &fence,
+                    order_uuid,
+                    status,
+                    exchange_order_id,
+                    filled_qty,
+                    avg_price,
+                    fee,
+                )
+                .await;
+        }
         sqlx::query!(
             r#"
             UPDATE `order`
@@ -134,4 +227,53 @@ impl OrderRepository {
 
         Ok(())
     }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn update_status_fenced(
+        &self,
+        fence: &Fence,
+        order_uuid: Uuid,
+        status: OrderStatus,
+        exchange_order_id: Option<String>,
+        filled_qty: rust_decimal::Decimal,
+        avg_price: Option<rust_decimal::Decimal>,
+        fee: Option<rust_decimal::Decimal>,
+    ) -> Result<()> {
+        let uuid = order_uuid.to_string();
+        let updated = sqlx::query(
+            r#"
+            UPDATE `order`
+            SET status = ?,
+                exchange_order_id = COALESCE(?, exchange_order_id),
+                filled_quantity = ?,
+                average_price = ?,
+                fee = ?,
+                fence_token = ?
+            WHERE order_uuid = ? AND (fence_token IS NULL OR fence_token <= ?)
+            "#,
+        )
+        .bind(status.to_string())
+        .bind(exchange_order_id)
+        .bind(filled_qty)
+        .bind(avg_price)
+        .bind(fee)
+        .bind(fence.token)
+        .bind(&uuid)
+        .bind(fence.token)
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+        if updated > 0 {
+            return Ok(());
+        }
+
+        // 没有更新到行：订单不存在 / 值未变化时照常返回，被更新任期写过时拒绝
+        let stored: Option<Option<u64>> =
+            sqlx::query_scalar("SELECT fence_token FROM `order` WHERE order_uuid = ?")
+                .bind(&uuid)
+                .fetch_optional(&self.pool)
+                .await?;
+        fence.check(&format!("order {}", uuid), stored.flatten())?;
+        Ok(())
+    }
 }
@@ -0,0 +1,121 @@
+use crate::quality::SeriesReport;
+use crate::repository::market_repo::BarSeriesKey;
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use quant_core::{BarPeriod, Exchange};
+use sqlx::{FromRow, MySqlPool};
+
+/// 每条 K 线序列最近一次质量扫描结果 (表 `market_bar_quality`)
+#[derive(Debug, Clone, FromRow)]
+pub struct QualityRecord {
+    pub exchange: Exchange,
+    pub symbol: String,
+    pub bar_period: BarPeriod,
+    #[sqlx(rename = "type")]
+    pub trade_type: u8,
+    pub bar_count: i64,
+    pub first_date: Option<NaiveDate>,
+    pub last_date: Option<NaiveDate>,
+    pub error_count: i64,
+    pub warning_count: i64,
+    /// 异常明细 (JSON 数组)
+    pub anomalies: String,
+    pub scanned_at: DateTime<Utc>,
+}
+
+impl QualityRecord {
+    pub fn is_clean(&self) -> bool {
+        self.error_count == 0
+    }
+}
+
+/// 数据质量仓储层
+/// 保存扫描结果，供回测 / 运维查询哪些序列是脏数据
+#[derive(Clone)]
+pub struct QualityRepository {
+    pool: MySqlPool,
+}
+
+impl QualityRepository {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+
+    /// 保存扫描结果 (每条序列只保留最近一次)
+    pub async fn save(&self, report: &SeriesReport) -> Result<u64> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO market_bar_quality (
+                exchange, symbol, bar_period, `type`,
+                bar_count, first_date, last_date,
+                error_count, warning_count, anomalies, scanned_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                bar_count = VALUES(bar_count),
+                first_date = VALUES(first_date),
+                last_date = VALUES(last_date),
+                error_count = VALUES(error_count),
+                warning_count = VALUES(warning_count),
+                anomalies = VALUES(anomalies),
+                scanned_at = VALUES(scanned_at)
+            "#,
+            report.key.exchange,
+            report.key.symbol,
+            report.key.bar_period.to_string(),
+            report.key.trade_type,
+            report.bars as i64,
+            report.first_date,
+            report.last_date,
+            report.errors() as i64,
+            report.warnings() as i64,
+            serde_json::to_string(&report.anomalies)?,
+            Utc::now()
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// 查询某条序列最近一次扫描结果
+    pub async fn find(&self, key: &BarSeriesKey) -> Result<Option<QualityRecord>> {
+        let record = sqlx::query_as::<_, QualityRecord>(
+            r#"
+            SELECT
+                exchange, symbol, bar_period, `type`,
+                bar_count, first_date, last_date,
+                error_count, warning_count, anomalies, scanned_at
+            FROM market_bar_quality
+            WHERE exchange = ? AND symbol = ? AND bar_period = ? AND `type` = ?
+            "#,
+        )
+        .bind(key.exchange)
+        .bind(&key.symbol)
+        .bind(key.bar_period.to_string())
+        .bind(key.trade_type)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// 查询存在 Error 级别异常的序列
+    pub async fn find_dirty(&self) -> Result<Vec<QualityRecord>> {
+        let records = sqlx::query_as::<_, QualityRecord>(
+            r#"
+            SELECT
+                exchange, symbol, bar_period, `type`,
+                bar_count, first_date, last_date,
+                error_count, warning_count, anomalies, scanned_at
+            FROM market_bar_quality
+            WHERE error_count > 0
+            ORDER BY exchange, symbol, bar_period, `type`
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+}
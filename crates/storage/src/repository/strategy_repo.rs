@@ -1,34 +1,112 @@
-use crate::repository::common;
-use anyhow::Result;
-use quant_core::enums::StrategyStatus;
-use quant_core::strategy::{Signal, Strategy, StrategyState};
+use crate::lock::Fencing;
+use anyhow::{anyhow, bail, Result};
+use chrono::{Duration, Utc};
+use quant_core::enums::{StatusActor, StrategyStatus};
+use quant_core::strategy::{Signal, Strategy, StrategyState, StrategyStatusChange};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use sqlx::MySqlPool;
-use tokio::sync::OnceCell;
+use sqlx::types::Json;
+use sqlx::{MySql, MySqlPool, Transaction};
+use tracing::warn;
 use uuid::Uuid;
 
-static ORDER_POOL: OnceCell<StrategyRepository> = OnceCell::const_new();
-
-/// **获取策略数据仓储层实例**
-pub async fn repository() -> &'static StrategyRepository {
-    ORDER_POOL
-        .get_or_init(|| async {
-            let pool = common::get_db_pool().await;
-            StrategyRepository::new(pool.clone())
-        })
-        .await
-}
-
 /// 策略仓储层
 /// 负责策略元数据、运行时状态和信号的持久化
 #[derive(Clone)]
 pub struct StrategyRepository {
     pool: MySqlPool,
+    retention: StateRetention,
+    fencing: Fencing,
+}
+
+/// 策略状态历史版本的保留策略 (引擎配置中的 `[storage.state_retention]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StateRetention {
+    /// 每个策略最多保留的版本数 (含当前版本)
+    pub keep_versions: usize,
+    /// 超过天数的旧版本也会被清理 (None 表示只按数量)
+    pub max_age_days: Option<u32>,
+}
+
+impl Default for StateRetention {
+    fn default() -> Self {
+        Self {
+            keep_versions: 50,
+            max_age_days: None,
+        }
+    }
+}
+
+/// `reason` 列的最大长度 (字符)
+const MAX_REASON_LEN: usize = 512;
+
+/// 校验状态变更是否符合生命周期 (内存实现共用)
+pub(crate) fn check_transition(uuid: Uuid, from: StrategyStatus, to: StrategyStatus) -> Result<()> {
+    if !from.can_transition_to(to) {
+        bail!(
+            "Invalid status transition for strategy {}: {} -> {}",
+            uuid,
+            from,
+            to
+        );
+    }
+    Ok(())
+}
+
+/// 超长的原因 (如完整的错误链) 截断到列宽
+pub(crate) fn truncate_reason(reason: &str) -> String {
+    reason.chars().take(MAX_REASON_LEN).collect()
 }
 
 impl StrategyRepository {
     pub fn new(pool: MySqlPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            retention: StateRetention::default(),
+            fencing: Fencing::default(),
+        }
+    }
+
+    pub fn with_retention(mut self, retention: StateRetention) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// 策略状态 / 运行状态写入以策略行的 `fence_token` 为条件，拒绝旧任期的写入
+    pub fn with_fencing(mut self, fencing: Fencing) -> Self {
+        self.fencing = fencing;
+        self
+    }
+
+    /// 在事务中认领策略行的 Fence：行上的 token 比本任期新时拒绝，否则记下本任期
+    ///
+    /// 没有设置 Fence 或策略未登记时不做任何事。
+    async fn claim_fence(&self, tx: &mut Transaction<'_, MySql>, uuid: &str) -> Result<()> {
+        let Some(fence) = self.fencing.current() else {
+            return Ok(());
+        };
+        let stored: Option<Option<u64>> = sqlx::query_scalar(
+            r#"
+            SELECT fence_token FROM `strategy`
+            WHERE uuid = ?
+            FOR UPDATE
+            "#,
+        )
+        .bind(uuid)
+        .fetch_optional(&mut **tx)
+        .await?;
+        let Some(stored) = stored else {
+            return Ok(());
+        };
+        fence.check(&format!("strategy {}", uuid), stored)?;
+
+        sqlx::query("UPDATE `strategy` SET fence_token = ? WHERE uuid = ?")
+            .bind(fence.token)
+            .bind(uuid)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
     }
 
     // =========================================================================
@@ -39,14 +117,15 @@ impl StrategyRepository {
     pub async fn create(&self, strategy: &Strategy) -> Result<u64> {
         let result = sqlx::query!(
             r#"
-            INSERT INTO `strategy` (uuid, name, class_name, status, config)
-            VALUES (?, ?, ?, ?, ?)
+            INSERT INTO `strategy` (uuid, name, class_name, status, reason, config)
+            VALUES (?, ?, ?, ?, ?, ?)
             "#,
             strategy.uuid.to_string(),
             strategy.name,
             strategy.class_name,
             strategy.status.to_string(), // Enum -> String
-            strategy.config              // SQLx 自动处理 serde_json::Value
+            strategy.reason,
+            strategy.config // SQLx 自动处理 serde_json::Value
         )
         .execute(&self.pool)
         .await?;
@@ -61,7 +140,7 @@ impl StrategyRepository {
         let strategy = sqlx::query_as::<_, Strategy>(
             r#"
             SELECT
-                id, uuid, name, class_name, status, config,
+                id, uuid, name, class_name, status, reason, config,
                 gmt_create, gmt_modified
             FROM `strategy`
             WHERE uuid = ?
@@ -80,7 +159,7 @@ impl StrategyRepository {
         let strategies = sqlx::query_as::<_, Strategy>(
             r#"
             SELECT
-                id, uuid, name, class_name, status, config,
+                id, uuid, name, class_name, status, reason, config,
                 gmt_create, gmt_modified
             FROM `strategy`
             WHERE status IN ('RUNNING', 'INITIALIZING')
@@ -92,52 +171,234 @@ impl StrategyRepository {
         Ok(strategies)
     }
 
-    /// 更新策略状态
-    pub async fn update_status(&self, uuid: Uuid, status: StrategyStatus) -> Result<()> {
-        // 如果提供了 reason，则更新 reason；否则保持原值 (COALESCE)
+    /// 获取全部策略 (按创建时间排序，CLI 列表使用)
+    pub async fn find_all(&self) -> Result<Vec<Strategy>> {
+        let strategies = sqlx::query_as::<_, Strategy>(
+            r#"
+            SELECT
+                id, uuid, name, class_name, status, reason, config,
+                gmt_create, gmt_modified
+            FROM `strategy`
+            ORDER BY gmt_create ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(strategies)
+    }
+
+    /// 更新策略状态：按生命周期校验后写入状态和原因，并追加一条审计记录 (同一事务)
+    pub async fn update_status(
+        &self,
+        uuid: Uuid,
+        status: StrategyStatus,
+        actor: StatusActor,
+        reason: &str,
+    ) -> Result<StrategyStatusChange> {
+        let strategy_uuid = uuid.to_string();
+        let reason = truncate_reason(reason);
+        let mut tx = self.pool.begin().await?;
+        // 锁住策略行，并发变更按顺序校验
+        let from: StrategyStatus = sqlx::query_scalar(
+            r#"
+            SELECT status FROM `strategy`
+            WHERE uuid = ?
+            FOR UPDATE
+            "#,
+        )
+        .bind(&strategy_uuid)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| anyhow!("Strategy {} not found", uuid))?;
+        check_transition(uuid, from, status)?;
+        self.claim_fence(&mut tx, &strategy_uuid).await?;
+
         sqlx::query!(
             r#"
             UPDATE `strategy`
-            SET status = ?
+            SET status = ?, reason = ?
             WHERE uuid = ?
             "#,
             status.to_string(),
-            uuid.to_string()
+            reason,
+            strategy_uuid
         )
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
-        Ok(())
+
+        let id = sqlx::query!(
+            r#"
+            INSERT INTO `strategy_status_audit` (strategy_uuid, from_status, to_status, reason, actor)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+            strategy_uuid,
+            from.to_string(),
+            status.to_string(),
+            reason,
+            actor.to_string()
+        )
+        .execute(&mut *tx)
+        .await?
+        .last_insert_id();
+        tx.commit().await?;
+
+        Ok(StrategyStatusChange {
+            id: id as i64,
+            strategy_uuid,
+            from_status: from,
+            to_status: status,
+            reason,
+            actor,
+            gmt_create: Utc::now(),
+        })
+    }
+
+    /// 最近的 `limit` 条状态变更 (按时间倒序)
+    pub async fn find_status_history(
+        &self,
+        uuid: Uuid,
+        limit: i64,
+    ) -> Result<Vec<StrategyStatusChange>> {
+        let changes = sqlx::query_as::<_, StrategyStatusChange>(
+            r#"
+            SELECT
+                id, strategy_uuid, from_status, to_status, reason, actor,
+                gmt_create
+            FROM `strategy_status_audit`
+            WHERE strategy_uuid = ?
+            ORDER BY id DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(uuid.to_string())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(changes)
     }
 
     // =========================================================================
     // 2. StrategyState (运行时状态)
     // =========================================================================
 
-    /// 保存/更新策略状态 (Upsert)
-    /// 如果不存在则插入，如果存在则更新 state_data
-    pub async fn save_state(&self, strategy_uuid: Uuid, state_data: &Value) -> Result<()> {
-        // 注意：数据库表字段是 strategy_uuid，实体中对应的字段是 uuid
+    /// 保存策略状态：追加历史版本并更新当前版本 (同一事务)，返回新版本号
+    pub async fn save_state(
+        &self,
+        strategy_uuid: Uuid,
+        schema_version: i32,
+        state_data: &Value,
+    ) -> Result<i64> {
+        let mut tx = self.pool.begin().await?;
+        self.claim_fence(&mut tx, &strategy_uuid.to_string())
+            .await?;
+        let version =
+            Self::append_state(&mut tx, strategy_uuid, schema_version, state_data).await?;
+        tx.commit().await?;
+
+        if let Err(e) = self.prune_state_versions(strategy_uuid, version).await {
+            warn!(
+                "Failed to prune state versions of strategy {}: {:?}",
+                strategy_uuid, e
+            );
+        }
+        Ok(version)
+    }
+
+    /// 在事务中分配下一个版本号，写入历史表并 Upsert 当前状态
+    async fn append_state(
+        tx: &mut Transaction<'_, MySql>,
+        strategy_uuid: Uuid,
+        schema_version: i32,
+        state_data: &Value,
+    ) -> Result<i64> {
+        let uuid = strategy_uuid.to_string();
+        // 锁住该策略的版本区间，并发保存时串行分配版本号
+        let latest: Option<i64> = sqlx::query_scalar(
+            r#"
+            SELECT MAX(version) FROM `strategy_state_version`
+            WHERE strategy_uuid = ?
+            FOR UPDATE
+            "#,
+        )
+        .bind(&uuid)
+        .fetch_one(&mut **tx)
+        .await?;
+        let version = latest.unwrap_or(0) + 1;
+
         sqlx::query!(
             r#"
-            INSERT INTO `strategy_state` (strategy_uuid, state_data)
-            VALUES (?, ?)
-            ON DUPLICATE KEY UPDATE state_data = ?
+            INSERT INTO `strategy_state_version` (strategy_uuid, version, schema_version, state_data)
+            VALUES (?, ?, ?, ?)
             "#,
-            strategy_uuid.to_string(),
-            state_data,
+            uuid,
+            version,
+            schema_version,
             state_data
         )
-        .execute(&self.pool)
+        .execute(&mut **tx)
         .await?;
-        Ok(())
+
+        sqlx::query!(
+            r#"
+            INSERT INTO `strategy_state` (strategy_uuid, state_data, version, schema_version)
+            VALUES (?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                state_data = VALUES(state_data),
+                version = VALUES(version),
+                schema_version = VALUES(schema_version)
+            "#,
+            uuid,
+            state_data,
+            version,
+            schema_version
+        )
+        .execute(&mut **tx)
+        .await?;
+        Ok(version)
+    }
+
+    /// 按保留策略删除旧版本 (当前版本始终保留)，返回删除条数
+    pub async fn prune_state_versions(&self, strategy_uuid: Uuid, current: i64) -> Result<u64> {
+        let uuid = strategy_uuid.to_string();
+        let keep = self.retention.keep_versions.max(1) as i64;
+        let mut deleted = sqlx::query!(
+            r#"
+            DELETE FROM `strategy_state_version`
+            WHERE strategy_uuid = ? AND version <= ?
+            "#,
+            uuid,
+            current - keep
+        )
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+
+        if let Some(days) = self.retention.max_age_days {
+            let cutoff = Utc::now() - Duration::days(days as i64);
+            deleted += sqlx::query!(
+                r#"
+                DELETE FROM `strategy_state_version`
+                WHERE strategy_uuid = ? AND version < ? AND gmt_create < ?
+                "#,
+                uuid,
+                current,
+                cutoff
+            )
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+        }
+        Ok(deleted)
     }
 
-    /// 加载策略状态
+    /// 加载当前策略状态
     pub async fn load_state(&self, strategy_uuid: Uuid) -> Result<Option<StrategyState>> {
         let state = sqlx::query_as::<_, StrategyState>(
             r#"
             SELECT
-                id, strategy_uuid, state_data,
+                id, strategy_uuid, state_data, version, schema_version,
                 gmt_create, gmt_modified
             FROM `strategy_state`
             WHERE strategy_uuid = ?
@@ -150,6 +411,70 @@ impl StrategyRepository {
         Ok(state)
     }
 
+    /// 加载指定历史版本
+    pub async fn load_state_version(
+        &self,
+        strategy_uuid: Uuid,
+        version: i64,
+    ) -> Result<Option<StrategyState>> {
+        let state = sqlx::query_as::<_, StrategyState>(
+            r#"
+            SELECT
+                id, strategy_uuid, state_data, version, schema_version,
+                gmt_create, gmt_create AS gmt_modified
+            FROM `strategy_state_version`
+            WHERE strategy_uuid = ? AND version = ?
+            "#,
+        )
+        .bind(strategy_uuid.to_string())
+        .bind(version)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(state)
+    }
+
+    /// 最近的 `limit` 个历史版本 (按版本号倒序)
+    pub async fn list_state_versions(
+        &self,
+        strategy_uuid: Uuid,
+        limit: i64,
+    ) -> Result<Vec<StrategyState>> {
+        let states = sqlx::query_as::<_, StrategyState>(
+            r#"
+            SELECT
+                id, strategy_uuid, state_data, version, schema_version,
+                gmt_create, gmt_create AS gmt_modified
+            FROM `strategy_state_version`
+            WHERE strategy_uuid = ?
+            ORDER BY version DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(strategy_uuid.to_string())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(states)
+    }
+
+    /// 回滚到指定版本：复制为新版本，返回新版本号
+    pub async fn rollback_state(&self, strategy_uuid: Uuid, version: i64) -> Result<i64> {
+        let target = self
+            .load_state_version(strategy_uuid, version)
+            .await?
+            .ok_or_else(|| {
+                anyhow!(
+                    "State version {} of strategy {} not found",
+                    version,
+                    strategy_uuid
+                )
+            })?;
+        self.save_state(strategy_uuid, target.schema_version, &target.state_data)
+            .await
+    }
+
     // =========================================================================
     // 3. Signal (交易信号)
     // =========================================================================
@@ -160,9 +485,11 @@ impl StrategyRepository {
             r#"
             INSERT INTO `signal` (
                 uuid, strategy_uuid, symbol, side,
-                price, quantity, reason
+                price, quantity, reason,
+                status, status_reason, expires_at, order_uuids, filled_quantity,
+                submitted_at, first_fill_at, executed_at
             )
-            VALUES (?, ?, ?, ?, ?, ?, ?)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
             signal.uuid.to_string(),
             signal.strategy_uuid.to_string(),
@@ -170,7 +497,40 @@ impl StrategyRepository {
             signal.side.to_string(),
             signal.price.map(|p| p.0), // Option<Price> -> Option<Decimal>
             signal.quantity.map(|q| q.0), // Option<Quantity> -> Option<Decimal>
-            signal.reason
+            signal.reason,
+            signal.status.to_string(),
+            signal.status_reason,
+            signal.expires_at,
+            Json(&signal.order_uuids),
+            signal.filled_quantity.0,
+            signal.submitted_at,
+            signal.first_fill_at,
+            signal.executed_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// 更新信号的生命周期字段 (状态 / 关联订单 / 成交进度)
+    pub async fn update_signal(&self, signal: &Signal) -> Result<u64> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE `signal`
+            SET quantity = ?, status = ?, status_reason = ?, order_uuids = ?,
+                filled_quantity = ?, submitted_at = ?, first_fill_at = ?, executed_at = ?
+            WHERE uuid = ?
+            "#,
+            signal.quantity.map(|q| q.0),
+            signal.status.to_string(),
+            signal.status_reason,
+            Json(&signal.order_uuids),
+            signal.filled_quantity.0,
+            signal.submitted_at,
+            signal.first_fill_at,
+            signal.executed_at,
+            signal.uuid
         )
         .execute(&self.pool)
         .await?;
@@ -178,6 +538,48 @@ impl StrategyRepository {
         Ok(result.rows_affected())
     }
 
+    /// 根据 UUID 查询信号
+    pub async fn find_signal(&self, uuid: Uuid) -> Result<Option<Signal>> {
+        let signal = sqlx::query_as::<_, Signal>(
+            r#"
+            SELECT
+                id, uuid, strategy_uuid, symbol, side,
+                price, quantity, reason,
+                status, status_reason, expires_at, order_uuids, filled_quantity,
+                submitted_at, first_fill_at, executed_at,
+                gmt_create, gmt_modified
+            FROM `signal`
+            WHERE uuid = ?
+            "#,
+        )
+        .bind(uuid.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(signal)
+    }
+
+    /// 未结束的信号 (待处理 / 等待成交)，引擎重启后据此恢复跟踪
+    pub async fn find_open_signals(&self) -> Result<Vec<Signal>> {
+        let signals = sqlx::query_as::<_, Signal>(
+            r#"
+            SELECT
+                id, uuid, strategy_uuid, symbol, side,
+                price, quantity, reason,
+                status, status_reason, expires_at, order_uuids, filled_quantity,
+                submitted_at, first_fill_at, executed_at,
+                gmt_create, gmt_modified
+            FROM `signal`
+            WHERE status IN ('GENERATED', 'SUBMITTED', 'PARTIALLY_EXECUTED')
+            ORDER BY gmt_create ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(signals)
+    }
+
     /// 查询某策略最近发出的信号 (用于调试或UI展示)
     pub async fn find_signals_by_strategy(
         &self,
@@ -189,6 +591,8 @@ impl StrategyRepository {
             SELECT
                 id, uuid, strategy_uuid, symbol, side,
                 price, quantity, reason,
+                status, status_reason, expires_at, order_uuids, filled_quantity,
+                submitted_at, first_fill_at, executed_at,
                 gmt_create, gmt_modified
             FROM `signal`
             WHERE strategy_uuid = ?
@@ -0,0 +1,808 @@
+use super::{AccountStore, FillStore, MarketDataStore, OrderStore, StrategyStore};
+use crate::lock::Fencing;
+use crate::repository::market_repo::{bar_key, BarKey, BarSeriesKey, UpsertCounts};
+use crate::repository::strategy_repo::{check_transition, truncate_reason, StateRetention};
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use quant_core::account::{Asset, Position};
+use quant_core::enums::{BarPeriod, OrderStatus, StatusActor, StrategyStatus};
+use quant_core::market::MarketBar;
+use quant_core::oms::{Fill, Order};
+use quant_core::primitive::{Price, Quantity};
+use quant_core::strategy::{Signal, Strategy, StrategyState, StrategyStatusChange};
+use rust_decimal::Decimal;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::RwLock;
+use uuid::Uuid;
+
+// =========================================================================
+// 内存实现 (单元测试 / 离线回测)
+// =========================================================================
+//
+// 语义对齐 MySQL 仓储:
+//   - 自增 id 从 1 开始，gmt_create / gmt_modified 由存储层写入当前时间
+//   - Upsert 的受影响行数: 新增 1，覆盖 2 (同 ON DUPLICATE KEY UPDATE)
+//   - 唯一键冲突的 INSERT 返回错误
+//   - 设置了 `Fencing` 时订单 / 策略写入同样校验每行记录的 fence token
+
+/// 受影响行数: 新增 1，覆盖 2
+fn upsert_rows(existed: bool) -> u64 {
+    if existed {
+        2
+    } else {
+        1
+    }
+}
+
+// -------------------------------------------------------------------------
+// 1. K 线
+// -------------------------------------------------------------------------
+
+/// 内存 K 线存储 (按唯一键排序)
+#[derive(Default)]
+pub struct MemoryMarketStore {
+    bars: RwLock<BTreeMap<BarKey, MarketBar>>,
+    next_id: AtomicI64,
+}
+
+impl MemoryMarketStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 写入一根 K 线，返回是否覆盖了已有数据
+    fn upsert(&self, bar: &MarketBar) -> bool {
+        let mut bars = self.bars.write().unwrap();
+        match bars.get_mut(&bar_key(bar)) {
+            Some(existing) => {
+                existing.open = bar.open;
+                existing.high = bar.high;
+                existing.low = bar.low;
+                existing.close = bar.close;
+                existing.volume = bar.volume;
+                existing.amount = bar.amount;
+                existing.end_time = bar.end_time;
+                true
+            }
+            None => {
+                let mut stored = bar.clone();
+                stored.id = self.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+                stored.gmt_create = Utc::now();
+                bars.insert(bar_key(bar), stored);
+                false
+            }
+        }
+    }
+
+    fn collect_series(&self, key: &BarSeriesKey) -> Vec<MarketBar> {
+        let exchange = key.exchange.to_string();
+        let period = key.bar_period.to_string();
+        self.bars
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|((e, s, p, t, _), _)| {
+                *e == exchange && *s == key.symbol && *p == period && *t == key.trade_type
+            })
+            .map(|(_, bar)| bar.clone())
+            .collect()
+    }
+}
+
+#[async_trait]
+impl MarketDataStore for MemoryMarketStore {
+    async fn save(&self, bar: &MarketBar) -> Result<u64> {
+        Ok(upsert_rows(self.upsert(bar)))
+    }
+
+    async fn save_batch(&self, bars: &[MarketBar]) -> Result<UpsertCounts> {
+        let mut counts = UpsertCounts::default();
+        for bar in bars {
+            if self.upsert(bar) {
+                counts.updated += 1;
+            } else {
+                counts.inserted += 1;
+            }
+        }
+        Ok(counts)
+    }
+
+    async fn find_recent_bars(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        bar_period: BarPeriod,
+        trade_type: u8,
+        limit: i64,
+    ) -> Result<Vec<MarketBar>> {
+        let key = BarSeriesKey {
+            exchange: exchange.parse()?,
+            symbol: symbol.to_string(),
+            bar_period,
+            trade_type,
+        };
+        let mut bars = self.collect_series(&key);
+        bars.reverse();
+        bars.truncate(limit.max(0) as usize);
+        Ok(bars)
+    }
+
+    async fn find_bars_by_range(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        bar_period: BarPeriod,
+        trade_type: u8,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<MarketBar>> {
+        let key = BarSeriesKey {
+            exchange: exchange.parse()?,
+            symbol: symbol.to_string(),
+            bar_period,
+            trade_type,
+        };
+        Ok(self
+            .collect_series(&key)
+            .into_iter()
+            .filter(|b| b.start_time >= start_date && b.start_time <= end_date)
+            .collect())
+    }
+
+    async fn find_series(
+        &self,
+        exchange: Option<&str>,
+        symbol: Option<&str>,
+    ) -> Result<Vec<BarSeriesKey>> {
+        let mut keys: Vec<BarSeriesKey> = Vec::new();
+        for ((e, s, _, _, _), bar) in self.bars.read().unwrap().iter() {
+            if exchange.is_some_and(|x| x != e) || symbol.is_some_and(|x| x != s) {
+                continue;
+            }
+            let key = BarSeriesKey {
+                exchange: bar.exchange,
+                symbol: s.clone(),
+                bar_period: bar.bar_period,
+                trade_type: bar.trade_type,
+            };
+            // BTreeMap 按唯一键有序，同一序列的 K 线相邻
+            if keys.last() != Some(&key) {
+                keys.push(key);
+            }
+        }
+        Ok(keys)
+    }
+
+    async fn find_series_bars(&self, key: &BarSeriesKey) -> Result<Vec<MarketBar>> {
+        Ok(self.collect_series(key))
+    }
+}
+
+// -------------------------------------------------------------------------
+// 2. 订单
+// -------------------------------------------------------------------------
+
+/// 内存订单存储 (按写入顺序保存)
+#[derive(Default)]
+pub struct MemoryOrderStore {
+    orders: RwLock<Vec<Order>>,
+    fencing: Fencing,
+    /// 每个订单最近一次写入的 fence token (对应 `fence_token` 列)
+    fences: RwLock<HashMap<String, u64>>,
+}
+
+impl MemoryOrderStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_fencing(mut self, fencing: Fencing) -> Self {
+        self.fencing = fencing;
+        self
+    }
+}
+
+#[async_trait]
+impl OrderStore for MemoryOrderStore {
+    async fn insert(&self, order: &Order) -> Result<u64> {
+        let mut orders = self.orders.write().unwrap();
+        if orders.iter().any(|o| o.uuid == order.uuid) {
+            bail!("Duplicate order_uuid: {}", order.uuid);
+        }
+        let now = Utc::now();
+        let mut stored = order.clone();
+        stored.id = orders.len() as i64 + 1;
+        stored.gmt_create = now;
+        stored.gmt_modified = now;
+        orders.push(stored);
+        Ok(1)
+    }
+
+    async fn find_by_uuid(&self, order_uuid: Uuid) -> Result<Option<Order>> {
+        let uuid = order_uuid.to_string();
+        Ok(self
+            .orders
+            .read()
+            .unwrap()
+            .iter()
+            .find(|o| o.uuid == uuid)
+            .cloned())
+    }
+
+    async fn find_by_strategy(&self, strategy_uuid: Uuid) -> Result<Vec<Order>> {
+        let uuid = strategy_uuid.to_string();
+        let mut orders: Vec<Order> = self
+            .orders
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|o| o.strategy_uuid.as_deref() == Some(uuid.as_str()))
+            .cloned()
+            .collect();
+        orders.reverse();
+        Ok(orders)
+    }
+
+    async fn find_by_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Order>> {
+        Ok(self
+            .orders
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|o| o.gmt_create >= start && o.gmt_create < end)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_open_orders(&self) -> Result<Vec<Order>> {
+        Ok(self
+            .orders
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|o| o.status.is_open())
+            .cloned()
+            .collect())
+    }
+
+    async fn update_status(
+        &self,
+        order_uuid: Uuid,
+        status: OrderStatus,
+        exchange_order_id: Option<String>,
+        filled_qty: Decimal,
+        avg_price: Option<Decimal>,
+        fee: Option<Decimal>,
+    ) -> Result<()> {
+        let uuid = order_uuid.to_string();
+        let mut orders = self.orders.write().unwrap();
+        if let Some(order) = orders.iter_mut().find(|o| o.uuid == uuid) {
+            if let Some(fence) = self.fencing.current() {
+                let mut fences = self.fences.write().unwrap();
+                fence.check(&format!("order {}", uuid), fences.get(&uuid).copied())?;
+                fences.insert(uuid.clone(), fence.token);
+            }
+            order.status = status;
+            if exchange_order_id.is_some() {
+                order.exchange_order_id = exchange_order_id;
+            }
+            order.filled_quantity = Quantity(filled_qty);
+            order.average_price = avg_price.map(Price);
+            order.fee = fee;
+            order.gmt_modified = Utc::now();
+        }
+        Ok(())
+    }
+}
+
+/// 内存成交存储
+#[derive(Default)]
+pub struct MemoryFillStore {
+    fills: RwLock<Vec<Fill>>,
+}
+
+impl MemoryFillStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 按撮合时间正序返回满足条件的成交
+    fn select(&self, filter: impl Fn(&Fill) -> bool) -> Vec<Fill> {
+        let mut fills: Vec<Fill> = self
+            .fills
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|f| filter(f))
+            .cloned()
+            .collect();
+        fills.sort_by_key(|f| f.trade_time);
+        fills
+    }
+}
+
+#[async_trait]
+impl FillStore for MemoryFillStore {
+    async fn insert(&self, fill: &Fill) -> Result<u64> {
+        let mut fills = self.fills.write().unwrap();
+        if fills.iter().any(|f| f.uuid == fill.uuid) {
+            bail!("Duplicate fill_uuid: {}", fill.uuid);
+        }
+        let mut stored = fill.clone();
+        stored.id = fills.len() as i64 + 1;
+        stored.gmt_create = Utc::now();
+        fills.push(stored);
+        Ok(1)
+    }
+
+    async fn find_by_order(&self, order_uuid: Uuid) -> Result<Vec<Fill>> {
+        let uuid = order_uuid.to_string();
+        Ok(self.select(|f| f.order_uuid == uuid))
+    }
+
+    async fn find_by_orders(&self, order_uuids: &[String]) -> Result<Vec<Fill>> {
+        Ok(self.select(|f| order_uuids.contains(&f.order_uuid)))
+    }
+
+    async fn find_by_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Fill>> {
+        Ok(self.select(|f| f.trade_time >= start && f.trade_time < end))
+    }
+}
+
+// -------------------------------------------------------------------------
+// 3. 账户
+// -------------------------------------------------------------------------
+
+/// 内存账户存储
+#[derive(Default)]
+pub struct MemoryAccountStore {
+    assets: RwLock<Vec<Asset>>,
+    positions: RwLock<Vec<Position>>,
+    next_id: AtomicI64,
+}
+
+impl MemoryAccountStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn next_id(&self) -> i64 {
+        self.next_id.fetch_add(1, Ordering::SeqCst) + 1
+    }
+}
+
+#[async_trait]
+impl AccountStore for MemoryAccountStore {
+    async fn upsert_asset(&self, asset: &Asset) -> Result<u64> {
+        let mut assets = self.assets.write().unwrap();
+        let now = Utc::now();
+        let existing = assets.iter_mut().find(|a| {
+            a.account_name == asset.account_name
+                && a.exchange == asset.exchange
+                && a.currency == asset.currency
+        });
+        let existed = existing.is_some();
+        match existing {
+            Some(a) => {
+                a.free = asset.free;
+                a.frozen = asset.frozen;
+                a.borrowed = asset.borrowed;
+                a.gmt_modified = now;
+            }
+            None => {
+                let mut stored = asset.clone();
+                stored.id = self.next_id();
+                stored.gmt_create = now;
+                stored.gmt_modified = now;
+                assets.push(stored);
+            }
+        }
+        Ok(upsert_rows(existed))
+    }
+
+    async fn find_assets_by_account(&self, account_name: &str) -> Result<Vec<Asset>> {
+        Ok(self
+            .assets
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|a| a.account_name == account_name)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_asset(
+        &self,
+        account_name: &str,
+        exchange: &str,
+        currency: &str,
+    ) -> Result<Option<Asset>> {
+        Ok(self
+            .assets
+            .read()
+            .unwrap()
+            .iter()
+            .find(|a| {
+                a.account_name == account_name
+                    && a.exchange.to_string() == exchange
+                    && a.currency == currency
+            })
+            .cloned())
+    }
+
+    async fn upsert_position(&self, pos: &Position) -> Result<u64> {
+        let mut positions = self.positions.write().unwrap();
+        let now = Utc::now();
+        let existing = positions.iter_mut().find(|p| {
+            p.account_name == pos.account_name
+                && p.exchange == pos.exchange
+                && p.symbol == pos.symbol
+                && p.side == pos.side
+        });
+        let existed = existing.is_some();
+        match existing {
+            Some(p) => {
+                p.quantity = pos.quantity;
+                p.entry_price = pos.entry_price;
+                p.unrealized_pnl = pos.unrealized_pnl;
+                p.leverage = pos.leverage;
+                p.gmt_modified = now;
+            }
+            None => {
+                let mut stored = pos.clone();
+                stored.id = self.next_id();
+                stored.gmt_create = now;
+                stored.gmt_modified = now;
+                positions.push(stored);
+            }
+        }
+        Ok(upsert_rows(existed))
+    }
+
+    async fn find_positions_by_account(&self, account_name: &str) -> Result<Vec<Position>> {
+        Ok(self
+            .positions
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|p| p.account_name == account_name)
+            .cloned()
+            .collect())
+    }
+
+    async fn clear_positions(&self, account_name: &str) -> Result<()> {
+        self.positions
+            .write()
+            .unwrap()
+            .retain(|p| p.account_name != account_name);
+        Ok(())
+    }
+}
+
+// -------------------------------------------------------------------------
+// 4. 策略
+// -------------------------------------------------------------------------
+
+/// 内存策略存储 (元数据 / 运行时状态 / 信号)
+#[derive(Default)]
+pub struct MemoryStrategyStore {
+    strategies: RwLock<Vec<Strategy>>,
+    /// 每个策略的状态版本 (按版本号正序，最后一个为当前版本)
+    states: RwLock<HashMap<String, Vec<StrategyState>>>,
+    retention: StateRetention,
+    /// 状态变更审计 (按时间正序)
+    status_changes: RwLock<Vec<StrategyStatusChange>>,
+    signals: RwLock<Vec<Signal>>,
+    next_id: AtomicI64,
+    fencing: Fencing,
+    /// 每个策略最近一次写入的 fence token (对应 `fence_token` 列)
+    fences: RwLock<HashMap<String, u64>>,
+}
+
+impl MemoryStrategyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_retention(mut self, retention: StateRetention) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    pub fn with_fencing(mut self, fencing: Fencing) -> Self {
+        self.fencing = fencing;
+        self
+    }
+
+    fn next_id(&self) -> i64 {
+        self.next_id.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// 认领已登记策略的 Fence (同 MySQL 实现：未设置 Fence / 未登记时不校验)
+    fn claim_fence(&self, uuid: &str) -> Result<()> {
+        let Some(fence) = self.fencing.current() else {
+            return Ok(());
+        };
+        if !self
+            .strategies
+            .read()
+            .unwrap()
+            .iter()
+            .any(|s| s.uuid == uuid)
+        {
+            return Ok(());
+        }
+        let mut fences = self.fences.write().unwrap();
+        fence.check(&format!("strategy {}", uuid), fences.get(uuid).copied())?;
+        fences.insert(uuid.to_string(), fence.token);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl StrategyStore for MemoryStrategyStore {
+    async fn create(&self, strategy: &Strategy) -> Result<u64> {
+        let mut strategies = self.strategies.write().unwrap();
+        if strategies.iter().any(|s| s.uuid == strategy.uuid) {
+            bail!("Duplicate strategy uuid: {}", strategy.uuid);
+        }
+        let now = Utc::now();
+        let mut stored = strategy.clone();
+        stored.id = self.next_id();
+        stored.gmt_create = now;
+        stored.gmt_modified = now;
+        strategies.push(stored);
+        Ok(1)
+    }
+
+    async fn find_by_uuid(&self, uuid: Uuid) -> Result<Option<Strategy>> {
+        let uuid = uuid.to_string();
+        Ok(self
+            .strategies
+            .read()
+            .unwrap()
+            .iter()
+            .find(|s| s.uuid == uuid)
+            .cloned())
+    }
+
+    async fn find_active_strategies(&self) -> Result<Vec<Strategy>> {
+        Ok(self
+            .strategies
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|s| s.status.is_active())
+            .cloned()
+            .collect())
+    }
+
+    async fn find_all(&self) -> Result<Vec<Strategy>> {
+        Ok(self.strategies.read().unwrap().clone())
+    }
+
+    async fn update_status(
+        &self,
+        uuid: Uuid,
+        status: StrategyStatus,
+        actor: StatusActor,
+        reason: &str,
+    ) -> Result<StrategyStatusChange> {
+        let strategy_uuid = uuid.to_string();
+        let from = self
+            .strategies
+            .read()
+            .unwrap()
+            .iter()
+            .find(|s| s.uuid == strategy_uuid)
+            .map(|s| s.status)
+            .ok_or_else(|| anyhow!("Strategy {} not found", uuid))?;
+        check_transition(uuid, from, status)?;
+        self.claim_fence(&strategy_uuid)?;
+
+        let mut strategies = self.strategies.write().unwrap();
+        let strategy = strategies
+            .iter_mut()
+            .find(|s| s.uuid == strategy_uuid)
+            .ok_or_else(|| anyhow!("Strategy {} not found", uuid))?;
+
+        let now = Utc::now();
+        let change = StrategyStatusChange {
+            id: self.next_id(),
+            strategy_uuid,
+            from_status: strategy.status,
+            to_status: status,
+            reason: truncate_reason(reason),
+            actor,
+            gmt_create: now,
+        };
+        strategy.status = status;
+        strategy.reason = change.reason.clone();
+        strategy.gmt_modified = now;
+        self.status_changes.write().unwrap().push(change.clone());
+        Ok(change)
+    }
+
+    async fn find_status_history(
+        &self,
+        uuid: Uuid,
+        limit: i64,
+    ) -> Result<Vec<StrategyStatusChange>> {
+        let uuid = uuid.to_string();
+        Ok(self
+            .status_changes
+            .read()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|c| c.strategy_uuid == uuid)
+            .take(limit.max(0) as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn save_state(
+        &self,
+        strategy_uuid: Uuid,
+        schema_version: i32,
+        state_data: &Value,
+    ) -> Result<i64> {
+        let uuid = strategy_uuid.to_string();
+        self.claim_fence(&uuid)?;
+        let now = Utc::now();
+        let mut states = self.states.write().unwrap();
+        let versions = states.entry(uuid.clone()).or_default();
+        let version = versions.last().map_or(0, |s| s.version) + 1;
+        versions.push(StrategyState {
+            id: self.next_id(),
+            uuid,
+            state_data: state_data.clone(),
+            version,
+            schema_version,
+            gmt_create: now,
+            gmt_modified: now,
+        });
+
+        // 保留策略 (与 MySQL 一致：当前版本始终保留)
+        let keep = self.retention.keep_versions.max(1) as i64;
+        let cutoff = self
+            .retention
+            .max_age_days
+            .map(|days| now - Duration::days(days as i64));
+        versions.retain(|s| {
+            s.version == version
+                || (s.version > version - keep && cutoff.is_none_or(|c| s.gmt_create >= c))
+        });
+        Ok(version)
+    }
+
+    async fn load_state(&self, strategy_uuid: Uuid) -> Result<Option<StrategyState>> {
+        Ok(self
+            .states
+            .read()
+            .unwrap()
+            .get(&strategy_uuid.to_string())
+            .and_then(|versions| versions.last())
+            .cloned())
+    }
+
+    async fn load_state_version(
+        &self,
+        strategy_uuid: Uuid,
+        version: i64,
+    ) -> Result<Option<StrategyState>> {
+        Ok(self
+            .states
+            .read()
+            .unwrap()
+            .get(&strategy_uuid.to_string())
+            .and_then(|versions| versions.iter().find(|s| s.version == version))
+            .cloned())
+    }
+
+    async fn list_state_versions(
+        &self,
+        strategy_uuid: Uuid,
+        limit: i64,
+    ) -> Result<Vec<StrategyState>> {
+        Ok(self
+            .states
+            .read()
+            .unwrap()
+            .get(&strategy_uuid.to_string())
+            .map(|versions| {
+                versions
+                    .iter()
+                    .rev()
+                    .take(limit.max(0) as usize)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn rollback_state(&self, strategy_uuid: Uuid, version: i64) -> Result<i64> {
+        let target = self
+            .load_state_version(strategy_uuid, version)
+            .await?
+            .ok_or_else(|| {
+                anyhow!(
+                    "State version {} of strategy {} not found",
+                    version,
+                    strategy_uuid
+                )
+            })?;
+        self.save_state(strategy_uuid, target.schema_version, &target.state_data)
+            .await
+    }
+
+    async fn save_signal(&self, signal: &Signal) -> Result<u64> {
+        let now = Utc::now();
+        let mut stored = signal.clone();
+        stored.id = self.next_id();
+        stored.gmt_create = now;
+        stored.gmt_modified = now;
+        self.signals.write().unwrap().push(stored);
+        Ok(1)
+    }
+
+    async fn update_signal(&self, signal: &Signal) -> Result<u64> {
+        let mut signals = self.signals.write().unwrap();
+        let Some(stored) = signals.iter_mut().find(|s| s.uuid == signal.uuid) else {
+            return Ok(0);
+        };
+        stored.quantity = signal.quantity;
+        stored.status = signal.status;
+        stored.status_reason = signal.status_reason.clone();
+        stored.order_uuids = signal.order_uuids.clone();
+        stored.filled_quantity = signal.filled_quantity;
+        stored.submitted_at = signal.submitted_at;
+        stored.first_fill_at = signal.first_fill_at;
+        stored.executed_at = signal.executed_at;
+        stored.gmt_modified = Utc::now();
+        Ok(1)
+    }
+
+    async fn find_signal(&self, uuid: Uuid) -> Result<Option<Signal>> {
+        let uuid = uuid.to_string();
+        Ok(self
+            .signals
+            .read()
+            .unwrap()
+            .iter()
+            .find(|s| s.uuid == uuid)
+            .cloned())
+    }
+
+    async fn find_open_signals(&self) -> Result<Vec<Signal>> {
+        Ok(self
+            .signals
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|s| !s.status.is_terminal())
+            .cloned()
+            .collect())
+    }
+
+    async fn find_signals_by_strategy(
+        &self,
+        strategy_uuid: Uuid,
+        limit: i64,
+    ) -> Result<Vec<Signal>> {
+        let uuid = strategy_uuid.to_string();
+        Ok(self
+            .signals
+            .read()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|s| s.strategy_uuid == uuid)
+            .take(limit.max(0) as usize)
+            .cloned()
+            .collect())
+    }
+}
@@ -0,0 +1,241 @@
+use crate::repository::market_repo::{BarSeriesKey, UpsertCounts};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use quant_core::account::{Asset, Position};
+use quant_core::enums::{BarPeriod, OrderStatus, StatusActor, StrategyStatus};
+use quant_core::market::MarketBar;
+use quant_core::oms::{Fill, Order};
+use quant_core::strategy::{Signal, StateMigration, Strategy, StrategyState, StrategyStatusChange};
+use rust_decimal::Decimal;
+use serde_json::Value;
+use uuid::Uuid;
+
+pub mod memory;
+mod mysql;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+pub mod timescale;
+
+pub use memory::{
+    MemoryAccountStore, MemoryFillStore, MemoryMarketStore, MemoryOrderStore, MemoryStrategyStore,
+};
+#[cfg(feature = "parquet")]
+pub use parquet::ParquetMarketStore;
+pub use timescale::TimescaleMarketStore;
+
+// =========================================================================
+// 存储后端抽象 (Store Traits)
+// =========================================================================
+//
+// 每个 Trait 对应一个仓储层，方法名和语义与 MySQL 仓储保持一致:
+//   - MySQL:  repository::*_repo::*Repository (生产环境)
+//   - 内存:   store::memory::Memory*Store      (单元测试 / 离线回测)
+//   - 时序库: store::timescale::TimescaleMarketStore (仅行情，高频数据)
+//   - 文件:   store::parquet::ParquetMarketStore (仅行情，本地 Parquet，无需数据库)
+//
+// 调用方持有 `Arc<dyn OrderStore>` 等 Trait 对象，即可在两种后端之间切换。
+
+/// 日期粒度接口的时间窗口: 当天 00:00 UTC
+pub(crate) fn day_start(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(chrono::NaiveTime::MIN).and_utc()
+}
+
+/// K 线存储
+///
+/// 唯一键: (exchange, symbol, bar_period, start_time, type)，写入均为 Upsert。
+#[async_trait]
+pub trait MarketDataStore: Send + Sync {
+    /// 保存一根 K 线 (Upsert)，返回受影响行数
+    async fn save(&self, bar: &MarketBar) -> Result<u64>;
+
+    /// 批量保存 K 线 (Upsert)，返回新增 / 覆盖行数
+    async fn save_batch(&self, bars: &[MarketBar]) -> Result<UpsertCounts>;
+
+    /// 最近 `limit` 根 K 线 (按 start_time 倒序)
+    async fn find_recent_bars(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        bar_period: BarPeriod,
+        trade_type: u8,
+        limit: i64,
+    ) -> Result<Vec<MarketBar>>;
+
+    /// [start_date, end_date] 内的 K 线 (按 start_time 正序)
+    async fn find_bars_by_range(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        bar_period: BarPeriod,
+        trade_type: u8,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<MarketBar>>;
+
+    /// 已有的 K 线序列 (可按交易所 / 标的过滤)
+    async fn find_series(
+        &self,
+        exchange: Option<&str>,
+        symbol: Option<&str>,
+    ) -> Result<Vec<BarSeriesKey>>;
+
+    /// 一条序列的全部 K 线 (按 start_time 正序)
+    async fn find_series_bars(&self, key: &BarSeriesKey) -> Result<Vec<MarketBar>>;
+}
+
+/// 订单存储
+#[async_trait]
+pub trait OrderStore: Send + Sync {
+    /// 新增订单 (order_uuid 重复时报错)
+    async fn insert(&self, order: &Order) -> Result<u64>;
+
+    async fn find_by_uuid(&self, order_uuid: Uuid) -> Result<Option<Order>>;
+
+    /// 某策略的全部订单 (按创建时间倒序)
+    async fn find_by_strategy(&self, strategy_uuid: Uuid) -> Result<Vec<Order>>;
+
+    /// [start, end) 内创建的订单 (按创建时间正序)
+    async fn find_by_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Order>>;
+
+    /// 未终结的订单 (按创建时间正序)
+    async fn find_open_orders(&self) -> Result<Vec<Order>>;
+
+    /// 更新订单状态与成交信息 (exchange_order_id 为 None 时保留原值)
+    async fn update_status(
+        &self,
+        order_uuid: Uuid,
+        status: OrderStatus,
+        exchange_order_id: Option<String>,
+        filled_qty: Decimal,
+        avg_price: Option<Decimal>,
+        fee: Option<Decimal>,
+    ) -> Result<()>;
+}
+
+/// 成交存储 (逐笔成交回报，TCA 的数据来源)
+#[async_trait]
+pub trait FillStore: Send + Sync {
+    /// 记录一笔成交
+    async fn insert(&self, fill: &Fill) -> Result<u64>;
+
+    /// 某订单的全部成交 (按撮合时间正序)
+    async fn find_by_order(&self, order_uuid: Uuid) -> Result<Vec<Fill>>;
+
+    /// 一批订单的全部成交 (按撮合时间正序)
+    async fn find_by_orders(&self, order_uuids: &[String]) -> Result<Vec<Fill>>;
+
+    /// [start, end) 内撮合的成交 (按撮合时间正序)
+    async fn find_by_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Fill>>;
+}
+
+/// 账户资产 / 持仓存储
+///
+/// 资产唯一键: (account_name, exchange, currency)；
+/// 持仓唯一键: (account_name, exchange, symbol, side)。
+#[async_trait]
+pub trait AccountStore: Send + Sync {
+    async fn upsert_asset(&self, asset: &Asset) -> Result<u64>;
+
+    async fn find_assets_by_account(&self, account_name: &str) -> Result<Vec<Asset>>;
+
+    async fn find_asset(
+        &self,
+        account_name: &str,
+        exchange: &str,
+        currency: &str,
+    ) -> Result<Option<Asset>>;
+
+    async fn upsert_position(&self, pos: &Position) -> Result<u64>;
+
+    async fn find_positions_by_account(&self, account_name: &str) -> Result<Vec<Position>>;
+
+    /// 清空某账户的所有持仓
+    async fn clear_positions(&self, account_name: &str) -> Result<()>;
+}
+
+/// 策略元数据 / 运行时状态 / 信号存储
+#[async_trait]
+pub trait StrategyStore: Send + Sync {
+    async fn create(&self, strategy: &Strategy) -> Result<u64>;
+
+    async fn find_by_uuid(&self, uuid: Uuid) -> Result<Option<Strategy>>;
+
+    /// 运行中 / 初始化中的策略
+    async fn find_active_strategies(&self) -> Result<Vec<Strategy>>;
+
+    /// 全部策略 (按创建时间正序)
+    async fn find_all(&self) -> Result<Vec<Strategy>>;
+
+    /// 按生命周期校验并变更状态，原因写入 `Strategy.reason`，同时记录审计
+    async fn update_status(
+        &self,
+        uuid: Uuid,
+        status: StrategyStatus,
+        actor: StatusActor,
+        reason: &str,
+    ) -> Result<StrategyStatusChange>;
+
+    /// 最近的 `limit` 条状态变更 (按时间倒序)
+    async fn find_status_history(
+        &self,
+        uuid: Uuid,
+        limit: i64,
+    ) -> Result<Vec<StrategyStatusChange>>;
+
+    /// 保存策略状态：追加一个新版本并设为当前版本，返回新版本号 (按保留策略清理旧版本)
+    async fn save_state(
+        &self,
+        strategy_uuid: Uuid,
+        schema_version: i32,
+        state_data: &Value,
+    ) -> Result<i64>;
+
+    /// 当前版本
+    async fn load_state(&self, strategy_uuid: Uuid) -> Result<Option<StrategyState>>;
+
+    /// 加载当前版本并用迁移钩子升级到代码使用的结构版本
+    async fn load_state_migrated(
+        &self,
+        strategy_uuid: Uuid,
+        migration: &dyn StateMigration,
+    ) -> Result<Option<StrategyState>> {
+        self.load_state(strategy_uuid)
+            .await?
+            .map(|state| state.migrate(migration))
+            .transpose()
+    }
+
+    async fn load_state_version(
+        &self,
+        strategy_uuid: Uuid,
+        version: i64,
+    ) -> Result<Option<StrategyState>>;
+
+    /// 最近的 `limit` 个历史版本 (按版本号倒序)
+    async fn list_state_versions(
+        &self,
+        strategy_uuid: Uuid,
+        limit: i64,
+    ) -> Result<Vec<StrategyState>>;
+
+    /// 回滚到指定版本：把该版本复制为一个新版本 (历史不会被删除)，返回新版本号
+    async fn rollback_state(&self, strategy_uuid: Uuid, version: i64) -> Result<i64>;
+
+    async fn save_signal(&self, signal: &Signal) -> Result<u64>;
+
+    /// 更新信号的生命周期字段 (状态 / 关联订单 / 成交进度)
+    async fn update_signal(&self, signal: &Signal) -> Result<u64>;
+
+    async fn find_signal(&self, uuid: Uuid) -> Result<Option<Signal>>;
+
+    /// 未结束的信号 (Generated / Submitted / PartiallyExecuted，按创建时间正序)
+    async fn find_open_signals(&self) -> Result<Vec<Signal>>;
+
+    /// 某策略最近的 `limit` 条信号 (按创建时间倒序)
+    async fn find_signals_by_strategy(
+        &self,
+        strategy_uuid: Uuid,
+        limit: i64,
+    ) -> Result<Vec<Signal>>;
+}
@@ -0,0 +1,262 @@
+use super::{AccountStore, FillStore, MarketDataStore, OrderStore, StrategyStore};
+use crate::repository::account_repo::AccountRepository;
+use crate::repository::fill_repo::FillRepository;
+use crate::repository::market_repo::{BarSeriesKey, MarketDataRepository, UpsertCounts};
+use crate::repository::order_repo::OrderRepository;
+use crate::repository::strategy_repo::StrategyRepository;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use quant_core::account::{Asset, Position};
+use quant_core::enums::{BarPeriod, OrderStatus, StatusActor, StrategyStatus};
+use quant_core::market::MarketBar;
+use quant_core::oms::{Fill, Order};
+use quant_core::strategy::{Signal, Strategy, StrategyState, StrategyStatusChange};
+use rust_decimal::Decimal;
+use serde_json::Value;
+use uuid::Uuid;
+
+// =========================================================================
+// MySQL 实现: 直接委托给各仓储层的同名方法
+// =========================================================================
+
+#[async_trait]
+impl MarketDataStore for MarketDataRepository {
+    async fn save(&self, bar: &MarketBar) -> Result<u64> {
+        MarketDataRepository::save(self, bar).await
+    }
+
+    async fn save_batch(&self, bars: &[MarketBar]) -> Result<UpsertCounts> {
+        MarketDataRepository::save_batch(self, bars).await
+    }
+
+    async fn find_recent_bars(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        bar_period: BarPeriod,
+        trade_type: u8,
+        limit: i64,
+    ) -> Result<Vec<MarketBar>> {
+        MarketDataRepository::find_recent_bars(
+            self, exchange, symbol, bar_period, trade_type, limit,
+        )
+        .await
+    }
+
+    async fn find_bars_by_range(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        bar_period: BarPeriod,
+        trade_type: u8,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<MarketBar>> {
+        MarketDataRepository::find_bars_by_range(
+            self, exchange, symbol, bar_period, trade_type, start_date, end_date,
+        )
+        .await
+    }
+
+    async fn find_series(
+        &self,
+        exchange: Option<&str>,
+        symbol: Option<&str>,
+    ) -> Result<Vec<BarSeriesKey>> {
+        MarketDataRepository::find_series(self, exchange, symbol).await
+    }
+
+    async fn find_series_bars(&self, key: &BarSeriesKey) -> Result<Vec<MarketBar>> {
+        MarketDataRepository::find_series_bars(self, key).await
+    }
+}
+
+#[async_trait]
+impl OrderStore for OrderRepository {
+    async fn insert(&self, order: &Order) -> Result<u64> {
+        OrderRepository::insert(self, order).await
+    }
+
+    async fn find_by_uuid(&self, order_uuid: Uuid) -> Result<Option<Order>> {
+        OrderRepository::find_by_uuid(self, order_uuid).await
+    }
+
+    async fn find_by_strategy(&self, strategy_uuid: Uuid) -> Result<Vec<Order>> {
+        OrderRepository::find_by_strategy(self, strategy_uuid).await
+    }
+
+    async fn find_by_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Order>> {
+        OrderRepository::find_by_range(self, start, end).await
+    }
+
+    async fn find_open_orders(&self) -> Result<Vec<Order>> {
+        OrderRepository::find_open_orders(self).await
+    }
+
+    async fn update_status(
+        &self,
+        order_uuid: Uuid,
+        status: OrderStatus,
+        exchange_order_id: Option<String>,
+        filled_qty: Decimal,
+        avg_price: Option<Decimal>,
+        fee: Option<Decimal>,
+    ) -> Result<()> {
+        OrderRepository::update_status(
+            self,
+            order_uuid,
+            status,
+            exchange_order_id,
+            filled_qty,
+            avg_price,
+            fee,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl FillStore for FillRepository {
+    async fn insert(&self, fill: &Fill) -> Result<u64> {
+        FillRepository::insert(self, fill).await
+    }
+
+    async fn find_by_order(&self, order_uuid: Uuid) -> Result<Vec<Fill>> {
+        FillRepository::find_by_order(self, order_uuid).await
+    }
+
+    async fn find_by_orders(&self, order_uuids: &[String]) -> Result<Vec<Fill>> {
+        FillRepository::find_by_orders(self, order_uuids).await
+    }
+
+    async fn find_by_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Fill>> {
+        FillRepository::find_by_range(self, start, end).await
+    }
+}
+
+#[async_trait]
+impl AccountStore for AccountRepository {
+    async fn upsert_asset(&self, asset: &Asset) -> Result<u64> {
+        AccountRepository::upsert_asset(self, asset).await
+    }
+
+    async fn find_assets_by_account(&self, account_name: &str) -> Result<Vec<Asset>> {
+        AccountRepository::find_assets_by_account(self, account_name).await
+    }
+
+    async fn find_asset(
+        &self,
+        account_name: &str,
+        exchange: &str,
+        currency: &str,
+    ) -> Result<Option<Asset>> {
+        AccountRepository::find_asset(self, account_name, exchange, currency).await
+    }
+
+    async fn upsert_position(&self, pos: &Position) -> Result<u64> {
+        AccountRepository::upsert_position(self, pos).await
+    }
+
+    async fn find_positions_by_account(&self, account_name: &str) -> Result<Vec<Position>> {
+        AccountRepository::find_positions_by_account(self, account_name).await
+    }
+
+    async fn clear_positions(&self, account_name: &str) -> Result<()> {
+        AccountRepository::clear_positions(self, account_name).await
+    }
+}
+
+#[async_trait]
+impl StrategyStore for StrategyRepository {
+    async fn create(&self, strategy: &Strategy) -> Result<u64> {
+        StrategyRepository::create(self, strategy).await
+    }
+
+    async fn find_by_uuid(&self, uuid: Uuid) -> Result<Option<Strategy>> {
+        StrategyRepository::find_by_uuid(self, uuid).await
+    }
+
+    async fn find_active_strategies(&self) -> Result<Vec<Strategy>> {
+        StrategyRepository::find_active_strategies(self).await
+    }
+
+    async fn find_all(&self) -> Result<Vec<Strategy>> {
+        StrategyRepository::find_all(self).await
+    }
+
+    async fn update_status(
+        &self,
+        uuid: Uuid,
+        status: StrategyStatus,
+        actor: StatusActor,
+        reason: &str,
+    ) -> Result<StrategyStatusChange> {
+        StrategyRepository::update_status(self, uuid, status, actor, reason).await
+    }
+
+    async fn find_status_history(
+        &self,
+        uuid: Uuid,
+        limit: i64,
+    ) -> Result<Vec<StrategyStatusChange>> {
+        StrategyRepository::find_status_history(self, uuid, limit).await
+    }
+
+    async fn save_state(
+        &self,
+        strategy_uuid: Uuid,
+        schema_version: i32,
+        state_data: &Value,
+    ) -> Result<i64> {
+        StrategyRepository::save_state(self, strategy_uuid, schema_version, state_data).await
+    }
+
+    async fn load_state(&self, strategy_uuid: Uuid) -> Result<Option<StrategyState>> {
+        StrategyRepository::load_state(self, strategy_uuid).await
+    }
+
+    async fn load_state_version(
+        &self,
+        strategy_uuid: Uuid,
+        version: i64,
+    ) -> Result<Option<StrategyState>> {
+        StrategyRepository::load_state_version(self, strategy_uuid, version).await
+    }
+
+    async fn list_state_versions(
+        &self,
+        strategy_uuid: Uuid,
+        limit: i64,
+    ) -> Result<Vec<StrategyState>> {
+        StrategyRepository::list_state_versions(self, strategy_uuid, limit).await
+    }
+
+    async fn rollback_state(&self, strategy_uuid: Uuid, version: i64) -> Result<i64> {
+        StrategyRepository::rollback_state(self, strategy_uuid, version).await
+    }
+
+    async fn save_signal(&self, signal: &Signal) -> Result<u64> {
+        StrategyRepository::save_signal(self, signal).await
+    }
+
+    async fn update_signal(&self, signal: &Signal) -> Result<u64> {
+        StrategyRepository::update_signal(self, signal).await
+    }
+
+    async fn find_signal(&self, uuid: Uuid) -> Result<Option<Signal>> {
+        StrategyRepository::find_signal(self, uuid).await
+    }
+
+    async fn find_open_signals(&self) -> Result<Vec<Signal>> {
+        StrategyRepository::find_open_signals(self).await
+    }
+
+    async fn find_signals_by_strategy(
+        &self,
+        strategy_uuid: Uuid,
+        limit: i64,
+    ) -> Result<Vec<Signal>> {
+        StrategyRepository::find_signals_by_strategy(self, strategy_uuid, limit).await
+    }
+}
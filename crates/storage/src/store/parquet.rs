@@ -0,0 +1,740 @@
+use super::{day_start, MarketDataStore};
+use crate::repository::market_repo::{BarSeriesKey, UpsertCounts};
+use ::parquet::basic::{Compression, ZstdLevel};
+use ::parquet::data_type::{
+    ByteArray, DataType, Decimal as ParquetDecimal, FixedLenByteArray, FixedLenByteArrayType,
+    Int64Type,
+};
+use ::parquet::file::properties::WriterProperties;
+use ::parquet::file::reader::{FileReader, SerializedFileReader};
+use ::parquet::file::statistics::Statistics;
+use ::parquet::file::writer::{SerializedFileWriter, SerializedRowGroupWriter};
+use ::parquet::record::{Field, Row, RowAccessor};
+use ::parquet::schema::parser::parse_message_type;
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Months, NaiveDate, TimeZone, Utc};
+use quant_core::enums::{BarPeriod, Exchange};
+use quant_core::market::{IntradayBar, MarketBar, MarketTick};
+use quant_core::primitive::{CurrencyPair, Price, Quantity};
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::info;
+
+// =========================================================================
+// 本地 Parquet 行情存储 (研究 / 离线回测，不需要数据库)
+// =========================================================================
+//
+// 目录布局 (Hive 风格分区，可直接用 DuckDB / Polars / PyArrow 读取):
+//   {root}/bars/exchange=BINANCE/symbol=BTC-USDT/period=D1/type=21/date=2024/data.parquet
+//   {root}/ticks/exchange=BINANCE/symbol=BTC-USDT/date=2024-03-01/data.parquet
+//
+// K 线分区粒度: 分钟线 (M1 / M5 / M15) 按月，其余按年；Tick 按天。
+// 价格 / 数量存为 DECIMAL(38, 8)，时间存为 UTC 毫秒时间戳。
+//
+// 读取时先按目录名裁剪分区，再用 Row Group 的 min/max 统计跳过时间窗口外的 Row Group。
+// 写入为分区级 Upsert: 读出旧文件、按 start_time 合并、写临时文件后原子替换。
+// 文件 IO 是同步的，适合单机研究，不适合作为在线服务的存储。
+
+/// 小数位数 (与 MySQL DECIMAL(30, 8) 一致)
+const DECIMAL_SCALE: u32 = 8;
+
+/// 默认每个 Row Group 的行数 (越小裁剪越细，文件越大)
+pub const DEFAULT_ROW_GROUP_SIZE: usize = 8192;
+
+const DATA_FILE: &str = "data.parquet";
+
+const BAR_SCHEMA: &str = "
+message market_bar {
+    REQUIRED INT64 start_time (TIMESTAMP(MILLIS, true));
+    REQUIRED INT64 end_time (TIMESTAMP(MILLIS, true));
+    REQUIRED FIXED_LEN_BYTE_ARRAY (16) open (DECIMAL(38, 8));
+    REQUIRED FIXED_LEN_BYTE_ARRAY (16) high (DECIMAL(38, 8));
+    REQUIRED FIXED_LEN_BYTE_ARRAY (16) low (DECIMAL(38, 8));
+    REQUIRED FIXED_LEN_BYTE_ARRAY (16) close (DECIMAL(38, 8));
+    REQUIRED FIXED_LEN_BYTE_ARRAY (16) volume (DECIMAL(38, 8));
+    OPTIONAL FIXED_LEN_BYTE_ARRAY (16) amount (DECIMAL(38, 8));
+}
+";
+
+const TICK_SCHEMA: &str = "
+message market_tick {
+    REQUIRED INT64 time (TIMESTAMP(MILLIS, true));
+    REQUIRED FIXED_LEN_BYTE_ARRAY (16) bid_price (DECIMAL(38, 8));
+    REQUIRED FIXED_LEN_BYTE_ARRAY (16) bid_size (DECIMAL(38, 8));
+    REQUIRED FIXED_LEN_BYTE_ARRAY (16) ask_price (DECIMAL(38, 8));
+    REQUIRED FIXED_LEN_BYTE_ARRAY (16) ask_size (DECIMAL(38, 8));
+    OPTIONAL FIXED_LEN_BYTE_ARRAY (16) last_price (DECIMAL(38, 8));
+}
+";
+
+// =========================================================================
+// 1. 路径 / 分区
+// =========================================================================
+
+/// 目录名中的标的: "BTC/USDT" <-> "BTC-USDT"
+fn symbol_dir(symbol: &str) -> String {
+    symbol.replace('/', "-")
+}
+
+fn symbol_from_dir(dir: &str) -> String {
+    dir.replacen('-', "/", 1)
+}
+
+/// K 线所在分区名
+fn bar_partition(period: BarPeriod, time: DateTime<Utc>) -> String {
+    match period {
+        BarPeriod::M1 | BarPeriod::M5 | BarPeriod::M15 => time.format("%Y-%m").to_string(),
+        BarPeriod::H1 | BarPeriod::H4 | BarPeriod::D1 => time.format("%Y").to_string(),
+    }
+}
+
+fn tick_partition(time: DateTime<Utc>) -> String {
+    time.format("%Y-%m-%d").to_string()
+}
+
+/// 分区覆盖的时间段 [start, end)，支持 "2024" / "2024-03" / "2024-03-01"
+fn partition_span(name: &str) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let parts: Vec<u32> = name
+        .split('-')
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()?;
+    let (start, end) = match parts.as_slice() {
+        [year] => {
+            let start = NaiveDate::from_ymd_opt(*year as i32, 1, 1)?;
+            (start, start.checked_add_months(Months::new(12))?)
+        }
+        [year, month] => {
+            let start = NaiveDate::from_ymd_opt(*year as i32, *month, 1)?;
+            (start, start.checked_add_months(Months::new(1))?)
+        }
+        [year, month, day] => {
+            let start = NaiveDate::from_ymd_opt(*year as i32, *month, *day)?;
+            (start, start.succ_opt()?)
+        }
+        _ => return None,
+    };
+    Some((day_start(start), day_start(end)))
+}
+
+/// 列出目录下 `{key}=...` 形式的子目录，返回 (值, 路径)，按值排序
+fn list_partitions(dir: &Path, key: &str) -> Result<Vec<(String, PathBuf)>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let prefix = format!("{}=", key);
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to list {:?}", dir))? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        if let Some(value) = entry
+            .file_name()
+            .to_str()
+            .and_then(|n| n.strip_prefix(&prefix))
+        {
+            entries.push((value.to_string(), entry.path()));
+        }
+    }
+    entries.sort();
+    Ok(entries)
+}
+
+/// 与 [start, end) 有交集的分区文件 (按时间正序)
+fn partition_files(
+    dir: &Path,
+    range: Option<(DateTime<Utc>, DateTime<Utc>)>,
+) -> Result<Vec<PathBuf>> {
+    Ok(list_partitions(dir, "date")?
+        .into_iter()
+        .filter(|(name, _)| match (range, partition_span(name)) {
+            (Some((start, end)), Some((from, to))) => from < end && to > start,
+            _ => true,
+        })
+        .map(|(_, path)| path.join(DATA_FILE))
+        .filter(|path| path.is_file())
+        .collect())
+}
+
+// =========================================================================
+// 2. 编解码
+// =========================================================================
+
+/// Decimal -> DECIMAL(38, 8) 的 16 字节大端补码
+fn encode_decimal(value: Decimal) -> FixedLenByteArray {
+    let value = value.round_dp(DECIMAL_SCALE);
+    let unscaled = value.mantissa() * 10i128.pow(DECIMAL_SCALE - value.scale());
+    FixedLenByteArray::from(ByteArray::from(unscaled.to_be_bytes().to_vec()))
+}
+
+fn decode_decimal(value: &ParquetDecimal) -> Result<Decimal> {
+    let bytes = value.data();
+    if bytes.is_empty() || bytes.len() > 16 {
+        bail!("Invalid decimal width {}", bytes.len());
+    }
+    // 按符号位扩展到 16 字节
+    let fill = if bytes[0] & 0x80 != 0 { 0xFF } else { 0x00 };
+    let mut buf = [fill; 16];
+    buf[16 - bytes.len()..].copy_from_slice(bytes);
+    Ok(Decimal::from_i128_with_scale(
+        i128::from_be_bytes(buf),
+        value.scale() as u32,
+    ))
+}
+
+fn decimal_at(row: &Row, i: usize) -> Result<Option<Decimal>> {
+    match row.get_column_iter().nth(i).map(|(_, field)| field) {
+        Some(Field::Decimal(d)) => decode_decimal(d).map(Some),
+        Some(Field::Null) => Ok(None),
+        other => bail!("Column {} is not a decimal: {:?}", i, other),
+    }
+}
+
+fn required_decimal(row: &Row, i: usize) -> Result<Decimal> {
+    decimal_at(row, i)?.ok_or_else(|| anyhow!("Column {} is null", i))
+}
+
+fn millis_to_time(ms: i64) -> Result<DateTime<Utc>> {
+    Utc.timestamp_millis_opt(ms)
+        .single()
+        .ok_or_else(|| anyhow!("Invalid timestamp {}", ms))
+}
+
+/// 可空列: (非空值, 定义级别)
+fn optional_decimals(
+    values: impl Iterator<Item = Option<Decimal>>,
+) -> (Vec<FixedLenByteArray>, Vec<i16>) {
+    let mut encoded = Vec::new();
+    let mut levels = Vec::new();
+    for value in values {
+        match value {
+            Some(v) => {
+                encoded.push(encode_decimal(v));
+                levels.push(1);
+            }
+            None => levels.push(0),
+        }
+    }
+    (encoded, levels)
+}
+
+fn write_column<T: DataType>(
+    rg: &mut SerializedRowGroupWriter<'_, File>,
+    values: &[T::T],
+    def_levels: Option<&[i16]>,
+) -> Result<()> {
+    let mut column = rg
+        .next_column()?
+        .ok_or_else(|| anyhow!("Parquet schema has fewer columns than written"))?;
+    column.typed::<T>().write_batch(values, def_levels, None)?;
+    column.close()?;
+    Ok(())
+}
+
+fn write_decimals(
+    rg: &mut SerializedRowGroupWriter<'_, File>,
+    values: impl Iterator<Item = Decimal>,
+) -> Result<()> {
+    let encoded: Vec<_> = values.map(encode_decimal).collect();
+    write_column::<FixedLenByteArrayType>(rg, &encoded, None)
+}
+
+/// 写临时文件后原子替换，中途失败不会损坏已有分区
+fn write_file<T>(
+    path: &Path,
+    schema: &str,
+    rows: &[T],
+    row_group_size: usize,
+    mut write_group: impl FnMut(&mut SerializedRowGroupWriter<'_, File>, &[T]) -> Result<()>,
+) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {:?}", dir))?;
+    }
+    let tmp = path.with_extension("parquet.tmp");
+    let schema = Arc::new(parse_message_type(schema)?);
+    let props = Arc::new(
+        WriterProperties::builder()
+            .set_compression(Compression::ZSTD(ZstdLevel::default()))
+            .set_max_row_group_size(row_group_size)
+            .build(),
+    );
+    let file = File::create(&tmp).with_context(|| format!("Failed to create {:?}", tmp))?;
+    let mut writer = SerializedFileWriter::new(file, schema, props)?;
+    for chunk in rows.chunks(row_group_size.max(1)) {
+        let mut rg = writer.next_row_group()?;
+        write_group(&mut rg, chunk)?;
+        rg.close()?;
+    }
+    writer.close()?;
+    fs::rename(&tmp, path).with_context(|| format!("Failed to replace {:?}", path))?;
+    Ok(())
+}
+
+/// 读取一个文件，跳过首列 (时间) 的 min/max 与 [start, end) 不相交的 Row Group
+fn read_file<T>(
+    path: &Path,
+    range: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    mut parse: impl FnMut(&Row) -> Result<T>,
+) -> Result<Vec<T>> {
+    let file = File::open(path).with_context(|| format!("Failed to open {:?}", path))?;
+    let reader = SerializedFileReader::new(file)
+        .with_context(|| format!("Failed to read parquet {:?}", path))?;
+    let window = range.map(|(start, end)| (start.timestamp_millis(), end.timestamp_millis()));
+
+    let mut rows = Vec::new();
+    for i in 0..reader.num_row_groups() {
+        if let Some((start, end)) = window {
+            let stats = reader.metadata().row_group(i).column(0).statistics();
+            if let Some(Statistics::Int64(s)) = stats {
+                if let (Some(min), Some(max)) = (s.min_opt(), s.max_opt()) {
+                    if *max < start || *min >= end {
+                        continue;
+                    }
+                }
+            }
+        }
+        for row in reader.get_row_group(i)?.get_row_iter(None)? {
+            rows.push(parse(&row?)?);
+        }
+    }
+    Ok(rows)
+}
+
+// =========================================================================
+// 3. 存储
+// =========================================================================
+
+/// 一条 K 线序列在分区目录中的位置
+struct SeriesDir<'a> {
+    exchange: &'a str,
+    symbol: &'a str,
+    bar_period: BarPeriod,
+    trade_type: u8,
+}
+
+#[derive(Debug, Clone)]
+pub struct ParquetMarketStore {
+    root: PathBuf,
+    row_group_size: usize,
+}
+
+impl ParquetMarketStore {
+    /// 打开 (必要时创建) 数据目录
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self> {
+        let root = root.into();
+        fs::create_dir_all(&root).with_context(|| format!("Failed to create {:?}", root))?;
+        Ok(Self {
+            root,
+            row_group_size: DEFAULT_ROW_GROUP_SIZE,
+        })
+    }
+
+    pub fn with_row_group_size(mut self, row_group_size: usize) -> Self {
+        self.row_group_size = row_group_size.max(1);
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn series_dir(&self, series: &SeriesDir<'_>) -> PathBuf {
+        self.root
+            .join("bars")
+            .join(format!("exchange={}", series.exchange))
+            .join(format!("symbol={}", symbol_dir(series.symbol)))
+            .join(format!("period={}", series.bar_period))
+            .join(format!("type={}", series.trade_type))
+    }
+
+    fn tick_dir(&self, exchange: &str, symbol: &str) -> PathBuf {
+        self.root
+            .join("ticks")
+            .join(format!("exchange={}", exchange))
+            .join(format!("symbol={}", symbol_dir(symbol)))
+    }
+
+    // =========================================================================
+    // 日内 K 线
+    // =========================================================================
+
+    /// 批量保存 K 线 (按分区 Upsert，唯一键 start_time)，返回新增 / 覆盖行数
+    pub fn save_intraday_bars(&self, bars: &[IntradayBar]) -> Result<UpsertCounts> {
+        let mut partitions: BTreeMap<PathBuf, Vec<&IntradayBar>> = BTreeMap::new();
+        for bar in bars {
+            let exchange = bar.exchange.to_string();
+            let symbol = bar.symbol.to_string();
+            let dir = self.series_dir(&SeriesDir {
+                exchange: &exchange,
+                symbol: &symbol,
+                bar_period: bar.bar_period,
+                trade_type: bar.trade_type,
+            });
+            let file = dir
+                .join(format!(
+                    "date={}",
+                    bar_partition(bar.bar_period, bar.start_time)
+                ))
+                .join(DATA_FILE);
+            partitions.entry(file).or_default().push(bar);
+        }
+
+        let mut counts = UpsertCounts::default();
+        for (file, new_bars) in partitions {
+            let template = new_bars[0];
+            let mut merged: BTreeMap<i64, IntradayBar> = BTreeMap::new();
+            if file.is_file() {
+                for bar in read_file(&file, None, |row| parse_bar(row, template))? {
+                    merged.insert(bar.start_time.timestamp_millis(), bar);
+                }
+            }
+            for bar in new_bars {
+                match merged.insert(bar.start_time.timestamp_millis(), bar.clone()) {
+                    Some(_) => counts.updated += 1,
+                    None => counts.inserted += 1,
+                }
+            }
+            let rows: Vec<IntradayBar> = merged.into_values().collect();
+            write_file(
+                &file,
+                BAR_SCHEMA,
+                &rows,
+                self.row_group_size,
+                write_bar_group,
+            )?;
+        }
+        Ok(counts)
+    }
+
+    /// [start, end) 内的 K 线 (按 start_time 正序)
+    pub fn find_intraday_bars(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        bar_period: BarPeriod,
+        trade_type: u8,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<IntradayBar>> {
+        self.read_series(
+            &SeriesDir {
+                exchange,
+                symbol,
+                bar_period,
+                trade_type,
+            },
+            Some((start, end)),
+        )
+    }
+
+    fn read_series(
+        &self,
+        series: &SeriesDir<'_>,
+        range: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    ) -> Result<Vec<IntradayBar>> {
+        let files = partition_files(&self.series_dir(series), range)?;
+        if files.is_empty() {
+            return Ok(Vec::new());
+        }
+        let template = IntradayBar {
+            exchange: Exchange::from_str(series.exchange)?,
+            symbol: CurrencyPair::from_str(series.symbol)?,
+            bar_period: series.bar_period,
+            trade_type: series.trade_type,
+            open: Price(Decimal::ZERO),
+            high: Price(Decimal::ZERO),
+            low: Price(Decimal::ZERO),
+            close: Price(Decimal::ZERO),
+            volume: Quantity(Decimal::ZERO),
+            amount: None,
+            start_time: DateTime::<Utc>::MIN_UTC,
+            end_time: DateTime::<Utc>::MIN_UTC,
+        };
+
+        let mut bars = Vec::new();
+        for file in files {
+            bars.extend(
+                read_file(&file, range, |row| parse_bar(row, &template))?
+                    .into_iter()
+                    .filter(|b| range.is_none_or(|(s, e)| b.start_time >= s && b.start_time < e)),
+            );
+        }
+        Ok(bars)
+    }
+
+    // =========================================================================
+    // Tick
+    // =========================================================================
+
+    /// 批量写入 Tick (按天分区追加，分区内按时间排序)，返回写入行数
+    pub fn save_ticks(&self, ticks: &[MarketTick]) -> Result<u64> {
+        let mut partitions: BTreeMap<PathBuf, Vec<&MarketTick>> = BTreeMap::new();
+        for tick in ticks {
+            let file = self
+                .tick_dir(&tick.exchange.to_string(), &tick.symbol.to_string())
+                .join(format!("date={}", tick_partition(tick.time)))
+                .join(DATA_FILE);
+            partitions.entry(file).or_default().push(tick);
+        }
+
+        for (file, new_ticks) in partitions {
+            let template = new_ticks[0];
+            let mut rows = if file.is_file() {
+                read_file(&file, None, |row| parse_tick(row, template))?
+            } else {
+                Vec::new()
+            };
+            rows.extend(new_ticks.into_iter().cloned());
+            rows.sort_by_key(|t| t.time);
+            write_file(
+                &file,
+                TICK_SCHEMA,
+                &rows,
+                self.row_group_size,
+                write_tick_group,
+            )?;
+        }
+        Ok(ticks.len() as u64)
+    }
+
+    /// [start, end) 内的 Tick (按时间正序)
+    pub fn find_ticks(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<MarketTick>> {
+        let files = partition_files(&self.tick_dir(exchange, symbol), Some((start, end)))?;
+        if files.is_empty() {
+            return Ok(Vec::new());
+        }
+        let template = MarketTick {
+            exchange: Exchange::from_str(exchange)?,
+            symbol: CurrencyPair::from_str(symbol)?,
+            time: start,
+            bid_price: Price(Decimal::ZERO),
+            bid_size: Quantity(Decimal::ZERO),
+            ask_price: Price(Decimal::ZERO),
+            ask_size: Quantity(Decimal::ZERO),
+            last_price: None,
+        };
+
+        let mut ticks = Vec::new();
+        for file in files {
+            ticks.extend(
+                read_file(&file, Some((start, end)), |row| parse_tick(row, &template))?
+                    .into_iter()
+                    .filter(|t| t.time >= start && t.time < end),
+            );
+        }
+        Ok(ticks)
+    }
+}
+
+/// 读一行 K 线；交易所 / 标的 / 周期来自分区目录 (template)
+fn parse_bar(row: &Row, template: &IntradayBar) -> Result<IntradayBar> {
+    Ok(IntradayBar {
+        start_time: millis_to_time(row.get_timestamp_millis(0)?)?,
+        end_time: millis_to_time(row.get_timestamp_millis(1)?)?,
+        open: Price(required_decimal(row, 2)?),
+        high: Price(required_decimal(row, 3)?),
+        low: Price(required_decimal(row, 4)?),
+        close: Price(required_decimal(row, 5)?),
+        volume: Quantity(required_decimal(row, 6)?),
+        amount: decimal_at(row, 7)?,
+        ..template.clone()
+    })
+}
+
+fn write_bar_group(
+    rg: &mut SerializedRowGroupWriter<'_, File>,
+    bars: &[IntradayBar],
+) -> Result<()> {
+    let start: Vec<i64> = bars
+        .iter()
+        .map(|b| b.start_time.timestamp_millis())
+        .collect();
+    let end: Vec<i64> = bars.iter().map(|b| b.end_time.timestamp_millis()).collect();
+    write_column::<Int64Type>(rg, &start, None)?;
+    write_column::<Int64Type>(rg, &end, None)?;
+    write_decimals(rg, bars.iter().map(|b| b.open.0))?;
+    write_decimals(rg, bars.iter().map(|b| b.high.0))?;
+    write_decimals(rg, bars.iter().map(|b| b.low.0))?;
+    write_decimals(rg, bars.iter().map(|b| b.close.0))?;
+    write_decimals(rg, bars.iter().map(|b| b.volume.0))?;
+    let (amount, levels) = optional_decimals(bars.iter().map(|b| b.amount));
+    write_column::<FixedLenByteArrayType>(rg, &amount, Some(&levels))
+}
+
+fn parse_tick(row: &Row, template: &MarketTick) -> Result<MarketTick> {
+    Ok(MarketTick {
+        time: millis_to_time(row.get_timestamp_millis(0)?)?,
+        bid_price: Price(required_decimal(row, 1)?),
+        bid_size: Quantity(required_decimal(row, 2)?),
+        ask_price: Price(required_decimal(row, 3)?),
+        ask_size: Quantity(required_decimal(row, 4)?),
+        last_price: decimal_at(row, 5)?.map(Price),
+        ..template.clone()
+    })
+}
+
+fn write_tick_group(
+    rg: &mut SerializedRowGroupWriter<'_, File>,
+    ticks: &[MarketTick],
+) -> Result<()> {
+    let time: Vec<i64> = ticks.iter().map(|t| t.time.timestamp_millis()).collect();
+    write_column::<Int64Type>(rg, &time, None)?;
+    write_decimals(rg, ticks.iter().map(|t| t.bid_price.0))?;
+    write_decimals(rg, ticks.iter().map(|t| t.bid_size.0))?;
+    write_decimals(rg, ticks.iter().map(|t| t.ask_price.0))?;
+    write_decimals(rg, ticks.iter().map(|t| t.ask_size.0))?;
+    let (last, levels) = optional_decimals(ticks.iter().map(|t| t.last_price.map(|p| p.0)));
+    write_column::<FixedLenByteArrayType>(rg, &last, Some(&levels))
+}
+
+// =========================================================================
+// 4. MarketDataStore: 日期粒度接口 (与 MySQL 仓储一致)
+// =========================================================================
+
+#[async_trait]
+impl MarketDataStore for ParquetMarketStore {
+    async fn save(&self, bar: &MarketBar) -> Result<u64> {
+        let counts = self.save_intraday_bars(&[IntradayBar::from(bar)])?;
+        // 与 MySQL ON DUPLICATE KEY UPDATE 的返回值保持一致: 插入 1，覆盖 2
+        Ok(if counts.inserted > 0 { 1 } else { 2 })
+    }
+
+    async fn save_batch(&self, bars: &[MarketBar]) -> Result<UpsertCounts> {
+        let bars: Vec<IntradayBar> = bars.iter().map(IntradayBar::from).collect();
+        self.save_intraday_bars(&bars)
+    }
+
+    async fn find_recent_bars(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        bar_period: BarPeriod,
+        trade_type: u8,
+        limit: i64,
+    ) -> Result<Vec<MarketBar>> {
+        let series = SeriesDir {
+            exchange,
+            symbol,
+            bar_period,
+            trade_type,
+        };
+        // 从最新的分区往前读，够数即停
+        let limit = usize::try_from(limit).unwrap_or(0);
+        let mut partitions = list_partitions(&self.series_dir(&series), "date")?;
+        partitions.reverse();
+        let mut bars = Vec::new();
+        for (name, _) in partitions {
+            if bars.len() >= limit {
+                break;
+            }
+            let span = partition_span(&name);
+            bars.extend(self.read_series(&series, span)?);
+        }
+        bars.sort_by_key(|b| std::cmp::Reverse(b.start_time));
+        bars.truncate(limit);
+        Ok(bars.iter().map(IntradayBar::to_market_bar).collect())
+    }
+
+    async fn find_bars_by_range(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        bar_period: BarPeriod,
+        trade_type: u8,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<MarketBar>> {
+        let bars = self.find_intraday_bars(
+            exchange,
+            symbol,
+            bar_period,
+            trade_type,
+            day_start(start_date),
+            day_start(end_date) + Duration::days(1),
+        )?;
+        Ok(bars.iter().map(IntradayBar::to_market_bar).collect())
+    }
+
+    async fn find_series(
+        &self,
+        exchange: Option<&str>,
+        symbol: Option<&str>,
+    ) -> Result<Vec<BarSeriesKey>> {
+        let mut series = Vec::new();
+        for (ex, ex_dir) in list_partitions(&self.root.join("bars"), "exchange")? {
+            if exchange.is_some_and(|e| e != ex) {
+                continue;
+            }
+            for (sym, sym_dir) in list_partitions(&ex_dir, "symbol")? {
+                let sym = symbol_from_dir(&sym);
+                if symbol.is_some_and(|s| s != sym) {
+                    continue;
+                }
+                for (period, period_dir) in list_partitions(&sym_dir, "period")? {
+                    for (trade_type, _) in list_partitions(&period_dir, "type")? {
+                        series.push(BarSeriesKey {
+                            exchange: Exchange::from_str(&ex)?,
+                            symbol: sym.clone(),
+                            bar_period: BarPeriod::from_str(&period)?,
+                            trade_type: trade_type.parse()?,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(series)
+    }
+
+    async fn find_series_bars(&self, key: &BarSeriesKey) -> Result<Vec<MarketBar>> {
+        let bars = self.read_series(
+            &SeriesDir {
+                exchange: &key.exchange.to_string(),
+                symbol: &key.symbol,
+                bar_period: key.bar_period,
+                trade_type: key.trade_type,
+            },
+            None,
+        )?;
+        Ok(bars.iter().map(IntradayBar::to_market_bar).collect())
+    }
+}
+
+// =========================================================================
+// 5. 导出 (数据库 -> Parquet)
+// =========================================================================
+
+/// 导出结果
+#[derive(Debug, Clone, Default)]
+pub struct ExportSummary {
+    pub series: usize,
+    pub bars: usize,
+}
+
+/// 把 `source` 中匹配的 K 线序列整条导出到 Parquet 目录 (重复导出会覆盖同一根 K 线)
+pub async fn export_bars(
+    source: &dyn MarketDataStore,
+    target: &ParquetMarketStore,
+    exchange: Option<&str>,
+    symbol: Option<&str>,
+) -> Result<ExportSummary> {
+    let mut summary = ExportSummary::default();
+    for key in source.find_series(exchange, symbol).await? {
+        let bars = source.find_series_bars(&key).await?;
+        target.save_batch(&bars).await?;
+        info!("  -> Exported {} bars for {}", bars.len(), key);
+        summary.series += 1;
+        summary.bars += bars.len();
+    }
+    Ok(summary)
+}
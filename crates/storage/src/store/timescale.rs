@@ -0,0 +1,631 @@
+use super::{day_start, MarketDataStore};
+use crate::migrate::{collect_status, MigrationStatus};
+use crate::repository::common::StorageConfig;
+use crate::repository::market_repo::{BarSeriesKey, UpsertCounts};
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use quant_core::enums::{BarPeriod, Exchange, Side};
+use quant_core::market::{IntradayBar, MarketBar, MarketTick, MarketTrade};
+use quant_core::primitive::{CurrencyPair, Price, Quantity};
+use rust_decimal::Decimal;
+use sqlx::migrate::Migrator;
+use sqlx::postgres::PgPoolOptions;
+use sqlx::{FromRow, PgPool, Postgres, QueryBuilder};
+use std::str::FromStr;
+use std::time::Duration as StdDuration;
+use tracing::info;
+
+// =========================================================================
+// TimescaleDB 行情存储 (crates/storage/migrations_timescale)
+// =========================================================================
+//
+// 表结构:
+//   - market_bar    任意周期 K 线 (主要存 M1)，start_time 为 TIMESTAMPTZ
+//   - market_tick   盘口快照
+//   - market_trade  逐笔成交
+//   - market_bar_{m5,m15,h1,h4,d1}  M1 的连续聚合视图
+//
+// 读取某个周期时优先读直接写入的数据；没有时回退到连续聚合视图。
+// 实现 [`MarketDataStore`] (日期粒度，与 MySQL 仓储相同)，
+// 另外提供以 DateTime 为粒度的日内 K 线 / Tick / 成交接口。
+
+/// 嵌入的 TimescaleDB 迁移
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations_timescale");
+
+/// Postgres 单条语句最多 65535 个占位符，批量写入按此拆分
+const MAX_BIND_PARAMS: usize = 65_535;
+
+const BAR_COLUMNS: &str = "exchange, symbol, bar_period, \"type\", \
+    open, high, low, close, volume, amount, start_time, end_time";
+
+/// 按配置创建 TimescaleDB 连接池并 ping 一次 (连接池参数与 MySQL 共用)
+pub async fn connect(config: &StorageConfig) -> Result<PgPool> {
+    let Some(url) = config.timescale_url.as_deref().filter(|u| !u.is_empty()) else {
+        bail!("TimescaleDB URL missing: set [storage].timescale_url or TIMESCALE_URL");
+    };
+    let pool = PgPoolOptions::new()
+        .max_connections(config.max_connections)
+        .min_connections(config.min_connections)
+        .acquire_timeout(StdDuration::from_secs(config.acquire_timeout_secs))
+        .idle_timeout(StdDuration::from_secs(config.idle_timeout_secs))
+        .connect(url)
+        .await
+        .context("Failed to connect to TimescaleDB")?;
+
+    sqlx::query("SELECT 1")
+        .execute(&pool)
+        .await
+        .context("Failed to execute ping query")?;
+
+    info!("✅ Successfully connected to TimescaleDB pool.");
+    Ok(pool)
+}
+
+/// 执行全部未执行的 TimescaleDB 迁移
+pub async fn run_migrations(pool: &PgPool) -> Result<()> {
+    MIGRATOR
+        .run(pool)
+        .await
+        .context("Failed to run TimescaleDB migrations")?;
+    Ok(())
+}
+
+/// 查询每个 TimescaleDB 迁移的执行状态
+pub async fn status(pool: &PgPool) -> Result<Vec<MigrationStatus>> {
+    let mut conn = pool.acquire().await?;
+    collect_status(&MIGRATOR, &mut *conn).await
+}
+
+/// 由 M1 连续聚合得到的周期对应的视图
+pub fn aggregate_view(period: BarPeriod) -> Option<&'static str> {
+    match period {
+        BarPeriod::M1 => None,
+        BarPeriod::M5 => Some("market_bar_m5"),
+        BarPeriod::M15 => Some("market_bar_m15"),
+        BarPeriod::H1 => Some("market_bar_h1"),
+        BarPeriod::H4 => Some("market_bar_h4"),
+        BarPeriod::D1 => Some("market_bar_d1"),
+    }
+}
+
+/// 全部连续聚合视图 (周期从小到大)
+const AGGREGATED_PERIODS: [BarPeriod; 5] = [
+    BarPeriod::M5,
+    BarPeriod::M15,
+    BarPeriod::H1,
+    BarPeriod::H4,
+    BarPeriod::D1,
+];
+
+// =========================================================================
+// 行映射 (核心类型只实现了 MySQL 编解码，这里按基础类型读取再转换)
+// =========================================================================
+
+#[derive(Debug, FromRow)]
+struct BarRow {
+    exchange: String,
+    symbol: String,
+    bar_period: String,
+    #[sqlx(rename = "type")]
+    trade_type: i16,
+    open: Decimal,
+    high: Decimal,
+    low: Decimal,
+    close: Decimal,
+    volume: Decimal,
+    amount: Option<Decimal>,
+    start_time: DateTime<Utc>,
+    end_time: DateTime<Utc>,
+}
+
+impl TryFrom<BarRow> for IntradayBar {
+    type Error = anyhow::Error;
+
+    fn try_from(row: BarRow) -> Result<Self> {
+        Ok(Self {
+            exchange: Exchange::from_str(&row.exchange)?,
+            symbol: CurrencyPair::from_str(&row.symbol)?,
+            bar_period: BarPeriod::from_str(&row.bar_period)?,
+            trade_type: u8::try_from(row.trade_type)?,
+            open: Price(row.open),
+            high: Price(row.high),
+            low: Price(row.low),
+            close: Price(row.close),
+            volume: Quantity(row.volume),
+            amount: row.amount,
+            start_time: row.start_time,
+            end_time: row.end_time,
+        })
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct TickRow {
+    exchange: String,
+    symbol: String,
+    time: DateTime<Utc>,
+    bid_price: Decimal,
+    bid_size: Decimal,
+    ask_price: Decimal,
+    ask_size: Decimal,
+    last_price: Option<Decimal>,
+}
+
+impl TryFrom<TickRow> for MarketTick {
+    type Error = anyhow::Error;
+
+    fn try_from(row: TickRow) -> Result<Self> {
+        Ok(Self {
+            exchange: Exchange::from_str(&row.exchange)?,
+            symbol: CurrencyPair::from_str(&row.symbol)?,
+            time: row.time,
+            bid_price: Price(row.bid_price),
+            bid_size: Quantity(row.bid_size),
+            ask_price: Price(row.ask_price),
+            ask_size: Quantity(row.ask_size),
+            last_price: row.last_price.map(Price),
+        })
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct TradeRow {
+    exchange: String,
+    symbol: String,
+    trade_id: String,
+    time: DateTime<Utc>,
+    price: Decimal,
+    quantity: Decimal,
+    side: Option<String>,
+}
+
+impl TryFrom<TradeRow> for MarketTrade {
+    type Error = anyhow::Error;
+
+    fn try_from(row: TradeRow) -> Result<Self> {
+        Ok(Self {
+            exchange: Exchange::from_str(&row.exchange)?,
+            symbol: CurrencyPair::from_str(&row.symbol)?,
+            trade_id: row.trade_id,
+            time: row.time,
+            price: Price(row.price),
+            quantity: Quantity(row.quantity),
+            side: row.side.as_deref().map(Side::from_str).transpose()?,
+        })
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct SeriesRow {
+    exchange: String,
+    symbol: String,
+    bar_period: String,
+    #[sqlx(rename = "type")]
+    trade_type: i16,
+}
+
+/// K 线查询条件 (同一条序列 + 可选时间窗口)
+struct BarQuery<'a> {
+    exchange: &'a str,
+    symbol: &'a str,
+    bar_period: BarPeriod,
+    trade_type: u8,
+    /// [start, end)
+    range: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    /// 最近 N 根 (按时间倒序取，返回前再转为正序)
+    latest: Option<i64>,
+}
+
+/// 多行 upsert，RETURNING 区分插入 (xmax = 0) 与覆盖
+fn build_bar_upsert(bars: &[IntradayBar]) -> QueryBuilder<'_, Postgres> {
+    let mut qb =
+        QueryBuilder::<Postgres>::new(format!("INSERT INTO market_bar ({}) ", BAR_COLUMNS));
+    qb.push_values(bars, |mut b, bar| {
+        b.push_bind(bar.exchange.to_string())
+            .push_bind(bar.symbol.to_string())
+            .push_bind(bar.bar_period.to_string())
+            .push_bind(i16::from(bar.trade_type))
+            .push_bind(bar.open.0)
+            .push_bind(bar.high.0)
+            .push_bind(bar.low.0)
+            .push_bind(bar.close.0)
+            .push_bind(bar.volume.0)
+            .push_bind(bar.amount)
+            .push_bind(bar.start_time)
+            .push_bind(bar.end_time);
+    });
+    qb.push(
+        " ON CONFLICT (exchange, symbol, bar_period, \"type\", start_time) DO UPDATE SET \
+            open = EXCLUDED.open, \
+            high = EXCLUDED.high, \
+            low = EXCLUDED.low, \
+            close = EXCLUDED.close, \
+            volume = EXCLUDED.volume, \
+            amount = EXCLUDED.amount, \
+            end_time = EXCLUDED.end_time \
+         RETURNING (xmax = 0) AS inserted",
+    );
+    qb
+}
+
+#[derive(Clone)]
+pub struct TimescaleMarketStore {
+    pool: PgPool,
+}
+
+impl TimescaleMarketStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
+    // =========================================================================
+    // 日内 K 线
+    // =========================================================================
+
+    /// 批量保存日内 K 线 (Upsert)，返回新增 / 覆盖行数
+    pub async fn save_intraday_bars(&self, bars: &[IntradayBar]) -> Result<UpsertCounts> {
+        let mut counts = UpsertCounts::default();
+        for chunk in bars.chunks(MAX_BIND_PARAMS / 12) {
+            let inserted: Vec<bool> = build_bar_upsert(chunk)
+                .build_query_scalar()
+                .fetch_all(&self.pool)
+                .await?;
+            let new_rows = inserted.iter().filter(|i| **i).count() as u64;
+            counts.inserted += new_rows;
+            counts.updated += inserted.len() as u64 - new_rows;
+        }
+        Ok(counts)
+    }
+
+    /// [start, end) 内的日内 K 线 (按 start_time 正序)
+    ///
+    /// M1 以上的周期没有直接写入的数据时读连续聚合视图。
+    pub async fn find_intraday_bars(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        bar_period: BarPeriod,
+        trade_type: u8,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<IntradayBar>> {
+        self.query_bars(&BarQuery {
+            exchange,
+            symbol,
+            bar_period,
+            trade_type,
+            range: Some((start, end)),
+            latest: None,
+        })
+        .await
+    }
+
+    /// 手动刷新连续聚合 (回补历史 M1 后调用；刷新策略只覆盖最近的窗口)
+    pub async fn refresh_aggregates(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<()> {
+        for period in AGGREGATED_PERIODS {
+            let view = aggregate_view(period).expect("aggregated period has a view");
+            sqlx::query(&format!(
+                "CALL refresh_continuous_aggregate('{}', $1, $2)",
+                view
+            ))
+            .bind(start)
+            .bind(end)
+            .execute(&self.pool)
+            .await
+            .with_context(|| format!("Failed to refresh {}", view))?;
+        }
+        Ok(())
+    }
+
+    async fn query_bars(&self, query: &BarQuery<'_>) -> Result<Vec<IntradayBar>> {
+        let mut rows = self.fetch_bar_rows(query, None).await?;
+        if rows.is_empty() {
+            if let Some(view) = aggregate_view(query.bar_period) {
+                rows = self.fetch_bar_rows(query, Some(view)).await?;
+            }
+        }
+        if query.latest.is_some() {
+            rows.reverse();
+        }
+        rows.into_iter().map(IntradayBar::try_from).collect()
+    }
+
+    /// 从原始表 (view = None) 或连续聚合视图读取一条序列
+    async fn fetch_bar_rows(
+        &self,
+        query: &BarQuery<'_>,
+        view: Option<&str>,
+    ) -> Result<Vec<BarRow>> {
+        let mut qb = match view {
+            None => {
+                let mut qb = QueryBuilder::<Postgres>::new(format!(
+                    "SELECT {} FROM market_bar WHERE bar_period = ",
+                    BAR_COLUMNS
+                ));
+                qb.push_bind(query.bar_period.to_string());
+                qb
+            }
+            Some(view) => QueryBuilder::<Postgres>::new(format!(
+                "SELECT exchange, symbol, '{}' AS bar_period, \"type\", \
+                    open, high, low, close, volume, amount, \
+                    bucket AS start_time, bucket + INTERVAL '{} seconds' AS end_time \
+                 FROM {} WHERE TRUE",
+                query.bar_period,
+                query.bar_period.duration().num_seconds(),
+                view
+            )),
+        };
+        let time_column = if view.is_some() {
+            "bucket"
+        } else {
+            "start_time"
+        };
+        qb.push(" AND exchange = ")
+            .push_bind(query.exchange.to_string())
+            .push(" AND symbol = ")
+            .push_bind(query.symbol.to_string())
+            .push(" AND \"type\" = ")
+            .push_bind(i16::from(query.trade_type));
+        if let Some((start, end)) = query.range {
+            qb.push(format!(" AND {} >= ", time_column))
+                .push_bind(start)
+                .push(format!(" AND {} < ", time_column))
+                .push_bind(end);
+        }
+        match query.latest {
+            Some(limit) => {
+                qb.push(format!(" ORDER BY {} DESC LIMIT ", time_column))
+                    .push_bind(limit);
+            }
+            None => {
+                qb.push(format!(" ORDER BY {} ASC", time_column));
+            }
+        }
+
+        Ok(qb.build_query_as::<BarRow>().fetch_all(&self.pool).await?)
+    }
+
+    // =========================================================================
+    // Tick / 逐笔成交
+    // =========================================================================
+
+    /// 批量写入 Tick，返回写入行数
+    pub async fn save_ticks(&self, ticks: &[MarketTick]) -> Result<u64> {
+        let mut rows = 0;
+        for chunk in ticks.chunks(MAX_BIND_PARAMS / 8) {
+            let mut qb = QueryBuilder::<Postgres>::new(
+                "INSERT INTO market_tick (\
+                    exchange, symbol, \"time\", bid_price, bid_size, ask_price, ask_size, last_price) ",
+            );
+            qb.push_values(chunk, |mut b, tick| {
+                b.push_bind(tick.exchange.to_string())
+                    .push_bind(tick.symbol.to_string())
+                    .push_bind(tick.time)
+                    .push_bind(tick.bid_price.0)
+                    .push_bind(tick.bid_size.0)
+                    .push_bind(tick.ask_price.0)
+                    .push_bind(tick.ask_size.0)
+                    .push_bind(tick.last_price.map(|p| p.0));
+            });
+            rows += qb.build().execute(&self.pool).await?.rows_affected();
+        }
+        Ok(rows)
+    }
+
+    /// [start, end) 内的 Tick (按时间正序)
+    pub async fn find_ticks(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<MarketTick>> {
+        let rows = sqlx::query_as::<_, TickRow>(
+            r#"
+            SELECT exchange, symbol, "time",
+                   bid_price, bid_size, ask_price, ask_size, last_price
+            FROM market_tick
+            WHERE exchange = $1
+              AND symbol = $2
+              AND "time" >= $3
+              AND "time" < $4
+            ORDER BY "time" ASC
+            "#,
+        )
+        .bind(exchange)
+        .bind(symbol)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(MarketTick::try_from).collect()
+    }
+
+    /// 批量写入逐笔成交 (trade_id 重复的忽略)，返回新写入行数
+    pub async fn save_trades(&self, trades: &[MarketTrade]) -> Result<u64> {
+        let mut rows = 0;
+        for chunk in trades.chunks(MAX_BIND_PARAMS / 7) {
+            let mut qb = QueryBuilder::<Postgres>::new(
+                "INSERT INTO market_trade (\
+                    exchange, symbol, trade_id, \"time\", price, quantity, side) ",
+            );
+            qb.push_values(chunk, |mut b, trade| {
+                b.push_bind(trade.exchange.to_string())
+                    .push_bind(trade.symbol.to_string())
+                    .push_bind(&trade.trade_id)
+                    .push_bind(trade.time)
+                    .push_bind(trade.price.0)
+                    .push_bind(trade.quantity.0)
+                    .push_bind(trade.side.map(|s| s.to_string()));
+            });
+            qb.push(" ON CONFLICT DO NOTHING");
+            rows += qb.build().execute(&self.pool).await?.rows_affected();
+        }
+        Ok(rows)
+    }
+
+    /// [start, end) 内的逐笔成交 (按时间正序)
+    pub async fn find_trades(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<MarketTrade>> {
+        let rows = sqlx::query_as::<_, TradeRow>(
+            r#"
+            SELECT exchange, symbol, trade_id, "time", price, quantity, side
+            FROM market_trade
+            WHERE exchange = $1
+              AND symbol = $2
+              AND "time" >= $3
+              AND "time" < $4
+            ORDER BY "time" ASC, trade_id ASC
+            "#,
+        )
+        .bind(exchange)
+        .bind(symbol)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(MarketTrade::try_from).collect()
+    }
+}
+
+// =========================================================================
+// MarketDataStore: 日期粒度接口 (与 MySQL 仓储一致)
+// =========================================================================
+
+#[async_trait]
+impl MarketDataStore for TimescaleMarketStore {
+    async fn save(&self, bar: &MarketBar) -> Result<u64> {
+        let counts = self.save_intraday_bars(&[IntradayBar::from(bar)]).await?;
+        // 与 MySQL ON DUPLICATE KEY UPDATE 的返回值保持一致: 插入 1，覆盖 2
+        Ok(if counts.inserted > 0 { 1 } else { 2 })
+    }
+
+    async fn save_batch(&self, bars: &[MarketBar]) -> Result<UpsertCounts> {
+        let bars: Vec<IntradayBar> = bars.iter().map(IntradayBar::from).collect();
+        self.save_intraday_bars(&bars).await
+    }
+
+    async fn find_recent_bars(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        bar_period: BarPeriod,
+        trade_type: u8,
+        limit: i64,
+    ) -> Result<Vec<MarketBar>> {
+        let mut bars = self
+            .query_bars(&BarQuery {
+                exchange,
+                symbol,
+                bar_period,
+                trade_type,
+                range: None,
+                latest: Some(limit),
+            })
+            .await?;
+        // 与 MySQL 仓储一致: 按 start_time 倒序
+        bars.reverse();
+        Ok(bars.iter().map(IntradayBar::to_market_bar).collect())
+    }
+
+    async fn find_bars_by_range(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        bar_period: BarPeriod,
+        trade_type: u8,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<MarketBar>> {
+        let bars = self
+            .find_intraday_bars(
+                exchange,
+                symbol,
+                bar_period,
+                trade_type,
+                day_start(start_date),
+                day_start(end_date) + Duration::days(1),
+            )
+            .await?;
+        Ok(bars.iter().map(IntradayBar::to_market_bar).collect())
+    }
+
+    /// 直接写入的序列，加上每条 M1 序列可聚合出的高周期序列
+    async fn find_series(
+        &self,
+        exchange: Option<&str>,
+        symbol: Option<&str>,
+    ) -> Result<Vec<BarSeriesKey>> {
+        let rows = sqlx::query_as::<_, SeriesRow>(
+            r#"
+            SELECT DISTINCT exchange, symbol, bar_period, "type"
+            FROM market_bar
+            WHERE ($1::text IS NULL OR exchange = $1)
+              AND ($2::text IS NULL OR symbol = $2)
+            "#,
+        )
+        .bind(exchange)
+        .bind(symbol)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut series = Vec::with_capacity(rows.len());
+        for row in rows {
+            let key = BarSeriesKey {
+                exchange: Exchange::from_str(&row.exchange)?,
+                symbol: row.symbol,
+                bar_period: BarPeriod::from_str(&row.bar_period)?,
+                trade_type: u8::try_from(row.trade_type)?,
+            };
+            if key.bar_period == BarPeriod::M1 {
+                series.extend(AGGREGATED_PERIODS.iter().map(|p| BarSeriesKey {
+                    bar_period: *p,
+                    ..key.clone()
+                }));
+            }
+            series.push(key);
+        }
+        series.sort_by(|a, b| {
+            (
+                a.exchange.to_string(),
+                &a.symbol,
+                a.bar_period.duration(),
+                a.trade_type,
+            )
+                .cmp(&(
+                    b.exchange.to_string(),
+                    &b.symbol,
+                    b.bar_period.duration(),
+                    b.trade_type,
+                ))
+        });
+        series.dedup();
+        Ok(series)
+    }
+
+    async fn find_series_bars(&self, key: &BarSeriesKey) -> Result<Vec<MarketBar>> {
+        let bars = self
+            .query_bars(&BarQuery {
+                exchange: &key.exchange.to_string(),
+                symbol: &key.symbol,
+                bar_period: key.bar_period,
+                trade_type: key.trade_type,
+                range: None,
+                latest: None,
+            })
+            .await?;
+        Ok(bars.iter().map(IntradayBar::to_market_bar).collect())
+    }
+}
@@ -0,0 +1,408 @@
+use crate::redis::{RedisService, StreamEntry};
+use anyhow::{anyhow, Context, Result};
+use quant_core::bus::{EventBus, EventSink, TopicFilter};
+use quant_core::event::{Envelope, Topic};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+// =========================================================================
+// Redis Streams 跨进程事件
+// =========================================================================
+//
+// 每条消息只有一个字段 `payload` (JSON)。消费组保证同组内每条消息只投递给一个消费者:
+//   - 处理完调用 `ack`，否则消息留在 PEL (待确认列表)
+//   - 空闲超过 `claim_idle_ms` 的待确认消息会被组内其他消费者接管 (消费者崩溃)
+//   - 投递超过 `max_deliveries` 次或无法解码的消息转入死信流 `{stream}:dead` 并确认
+
+const PAYLOAD_FIELD: &str = "payload";
+
+/// 单个流 + 消费组的配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StreamConfig {
+    pub stream: String,
+    pub group: String,
+    /// 消费者名称 (同组内唯一；保持稳定，重启后可以继续处理自己的待确认消息)
+    pub consumer: String,
+    /// 流的近似长度上限，超出后裁掉最旧的消息
+    pub max_len: usize,
+    /// 每次最多读取条数
+    pub batch_size: usize,
+    /// 没有新消息时最多阻塞等待的毫秒数
+    pub block_ms: u64,
+    /// 待确认消息空闲多久后可被接管 (毫秒)
+    pub claim_idle_ms: u64,
+    /// 最大投递次数，超过后转入死信流
+    pub max_deliveries: u64,
+    /// 死信流，为空时使用 `{stream}:dead`
+    pub dead_letter_stream: Option<String>,
+}
+
+impl Default for StreamConfig {
+    fn default() -> Self {
+        Self {
+            stream: "photon:events".to_string(),
+            group: "photon".to_string(),
+            consumer: format!("consumer-{}", std::process::id()),
+            max_len: 100_000,
+            batch_size: 100,
+            block_ms: 1_000,
+            claim_idle_ms: 30_000,
+            max_deliveries: 5,
+            dead_letter_stream: None,
+        }
+    }
+}
+
+impl StreamConfig {
+    pub fn dead_letter_stream(&self) -> String {
+        self.dead_letter_stream
+            .clone()
+            .unwrap_or_else(|| format!("{}:dead", self.stream))
+    }
+}
+
+/// 读取到的一条类型化消息
+#[derive(Debug, Clone)]
+pub struct StreamMessage<T> {
+    pub id: String,
+    pub payload: T,
+    /// 第几次投递 (新消息为 1，被接管的消息大于 1)
+    pub deliveries: u64,
+}
+
+/// 类型化的 Redis Stream (生产 + 消费组消费)
+pub struct EventStream<T> {
+    redis: RedisService,
+    config: StreamConfig,
+    _payload: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for EventStream<T> {
+    fn clone(&self) -> Self {
+        Self {
+            redis: self.redis.clone(),
+            config: self.config.clone(),
+            _payload: PhantomData,
+        }
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> EventStream<T> {
+    pub fn new(redis: RedisService, config: StreamConfig) -> Self {
+        Self {
+            redis,
+            config,
+            _payload: PhantomData,
+        }
+    }
+
+    pub fn config(&self) -> &StreamConfig {
+        &self.config
+    }
+
+    /// 发布一条消息，返回消息 ID
+    pub async fn publish(&self, payload: &T) -> Result<String> {
+        let data = serde_json::to_string(payload).context("Failed to encode stream payload")?;
+        self.redis
+            .xadd(
+                &self.config.stream,
+                &[(PAYLOAD_FIELD, &data)],
+                Some(self.config.max_len),
+            )
+            .await
+    }
+
+    /// 确保消费组存在；`start_id` 只在新建时生效 ("$" 只消费之后的消息，"0" 从头消费)
+    pub async fn ensure_group(&self, start_id: &str) -> Result<()> {
+        if self
+            .redis
+            .xgroup_create(&self.config.stream, &self.config.group, start_id)
+            .await?
+        {
+            info!(
+                "📮 Created consumer group {} on {} from {}",
+                self.config.group, self.config.stream, start_id
+            );
+        }
+        Ok(())
+    }
+
+    /// 读取一批消息：先接管超时的待确认消息，没有时再阻塞读取新消息
+    ///
+    /// 返回的消息处理完后需要调用 [`EventStream::ack`]。
+    pub async fn read(&self) -> Result<Vec<StreamMessage<T>>> {
+        let reclaimed = self.reclaim().await?;
+        if !reclaimed.is_empty() {
+            return Ok(reclaimed);
+        }
+        let entries = self
+            .redis
+            .xread_group(
+                &self.config.stream,
+                &self.config.group,
+                &self.config.consumer,
+                self.config.batch_size,
+                self.config.block_ms,
+            )
+            .await?;
+        self.decode_all(entries, 1).await
+    }
+
+    pub async fn ack(&self, ids: &[String]) -> Result<u64> {
+        self.redis
+            .xack(&self.config.stream, &self.config.group, ids)
+            .await
+    }
+
+    /// 从 `start_id` (含) 开始回放最多 `count` 条消息，不影响消费组进度
+    ///
+    /// `start_id` 为 "-" 表示从最早的消息开始；无法解码的消息会被跳过。
+    pub async fn replay(&self, start_id: &str, count: usize) -> Result<Vec<StreamMessage<T>>> {
+        let entries = self
+            .redis
+            .xrange(&self.config.stream, start_id, "+", count)
+            .await?;
+        Ok(entries
+            .into_iter()
+            .filter_map(|entry| match decode(&entry) {
+                Ok(payload) => Some(StreamMessage {
+                    id: entry.id,
+                    payload,
+                    deliveries: 0,
+                }),
+                Err(e) => {
+                    warn!("Skipping undecodable message {}: {:?}", entry.id, e);
+                    None
+                }
+            })
+            .collect())
+    }
+
+    /// 死信流中最多 `count` 条消息 (原始字段 + reason / source_id)
+    pub async fn dead_letters(&self, count: usize) -> Result<Vec<StreamEntry>> {
+        self.redis
+            .xrange(&self.config.dead_letter_stream(), "-", "+", count)
+            .await
+    }
+
+    /// 接管空闲超时的待确认消息；投递次数用尽的转入死信流
+    async fn reclaim(&self) -> Result<Vec<StreamMessage<T>>> {
+        let pending = self
+            .redis
+            .xpending(
+                &self.config.stream,
+                &self.config.group,
+                self.config.batch_size,
+            )
+            .await?;
+        let stale: Vec<_> = pending
+            .into_iter()
+            .filter(|p| p.idle_ms >= self.config.claim_idle_ms)
+            .collect();
+        if stale.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids: Vec<String> = stale.iter().map(|p| p.id.clone()).collect();
+        let claimed = self
+            .redis
+            .xclaim(
+                &self.config.stream,
+                &self.config.group,
+                &self.config.consumer,
+                self.config.claim_idle_ms,
+                &ids,
+            )
+            .await?;
+
+        let mut alive = Vec::new();
+        for entry in claimed {
+            let deliveries = stale
+                .iter()
+                .find(|p| p.id == entry.id)
+                .map_or(1, |p| p.deliveries + 1);
+            if deliveries > self.config.max_deliveries {
+                let reason = format!("exceeded {} deliveries", self.config.max_deliveries);
+                self.dead_letter(&entry, &reason).await?;
+            } else {
+                alive.push((entry, deliveries));
+            }
+        }
+
+        let mut messages = Vec::new();
+        for (entry, deliveries) in alive {
+            messages.extend(self.decode_all(vec![entry], deliveries).await?);
+        }
+        Ok(messages)
+    }
+
+    /// 解码一批消息；无法解码的直接转入死信流
+    async fn decode_all(
+        &self,
+        entries: Vec<StreamEntry>,
+        deliveries: u64,
+    ) -> Result<Vec<StreamMessage<T>>> {
+        let mut messages = Vec::with_capacity(entries.len());
+        for entry in entries {
+            match decode(&entry) {
+                Ok(payload) => messages.push(StreamMessage {
+                    deliveries,
+                    id: entry.id,
+                    payload,
+                }),
+                Err(e) => self.dead_letter(&entry, &format!("{:#}", e)).await?,
+            }
+        }
+        Ok(messages)
+    }
+
+    /// 把消息写入死信流并从消费组中确认
+    async fn dead_letter(&self, entry: &StreamEntry, reason: &str) -> Result<()> {
+        warn!(
+            "☠️ Moving message {} of {} to dead letters: {}",
+            entry.id, self.config.stream, reason
+        );
+        let mut fields: Vec<(&str, &str)> = entry
+            .fields
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        fields.push(("reason", reason));
+        fields.push(("source_id", &entry.id));
+        self.redis
+            .xadd(
+                &self.config.dead_letter_stream(),
+                &fields,
+                Some(self.config.max_len),
+            )
+            .await?;
+        self.ack(std::slice::from_ref(&entry.id)).await?;
+        Ok(())
+    }
+}
+
+fn decode<T: DeserializeOwned>(entry: &StreamEntry) -> Result<T> {
+    let raw = entry
+        .fields
+        .get(PAYLOAD_FIELD)
+        .ok_or_else(|| anyhow!("Missing '{}' field", PAYLOAD_FIELD))?;
+    serde_json::from_str(raw).context("Invalid stream payload")
+}
+
+// =========================================================================
+// 事件总线桥接 (EventBus <-> Redis Stream)
+// =========================================================================
+
+/// 从 Redis 收到的事件在本地总线上的来源前缀，这类事件不会再被转发出去 (避免回环)
+pub const REMOTE_SOURCE_PREFIX: &str = "remote:";
+
+/// 跨进程传输的事件：信封 + 发出事件的节点名
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteEvent {
+    pub node: String,
+    pub envelope: Envelope,
+}
+
+/// 把本地总线事件转交给后台发送任务的 Sink
+///
+/// `record` 在发布路径上同步调用，这里只入队，不等待 Redis。
+pub struct StreamSink {
+    filter: TopicFilter,
+    tx: mpsc::UnboundedSender<Envelope>,
+}
+
+impl StreamSink {
+    /// 创建 Sink 和对应的接收端 (由发送任务消费)
+    pub fn channel(filter: TopicFilter) -> (Self, mpsc::UnboundedReceiver<Envelope>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (Self { filter, tx }, rx)
+    }
+}
+
+impl EventSink for StreamSink {
+    fn record(&self, envelope: &Envelope) -> Result<()> {
+        if envelope.source.starts_with(REMOTE_SOURCE_PREFIX)
+            || !self.filter.matches(envelope.topic())
+        {
+            return Ok(());
+        }
+        self.tx
+            .send(envelope.clone())
+            .map_err(|_| anyhow!("Stream bridge is closed"))
+    }
+}
+
+/// 出站桥接：把本地总线上 `topics` 主题的事件发布到 Redis Stream
+pub fn bridge_outbound(
+    bus: &EventBus,
+    stream: EventStream<RemoteEvent>,
+    node: impl Into<String>,
+    topics: impl IntoIterator<Item = Topic>,
+) -> JoinHandle<()> {
+    let (sink, mut rx) = StreamSink::channel(TopicFilter::topics(topics));
+    bus.add_sink(std::sync::Arc::new(sink));
+    let node = node.into();
+    tokio::spawn(async move {
+        while let Some(envelope) = rx.recv().await {
+            let event = RemoteEvent {
+                node: node.clone(),
+                envelope,
+            };
+            if let Err(e) = stream.publish(&event).await {
+                error!(
+                    "Failed to bridge event #{} to {}: {:?}",
+                    event.envelope.seq,
+                    stream.config().stream,
+                    e
+                );
+            }
+        }
+    })
+}
+
+/// 入站桥接：消费 Redis Stream，把其他节点 `topics` 主题的事件重新发布到本地总线
+///
+/// 本地重新分配序号，来源标记为 `remote:{node}:{source}`；发布到总线后才确认消息。
+pub async fn bridge_inbound(
+    bus: EventBus,
+    stream: EventStream<RemoteEvent>,
+    node: impl Into<String>,
+    topics: impl IntoIterator<Item = Topic>,
+) -> Result<JoinHandle<()>> {
+    stream.ensure_group("$").await?;
+    let filter = TopicFilter::topics(topics);
+    let node = node.into();
+    Ok(tokio::spawn(async move {
+        loop {
+            let messages = match stream.read().await {
+                Ok(messages) => messages,
+                Err(e) => {
+                    error!("Failed to read {}: {:?}", stream.config().stream, e);
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    continue;
+                }
+            };
+            let mut ids = Vec::with_capacity(messages.len());
+            for message in messages {
+                let RemoteEvent {
+                    node: origin,
+                    envelope,
+                } = message.payload;
+                if origin != node && filter.matches(envelope.topic()) {
+                    let source = format!("{}{}:{}", REMOTE_SOURCE_PREFIX, origin, envelope.source);
+                    bus.publish(source, envelope.event).await;
+                }
+                ids.push(message.id);
+            }
+            if let Err(e) = stream.ack(&ids).await {
+                error!("Failed to ack {} messages: {:?}", ids.len(), e);
+            }
+        }
+    }))
+}
@@ -7,6 +7,7 @@ mod tests {
     use quant_core::primitive::CurrencyPair;
 
     use quant_storage::repository::account_repo;
+    use quant_storage::repository::common::BatchOptions;
     use rust_decimal_macros::dec;
     use std::str::FromStr;
     use uuid::Uuid;
@@ -15,8 +16,9 @@ mod tests {
     // =========================================================================
 
     async fn get_test_repo() -> account_repo::AccountRepository {
-        // 复用你的 get_db_pool，但它会返回一个新的 Pool 实例
-        let pool = quant_storage::repository::common::get_real_pool().await;
+        let pool = quant_storage::repository::common::connect_from_env()
+            .await
+            .expect("Failed to connect to test database");
         account_repo::AccountRepository::new(pool.clone())
     }
 
@@ -144,4 +146,62 @@ mod tests {
 
         Ok(())
     }
+
+    // =========================================================================
+    // 4. 批量 Upsert
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_batch_upsert_assets_and_positions() -> Result<()> {
+        let repo = get_test_repo().await;
+        let account_name = format!("test_batch_{}", Uuid::new_v4());
+
+        // 1. 资产: 3 个币种，批大小 2
+        let mut assets: Vec<Asset> = ["USDT", "BTC", "ETH"]
+            .iter()
+            .map(|currency| {
+                let mut asset = mock_asset(&account_name);
+                asset.currency = currency.to_string();
+                asset
+            })
+            .collect();
+        let options = BatchOptions::new(2).transactional();
+        let report = repo.upsert_assets(&assets, &options).await?;
+        assert_eq!(report.batches, vec![2, 1]);
+
+        // 再次写入: 命中唯一键，更新余额
+        for asset in &mut assets {
+            asset.free = dec!(42);
+        }
+        repo.upsert_assets(&assets, &options).await?;
+        let saved = repo.find_assets_by_account(&account_name).await?;
+        assert_eq!(saved.len(), 3);
+        assert!(saved.iter().all(|a| a.free == dec!(42)));
+
+        // 2. 持仓: 与逐行 upsert 结果一致
+        let positions = vec![mock_position(&account_name), {
+            let mut pos = mock_position(&account_name);
+            pos.symbol = CurrencyPair::from_str("BTC/USDT").unwrap();
+            pos
+        }];
+        let report = repo
+            .upsert_positions(&positions, &BatchOptions::default())
+            .await?;
+        assert_eq!(report.total(), 2);
+
+        let saved = repo.find_positions_by_account(&account_name).await?;
+        assert_eq!(saved.len(), 2);
+        for pos in &positions {
+            let found = saved
+                .iter()
+                .find(|p| p.symbol == pos.symbol)
+                .expect("Position should be found");
+            assert_eq!(found.quantity, pos.quantity);
+            assert_eq!(found.entry_price, pos.entry_price);
+            assert_eq!(found.leverage, pos.leverage);
+        }
+
+        repo.clear_positions(&account_name).await?;
+        Ok(())
+    }
 }
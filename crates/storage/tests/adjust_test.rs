@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use quant_core::enums::{BarPeriod, Exchange};
+    use quant_core::market::{CorporateAction, MarketBar};
+    use quant_core::primitive::{Price, Quantity};
+    use quant_storage::adjust::{adjust_bars, adjustment_events, AdjustMode};
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    // =========================================================================
+    // 1. 辅助函数
+    // =========================================================================
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    /// OHLC 都等于 close 的日线
+    fn bar(date: NaiveDate, close: Decimal, volume: Decimal) -> MarketBar {
+        MarketBar::new(
+            Exchange::Nasdaq,
+            "AAPL/USD",
+            BarPeriod::D1,
+            21,
+            Price(close),
+            Price(close),
+            Price(close),
+            Price(close),
+            Quantity(volume),
+            date,
+        )
+        .expect("Failed to create bar")
+    }
+
+    /// 2020-08-28 收 400，08-31 起 4 拆 1 后收 100
+    fn split_series() -> (Vec<MarketBar>, Vec<CorporateAction>) {
+        let bars = vec![
+            bar(date(2020, 8, 27), dec!(500), dec!(100)),
+            bar(date(2020, 8, 28), dec!(400), dec!(100)),
+            bar(date(2020, 8, 31), dec!(100), dec!(400)),
+            bar(date(2020, 9, 1), dec!(110), dec!(400)),
+        ];
+        let actions =
+            vec![
+                CorporateAction::split(Exchange::Nasdaq, "AAPL/USD", date(2020, 8, 31), dec!(4))
+                    .unwrap(),
+            ];
+        (bars, actions)
+    }
+
+    fn closes(bars: &[MarketBar]) -> Vec<Decimal> {
+        bars.iter().map(|b| b.close.0).collect()
+    }
+
+    // =========================================================================
+    // 2. 测试用例
+    // =========================================================================
+
+    #[test]
+    fn test_backward_split() {
+        let (bars, actions) = split_series();
+        let adjusted = adjust_bars(&bars, &actions, AdjustMode::Backward);
+
+        // 除权前价格缩小 4 倍，成交量放大 4 倍；除权后不变
+        assert_eq!(
+            closes(&adjusted),
+            vec![dec!(125), dec!(100), dec!(100), dec!(110)]
+        );
+        assert_eq!(adjusted[0].volume.0, dec!(400));
+        assert_eq!(adjusted[3].volume.0, dec!(400));
+        // 原始序列不受影响
+        assert_eq!(bars[0].close.0, dec!(500));
+    }
+
+    #[test]
+    fn test_forward_split() {
+        let (bars, actions) = split_series();
+        let adjusted = adjust_bars(&bars, &actions, AdjustMode::Forward);
+
+        // 最早价格不变，除权后价格放大 4 倍
+        assert_eq!(
+            closes(&adjusted),
+            vec![dec!(500), dec!(400), dec!(400), dec!(440)]
+        );
+        assert_eq!(adjusted[0].volume.0, dec!(100));
+        assert_eq!(adjusted[2].volume.0, dec!(100));
+    }
+
+    #[test]
+    fn test_none_keeps_raw() {
+        let (bars, actions) = split_series();
+        let adjusted = adjust_bars(&bars, &actions, AdjustMode::None);
+        assert_eq!(closes(&adjusted), closes(&bars));
+    }
+
+    #[test]
+    fn test_cash_dividend() {
+        let bars = vec![
+            bar(date(2024, 2, 8), dec!(100), dec!(10)),
+            bar(date(2024, 2, 9), dec!(99), dec!(10)),
+        ];
+        let actions = vec![CorporateAction::cash_dividend(
+            Exchange::Nasdaq,
+            "AAPL/USD",
+            date(2024, 2, 9),
+            dec!(1),
+        )
+        .unwrap()];
+
+        // 因子 = (100 - 1) / 100
+        let events = adjustment_events(&bars, &actions);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].price_factor, dec!(0.99));
+        assert_eq!(events[0].volume_factor, Decimal::ONE);
+
+        let backward = adjust_bars(&bars, &actions, AdjustMode::Backward);
+        assert_eq!(closes(&backward), vec![dec!(99), dec!(99)]);
+        assert_eq!(backward[0].volume.0, dec!(10));
+
+        let forward = adjust_bars(&bars, &actions, AdjustMode::Forward);
+        assert_eq!(closes(&forward), vec![dec!(100), dec!(100)]);
+    }
+
+    #[test]
+    fn test_events_skip_unusable_actions() {
+        let bars = vec![bar(date(2024, 2, 9), dec!(99), dec!(10))];
+        let actions = vec![
+            // 除息日前没有 K 线，无法计算因子
+            CorporateAction::cash_dividend(Exchange::Nasdaq, "AAPL/USD", date(2024, 2, 9), dec!(1))
+                .unwrap(),
+            // 代码变更不影响价格
+            CorporateAction::symbol_change(
+                Exchange::Nasdaq,
+                "AAPL/USD",
+                date(2024, 3, 1),
+                "APPL/USD",
+            )
+            .unwrap(),
+        ];
+        assert!(adjustment_events(&bars, &actions).is_empty());
+        assert_eq!(
+            closes(&adjust_bars(&bars, &actions, AdjustMode::Backward)),
+            vec![dec!(99)]
+        );
+    }
+}
@@ -0,0 +1,255 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::common;
+    use anyhow::Result;
+    use chrono::{Duration, NaiveDate, Utc};
+    use quant_core::account::{Asset, Position};
+    use quant_core::enums::{BarPeriod, Exchange, OrderStatus, Side};
+    use quant_core::market::{MarketBar, MarketTick};
+    use quant_core::oms::Order;
+    use quant_core::primitive::{CurrencyPair, Price, Quantity};
+    use quant_storage::cache::{CacheConfig, CachedAccountStore, CachedOrderStore, HotCache};
+    use quant_storage::lock::{DistributedLock, FencedOut, Fencing, LockOptions};
+    use quant_storage::repository::common::StorageConfig;
+    use quant_storage::store::{AccountStore, MemoryAccountStore, MemoryOrderStore, OrderStore};
+    use rust_decimal_macros::dec;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    /// 每个用例用独立前缀避免互相干扰
+    fn cache() -> HotCache {
+        let config = CacheConfig {
+            key_prefix: format!("photon-test:{}", Uuid::new_v4()),
+            ..CacheConfig::default()
+        };
+        HotCache::new(common::redis(), config)
+    }
+
+    fn tick(secs: i64, bid: rust_decimal::Decimal) -> MarketTick {
+        MarketTick {
+            exchange: Exchange::Binance,
+            symbol: CurrencyPair::from_str("BTC/USDT").unwrap(),
+            time: Utc::now() + Duration::seconds(secs),
+            bid_price: Price(bid),
+            bid_size: Quantity(dec!(1)),
+            ask_price: Price(bid + dec!(0.5)),
+            ask_size: Quantity(dec!(1)),
+            last_price: None,
+        }
+    }
+
+    fn order(strategy: &str) -> Order {
+        Order::new_limit(
+            "BTC/USDT",
+            Exchange::Binance,
+            Some(strategy.to_string()),
+            Side::Buy,
+            Price(dec!(50000)),
+            Quantity(dec!(0.1)),
+        )
+    }
+
+    #[test]
+    fn test_cache_config() {
+        let config: StorageConfig =
+            serde_json::from_str(r#"{"cache": {"key_prefix": "paper", "quote_ttl_secs": 5}}"#)
+                .unwrap();
+        assert_eq!(config.cache.key_prefix, "paper");
+        assert_eq!(config.cache.quote_ttl_secs, 5);
+        assert_eq!(
+            config.cache.order_ttl_secs,
+            CacheConfig::default().order_ttl_secs
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis (REDIS_URL)"]
+    async fn test_latest_quote_and_bar() -> Result<()> {
+        let cache = cache();
+        assert!(cache.latest_quote("BINANCE", "BTC/USDT").await?.is_none());
+
+        assert!(cache.set_quote(&tick(0, dec!(100))).await?);
+        // 乱序到达的旧报价被忽略
+        assert!(!cache.set_quote(&tick(-5, dec!(90))).await?);
+        let quote = cache.latest_quote("BINANCE", "BTC/USDT").await?.unwrap();
+        assert_eq!(quote.bid_price, Price(dec!(100)));
+
+        let day = NaiveDate::from_ymd_opt(2024, 6, 3).unwrap();
+        let bar = |d: NaiveDate, close| {
+            MarketBar::new(
+                Exchange::Binance,
+                "BTC/USDT",
+                BarPeriod::D1,
+                21,
+                Price(close),
+                Price(close),
+                Price(close),
+                Price(close),
+                Quantity(dec!(1)),
+                d,
+            )
+            .unwrap()
+        };
+        assert!(cache.set_bar(&bar(day, dec!(70000))).await?);
+        assert!(
+            !cache
+                .set_bar(&bar(day - Duration::days(1), dec!(1)))
+                .await?
+        );
+        let latest = cache
+            .latest_bar("BINANCE", "BTC/USDT", BarPeriod::D1, 21)
+            .await?
+            .unwrap();
+        assert_eq!((latest.start_time, latest.close), (day, Price(dec!(70000))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis (REDIS_URL)"]
+    async fn test_open_orders_by_strategy() -> Result<()> {
+        let cache = cache();
+        let strategy = Uuid::new_v4().to_string();
+        let mut first = order(&strategy);
+        let second = order(&strategy);
+        cache.put_order(&first).await?;
+        cache.put_order(&second).await?;
+        assert_eq!(cache.open_orders(Some(&strategy)).await?.len(), 2);
+        assert!(cache.open_orders(None).await?.is_empty());
+
+        first.status = OrderStatus::Filled;
+        cache.put_order(&first).await?;
+        let open = cache.open_orders(Some(&strategy)).await?;
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].uuid, second.uuid);
+
+        cache.set_open_orders(Some(&strategy), &[]).await?;
+        assert!(cache.open_orders(Some(&strategy)).await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis (REDIS_URL)"]
+    async fn test_replace_open_orders() -> Result<()> {
+        let cache = cache();
+        let strategy = Uuid::new_v4().to_string();
+        cache.put_order(&order(&strategy)).await?;
+
+        // 按数据库结果整体覆盖：陈旧条目被清掉
+        let fresh = order(&strategy);
+        let manual = Order {
+            strategy_uuid: None,
+            ..order(&strategy)
+        };
+        let strategies = cache
+            .replace_open_orders(&[fresh.clone(), manual.clone()])
+            .await?;
+        assert_eq!(strategies, 2);
+        let open = cache.open_orders(Some(&strategy)).await?;
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].uuid, fresh.uuid);
+        assert_eq!(cache.open_orders(None).await?[0].uuid, manual.uuid);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis (REDIS_URL)"]
+    async fn test_stale_fence_cannot_write_orders() -> Result<()> {
+        let redis = common::redis();
+        let lock_key = format!("photon-test:lock:{}", Uuid::new_v4());
+        let options = LockOptions::default();
+        let fencing = Fencing::new();
+        let cache = cache().with_fencing(fencing.clone());
+        let strategy = Uuid::new_v4().to_string();
+
+        let old = DistributedLock::try_acquire(&redis, &lock_key, &options)
+            .await?
+            .expect("first term");
+        let stale = old.fence();
+        old.release().await?;
+        let new = DistributedLock::try_acquire(&redis, &lock_key, &options)
+            .await?
+            .expect("second term");
+
+        // 新任期写入正常
+        fencing.set(new.fence());
+        let current = order(&strategy);
+        cache.put_order(&current).await?;
+
+        // 旧任期的写入在 Lua 中被拒绝，缓存保持不变
+        fencing.set(stale);
+        let err = cache.put_order(&order(&strategy)).await.unwrap_err();
+        assert!(err.downcast_ref::<FencedOut>().is_some());
+        assert!(cache
+            .set_open_orders(Some(&strategy), &[])
+            .await
+            .unwrap_err()
+            .is::<FencedOut>());
+        let open = cache.open_orders(Some(&strategy)).await?;
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].uuid, current.uuid);
+
+        new.release().await?;
+        redis.delete(&format!("{}:fence", lock_key)).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis (REDIS_URL)"]
+    async fn test_cached_order_store_write_through() -> Result<()> {
+        let cache = cache();
+        let store = CachedOrderStore::new(Arc::new(MemoryOrderStore::new()), cache.clone());
+        let strategy = Uuid::new_v4().to_string();
+        let order = order(&strategy);
+        store.insert(&order).await?;
+        assert_eq!(cache.open_orders(Some(&strategy)).await?.len(), 1);
+
+        let uuid = Uuid::from_str(&order.uuid)?;
+        store
+            .update_status(uuid, OrderStatus::Canceled, None, dec!(0), None, None)
+            .await?;
+        assert!(cache.open_orders(Some(&strategy)).await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis (REDIS_URL)"]
+    async fn test_cached_account_store() -> Result<()> {
+        let cache = cache();
+        let inner = Arc::new(MemoryAccountStore::new());
+        let store = CachedAccountStore::new(inner.clone(), cache.clone());
+
+        // 空账户也会建立快照
+        assert!(cache.positions("main").await?.is_none());
+        assert!(store.find_positions_by_account("main").await?.is_empty());
+        assert_eq!(cache.positions("main").await?.map(|p| p.len()), Some(0));
+
+        let mut asset = Asset::new("main", Exchange::Binance, "USDT");
+        asset.free = dec!(1000.5);
+        store.upsert_asset(&asset).await?;
+        let mut long = Position::new("main", Exchange::Binance, "BTC/USDT", Side::Buy);
+        long.quantity = dec!(0.25);
+        store.upsert_position(&long).await?;
+        assert_eq!(cache.positions("main").await?.unwrap().len(), 1);
+
+        // 绕过包装直接改底层存储：读取仍命中缓存快照
+        let mut short = Position::new("main", Exchange::Binance, "ETH/USDT", Side::Sell);
+        short.quantity = dec!(1);
+        inner.upsert_position(&short).await?;
+        assert_eq!(store.find_positions_by_account("main").await?.len(), 1);
+
+        let found = store.find_asset("main", "BINANCE", "USDT").await?.unwrap();
+        assert_eq!(found.free, dec!(1000.5));
+        assert!(store.find_asset("main", "BINANCE", "BTC").await?.is_none());
+
+        store.clear_positions("main").await?;
+        assert!(store.find_positions_by_account("main").await?.is_empty());
+
+        cache.invalidate_account("main").await?;
+        assert!(cache.assets("main").await?.is_none());
+        assert_eq!(store.find_assets_by_account("main").await?.len(), 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+//! 集成测试共用的外部服务连接
+//!
+//! 依赖真实服务的用例标记为 `#[ignore]`，配置好对应环境变量后用
+//! `cargo test -p quant-storage -- --ignored` 运行。
+#![allow(dead_code)]
+
+use quant_storage::redis::RedisService;
+use quant_storage::repository::common::StorageConfig;
+use quant_storage::store::timescale;
+use quant_storage::store::TimescaleMarketStore;
+
+/// 需要 Redis (REDIS_URL)
+pub fn redis() -> RedisService {
+    let url = StorageConfig::from_env()
+        .redis_url
+        .expect("REDIS_URL must be set to run Redis integration tests");
+    RedisService::new(&url).expect("Failed to initialize RedisService")
+}
+
+/// 需要 TimescaleDB (TIMESCALE_URL)，连接后执行迁移
+pub async fn timescale() -> TimescaleMarketStore {
+    let config = StorageConfig::from_env();
+    assert!(
+        config.timescale_url.is_some(),
+        "TIMESCALE_URL must be set to run TimescaleDB integration tests"
+    );
+    let pool = timescale::connect(&config)
+        .await
+        .expect("Failed to connect TimescaleDB");
+    timescale::run_migrations(&pool)
+        .await
+        .expect("Failed to run TimescaleDB migrations");
+    TimescaleMarketStore::new(pool)
+}
@@ -1,13 +1,20 @@
 #[cfg(test)]
 mod common_repo_test {
     use quant_storage::repository::common;
-    use sqlx::Row;
+    use sqlx::{MySqlPool, Row};
     use tokio;
     use tracing::info;
 
+    async fn pool() -> MySqlPool {
+        common::connect_from_env()
+            .await
+            .expect("Failed to connect to test database")
+    }
+
     #[tokio::test]
     async fn test_dql() {
-        let result = common::dql("select count(1) as count from `asset`").await;
+        let pool = pool().await;
+        let result = common::dql(&pool, "select count(1) as count from `asset`").await;
         assert!(result.is_ok());
         let vec = result.unwrap();
         assert_eq!(vec.len(), 1);
@@ -17,9 +24,11 @@ mod common_repo_test {
 
     #[tokio::test]
     async fn test_dml() {
-        let result = common::dml("INSERT INTO photon_db.asset ( uuid, account_name, exchange, currency, free, frozen, borrowed, gmt_create, gmt_modified) VALUES ('a56baf7e-1241-4591-ac20-a22cf928009c', 'test_asset_480339bc-9dfd-4d27-80a0-6217b2c23abc', 'BINANCE', 'USDT', 1000.50000000, 100.00000000, 0.00000000, '2025-12-29 17:57:51.901', '2025-12-29 17:57:51.901');").await.expect("insert asset failed");
+        let pool = pool().await;
+        let result = common::dml(&pool, "INSERT INTO photon_db.asset ( uuid, account_name, exchange, currency, free, frozen, borrowed, gmt_create, gmt_modified) VALUES ('a56baf7e-1241-4591-ac20-a22cf928009c', 'test_asset_480339bc-9dfd-4d27-80a0-6217b2c23abc', 'BINANCE', 'USDT', 1000.50000000, 100.00000000, 0.00000000, '2025-12-29 17:57:51.901', '2025-12-29 17:57:51.901');").await.expect("insert asset failed");
         assert_eq!(result, 1);
         common::dml(
+            &pool,
             "DELETE FROM photon_db.asset WHERE uuid = 'a56baf7e-1241-4591-ac20-a22cf928009c'",
         )
         .await
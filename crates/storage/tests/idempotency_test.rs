@@ -0,0 +1,81 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::common::redis;
+    use anyhow::Result;
+    use quant_storage::idempotency::{Idempotency, IdempotencyStore};
+    use serde::{Deserialize, Serialize};
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    /// 每个用例使用独立前缀
+    fn prefix() -> String {
+        format!("photon-test:{}", Uuid::new_v4())
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Submitted {
+        exchange_order_id: String,
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis (REDIS_URL)"]
+    async fn test_idempotency_lifecycle() -> Result<()> {
+        let redis = redis();
+        let store: IdempotencyStore<Submitted> =
+            IdempotencyStore::new(redis, Duration::from_secs(60)).with_prefix(prefix());
+
+        let Idempotency::Started(claim) = store.begin("order-1").await? else {
+            panic!("First begin must start");
+        };
+        assert_eq!(store.begin("order-1").await?, Idempotency::InFlight);
+
+        let outcome = Submitted {
+            exchange_order_id: "B-42".to_string(),
+        };
+        assert!(store.complete(&claim, &outcome).await?);
+        assert_eq!(
+            store.begin("order-1").await?,
+            Idempotency::Completed(outcome.clone())
+        );
+        // 已完成的记录不能被改写
+        assert!(!store.complete(&claim, &outcome).await?);
+        assert!(!store.abandon(&claim).await?);
+
+        // 放弃占位后可以重新开始
+        let Idempotency::Started(claim) = store.begin("order-2").await? else {
+            panic!("First begin must start");
+        };
+        assert!(store.abandon(&claim).await?);
+        assert!(matches!(
+            store.begin("order-2").await?,
+            Idempotency::Started(_)
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis (REDIS_URL)"]
+    async fn test_idempotency_pending_expires() -> Result<()> {
+        let redis = redis();
+        let store: IdempotencyStore<Submitted> =
+            IdempotencyStore::new(redis, Duration::from_secs(60))
+                .with_prefix(prefix())
+                .with_pending_ttl(Duration::from_millis(100));
+
+        let Idempotency::Started(stale) = store.begin("order-1").await? else {
+            panic!("First begin must start");
+        };
+        // 占用者崩溃：占位过期后别人可以接手，旧凭证失效
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let Idempotency::Started(_) = store.begin("order-1").await? else {
+            panic!("Expired claim must be reusable");
+        };
+        let outcome = Submitted {
+            exchange_order_id: "late".to_string(),
+        };
+        assert!(!store.complete(&stale, &outcome).await?);
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use chrono::NaiveDate;
+    use quant_core::enums::Exchange;
+    use quant_storage::import::{
+        parse_file, parse_trade_date, Checkpoint, ColumnMapping, ExchangeMap, FileProgress,
+        ImportOptions, SourceFormat,
+    };
+    use rust_decimal_macros::dec;
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    // =========================================================================
+    // 1. 辅助函数
+    // =========================================================================
+
+    /// 在临时目录写入一个文件，返回路径
+    fn temp_file(name: &str, content: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("photon-import-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    // =========================================================================
+    // 2. 测试用例
+    // =========================================================================
+
+    #[test]
+    fn test_parse_default_layout_with_rejects() -> Result<()> {
+        let path = temp_file(
+            "us.csv",
+            "\u{feff}exchange,symbol,open,high,low,close,amount,volume,bob,eob,type\n\
+             XNAS,AAL,54.28,54.6,53.07,53.91,579582022.1,10756705,2015-01-02 00:00:00-05:00,2015-01-02 00:00:00-05:00,21\n\
+             XNYS,IBM,abc,1,1,1,1,1,2015-01-02 00:00:00-05:00,2015-01-02 00:00:00-05:00,21\n\
+             XNYS,IBM,10,9,8,9,1,1,2015-01-02 00:00:00-05:00,2015-01-02 00:00:00-05:00,21\n",
+        );
+
+        let parsed = parse_file(&path, SourceFormat::Csv, &ImportOptions::default())?;
+        assert_eq!(parsed.records, 3);
+        assert_eq!(parsed.bars.len(), 1);
+
+        let (index, bar) = &parsed.bars[0];
+        assert_eq!(*index, 0);
+        assert_eq!(bar.exchange, Exchange::Nasdaq);
+        assert_eq!(bar.symbol.to_string(), "AAL/USD");
+        assert_eq!(bar.start_time, date(2015, 1, 2));
+        assert_eq!(bar.amount, Some(dec!(579582022.1)));
+
+        // 非数字价格 + high < open 的行都被拒绝，且行号指向源文件
+        let lines: Vec<usize> = parsed.rejected.iter().map(|(_, r)| r.line).collect();
+        assert_eq!(lines, vec![3, 4]);
+        assert!(parsed.rejected[1].1.reason.contains("Inconsistent OHLC"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_exchange_is_hard_error() {
+        let path = temp_file(
+            "bad.csv",
+            "exchange,symbol,open,high,low,close,amount,volume,bob,type\n\
+             XLON,VOD,1,1,1,1,1,1,2020-01-02,21\n",
+        );
+        let err = parse_file(&path, SourceFormat::Csv, &ImportOptions::default()).unwrap_err();
+        assert!(format!("{:#}", err).contains("Unknown exchange code 'XLON'"));
+
+        let mut exchanges = ExchangeMap::default();
+        exchanges.add_spec("XLON=NYSE").unwrap();
+        assert_eq!(exchanges.resolve("xlon").unwrap(), Exchange::Nyse);
+        assert_eq!(exchanges.resolve("binance").unwrap(), Exchange::Binance);
+        assert!(exchanges.add_spec("XLON=LSE").is_err());
+    }
+
+    #[test]
+    fn test_custom_column_mapping_and_defaults() -> Result<()> {
+        // 无 exchange / symbol / type 列: 交易所取默认值，标的取文件名
+        let path = temp_file(
+            "BTC.csv",
+            "Date,Open,High,Low,Close,Vol\n\
+             1704067200000,42000,43000,41000,42500,12.5\n",
+        );
+        let mut columns = ColumnMapping::default();
+        for spec in [
+            "exchange=",
+            "symbol=",
+            "type=",
+            "amount=",
+            "time=Date",
+            "open=Open",
+            "high=High",
+            "low=Low",
+            "close=Close",
+            "volume=Vol",
+        ] {
+            columns.set(spec)?;
+        }
+        assert!(columns.set("open=").is_err());
+        assert!(columns.set("foo=bar").is_err());
+
+        let options = ImportOptions {
+            quote: "USDT".to_string(),
+            columns,
+            default_exchange: Some(Exchange::Binance),
+            ..Default::default()
+        };
+        let parsed = parse_file(&path, SourceFormat::Csv, &options)?;
+        assert!(parsed.rejected.is_empty());
+        let bar = &parsed.bars[0].1;
+        assert_eq!(bar.exchange, Exchange::Binance);
+        assert_eq!(bar.symbol.to_string(), "BTC/USDT");
+        assert_eq!(bar.trade_type, 21);
+        assert_eq!(bar.start_time, date(2024, 1, 1));
+
+        // 缺少默认交易所属于配置错误
+        let no_exchange = ImportOptions {
+            default_exchange: None,
+            ..options
+        };
+        assert!(parse_file(&path, SourceFormat::Csv, &no_exchange).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_timezone_conversion() -> Result<()> {
+        let ny: chrono_tz::Tz = "America/New_York".parse().unwrap();
+        let shanghai: chrono_tz::Tz = "Asia/Shanghai".parse().unwrap();
+
+        // 偏移时间: 未指定时区取原始本地日期，指定后换算
+        let raw = "2015-01-02 00:00:00-05:00";
+        assert_eq!(parse_trade_date(raw, None)?, date(2015, 1, 2));
+        assert_eq!(parse_trade_date(raw, Some(ny))?, date(2015, 1, 2));
+        assert_eq!(parse_trade_date(raw, Some(shanghai))?, date(2015, 1, 2));
+        assert_eq!(
+            parse_trade_date("2015-01-02T03:00:00Z", Some(ny))?,
+            date(2015, 1, 1)
+        );
+
+        // 时间戳按 UTC 解释
+        assert_eq!(parse_trade_date("1704067200", None)?, date(2024, 1, 1));
+        assert_eq!(
+            parse_trade_date("1704067200", Some(ny))?,
+            date(2023, 12, 31)
+        );
+
+        // 无时区时间视为交易所本地时间
+        assert_eq!(
+            parse_trade_date("2024-03-10 23:30:00", Some(shanghai))?,
+            date(2024, 3, 10)
+        );
+        assert_eq!(parse_trade_date("20240310", None)?, date(2024, 3, 10));
+        assert!(parse_trade_date("10/03/2024", None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_checkpoint_round_trip() -> Result<()> {
+        let path = temp_file("checkpoint.json", "");
+        std::fs::remove_file(&path)?;
+        let data = PathBuf::from("/data/AAL.csv");
+
+        let mut checkpoint = Checkpoint::load(&path)?;
+        assert!(checkpoint.progress(&data, 100).is_none());
+        checkpoint.record(
+            &data,
+            FileProgress {
+                len: 100,
+                records_done: 42,
+                completed: false,
+            },
+        )?;
+
+        let reloaded = Checkpoint::load(&path)?;
+        assert_eq!(reloaded.progress(&data, 100).unwrap().records_done, 42);
+        // 文件大小变化后进度作废
+        assert!(reloaded.progress(&data, 101).is_none());
+        Ok(())
+    }
+}
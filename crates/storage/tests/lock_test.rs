@@ -0,0 +1,89 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::common::redis;
+    use anyhow::Result;
+    use quant_storage::lock::{DistributedLock, LockOptions};
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    fn lock_key() -> String {
+        format!("photon-test:lock:{}", Uuid::new_v4())
+    }
+
+    #[test]
+    fn test_lock_options() {
+        let options = LockOptions::with_ttl(Duration::from_secs(9)).wait(Duration::from_secs(1));
+        assert_eq!(options.renew_interval, Duration::from_secs(3));
+        assert_eq!(options.wait_timeout, Duration::from_secs(1));
+        assert_eq!(LockOptions::default().ttl, Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis (REDIS_URL)"]
+    async fn test_exclusive_and_fencing_tokens() -> Result<()> {
+        let redis = redis();
+        let key = lock_key();
+        let options = LockOptions::default();
+
+        let first = DistributedLock::try_acquire(&redis, &key, &options)
+            .await?
+            .expect("first acquire");
+        assert!(first.is_current().await?);
+        assert!(DistributedLock::try_acquire(&redis, &key, &options)
+            .await?
+            .is_none());
+        let first_token = first.fencing_token();
+        assert!(first.release().await?);
+
+        let second = DistributedLock::acquire(&redis, &key, &options)
+            .await?
+            .expect("second acquire");
+        assert!(second.fencing_token() > first_token);
+        drop(second);
+
+        // Drop 在后台释放
+        let third =
+            DistributedLock::acquire(&redis, &key, &options.clone().wait(Duration::from_secs(2)))
+                .await?
+                .expect("released on drop");
+        assert_eq!(third.fencing_token(), first_token + 2);
+        third.release().await?;
+        redis.delete(&format!("{}:fence", key)).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis (REDIS_URL)"]
+    async fn test_watchdog_renews_and_detects_loss() -> Result<()> {
+        let redis = redis();
+        let key = lock_key();
+        let options = LockOptions::with_ttl(Duration::from_millis(300));
+
+        let lock = DistributedLock::try_acquire(&redis, &key, &options)
+            .await?
+            .expect("acquire");
+        // 超过 TTL 仍然持有 (看门狗续期)
+        tokio::time::sleep(Duration::from_millis(900)).await;
+        assert!(lock.is_held());
+        assert!(DistributedLock::try_acquire(&redis, &key, &options)
+            .await?
+            .is_none());
+
+        // 锁被外部删除并由别人持有 -> 看门狗发现租约丢失
+        redis.delete(&key).await?;
+        let usurper = DistributedLock::try_acquire(&redis, &key, &options)
+            .await?
+            .expect("usurp");
+        tokio::time::timeout(Duration::from_secs(2), lock.lost()).await?;
+        assert!(!lock.is_held());
+        assert!(!lock.is_current().await?);
+        assert!(usurper.fencing_token() > lock.fencing_token());
+
+        assert!(!lock.release().await?, "Stale holder must not delete");
+        usurper.release().await?;
+        redis.delete(&format!("{}:fence", key)).await?;
+        Ok(())
+    }
+}
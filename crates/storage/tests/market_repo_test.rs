@@ -4,6 +4,7 @@ mod tests {
     use quant_core::enums::{BarPeriod, Exchange};
     use quant_core::market::MarketBar;
     use quant_core::primitive::{Price, Quantity};
+    use quant_storage::repository::common::BatchOptions;
     use quant_storage::repository::market_repo;
 
     use chrono::{Duration, NaiveDate};
@@ -15,7 +16,9 @@ mod tests {
     // =========================================================================
 
     async fn get_test_repo() -> market_repo::MarketDataRepository {
-        let pool = quant_storage::repository::common::get_real_pool().await;
+        let pool = quant_storage::repository::common::connect_from_env()
+            .await
+            .expect("Failed to connect to test database");
         market_repo::MarketDataRepository::new(pool.clone())
     }
 
@@ -228,4 +231,81 @@ mod tests {
 
         Ok(())
     }
+
+    /// 测试批量写入与逐行写入结果一致
+    #[tokio::test]
+    async fn test_save_all_matches_save() -> Result<()> {
+        let repo = get_test_repo().await;
+        let suffix = &Uuid::new_v4().simple().to_string()[..8];
+        let single_symbol = format!("S{}/USDT", suffix);
+        let bulk_symbol = format!("B{}/USDT", suffix);
+        let start = NaiveDate::from_ymd_opt(2023, 3, 1).unwrap();
+
+        let make = |symbol: &str| -> Vec<MarketBar> {
+            (0..5)
+                .map(|i| {
+                    let mut bar = mock_bar(
+                        Exchange::Binance,
+                        symbol,
+                        BarPeriod::D1,
+                        start + Duration::days(i),
+                    );
+                    bar.close = Price(dec!(50000) + rust_decimal::Decimal::from(i));
+                    bar.amount = Some(dec!(1234.5));
+                    bar
+                })
+                .collect()
+        };
+
+        // 1. 逐行写入 vs 批量写入 (批大小 2 -> 3 批，放在同一事务)
+        let mut single_rows = 0;
+        for bar in make(&single_symbol) {
+            single_rows += repo.save(&bar).await?;
+        }
+        let options = BatchOptions::new(2).transactional();
+        let report = repo.save_all(&make(&bulk_symbol), &options).await?;
+        assert_eq!(report.batches, vec![2, 2, 1]);
+        assert_eq!(report.total(), single_rows);
+
+        // 2. 回读比较
+        let end = start + Duration::days(10);
+        let single = repo
+            .find_bars_by_range("BINANCE", &single_symbol, BarPeriod::D1, 21, start, end)
+            .await?;
+        let bulk = repo
+            .find_bars_by_range("BINANCE", &bulk_symbol, BarPeriod::D1, 21, start, end)
+            .await?;
+        assert_eq!(single.len(), 5);
+        assert_eq!(bulk.len(), 5);
+        for (a, b) in single.iter().zip(&bulk) {
+            assert_eq!(a.start_time, b.start_time);
+            assert_eq!(a.end_time, b.end_time);
+            assert_eq!(a.open.0, b.open.0);
+            assert_eq!(a.high.0, b.high.0);
+            assert_eq!(a.low.0, b.low.0);
+            assert_eq!(a.close.0, b.close.0);
+            assert_eq!(a.volume.0, b.volume.0);
+            assert_eq!(a.amount, b.amount);
+        }
+
+        // 3. 再次批量写入走 ON DUPLICATE KEY UPDATE
+        let mut changed = make(&bulk_symbol);
+        for bar in &mut changed {
+            bar.close = Price(dec!(1));
+            bar.low = Price(dec!(1));
+        }
+        repo.save_all(&changed, &BatchOptions::default()).await?;
+        let updated = repo
+            .find_bars_by_range("BINANCE", &bulk_symbol, BarPeriod::D1, 21, start, end)
+            .await?;
+        assert_eq!(updated.len(), 5);
+        assert!(updated.iter().all(|b| b.close.0 == dec!(1)));
+
+        // 4. 超过占位符上限的批大小直接报错
+        assert!(repo
+            .save_all(&changed, &BatchOptions::new(10_000))
+            .await
+            .is_err());
+        Ok(())
+    }
 }
@@ -0,0 +1,535 @@
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use chrono::{Duration, NaiveDate, Utc};
+    use quant_core::account::{Asset, Position};
+    use quant_core::enums::{BarPeriod, Exchange, OrderStatus, Side, StatusActor, StrategyStatus};
+    use quant_core::market::MarketBar;
+    use quant_core::oms::{Fill, Order};
+    use quant_core::primitive::{Price, Quantity};
+    use quant_core::strategy::{Signal, StateMigration, Strategy};
+    use quant_storage::lock::{Fence, FencedOut, Fencing};
+    use quant_storage::repository::strategy_repo::StateRetention;
+    use quant_storage::store::{
+        AccountStore, FillStore, MarketDataStore, MemoryAccountStore, MemoryFillStore,
+        MemoryMarketStore, MemoryOrderStore, MemoryStrategyStore, OrderStore, StrategyStore,
+    };
+    use rust_decimal_macros::dec;
+    use serde_json::{json, Value};
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    // =========================================================================
+    // 辅助函数
+    // =========================================================================
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn bar(symbol: &str, day: NaiveDate, close: rust_decimal::Decimal) -> MarketBar {
+        MarketBar::new(
+            Exchange::Binance,
+            symbol,
+            BarPeriod::D1,
+            21,
+            Price(close),
+            Price(close),
+            Price(close),
+            Price(close),
+            Quantity(dec!(10)),
+            day,
+        )
+        .expect("Failed to create bar")
+    }
+
+    // =========================================================================
+    // 1. K 线
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_market_store_upsert_and_query() -> Result<()> {
+        let store: Arc<dyn MarketDataStore> = Arc::new(MemoryMarketStore::new());
+        let start = date(2024, 1, 1);
+        let bars: Vec<MarketBar> = (0..5)
+            .map(|i| {
+                bar(
+                    "BTC/USDT",
+                    start + Duration::days(i),
+                    dec!(100) + rust_decimal::Decimal::from(i),
+                )
+            })
+            .collect();
+
+        let counts = store.save_batch(&bars).await?;
+        assert_eq!((counts.inserted, counts.updated), (5, 0));
+
+        // 同一唯一键再次写入 = 覆盖
+        assert_eq!(store.save(&bar("BTC/USDT", start, dec!(99))).await?, 2);
+        assert_eq!(store.save(&bar("ETH/USDT", start, dec!(5))).await?, 1);
+
+        let range = store
+            .find_bars_by_range(
+                "BINANCE",
+                "BTC/USDT",
+                BarPeriod::D1,
+                21,
+                start,
+                start + Duration::days(2),
+            )
+            .await?;
+        assert_eq!(range.len(), 3);
+        assert_eq!(range[0].close.0, dec!(99));
+        assert!(range.windows(2).all(|w| w[0].start_time < w[1].start_time));
+
+        let recent = store
+            .find_recent_bars("BINANCE", "BTC/USDT", BarPeriod::D1, 21, 2)
+            .await?;
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].start_time, start + Duration::days(4));
+
+        let series = store.find_series(None, None).await?;
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[0].symbol, "BTC/USDT");
+        assert_eq!(store.find_series_bars(&series[1]).await?.len(), 1);
+        assert!(store.find_series(Some("NASDAQ"), None).await?.is_empty());
+        Ok(())
+    }
+
+    // =========================================================================
+    // 2. 订单
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_order_store_lifecycle() -> Result<()> {
+        let store: Arc<dyn OrderStore> = Arc::new(MemoryOrderStore::new());
+        let strategy_uuid = Uuid::new_v4();
+        let order = Order::new_limit(
+            "BTC/USDT",
+            Exchange::Binance,
+            Some(strategy_uuid.to_string()),
+            Side::Buy,
+            Price(dec!(50000)),
+            Quantity(dec!(0.1)),
+        );
+        let order_uuid = Uuid::from_str(&order.uuid)?;
+
+        assert_eq!(store.insert(&order).await?, 1);
+        assert!(
+            store.insert(&order).await.is_err(),
+            "Duplicate uuid must fail"
+        );
+        assert_eq!(store.find_open_orders().await?.len(), 1);
+
+        store
+            .update_status(
+                order_uuid,
+                OrderStatus::New,
+                Some("ex-1".into()),
+                dec!(0),
+                None,
+                None,
+            )
+            .await?;
+        store
+            .update_status(
+                order_uuid,
+                OrderStatus::Filled,
+                None,
+                dec!(0.1),
+                Some(dec!(49990)),
+                Some(dec!(0.5)),
+            )
+            .await?;
+
+        let found = store.find_by_uuid(order_uuid).await?.expect("order exists");
+        assert_eq!(found.status, OrderStatus::Filled);
+        assert_eq!(found.exchange_order_id.as_deref(), Some("ex-1"));
+        assert_eq!(found.filled_quantity.0, dec!(0.1));
+        assert_eq!(found.average_price.map(|p| p.0), Some(dec!(49990)));
+        assert!(store.find_open_orders().await?.is_empty());
+
+        assert_eq!(store.find_by_strategy(strategy_uuid).await?.len(), 1);
+        let now = Utc::now();
+        let in_range = store
+            .find_by_range(now - Duration::minutes(1), now + Duration::minutes(1))
+            .await?;
+        assert_eq!(in_range.len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fill_store_queries() -> Result<()> {
+        let store: Arc<dyn FillStore> = Arc::new(MemoryFillStore::new());
+        let order = Order::new_market(
+            "BTC/USDT",
+            Exchange::Binance,
+            None,
+            Side::Buy,
+            Quantity(dec!(1)),
+        );
+        let other = Order::new_market(
+            "ETH/USDT",
+            Exchange::Binance,
+            None,
+            Side::Sell,
+            Quantity(dec!(2)),
+        );
+        let t0 = Utc::now();
+        let late = Fill::new(
+            &order,
+            Price(dec!(100)),
+            Quantity(dec!(0.6)),
+            dec!(0),
+            t0 + Duration::minutes(2),
+        );
+        let early = Fill::new(&order, Price(dec!(99)), Quantity(dec!(0.4)), dec!(0), t0);
+        let unrelated = Fill::new(&other, Price(dec!(10)), Quantity(dec!(2)), dec!(0), t0);
+        for fill in [&late, &early, &unrelated] {
+            assert_eq!(store.insert(fill).await?, 1);
+        }
+        assert!(
+            store.insert(&late).await.is_err(),
+            "Duplicate uuid must fail"
+        );
+
+        let by_order = store.find_by_order(Uuid::from_str(&order.uuid)?).await?;
+        assert_eq!(
+            by_order.iter().map(|f| f.uuid.as_str()).collect::<Vec<_>>(),
+            vec![early.uuid.as_str(), late.uuid.as_str()]
+        );
+        assert_eq!(
+            store
+                .find_by_orders(&[order.uuid.clone(), other.uuid.clone()])
+                .await?
+                .len(),
+            3
+        );
+        assert_eq!(
+            store
+                .find_by_range(t0, t0 + Duration::minutes(1))
+                .await?
+                .len(),
+            2
+        );
+        Ok(())
+    }
+
+    // =========================================================================
+    // 3. 账户
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_account_store_upsert() -> Result<()> {
+        let store: Arc<dyn AccountStore> = Arc::new(MemoryAccountStore::new());
+
+        let mut asset = Asset::new("main", Exchange::Binance, "USDT");
+        asset.free = dec!(1000);
+        assert_eq!(store.upsert_asset(&asset).await?, 1);
+
+        // 新 uuid 但唯一键相同 -> 覆盖
+        let mut again = Asset::new("main", Exchange::Binance, "USDT");
+        again.free = dec!(800);
+        again.frozen = dec!(200);
+        assert_eq!(store.upsert_asset(&again).await?, 2);
+
+        let found = store
+            .find_asset("main", "BINANCE", "USDT")
+            .await?
+            .expect("asset exists");
+        assert_eq!(found.uuid, asset.uuid);
+        assert_eq!((found.free, found.frozen), (dec!(800), dec!(200)));
+        assert_eq!(store.find_assets_by_account("main").await?.len(), 1);
+
+        let mut long = Position::new("main", Exchange::Binance, "BTC/USDT", Side::Buy);
+        long.quantity = dec!(1);
+        store.upsert_position(&long).await?;
+        store
+            .upsert_position(&Position::new(
+                "main",
+                Exchange::Binance,
+                "BTC/USDT",
+                Side::Sell,
+            ))
+            .await?;
+        assert_eq!(store.find_positions_by_account("main").await?.len(), 2);
+
+        store.clear_positions("main").await?;
+        assert!(store.find_positions_by_account("main").await?.is_empty());
+        Ok(())
+    }
+
+    // =========================================================================
+    // 4. 策略
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_strategy_status_transitions_and_audit() -> Result<()> {
+        let store = MemoryStrategyStore::new();
+        let strategy = Strategy::new("grid", "GridStrategy", json!({}));
+        let uuid = Uuid::from_str(&strategy.uuid)?;
+        store.create(&strategy).await?;
+
+        let step = |status, actor, reason: &'static str| {
+            let store = &store;
+            async move { store.update_status(uuid, status, actor, reason).await }
+        };
+        step(StrategyStatus::Initializing, StatusActor::System, "boot").await?;
+        step(StrategyStatus::Running, StatusActor::User, "go live").await?;
+        step(StrategyStatus::Paused, StatusActor::Risk, "drawdown limit").await?;
+        step(StrategyStatus::Stopped, StatusActor::Agent, "regime change").await?;
+
+        // 已停止的策略必须重新初始化
+        let err = step(StrategyStatus::Running, StatusActor::User, "")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("STOPPED -> RUNNING"));
+        assert!(store
+            .update_status(
+                Uuid::new_v4(),
+                StrategyStatus::Stopped,
+                StatusActor::User,
+                ""
+            )
+            .await
+            .is_err());
+
+        let current = store.find_by_uuid(uuid).await?.unwrap();
+        assert_eq!(current.status, StrategyStatus::Stopped);
+        assert_eq!(current.reason, "regime change");
+
+        let history = store.find_status_history(uuid, 10).await?;
+        let trail: Vec<_> = history
+            .iter()
+            .map(|c| (c.from_status, c.to_status, c.actor))
+            .collect();
+        assert_eq!(
+            trail,
+            vec![
+                (
+                    StrategyStatus::Paused,
+                    StrategyStatus::Stopped,
+                    StatusActor::Agent
+                ),
+                (
+                    StrategyStatus::Running,
+                    StrategyStatus::Paused,
+                    StatusActor::Risk
+                ),
+                (
+                    StrategyStatus::Initializing,
+                    StrategyStatus::Running,
+                    StatusActor::User
+                ),
+                (
+                    StrategyStatus::Created,
+                    StrategyStatus::Initializing,
+                    StatusActor::System
+                ),
+            ]
+        );
+        assert_eq!(history[1].reason, "drawdown limit");
+        assert_eq!(store.find_status_history(uuid, 1).await?.len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_strategy_store_state_and_signals() -> Result<()> {
+        let store: Arc<dyn StrategyStore> = Arc::new(MemoryStrategyStore::new());
+        let strategy = Strategy::new("grid", "GridStrategy", json!({"grid_num": 10}));
+        let uuid = Uuid::from_str(&strategy.uuid)?;
+
+        store.create(&strategy).await?;
+        assert!(store.create(&strategy).await.is_err());
+        assert!(store.find_active_strategies().await?.is_empty());
+
+        store
+            .update_status(uuid, StrategyStatus::Initializing, StatusActor::System, "")
+            .await?;
+        store
+            .update_status(uuid, StrategyStatus::Running, StatusActor::System, "")
+            .await?;
+        assert_eq!(store.find_active_strategies().await?.len(), 1);
+        assert_eq!(
+            store.find_by_uuid(uuid).await?.unwrap().status,
+            StrategyStatus::Running
+        );
+
+        assert!(store.load_state(uuid).await?.is_none());
+        assert_eq!(store.save_state(uuid, 1, &json!({"step": 1})).await?, 1);
+        assert_eq!(store.save_state(uuid, 1, &json!({"step": 2})).await?, 2);
+        let current = store.load_state(uuid).await?.unwrap();
+        assert_eq!(current.state_data, json!({"step": 2}));
+        assert_eq!(current.version, 2);
+
+        for i in 0..3 {
+            let signal = Signal::new_market(
+                strategy.uuid.clone(),
+                "BTC/USDT",
+                Side::Buy,
+                Quantity(dec!(1)),
+                format!("signal {}", i),
+            );
+            store.save_signal(&signal).await?;
+        }
+        let signals = store.find_signals_by_strategy(uuid, 2).await?;
+        assert_eq!(signals.len(), 2);
+        assert_eq!(signals[0].reason, "signal 2");
+        Ok(())
+    }
+
+    /// v1: {"qty": 1}  ->  v2: {"position": {"qty": 1}}
+    struct NestPosition;
+
+    impl StateMigration for NestPosition {
+        fn current_version(&self) -> i32 {
+            2
+        }
+
+        fn upgrade(&self, from_version: i32, state: Value) -> Result<Value> {
+            match from_version {
+                1 => Ok(json!({ "position": state })),
+                v => anyhow::bail!("Unknown schema {}", v),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_strategy_state_versions_and_rollback() -> Result<()> {
+        let store = MemoryStrategyStore::new().with_retention(StateRetention {
+            keep_versions: 3,
+            max_age_days: None,
+        });
+        let uuid = Uuid::new_v4();
+        for step in 1..=4 {
+            store.save_state(uuid, 1, &json!({ "qty": step })).await?;
+        }
+
+        // 只保留最近 3 个版本
+        let history = store.list_state_versions(uuid, 10).await?;
+        let versions: Vec<i64> = history.iter().map(|s| s.version).collect();
+        assert_eq!(versions, vec![4, 3, 2]);
+        assert!(store.load_state_version(uuid, 1).await?.is_none());
+
+        // 回滚 = 复制为新版本
+        assert_eq!(store.rollback_state(uuid, 2).await?, 5);
+        let current = store.load_state(uuid).await?.unwrap();
+        assert_eq!(
+            (current.version, current.state_data),
+            (5, json!({"qty": 2}))
+        );
+        assert!(store.rollback_state(uuid, 1).await.is_err());
+
+        // 加载时升级旧结构
+        let migrated = store
+            .load_state_migrated(uuid, &NestPosition)
+            .await?
+            .unwrap();
+        assert_eq!(migrated.schema_version, 2);
+        assert_eq!(migrated.state_data, json!({"position": {"qty": 2}}));
+
+        // 比代码新的结构拒绝加载
+        store.save_state(uuid, 3, &json!({})).await?;
+        assert!(store
+            .load_state_migrated(uuid, &NestPosition)
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    // =========================================================================
+    // 5. Fencing
+    // =========================================================================
+
+    fn fence(token: u64) -> Fence {
+        Fence {
+            key: "photon-test:leader:fence".to_string(),
+            token,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stale_fencing_token_is_refused() -> Result<()> {
+        let fencing = Fencing::new();
+        let orders = MemoryOrderStore::new().with_fencing(fencing.clone());
+        let strategies = MemoryStrategyStore::new().with_fencing(fencing.clone());
+        let order = Order::new_limit(
+            "BTC/USDT",
+            Exchange::Binance,
+            None,
+            Side::Buy,
+            Price(dec!(50000)),
+            Quantity(dec!(0.1)),
+        );
+        let order_uuid = Uuid::from_str(&order.uuid)?;
+        orders.insert(&order).await?;
+        let strategy = Strategy::new("grid", "GridStrategy", json!({}));
+        let strategy_uuid = Uuid::from_str(&strategy.uuid)?;
+        strategies.create(&strategy).await?;
+
+        // 新任期 (token 2) 先写入
+        fencing.set(fence(2));
+        orders
+            .update_status(order_uuid, OrderStatus::New, None, dec!(0), None, None)
+            .await?;
+        strategies
+            .update_status(
+                strategy_uuid,
+                StrategyStatus::Initializing,
+                StatusActor::System,
+                "boot",
+            )
+            .await?;
+
+        // 旧任期 (token 1) 的迟到写入被拒绝，数据保持不变
+        fencing.set(fence(1));
+        let err = orders
+            .update_status(order_uuid, OrderStatus::Canceled, None, dec!(0), None, None)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<FencedOut>(),
+            Some(&FencedOut {
+                resource: format!("order {}", order_uuid),
+                token: 1,
+            })
+        );
+        assert_eq!(
+            orders.find_by_uuid(order_uuid).await?.unwrap().status,
+            OrderStatus::New
+        );
+        let err = strategies
+            .update_status(
+                strategy_uuid,
+                StrategyStatus::Stopped,
+                StatusActor::System,
+                "engine shutdown",
+            )
+            .await
+            .unwrap_err();
+        assert!(err.downcast_ref::<FencedOut>().is_some());
+        assert!(strategies
+            .save_state(strategy_uuid, 1, &json!({}))
+            .await
+            .unwrap_err()
+            .is::<FencedOut>());
+        assert!(strategies.load_state(strategy_uuid).await?.is_none());
+        assert_eq!(
+            strategies
+                .find_by_uuid(strategy_uuid)
+                .await?
+                .unwrap()
+                .status,
+            StrategyStatus::Initializing
+        );
+
+        // 同一任期 / 更新的任期照常写入
+        fencing.set(fence(3));
+        strategies.save_state(strategy_uuid, 1, &json!({})).await?;
+        orders
+            .update_status(order_uuid, OrderStatus::Canceled, None, dec!(0), None, None)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+#[cfg(test)]
+mod tests {
+    use quant_storage::migrate::MIGRATOR;
+
+    /// 仓储层用到的全部表
+    const TABLES: &[&str] = &[
+        "market_bar",
+        "market_bar_quality",
+        "market_bar_quarantine",
+        "corporate_action",
+        "order",
+        "fill",
+        "asset",
+        "position",
+        "strategy",
+        "strategy_state",
+        "strategy_state_version",
+        "strategy_status_audit",
+        "signal",
+    ];
+
+    #[test]
+    fn test_migrations_are_ordered() {
+        let versions: Vec<i64> = MIGRATOR.iter().map(|m| m.version).collect();
+        assert!(!versions.is_empty());
+        assert!(versions.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_migrations_create_all_tables() {
+        let sql: String = MIGRATOR.iter().map(|m| m.sql.as_ref()).collect();
+        for table in TABLES {
+            let ddl = format!("CREATE TABLE IF NOT EXISTS `{}`", table);
+            assert!(sql.contains(&ddl), "No migration creates `{}`", table);
+        }
+    }
+}
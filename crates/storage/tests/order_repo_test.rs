@@ -7,14 +7,16 @@ mod tests {
     use quant_core::primitive::{Price, Quantity};
 
     use anyhow::Result;
+    use quant_storage::repository::common::BatchOptions;
     use quant_storage::repository::order_repo;
     use rust_decimal_macros::dec;
     use std::str::FromStr;
     use uuid::Uuid;
 
     async fn get_test_repo() -> order_repo::OrderRepository {
-        // 复用你的 get_db_pool，但它会返回一个新的 Pool 实例
-        let pool = quant_storage::repository::common::get_real_pool().await;
+        let pool = quant_storage::repository::common::connect_from_env()
+            .await
+            .expect("Failed to connect to test database");
         order_repo::OrderRepository::new(pool.clone())
     }
 
@@ -192,4 +194,89 @@ mod tests {
 
         Ok(())
     }
+
+    // =========================================================================
+    // 4. 未终结订单：停机撤单使用
+    // =========================================================================
+    #[tokio::test]
+    async fn test_find_open_orders() -> Result<()> {
+        let repo = get_test_repo().await;
+
+        let open = Order::new_limit(
+            "DOGE/USDT",
+            Exchange::Binance,
+            None,
+            Side::Buy,
+            Price(dec!(0.1)),
+            Quantity(dec!(100.0)),
+        );
+        let closed = Order::new_market(
+            "DOGE/USDT",
+            Exchange::Binance,
+            None,
+            Side::Sell,
+            Quantity(dec!(100.0)),
+        );
+        repo.insert(&open).await?;
+        repo.insert(&closed).await?;
+        repo.update_status(
+            Uuid::from_str(&closed.uuid)?,
+            OrderStatus::Filled,
+            None,
+            dec!(100.0),
+            Some(dec!(0.1)),
+            None,
+        )
+        .await?;
+
+        let orders = repo.find_open_orders().await?;
+        assert!(orders.iter().any(|o| o.uuid == open.uuid));
+        assert!(orders.iter().all(|o| o.uuid != closed.uuid));
+        assert!(orders.iter().all(|o| o.status.is_open()));
+
+        Ok(())
+    }
+
+    // =========================================================================
+    // 5. 批量插入
+    // =========================================================================
+    #[tokio::test]
+    async fn test_insert_all() -> Result<()> {
+        let repo = get_test_repo().await;
+        let strategy_id = Uuid::new_v4().to_string();
+
+        let orders: Vec<Order> = (1..=3)
+            .map(|i| {
+                Order::new_limit(
+                    "ETH/USDT",
+                    Exchange::Binance,
+                    Some(strategy_id.clone()),
+                    Side::Sell,
+                    Price(dec!(3000) + rust_decimal::Decimal::from(i)),
+                    Quantity(dec!(0.1)),
+                )
+            })
+            .collect();
+
+        let report = repo.insert_all(&orders, &BatchOptions::new(2)).await?;
+        assert_eq!(report.batches, vec![2, 1]);
+
+        let found = repo.find_by_strategy(Uuid::from_str(&strategy_id)?).await?;
+        assert_eq!(found.len(), 3);
+        for order in &orders {
+            let saved = found
+                .iter()
+                .find(|o| o.uuid == order.uuid)
+                .expect("Order should be found");
+            assert_eq!(saved.price, order.price);
+            assert_eq!(saved.quantity, order.quantity);
+            assert_eq!(saved.status, order.status);
+            assert_eq!(saved.side, order.side);
+        }
+
+        // 空列表不产生任何批次
+        let empty = repo.insert_all(&[], &BatchOptions::default()).await?;
+        assert!(empty.batches.is_empty());
+        Ok(())
+    }
 }
@@ -0,0 +1,276 @@
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+    use quant_core::enums::{BarPeriod, Exchange};
+    use quant_core::market::{IntradayBar, MarketBar, MarketTick};
+    use quant_core::primitive::{CurrencyPair, Price, Quantity};
+    use quant_storage::store::parquet::{self, ParquetMarketStore};
+    use quant_storage::store::{MarketDataStore, MemoryMarketStore};
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use std::path::PathBuf;
+    use std::str::FromStr;
+    use uuid::Uuid;
+
+    // =========================================================================
+    // 辅助函数
+    // =========================================================================
+
+    /// 每个用例独立的临时目录，用完即删
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            Self(std::env::temp_dir().join(format!("photon-parquet-{}", Uuid::new_v4())))
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn daily_bar(symbol: &str, day: NaiveDate, close: Decimal) -> MarketBar {
+        MarketBar::new(
+            Exchange::Binance,
+            symbol,
+            BarPeriod::D1,
+            21,
+            Price(close),
+            Price(close),
+            Price(close),
+            Price(close),
+            Quantity(dec!(10)),
+            day,
+        )
+        .expect("Failed to create bar")
+    }
+
+    fn m1_bar(start: DateTime<Utc>, close: Decimal) -> IntradayBar {
+        IntradayBar {
+            exchange: Exchange::Binance,
+            symbol: CurrencyPair::from_str("BTC/USDT").unwrap(),
+            bar_period: BarPeriod::M1,
+            trade_type: 21,
+            open: Price(close),
+            high: Price(close + dec!(1)),
+            low: Price(close - dec!(1)),
+            close: Price(close),
+            volume: Quantity(dec!(0.5)),
+            amount: Some(close * dec!(0.5)),
+            start_time: start,
+            end_time: start + BarPeriod::M1.duration(),
+        }
+    }
+
+    // =========================================================================
+    // 1. 日线 (MarketDataStore)
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_daily_upsert_and_query() -> Result<()> {
+        let dir = TempDir::new();
+        let store = ParquetMarketStore::open(&dir.0)?;
+        // 跨年写入，落在两个分区
+        let start = date(2023, 12, 30);
+        let bars: Vec<MarketBar> = (0..5)
+            .map(|i| {
+                daily_bar(
+                    "BTC/USDT",
+                    start + Duration::days(i),
+                    dec!(100) + Decimal::from(i),
+                )
+            })
+            .collect();
+
+        let counts = store.save_batch(&bars).await?;
+        assert_eq!((counts.inserted, counts.updated), (5, 0));
+        assert_eq!(
+            store.save(&daily_bar("BTC/USDT", start, dec!(99))).await?,
+            2
+        );
+        assert_eq!(store.save(&daily_bar("ETH/USDT", start, dec!(5))).await?, 1);
+
+        let range = store
+            .find_bars_by_range(
+                "BINANCE",
+                "BTC/USDT",
+                BarPeriod::D1,
+                21,
+                start,
+                date(2024, 1, 1),
+            )
+            .await?;
+        assert_eq!(range.len(), 3);
+        assert_eq!(range[0].close.0, dec!(99));
+        assert!(range.windows(2).all(|w| w[0].start_time < w[1].start_time));
+
+        let recent = store
+            .find_recent_bars("BINANCE", "BTC/USDT", BarPeriod::D1, 21, 2)
+            .await?;
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].start_time, start + Duration::days(4));
+
+        let series = store.find_series(None, None).await?;
+        assert_eq!(series.len(), 2);
+        assert_eq!(store.find_series_bars(&series[0]).await?.len(), 5);
+        assert_eq!(store.find_series(None, Some("ETH/USDT")).await?.len(), 1);
+        assert!(store.find_series(Some("OKX"), None).await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_missing_series_is_empty() -> Result<()> {
+        let dir = TempDir::new();
+        let store = ParquetMarketStore::open(&dir.0)?;
+        let bars = store
+            .find_bars_by_range(
+                "BINANCE",
+                "BTC/USDT",
+                BarPeriod::D1,
+                21,
+                date(2024, 1, 1),
+                date(2024, 12, 31),
+            )
+            .await?;
+        assert!(bars.is_empty());
+        assert!(store.find_series(None, None).await?.is_empty());
+        Ok(())
+    }
+
+    // =========================================================================
+    // 2. 日内 K 线 / 行组裁剪
+    // =========================================================================
+
+    #[test]
+    fn test_intraday_row_groups_and_decimals() -> Result<()> {
+        let dir = TempDir::new();
+        // 很小的行组，确保范围查询跨越多个行组
+        let store = ParquetMarketStore::open(&dir.0)?.with_row_group_size(16);
+        let start = Utc.with_ymd_and_hms(2024, 1, 31, 23, 0, 0).unwrap();
+        let mut bars: Vec<IntradayBar> = (0..120)
+            .map(|i| {
+                m1_bar(
+                    start + Duration::minutes(i),
+                    dec!(42000.12345678) + Decimal::from(i),
+                )
+            })
+            .collect();
+        // 负数 / 高精度小数也要原样往返
+        bars[0].close = Price(dec!(-0.00000001));
+
+        let counts = store.save_intraday_bars(&bars)?;
+        assert_eq!((counts.inserted, counts.updated), (120, 0));
+
+        let from = start + Duration::minutes(50);
+        let found = store.find_intraday_bars(
+            "BINANCE",
+            "BTC/USDT",
+            BarPeriod::M1,
+            21,
+            from,
+            from + Duration::minutes(20),
+        )?;
+        assert_eq!(found.len(), 20);
+        assert_eq!(found[0], bars[50]);
+        assert_eq!(found[19].start_time, from + Duration::minutes(19));
+
+        let head = store.find_intraday_bars(
+            "BINANCE",
+            "BTC/USDT",
+            BarPeriod::M1,
+            21,
+            start,
+            start + Duration::minutes(1),
+        )?;
+        assert_eq!(head[0].close.0, dec!(-0.00000001));
+        assert_eq!(head[0].amount, bars[0].amount);
+
+        // 覆盖一根后，跨月分区总数不变
+        let counts = store.save_intraday_bars(&bars[60..61])?;
+        assert_eq!((counts.inserted, counts.updated), (0, 1));
+        let all = store.find_intraday_bars(
+            "BINANCE",
+            "BTC/USDT",
+            BarPeriod::M1,
+            21,
+            start,
+            start + Duration::hours(3),
+        )?;
+        assert_eq!(all.len(), 120);
+        Ok(())
+    }
+
+    // =========================================================================
+    // 3. Tick
+    // =========================================================================
+
+    #[test]
+    fn test_ticks_append_and_query() -> Result<()> {
+        let dir = TempDir::new();
+        let store = ParquetMarketStore::open(&dir.0)?;
+        let start = Utc.with_ymd_and_hms(2024, 3, 1, 23, 59, 0).unwrap();
+        let tick = |secs: i64, last: Option<Decimal>| MarketTick {
+            exchange: Exchange::Binance,
+            symbol: CurrencyPair::from_str("BTC/USDT").unwrap(),
+            time: start + Duration::seconds(secs),
+            bid_price: Price(dec!(100)),
+            bid_size: Quantity(dec!(1)),
+            ask_price: Price(dec!(100.5)),
+            ask_size: Quantity(dec!(2)),
+            last_price: last.map(Price),
+        };
+
+        // 乱序、跨天写入
+        assert_eq!(
+            store.save_ticks(&[tick(90, None), tick(10, Some(dec!(100.2)))])?,
+            2
+        );
+        assert_eq!(store.save_ticks(&[tick(30, None)])?, 1);
+
+        let ticks = store.find_ticks("BINANCE", "BTC/USDT", start, start + Duration::minutes(5))?;
+        assert_eq!(ticks.len(), 3);
+        assert!(ticks.windows(2).all(|w| w[0].time < w[1].time));
+        assert_eq!(ticks[0].last_price, Some(Price(dec!(100.2))));
+        assert_eq!(ticks[1].last_price, None);
+
+        let first_day =
+            store.find_ticks("BINANCE", "BTC/USDT", start, start + Duration::seconds(60))?;
+        assert_eq!(first_day.len(), 2);
+        Ok(())
+    }
+
+    // =========================================================================
+    // 4. 导出
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_export_from_store() -> Result<()> {
+        let dir = TempDir::new();
+        let source = MemoryMarketStore::new();
+        let start = date(2024, 1, 1);
+        for symbol in ["BTC/USDT", "ETH/USDT"] {
+            let bars: Vec<MarketBar> = (0..3)
+                .map(|i| daily_bar(symbol, start + Duration::days(i), dec!(10)))
+                .collect();
+            source.save_batch(&bars).await?;
+        }
+
+        let target = ParquetMarketStore::open(&dir.0)?;
+        let summary = parquet::export_bars(&source, &target, None, Some("BTC/USDT")).await?;
+        assert_eq!((summary.series, summary.bars), (1, 3));
+
+        // 重复导出是幂等的
+        parquet::export_bars(&source, &target, None, None).await?;
+        let series = target.find_series(None, None).await?;
+        assert_eq!(series.len(), 2);
+        assert_eq!(target.find_series_bars(&series[0]).await?.len(), 3);
+        Ok(())
+    }
+}
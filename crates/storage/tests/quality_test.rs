@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, NaiveDate};
+    use quant_core::enums::{BarPeriod, Exchange};
+    use quant_core::market::MarketBar;
+    use quant_core::primitive::{Price, Quantity};
+    use quant_storage::quality::{repair_bar, scan_series, AnomalyKind, QualityRules, Severity};
+    use quant_storage::repository::market_repo::BarSeriesKey;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    // =========================================================================
+    // 1. 辅助函数
+    // =========================================================================
+
+    fn key(exchange: Exchange) -> BarSeriesKey {
+        BarSeriesKey {
+            exchange,
+            symbol: "AAPL/USD".to_string(),
+            bar_period: BarPeriod::D1,
+            trade_type: 21,
+        }
+    }
+
+    fn bar(id: i64, date: NaiveDate, ohlc: [Decimal; 4]) -> MarketBar {
+        let mut bar = MarketBar::new(
+            Exchange::Nasdaq,
+            "AAPL/USD",
+            BarPeriod::D1,
+            21,
+            Price(ohlc[0]),
+            Price(ohlc[1]),
+            Price(ohlc[2]),
+            Price(ohlc[3]),
+            Quantity(dec!(1000)),
+            date,
+        )
+        .expect("Failed to create bar");
+        bar.id = id;
+        bar
+    }
+
+    fn kinds(bars: &[MarketBar], exchange: Exchange) -> Vec<(NaiveDate, AnomalyKind)> {
+        scan_series(&key(exchange), bars, &QualityRules::default())
+            .anomalies
+            .into_iter()
+            .map(|a| (a.date, a.kind))
+            .collect()
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    // =========================================================================
+    // 2. 测试用例
+    // =========================================================================
+
+    #[test]
+    fn test_clean_series() {
+        // 2024-07-01 (周一) .. 07-05，07-04 独立日休市
+        let bars: Vec<MarketBar> = [1, 2, 3, 5]
+            .iter()
+            .map(|d| {
+                bar(
+                    *d as i64,
+                    date(2024, 7, *d),
+                    [dec!(10), dec!(11), dec!(9), dec!(10)],
+                )
+            })
+            .collect();
+        let report = scan_series(&key(Exchange::Nasdaq), &bars, &QualityRules::default());
+        assert!(report.anomalies.is_empty(), "{:?}", report.anomalies);
+        assert!(report.is_clean());
+        assert_eq!(report.first_date, Some(date(2024, 7, 1)));
+        assert_eq!(report.last_date, Some(date(2024, 7, 5)));
+    }
+
+    #[test]
+    fn test_price_anomalies() {
+        let bars = vec![
+            bar(1, date(2024, 7, 1), [dec!(10), dec!(9), dec!(11), dec!(10)]),
+            bar(2, date(2024, 7, 2), [dec!(12), dec!(11), dec!(9), dec!(10)]),
+            bar(3, date(2024, 7, 3), [dec!(10), dec!(11), dec!(9), dec!(8)]),
+            bar(4, date(2024, 7, 5), [dec!(0), dec!(11), dec!(9), dec!(10)]),
+        ];
+        let found = kinds(&bars, Exchange::Nasdaq);
+        assert!(found.contains(&(date(2024, 7, 1), AnomalyKind::HighBelowLow)));
+        assert!(found.contains(&(date(2024, 7, 2), AnomalyKind::OpenOutOfRange)));
+        assert!(found.contains(&(date(2024, 7, 3), AnomalyKind::CloseOutOfRange)));
+        assert!(found.contains(&(date(2024, 7, 5), AnomalyKind::NonPositivePrice)));
+
+        let report = scan_series(&key(Exchange::Nasdaq), &bars, &QualityRules::default());
+        assert_eq!(report.errors(), 4);
+        assert!(!report.is_clean());
+        assert_eq!(report.first_error().unwrap().bar_id, Some(1));
+    }
+
+    #[test]
+    fn test_gaps_duplicates_and_jumps() {
+        let ohlc = |c: Decimal| [c, c, c, c];
+        let bars = vec![
+            bar(1, date(2024, 7, 1), ohlc(dec!(10))),
+            bar(2, date(2024, 7, 1), ohlc(dec!(10))),
+            // 07-02 缺失，07-04 休市却有数据，07-05 暴涨 100%
+            bar(3, date(2024, 7, 3), ohlc(dec!(10))),
+            bar(4, date(2024, 7, 4), ohlc(dec!(10))),
+            bar(5, date(2024, 7, 5), ohlc(dec!(20))),
+        ];
+        let found = kinds(&bars, Exchange::Nasdaq);
+        assert!(found.contains(&(date(2024, 7, 1), AnomalyKind::DuplicateDay)));
+        assert!(found.contains(&(date(2024, 7, 2), AnomalyKind::MissingDay)));
+        assert!(found.contains(&(date(2024, 7, 4), AnomalyKind::NonTradingDay)));
+        assert!(found.contains(&(
+            date(2024, 7, 5),
+            AnomalyKind::SuspiciousJump { change: 1.0 }
+        )));
+        assert_eq!(AnomalyKind::MissingDay.severity(), Severity::Warning);
+
+        // 加密货币 7x24: 周末不算缺失，关闭缺口检测后不报 MissingDay
+        let start = date(2024, 7, 5);
+        let crypto: Vec<MarketBar> = [0, 1, 3]
+            .iter()
+            .map(|d| bar(0, start + Duration::days(*d), ohlc(dec!(10))))
+            .collect();
+        let found = kinds(&crypto, Exchange::Binance);
+        assert_eq!(found, vec![(date(2024, 7, 7), AnomalyKind::MissingDay)]);
+        let rules = QualityRules {
+            check_gaps: false,
+            ..Default::default()
+        };
+        assert!(scan_series(&key(Exchange::Binance), &crypto, &rules)
+            .anomalies
+            .is_empty());
+    }
+
+    #[test]
+    fn test_repair_bar() {
+        let broken = bar(1, date(2024, 7, 1), [dec!(10), dec!(9), dec!(11), dec!(12)]);
+        let fixed = repair_bar(&broken).unwrap();
+        assert_eq!(fixed.high.0, dec!(12));
+        assert_eq!(fixed.low.0, dec!(9));
+        assert_eq!(fixed.open.0, dec!(10));
+        assert!(scan_series(&key(Exchange::Nasdaq), &[fixed], &QualityRules::default()).is_clean());
+
+        let zero = bar(2, date(2024, 7, 1), [dec!(0), dec!(1), dec!(1), dec!(1)]);
+        assert!(repair_bar(&zero).is_none());
+    }
+}
@@ -0,0 +1,124 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::common::redis;
+    use anyhow::Result;
+    use quant_core::enums::Exchange;
+    use quant_storage::ratelimit::{RateLimitPolicy, RateLimiter};
+    use quant_storage::redis::RedisService;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    /// 每个用例使用独立前缀
+    fn prefix() -> String {
+        format!("photon-test:{}", Uuid::new_v4())
+    }
+
+    #[tokio::test]
+    async fn test_policy_config_and_cost_validation() -> Result<()> {
+        let policy: RateLimitPolicy = serde_json::from_str(
+            r#"{"kind": "TOKEN_BUCKET", "capacity": 20, "refill_per_sec": 10.0}"#,
+        )?;
+        assert_eq!(
+            policy,
+            RateLimitPolicy::TokenBucket {
+                capacity: 20,
+                refill_per_sec: 10.0
+            }
+        );
+
+        // 超过上限的消耗永远无法满足，不访问 Redis 直接报错
+        let limiter = RateLimiter::new(RedisService::new("redis://127.0.0.1:1")?, policy);
+        assert!(limiter
+            .try_acquire(Exchange::Binance, "order", 21)
+            .await
+            .is_err());
+        assert!(limiter
+            .try_acquire(Exchange::Binance, "order", 0)
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis (REDIS_URL)"]
+    async fn test_sliding_window() -> Result<()> {
+        let redis = redis();
+        let limiter = RateLimiter::new(
+            redis,
+            RateLimitPolicy::SlidingWindow {
+                limit: 3,
+                window_ms: 500,
+            },
+        )
+        .with_prefix(prefix());
+
+        let first = limiter.try_acquire(Exchange::Binance, "order", 2).await?;
+        assert!(first.allowed);
+        assert_eq!(first.remaining, 1);
+        let denied = limiter.try_acquire(Exchange::Binance, "order", 2).await?;
+        assert!(!denied.allowed);
+        assert!(denied.retry_after <= Duration::from_millis(500));
+
+        // 不同接口 / 交易所各自计数
+        assert!(
+            limiter
+                .try_acquire(Exchange::Binance, "cancel", 3)
+                .await?
+                .allowed
+        );
+        assert!(
+            limiter
+                .try_acquire(Exchange::Okx, "order", 3)
+                .await?
+                .allowed
+        );
+
+        // 窗口滑过后恢复
+        assert!(
+            limiter
+                .acquire(Exchange::Binance, "order", 3, Duration::from_secs(2))
+                .await?
+        );
+        limiter.reset(Exchange::Binance, "order").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis (REDIS_URL)"]
+    async fn test_token_bucket() -> Result<()> {
+        let redis = redis();
+        let limiter = RateLimiter::new(
+            redis,
+            RateLimitPolicy::TokenBucket {
+                capacity: 5,
+                refill_per_sec: 10.0,
+            },
+        )
+        .with_prefix(prefix());
+
+        // 允许一次性突发到容量
+        let burst = limiter.try_acquire(Exchange::Binance, "order", 5).await?;
+        assert!(burst.allowed);
+        assert_eq!(burst.remaining, 0);
+
+        let denied = limiter.try_acquire(Exchange::Binance, "order", 2).await?;
+        assert!(!denied.allowed);
+        assert!(denied.retry_after > Duration::ZERO);
+        assert!(denied.retry_after <= Duration::from_millis(200));
+
+        assert!(
+            !limiter
+                .acquire(Exchange::Binance, "order", 5, Duration::from_millis(50))
+                .await?
+        );
+        assert!(
+            limiter
+                .acquire(Exchange::Binance, "order", 2, Duration::from_secs(1))
+                .await?
+        );
+        limiter.reset(Exchange::Binance, "order").await?;
+        Ok(())
+    }
+}
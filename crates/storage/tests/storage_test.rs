@@ -0,0 +1,27 @@
+#[cfg(test)]
+mod tests {
+    use quant_storage::repository::common::{self, StorageConfig};
+    use quant_storage::Storage;
+
+    #[test]
+    fn test_storage_config_defaults() {
+        let config: StorageConfig =
+            serde_json::from_str(r#"{"database_url": "mysql://localhost/photon_db"}"#).unwrap();
+        assert_eq!(config.database_url, "mysql://localhost/photon_db");
+        assert_eq!(config.max_connections, 20);
+        assert_eq!(config.min_connections, 5);
+        assert!(config.redis_url.is_none());
+
+        // 已配置的连接串不会被环境变量覆盖
+        let config = config.with_env_fallback();
+        assert_eq!(config.database_url, "mysql://localhost/photon_db");
+    }
+
+    #[tokio::test]
+    async fn test_connect_without_url_is_error() {
+        let config = StorageConfig::default();
+        let err = common::connect(&config).await.unwrap_err();
+        assert!(err.to_string().contains("Database URL missing"));
+        assert!(Storage::connect(&config).await.is_err());
+    }
+}
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use quant_core::enums::{Side, StrategyStatus};
+    use quant_core::enums::{Side, SignalStatus, StatusActor, StrategyStatus};
     use quant_core::primitive::{Price, Quantity};
     use quant_core::strategy::{Signal, Strategy};
     use quant_storage::repository::strategy_repo;
@@ -15,7 +15,9 @@ mod tests {
     // 辅助函数
     // =========================================================================
     async fn get_test_repo() -> strategy_repo::StrategyRepository {
-        let pool = quant_storage::repository::common::get_real_pool().await;
+        let pool = quant_storage::repository::common::connect_from_env()
+            .await
+            .expect("Failed to connect to test database");
         strategy_repo::StrategyRepository::new(pool.clone())
     }
 
@@ -56,12 +58,43 @@ mod tests {
         assert_eq!(found.name, name); // 验证名称
         assert!(matches!(found.status, StrategyStatus::Created));
 
-        // 4. 状态流转测试
-        repo.update_status(strategy_uuid, StrategyStatus::Running)
-            .await?;
+        // 4. 状态流转测试 (Created 不能直接进入 Running)
+        assert!(repo
+            .update_status(
+                strategy_uuid,
+                StrategyStatus::Running,
+                StatusActor::User,
+                ""
+            )
+            .await
+            .is_err());
+        repo.update_status(
+            strategy_uuid,
+            StrategyStatus::Initializing,
+            StatusActor::System,
+            "boot",
+        )
+        .await?;
+        repo.update_status(
+            strategy_uuid,
+            StrategyStatus::Running,
+            StatusActor::User,
+            "go live",
+        )
+        .await?;
 
         let updated = repo.find_by_uuid(strategy_uuid).await?.unwrap();
         assert!(matches!(updated.status, StrategyStatus::Running));
+        assert_eq!(updated.reason, "go live");
+
+        // 5. 审计记录 (倒序)
+        let history = repo.find_status_history(strategy_uuid, 10).await?;
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].from_status, StrategyStatus::Initializing);
+        assert_eq!(history[0].to_status, StrategyStatus::Running);
+        assert_eq!(history[0].actor, StatusActor::User);
+        assert_eq!(history[1].from_status, StrategyStatus::Created);
+        assert_eq!(history[1].reason, "boot");
 
         Ok(())
     }
@@ -87,9 +120,15 @@ mod tests {
         let u2 = Uuid::from_str(&s2.uuid)?;
         let u3 = Uuid::from_str(&s3.uuid)?;
 
-        repo.update_status(u1, StrategyStatus::Running).await?; // 活跃
-        repo.update_status(u2, StrategyStatus::Initializing).await?; // 活跃
-        repo.update_status(u3, StrategyStatus::Stopped).await?; // 非活跃
+        let system = StatusActor::System;
+        repo.update_status(u1, StrategyStatus::Initializing, system, "")
+            .await?;
+        repo.update_status(u1, StrategyStatus::Running, system, "")
+            .await?; // 活跃
+        repo.update_status(u2, StrategyStatus::Initializing, system, "")
+            .await?; // 活跃
+        repo.update_status(u3, StrategyStatus::Stopped, system, "")
+            .await?; // 非活跃
 
         // 3. 调用 find_active_strategies
         let active_list = repo.find_active_strategies().await?;
@@ -131,23 +170,33 @@ mod tests {
         });
 
         // 2. 保存
-        repo.save_state(strategy_uuid, &initial_data).await?;
+        assert_eq!(repo.save_state(strategy_uuid, 1, &initial_data).await?, 1);
 
         // 验证
         let loaded = repo.load_state(strategy_uuid).await?.unwrap();
         assert_eq!(loaded.uuid, strategy_uuid.to_string());
         assert_eq!(loaded.state_data, initial_data);
 
-        // 3. 更新 (测试 Duplicate Key Update)
+        // 3. 更新 (追加新版本，当前状态随之更新)
         let updated_data = json!({
             "cash_balance": 5000.0,
             "positions": [{"symbol": "BTC/USDT", "amt": 0.1}]
         });
 
-        repo.save_state(strategy_uuid, &updated_data).await?;
+        assert_eq!(repo.save_state(strategy_uuid, 1, &updated_data).await?, 2);
 
         let reloaded = repo.load_state(strategy_uuid).await?.unwrap();
         assert_eq!(reloaded.state_data, updated_data);
+        assert_eq!(reloaded.version, 2);
+
+        // 4. 历史与回滚
+        let history = repo.list_state_versions(strategy_uuid, 10).await?;
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].state_data, initial_data);
+        assert_eq!(repo.rollback_state(strategy_uuid, 1).await?, 3);
+        let rolled_back = repo.load_state(strategy_uuid).await?.unwrap();
+        assert_eq!(rolled_back.state_data, initial_data);
+        assert_eq!(rolled_back.version, 3);
 
         Ok(())
     }
@@ -203,6 +252,26 @@ mod tests {
         assert_eq!(all.len(), 2);
         assert_eq!(all[0].uuid, signal_market.uuid);
         assert_eq!(all[1].uuid, signal_limit.uuid);
+        assert_eq!(all[1].status, SignalStatus::Generated);
+
+        // 4. 生命周期：提交 -> 成交
+        let mut submitted = signal_limit.clone();
+        submitted.submit("order-1", Quantity(dec!(0.5)))?;
+        submitted.record_fill(Quantity(dec!(0.5)), chrono::Utc::now())?;
+        assert_eq!(repo.update_signal(&submitted).await?, 1);
+
+        let stored = repo
+            .find_signal(Uuid::from_str(&signal_limit.uuid)?)
+            .await?
+            .unwrap();
+        assert_eq!(stored.status, SignalStatus::Executed);
+        assert_eq!(stored.order_uuids, vec!["order-1".to_string()]);
+        assert_eq!(stored.filled_quantity, Quantity(dec!(0.5)));
+        assert!(stored.first_fill_at.is_some());
+
+        let open = repo.find_open_signals().await?;
+        assert!(open.iter().any(|s| s.uuid == signal_market.uuid));
+        assert!(!open.iter().any(|s| s.uuid == signal_limit.uuid));
 
         Ok(())
     }
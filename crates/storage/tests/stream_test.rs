@@ -0,0 +1,190 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::common::redis;
+    use anyhow::Result;
+    use chrono::Utc;
+    use quant_core::bus::{EventBus, EventSink, SubscribeOptions, TopicFilter};
+    use quant_core::event::{Envelope, Event, RiskEvent, RiskLevel, TimerEvent, Topic};
+    use quant_storage::stream::{
+        self, EventStream, RemoteEvent, StreamConfig, StreamSink, REMOTE_SOURCE_PREFIX,
+    };
+    use serde::{Deserialize, Serialize};
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Ping {
+        n: u32,
+    }
+
+    fn timer(name: &str) -> Event {
+        Event::Timer(TimerEvent {
+            name: name.to_string(),
+            fired_at: Utc::now(),
+        })
+    }
+
+    fn config(stream: &str, consumer: &str) -> StreamConfig {
+        StreamConfig {
+            stream: stream.to_string(),
+            group: "test".to_string(),
+            consumer: consumer.to_string(),
+            block_ms: 100,
+            ..StreamConfig::default()
+        }
+    }
+
+    // =========================================================================
+    // 1. 配置 / 出站过滤 (无需 Redis)
+    // =========================================================================
+
+    #[test]
+    fn test_stream_config_defaults() {
+        let config: StreamConfig =
+            serde_json::from_str(r#"{"stream": "photon:fast", "max_deliveries": 3}"#).unwrap();
+        assert_eq!(config.stream, "photon:fast");
+        assert_eq!(config.max_deliveries, 3);
+        assert_eq!(config.dead_letter_stream(), "photon:fast:dead");
+        assert!(config.consumer.starts_with("consumer-"));
+    }
+
+    #[test]
+    fn test_sink_filters_topics_and_remote_events() -> Result<()> {
+        let (sink, mut rx) = StreamSink::channel(TopicFilter::topics([Topic::Timer]));
+        sink.record(&Envelope::new(1, "feed", timer("local")))?;
+        sink.record(&Envelope::new(
+            2,
+            format!("{}agent:feed", REMOTE_SOURCE_PREFIX),
+            timer("echo"),
+        ))?;
+        sink.record(&Envelope::new(
+            3,
+            "risk",
+            Event::Risk(RiskEvent {
+                level: RiskLevel::Info,
+                rule: "noop".to_string(),
+                strategy_uuid: None,
+                order_uuid: None,
+                message: String::new(),
+            }),
+        ))?;
+
+        let forwarded = rx.try_recv()?;
+        assert_eq!(forwarded.seq, 1);
+        assert!(
+            rx.try_recv().is_err(),
+            "Remote / filtered events stay local"
+        );
+        Ok(())
+    }
+
+    // =========================================================================
+    // 2. 消费组
+    // =========================================================================
+
+    #[tokio::test]
+    #[ignore = "requires Redis (REDIS_URL)"]
+    async fn test_publish_read_ack_and_replay() -> Result<()> {
+        let redis = redis();
+        let name = format!("photon-test:stream:{}", Uuid::new_v4());
+        let stream: EventStream<Ping> = EventStream::new(redis.clone(), config(&name, "c1"));
+        stream.ensure_group("0").await?;
+        stream.ensure_group("0").await?; // 重复创建不报错
+
+        let first = stream.publish(&Ping { n: 1 }).await?;
+        stream.publish(&Ping { n: 2 }).await?;
+
+        let messages = stream.read().await?;
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].payload, Ping { n: 1 });
+        assert_eq!(messages[0].deliveries, 1);
+        let ids: Vec<String> = messages.iter().map(|m| m.id.clone()).collect();
+        assert_eq!(stream.ack(&ids).await?, 2);
+        assert!(stream.read().await?.is_empty());
+
+        // 回放不受消费组进度影响
+        let replayed = stream.replay(&first, 10).await?;
+        assert_eq!(replayed.len(), 2);
+        assert_eq!(replayed[1].payload, Ping { n: 2 });
+
+        redis.delete(&name).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis (REDIS_URL)"]
+    async fn test_reclaim_and_dead_letters() -> Result<()> {
+        let redis = redis();
+        let name = format!("photon-test:stream:{}", Uuid::new_v4());
+        let crashed: EventStream<Ping> = EventStream::new(redis.clone(), config(&name, "crashed"));
+        let mut survivor_config = config(&name, "survivor");
+        survivor_config.claim_idle_ms = 0;
+        survivor_config.max_deliveries = 2;
+        let survivor: EventStream<Ping> = EventStream::new(redis.clone(), survivor_config);
+        crashed.ensure_group("0").await?;
+
+        crashed.publish(&Ping { n: 7 }).await?;
+        // 无法解码的消息直接进死信
+        redis.xadd(&name, &[("payload", "not json")], None).await?;
+
+        // 第一个消费者读到后没有确认 (模拟崩溃)
+        assert_eq!(crashed.read().await?.len(), 1);
+
+        // 第二个消费者接管 (第 2 次投递)
+        let taken = survivor.read().await?;
+        assert_eq!(taken.len(), 1);
+        assert_eq!((taken[0].payload.n, taken[0].deliveries), (7, 2));
+
+        // 仍未确认 -> 超过最大投递次数，转入死信
+        assert!(survivor.read().await?.is_empty());
+        let dead = survivor.dead_letters(10).await?;
+        assert_eq!(dead.len(), 2);
+        assert!(dead
+            .iter()
+            .any(|d| d.fields["reason"].contains("deliveries")));
+        assert!(dead.iter().any(|d| d.fields["payload"] == "not json"));
+
+        redis.delete(&name).await?;
+        redis
+            .delete(&survivor.config().dead_letter_stream())
+            .await?;
+        Ok(())
+    }
+
+    // =========================================================================
+    // 3. 总线桥接
+    // =========================================================================
+
+    #[tokio::test]
+    #[ignore = "requires Redis (REDIS_URL)"]
+    async fn test_bridge_between_buses() -> Result<()> {
+        let redis = redis();
+        let name = format!("photon-test:stream:{}", Uuid::new_v4());
+        let fast_bus = EventBus::new();
+        let slow_bus = EventBus::new();
+
+        let mut slow_config = config(&name, "slow");
+        slow_config.group = "slow".to_string();
+        let slow_stream: EventStream<RemoteEvent> = EventStream::new(redis.clone(), slow_config);
+        let inbound =
+            stream::bridge_inbound(slow_bus.clone(), slow_stream, "slow", [Topic::Timer]).await?;
+        let received = slow_bus.subscribe(SubscribeOptions::new("agent"));
+
+        let fast_stream = EventStream::new(redis.clone(), config(&name, "fast"));
+        let outbound = stream::bridge_outbound(&fast_bus, fast_stream, "fast", [Topic::Timer]);
+        fast_bus.publish("feed", timer("heartbeat")).await;
+
+        let envelope = tokio::time::timeout(Duration::from_secs(5), received.recv())
+            .await?
+            .expect("bridged event");
+        assert_eq!(envelope.source, "remote:fast:feed");
+        assert!(matches!(&envelope.event, Event::Timer(t) if t.name == "heartbeat"));
+
+        inbound.abort();
+        outbound.abort();
+        redis.delete(&name).await?;
+        Ok(())
+    }
+}
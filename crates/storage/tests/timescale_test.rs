@@ -0,0 +1,116 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::common::timescale as store;
+    use anyhow::Result;
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use quant_core::enums::{BarPeriod, Exchange};
+    use quant_core::market::IntradayBar;
+    use quant_core::primitive::{CurrencyPair, Price, Quantity};
+    use quant_storage::repository::common::{MarketBackend, StorageConfig};
+    use quant_storage::store::timescale::{self, aggregate_view};
+    use quant_storage::store::MarketDataStore;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use std::str::FromStr;
+
+    fn m1_bar(symbol: &str, start: DateTime<Utc>, close: Decimal) -> IntradayBar {
+        IntradayBar {
+            exchange: Exchange::Binance,
+            symbol: CurrencyPair::from_str(symbol).unwrap(),
+            bar_period: BarPeriod::M1,
+            trade_type: 21,
+            open: Price(close),
+            high: Price(close + dec!(1)),
+            low: Price(close - dec!(1)),
+            close: Price(close),
+            volume: Quantity(dec!(2)),
+            amount: None,
+            start_time: start,
+            end_time: start + BarPeriod::M1.duration(),
+        }
+    }
+
+    #[test]
+    fn test_backend_config() {
+        let config: StorageConfig = serde_json::from_str(
+            r#"{"market_backend": "TIMESCALE", "timescale_url": "postgres://localhost/ts"}"#,
+        )
+        .unwrap();
+        assert_eq!(config.market_backend, MarketBackend::Timescale);
+        assert_eq!(
+            StorageConfig::default().market_backend,
+            MarketBackend::Mysql
+        );
+    }
+
+    #[test]
+    fn test_aggregate_views() {
+        assert_eq!(aggregate_view(BarPeriod::M1), None);
+        assert_eq!(aggregate_view(BarPeriod::H1), Some("market_bar_h1"));
+        assert_eq!(BarPeriod::M15.duration(), Duration::minutes(15));
+
+        let start = Utc.with_ymd_and_hms(2024, 3, 1, 23, 59, 0).unwrap();
+        let daily = m1_bar("BTC/USDT", start, dec!(100)).to_market_bar();
+        assert_eq!(daily.start_time.to_string(), "2024-03-01");
+        assert_eq!(daily.end_time.to_string(), "2024-03-02");
+    }
+
+    #[tokio::test]
+    async fn test_connect_requires_url() {
+        let err = timescale::connect(&StorageConfig::default())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("TimescaleDB URL missing"));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires TimescaleDB (TIMESCALE_URL)"]
+    async fn test_intraday_roundtrip_and_aggregates() -> Result<()> {
+        let store = store().await;
+        let symbol = format!(
+            "T{}/USDT",
+            uuid::Uuid::new_v4().simple().to_string()[..8].to_uppercase()
+        );
+        let start = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+        let bars: Vec<IntradayBar> = (0..10)
+            .map(|i| {
+                m1_bar(
+                    &symbol,
+                    start + Duration::minutes(i),
+                    dec!(100) + Decimal::from(i),
+                )
+            })
+            .collect();
+
+        let counts = store.save_intraday_bars(&bars).await?;
+        assert_eq!((counts.inserted, counts.updated), (10, 0));
+        let counts = store.save_intraday_bars(&bars[..2]).await?;
+        assert_eq!((counts.inserted, counts.updated), (0, 2));
+
+        let end = start + Duration::minutes(10);
+        let m1 = store
+            .find_intraday_bars("BINANCE", &symbol, BarPeriod::M1, 21, start, end)
+            .await?;
+        assert_eq!(m1, bars);
+
+        // M5 没有直接写入，读连续聚合 (实时聚合包含未物化的数据)
+        let m5 = store
+            .find_intraday_bars("BINANCE", &symbol, BarPeriod::M5, 21, start, end)
+            .await?;
+        assert_eq!(m5.len(), 2);
+        assert_eq!(m5[0].open.0, dec!(100));
+        assert_eq!(m5[0].close.0, dec!(104));
+        assert_eq!(m5[0].volume.0, dec!(10));
+        assert_eq!(m5[1].start_time, start + Duration::minutes(5));
+
+        let recent = store
+            .find_recent_bars("BINANCE", &symbol, BarPeriod::M1, 21, 3)
+            .await?;
+        assert_eq!(recent.len(), 3);
+        let series = store.find_series(Some("BINANCE"), Some(&symbol)).await?;
+        assert_eq!(series.len(), 6);
+        Ok(())
+    }
+}
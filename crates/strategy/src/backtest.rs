@@ -0,0 +1,444 @@
+use crate::traits::{OrderIntent, StrategyContext, TradingStrategy};
+use anyhow::Result;
+use chrono::NaiveDate;
+use quant_core::enums::Side;
+use quant_core::market::MarketBar;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::io::Write;
+
+const BPS: Decimal = Decimal::from_parts(10000, 0, 0, false, 0);
+
+// =========================================================================
+// 1. 配置与结果
+// =========================================================================
+
+/// 回测参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestConfig {
+    pub initial_cash: Decimal,
+    /// 手续费率 (按成交额，e.g. 0.001 = 10bps)
+    pub fee_rate: Decimal,
+    /// 滑点 (bps)，买入上浮、卖出下浮
+    pub slippage_bps: Decimal,
+    /// 年化使用的周期数 (日线 252，加密货币日线 365)
+    pub periods_per_year: u32,
+}
+
+impl Default for BacktestConfig {
+    fn default() -> Self {
+        Self {
+            initial_cash: Decimal::from(100_000),
+            fee_rate: Decimal::new(1, 3),
+            slippage_bps: Decimal::ZERO,
+            periods_per_year: 252,
+        }
+    }
+}
+
+/// 成交明细
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trade {
+    pub date: NaiveDate,
+    pub symbol: String,
+    pub side: Side,
+    pub quantity: Decimal,
+    /// 含滑点的成交价
+    pub price: Decimal,
+    pub fee: Decimal,
+    /// 卖出时的已实现盈亏 (扣除买卖双边手续费)
+    pub realized_pnl: Option<Decimal>,
+    pub reason: String,
+}
+
+/// 每个周期结束时的权益快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EquityPoint {
+    pub date: NaiveDate,
+    pub cash: Decimal,
+    pub market_value: Decimal,
+    pub equity: Decimal,
+}
+
+/// 绩效指标
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestMetrics {
+    pub initial_cash: Decimal,
+    pub final_equity: Decimal,
+    pub total_return: f64,
+    pub annualized_return: f64,
+    /// 年化波动率
+    pub volatility: f64,
+    pub sharpe: f64,
+    /// 最大回撤 (正数，0.2 = 20%)
+    pub max_drawdown: f64,
+    pub trade_count: usize,
+    /// 盈利的平仓交易占比 (没有平仓时为 None)
+    pub win_rate: Option<f64>,
+    pub total_fees: Decimal,
+}
+
+/// 回测报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestReport {
+    pub strategy: String,
+    pub config: BacktestConfig,
+    pub metrics: BacktestMetrics,
+    pub trades: Vec<Trade>,
+    pub equity_curve: Vec<EquityPoint>,
+}
+
+impl BacktestReport {
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn write_trades_csv<W: Write>(&self, writer: W) -> Result<()> {
+        let mut wtr = csv::Writer::from_writer(writer);
+        for trade in &self.trades {
+            wtr.serialize(trade)?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    pub fn write_equity_csv<W: Write>(&self, writer: W) -> Result<()> {
+        let mut wtr = csv::Writer::from_writer(writer);
+        for point in &self.equity_curve {
+            wtr.serialize(point)?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+}
+
+// =========================================================================
+// 2. 回测器
+// =========================================================================
+
+/// 账户状态 (现金 + 持仓 + 持仓均价)，按 `K` 区分标的 (单策略回测为标的代码)
+#[derive(Debug)]
+pub(crate) struct Book<K = String> {
+    pub(crate) cash: Decimal,
+    pub(crate) positions: HashMap<K, Decimal>,
+    /// 持仓均价 (含买入手续费)
+    pub(crate) avg_cost: HashMap<K, Decimal>,
+    pub(crate) last_close: HashMap<K, Decimal>,
+}
+
+impl<K> Default for Book<K> {
+    fn default() -> Self {
+        Self {
+            cash: Decimal::ZERO,
+            positions: HashMap::new(),
+            avg_cost: HashMap::new(),
+            last_close: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Eq + Hash> Book<K> {
+    pub(crate) fn market_value(&self) -> Decimal {
+        self.positions
+            .iter()
+            .map(|(symbol, qty)| {
+                *qty * self
+                    .last_close
+                    .get(symbol)
+                    .copied()
+                    .unwrap_or(Decimal::ZERO)
+            })
+            .sum()
+    }
+}
+
+/// K 线驱动的回测器
+///
+/// 撮合规则：策略在 K 线收盘后下单，订单在该标的下一根 K 线开盘价成交 (避免未来函数)。
+/// 不支持做空，卖出数量不超过持仓；买入数量受现金约束。
+pub struct Backtester {
+    config: BacktestConfig,
+}
+
+impl Backtester {
+    pub fn new(config: BacktestConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn run(
+        &self,
+        strategy: &mut dyn TradingStrategy,
+        bars: &[MarketBar],
+    ) -> Result<BacktestReport> {
+        self.run_with_warmup(strategy, &[], bars)
+    }
+
+    /// 先用 `warmup` K 线预热策略 (只更新指标，下单意图丢弃，不计入权益)，再在 `bars` 上回测
+    ///
+    /// 用于样本外评估：策略带着样本内的历史进入测试区间，避免冷启动期间空仓。
+    pub fn run_with_warmup(
+        &self,
+        strategy: &mut dyn TradingStrategy,
+        warmup: &[MarketBar],
+        bars: &[MarketBar],
+    ) -> Result<BacktestReport> {
+        let mut warm: Vec<&MarketBar> = warmup.iter().collect();
+        warm.sort_by_key(|b| (b.start_time, b.symbol.to_string()));
+        let mut ctx = StrategyContext::new(self.config.initial_cash);
+        for bar in warm {
+            ctx.date = Some(bar.start_time);
+            strategy.on_bar(&mut ctx, bar);
+            ctx.take_orders();
+        }
+
+        let mut sorted: Vec<&MarketBar> = bars.iter().collect();
+        sorted.sort_by_key(|b| (b.start_time, b.symbol.to_string()));
+
+        let mut book = Book {
+            cash: self.config.initial_cash,
+            ..Default::default()
+        };
+        let mut pending: Vec<OrderIntent> = Vec::new();
+        let mut trades = Vec::new();
+        let mut equity_curve = Vec::new();
+
+        let mut i = 0;
+        while i < sorted.len() {
+            let date = sorted[i].start_time;
+            let mut j = i;
+            while j < sorted.len() && sorted[j].start_time == date {
+                j += 1;
+            }
+            let day = &sorted[i..j];
+
+            // 1. 开盘：撮合上一周期挂出的订单
+            let opens: HashMap<String, Decimal> = day
+                .iter()
+                .map(|b| (b.symbol.to_string(), b.open.0))
+                .collect();
+            let mut still_pending = Vec::new();
+            for intent in pending.drain(..) {
+                match opens.get(&intent.symbol) {
+                    Some(open) => {
+                        if let Some(trade) =
+                            self.execute(&mut book, &intent.symbol, date, &intent, *open)
+                        {
+                            trades.push(trade);
+                        }
+                    }
+                    None => still_pending.push(intent),
+                }
+            }
+            pending = still_pending;
+
+            // 2. 收盘：更新价格并驱动策略
+            for bar in day {
+                book.last_close.insert(bar.symbol.to_string(), bar.close.0);
+            }
+            let mut ctx = StrategyContext {
+                date: Some(date),
+                cash: book.cash,
+                equity: book.cash + book.market_value(),
+                positions: book.positions.clone(),
+                orders: Vec::new(),
+            };
+            for bar in day {
+                strategy.on_bar(&mut ctx, bar);
+            }
+            pending.extend(ctx.take_orders());
+
+            let market_value = book.market_value();
+            equity_curve.push(EquityPoint {
+                date,
+                cash: book.cash,
+                market_value,
+                equity: book.cash + market_value,
+            });
+            i = j;
+        }
+
+        let metrics = compute_metrics(
+            &equity_curve,
+            &trades,
+            self.config.initial_cash,
+            self.config.periods_per_year,
+        );
+        Ok(BacktestReport {
+            strategy: strategy.name().to_string(),
+            config: self.config.clone(),
+            metrics,
+            trades,
+            equity_curve,
+        })
+    }
+
+    /// 按开盘价 (含滑点) 撮合一笔下单意图，`key` 为该标的在 `book` 中的键
+    pub(crate) fn execute<K: Eq + Hash + Clone>(
+        &self,
+        book: &mut Book<K>,
+        key: &K,
+        date: NaiveDate,
+        intent: &OrderIntent,
+        open: Decimal,
+    ) -> Option<Trade> {
+        let slippage = self.config.slippage_bps / BPS;
+        let position = book.positions.get(key).copied().unwrap_or(Decimal::ZERO);
+
+        match intent.side {
+            Side::Buy => {
+                let price = open * (Decimal::ONE + slippage);
+                let unit_cost = price * (Decimal::ONE + self.config.fee_rate);
+                let affordable = if unit_cost > Decimal::ZERO {
+                    (book.cash / unit_cost)
+                        .round_dp_with_strategy(8, rust_decimal::RoundingStrategy::ToZero)
+                } else {
+                    Decimal::ZERO
+                };
+                let quantity = intent.quantity.min(affordable);
+                if quantity <= Decimal::ZERO {
+                    return None;
+                }
+                let fee = quantity * price * self.config.fee_rate;
+                let cost = quantity * price + fee;
+                let new_position = position + quantity;
+                let old_cost = book.avg_cost.get(key).copied().unwrap_or(Decimal::ZERO);
+                book.avg_cost
+                    .insert(key.clone(), (old_cost * position + cost) / new_position);
+                book.positions.insert(key.clone(), new_position);
+                book.cash -= cost;
+                Some(Trade {
+                    date,
+                    symbol: intent.symbol.clone(),
+                    side: Side::Buy,
+                    quantity,
+                    price,
+                    fee,
+                    realized_pnl: None,
+                    reason: intent.reason.clone(),
+                })
+            }
+            Side::Sell => {
+                let quantity = intent.quantity.min(position);
+                if quantity <= Decimal::ZERO {
+                    return None;
+                }
+                let price = open * (Decimal::ONE - slippage);
+                let fee = quantity * price * self.config.fee_rate;
+                let avg_cost = book.avg_cost.get(key).copied().unwrap_or(Decimal::ZERO);
+                let realized = (price - avg_cost) * quantity - fee;
+
+                let remaining = position - quantity;
+                if remaining.is_zero() {
+                    book.positions.remove(key);
+                    book.avg_cost.remove(key);
+                } else {
+                    book.positions.insert(key.clone(), remaining);
+                }
+                book.cash += quantity * price - fee;
+                Some(Trade {
+                    date,
+                    symbol: intent.symbol.clone(),
+                    side: Side::Sell,
+                    quantity,
+                    price,
+                    fee,
+                    realized_pnl: Some(realized),
+                    reason: intent.reason.clone(),
+                })
+            }
+        }
+    }
+}
+
+// =========================================================================
+// 3. 绩效指标
+// =========================================================================
+
+/// 根据权益曲线和成交明细计算绩效指标
+pub fn compute_metrics(
+    equity_curve: &[EquityPoint],
+    trades: &[Trade],
+    initial_cash: Decimal,
+    periods_per_year: u32,
+) -> BacktestMetrics {
+    let initial = initial_cash.to_f64().unwrap_or(0.0);
+    let equities: Vec<f64> = equity_curve
+        .iter()
+        .map(|p| p.equity.to_f64().unwrap_or(0.0))
+        .collect();
+    let final_equity = equity_curve
+        .last()
+        .map(|p| p.equity)
+        .unwrap_or(initial_cash);
+
+    // 周期收益率 (第一期相对初始资金)
+    let mut returns = Vec::with_capacity(equities.len());
+    let mut prev = initial;
+    for &equity in &equities {
+        if prev > 0.0 {
+            returns.push(equity / prev - 1.0);
+        }
+        prev = equity;
+    }
+
+    let n = returns.len();
+    let mean = if n > 0 {
+        returns.iter().sum::<f64>() / n as f64
+    } else {
+        0.0
+    };
+    let std = if n > 1 {
+        (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1) as f64).sqrt()
+    } else {
+        0.0
+    };
+    let ppy = periods_per_year as f64;
+
+    let total_return = if initial > 0.0 {
+        final_equity.to_f64().unwrap_or(0.0) / initial - 1.0
+    } else {
+        0.0
+    };
+    let annualized_return = if n > 0 && total_return > -1.0 {
+        (1.0 + total_return).powf(ppy / n as f64) - 1.0
+    } else {
+        0.0
+    };
+    let sharpe = if std > 0.0 {
+        mean / std * ppy.sqrt()
+    } else {
+        0.0
+    };
+
+    let mut peak = initial;
+    let mut max_drawdown: f64 = 0.0;
+    for &equity in &equities {
+        peak = peak.max(equity);
+        if peak > 0.0 {
+            max_drawdown = max_drawdown.max((peak - equity) / peak);
+        }
+    }
+
+    let closed: Vec<Decimal> = trades.iter().filter_map(|t| t.realized_pnl).collect();
+    let win_rate = if closed.is_empty() {
+        None
+    } else {
+        Some(closed.iter().filter(|p| **p > Decimal::ZERO).count() as f64 / closed.len() as f64)
+    };
+
+    BacktestMetrics {
+        initial_cash,
+        final_equity,
+        total_return,
+        annualized_return,
+        volatility: std * ppy.sqrt(),
+        sharpe,
+        max_drawdown,
+        trade_count: trades.len(),
+        win_rate,
+        total_fees: trades.iter().map(|t| t.fee).sum(),
+    }
+}
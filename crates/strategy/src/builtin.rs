@@ -0,0 +1,113 @@
+use crate::traits::{StrategyContext, TradingStrategy};
+use quant_core::market::MarketBar;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+// =========================================================================
+// 1. 买入持有 (基准策略)
+// =========================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuyAndHoldParams {
+    /// 每个标的买入数量
+    pub quantity: Decimal,
+}
+
+/// 第一根 K 线买入后一直持有，用作回测基准
+pub struct BuyAndHold {
+    params: BuyAndHoldParams,
+}
+
+impl BuyAndHold {
+    pub const CLASS_NAME: &'static str = "BuyAndHold";
+
+    pub fn new(params: BuyAndHoldParams) -> Self {
+        Self { params }
+    }
+}
+
+impl TradingStrategy for BuyAndHold {
+    fn name(&self) -> &str {
+        Self::CLASS_NAME
+    }
+
+    fn on_bar(&mut self, ctx: &mut StrategyContext, bar: &MarketBar) {
+        let symbol = bar.symbol.to_string();
+        if ctx.position(&symbol).is_zero() {
+            ctx.order_target(symbol, self.params.quantity, "buy and hold");
+        }
+    }
+}
+
+// =========================================================================
+// 2. 双均线交叉
+// =========================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmaCrossParams {
+    /// 快线窗口
+    pub fast: usize,
+    /// 慢线窗口
+    pub slow: usize,
+    /// 金叉时的目标持仓
+    pub quantity: Decimal,
+}
+
+/// 快线上穿慢线做多，下穿平仓 (按标的独立计算)
+pub struct SmaCross {
+    params: SmaCrossParams,
+    closes: HashMap<String, VecDeque<Decimal>>,
+}
+
+impl SmaCross {
+    pub const CLASS_NAME: &'static str = "SmaCross";
+
+    pub fn new(params: SmaCrossParams) -> anyhow::Result<Self> {
+        if params.fast == 0 || params.fast >= params.slow {
+            anyhow::bail!(
+                "SmaCross requires 0 < fast < slow, got fast={} slow={}",
+                params.fast,
+                params.slow
+            );
+        }
+        Ok(Self {
+            params,
+            closes: HashMap::new(),
+        })
+    }
+
+    fn mean(values: impl Iterator<Item = Decimal>, n: usize) -> Decimal {
+        values.sum::<Decimal>() / Decimal::from(n)
+    }
+}
+
+impl TradingStrategy for SmaCross {
+    fn name(&self) -> &str {
+        Self::CLASS_NAME
+    }
+
+    fn on_bar(&mut self, ctx: &mut StrategyContext, bar: &MarketBar) {
+        let symbol = bar.symbol.to_string();
+        let closes = self.closes.entry(symbol.clone()).or_default();
+        closes.push_back(bar.close.0);
+        if closes.len() > self.params.slow {
+            closes.pop_front();
+        }
+        if closes.len() < self.params.slow {
+            return;
+        }
+
+        let fast = Self::mean(
+            closes.iter().rev().take(self.params.fast).copied(),
+            self.params.fast,
+        );
+        let slow = Self::mean(closes.iter().copied(), self.params.slow);
+
+        if fast > slow {
+            ctx.order_target(symbol, self.params.quantity, "fast sma above slow");
+        } else if fast < slow {
+            ctx.order_target(symbol, Decimal::ZERO, "fast sma below slow");
+        }
+    }
+}
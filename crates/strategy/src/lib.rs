@@ -1,14 +1,12 @@
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
-}
+pub mod backtest;
+pub mod builtin;
+pub mod optimize;
+pub mod portfolio;
+pub mod registry;
+pub mod robustness;
+pub mod sizing;
+pub mod traits;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+pub use backtest::*;
+pub use registry::*;
+pub use traits::*;
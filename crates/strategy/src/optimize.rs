@@ -0,0 +1,945 @@
+//! 参数优化与滚动前推分析 (Parameter Optimization & Walk-Forward)
+//!
+//! * **参数空间**: 来自 `Strategy.config`，其中 `search_space` 字段描述待优化参数
+//!   (键为参数路径，支持 `sizing.fraction` 形式的嵌套路径)，其余字段作为固定参数：
+//!
+//!   ```json
+//!   {
+//!     "quantity": 1,
+//!     "search_space": {
+//!       "fast": { "min": 5, "max": 20, "step": 5 },
+//!       "slow": { "values": [30, 60, 90] }
+//!     }
+//!   }
+//!   ```
+//!
+//!   `min / max / step` 全为整数时生成整数；不带 `step` 的区间为连续区间 (不能用于网格搜索)。
+//! * **搜索**: 网格 / 随机 / 贝叶斯 (TPE：在表现最好的试验附近采样)，试验在多个线程上并行回测
+//! * **排序**: 按目标指标从高到低排序，非法参数 (策略构建失败) 的试验排在最后
+//! * **过拟合诊断**: 最优试验的 Deflated Sharpe Ratio (按试验次数修正的夏普显著性)；
+//!   滚动前推的样本外 / 样本内得分比 (Walk-Forward Efficiency)
+
+use crate::backtest::{BacktestConfig, BacktestMetrics, BacktestReport, Backtester};
+use crate::registry::StrategyRegistry;
+use anyhow::{bail, Result};
+use chrono::NaiveDate;
+use quant_core::market::MarketBar;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// 网格搜索的组合数上限
+const MAX_GRID: usize = 100_000;
+/// TPE 中“好”试验的比例
+const TPE_GAMMA: f64 = 0.25;
+/// TPE 每个新试验比较的候选数
+const TPE_CANDIDATES: usize = 24;
+
+// =========================================================================
+// 1. 参数空间
+// =========================================================================
+
+/// 单个参数的取值范围
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ParamRange {
+    /// 离散取值
+    Choice { values: Vec<Value> },
+    /// 数值区间 [min, max]
+    Range {
+        min: f64,
+        max: f64,
+        #[serde(default)]
+        step: Option<f64>,
+    },
+}
+
+impl ParamRange {
+    fn is_integer(&self) -> bool {
+        match self {
+            Self::Range { min, max, step } => {
+                step.is_some_and(|s| s.fract() == 0.0) && min.fract() == 0.0 && max.fract() == 0.0
+            }
+            Self::Choice { .. } => false,
+        }
+    }
+
+    fn validate(&self, path: &str) -> Result<()> {
+        match self {
+            Self::Choice { values } if values.is_empty() => {
+                bail!("Parameter {} has no values", path)
+            }
+            Self::Range { min, max, step } => {
+                if min > max {
+                    bail!("Parameter {} has min {} > max {}", path, min, max);
+                }
+                if step.is_some_and(|s| s <= 0.0) {
+                    bail!("Parameter {} must have a positive step", path);
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 网格取值
+    fn grid(&self, path: &str) -> Result<Vec<Value>> {
+        match self {
+            Self::Choice { values } => Ok(values.clone()),
+            Self::Range {
+                min,
+                max,
+                step: Some(step),
+            } => {
+                let count = ((max - min) / step + 1e-9).floor() as usize + 1;
+                Ok((0..count)
+                    .map(|i| self.value(min + step * i as f64))
+                    .collect())
+            }
+            Self::Range { step: None, .. } => {
+                bail!("Parameter {} needs a step for grid search", path)
+            }
+        }
+    }
+
+    /// 均匀随机取值
+    fn sample(&self, rng: &mut StdRng) -> Value {
+        match self {
+            Self::Choice { values } => values[rng.gen_range(0..values.len())].clone(),
+            Self::Range { min, max, step } => {
+                let x = if max > min {
+                    rng.gen_range(*min..=*max)
+                } else {
+                    *min
+                };
+                self.value(self.snap(x, *step))
+            }
+        }
+    }
+
+    /// 按步长对齐并截断到区间内
+    fn snap(&self, x: f64, step: Option<f64>) -> f64 {
+        let Self::Range { min, max, .. } = self else {
+            return x;
+        };
+        let x = match step {
+            Some(step) => min + ((x - min) / step).round() * step,
+            None => x,
+        };
+        x.clamp(*min, *max)
+    }
+
+    fn value(&self, x: f64) -> Value {
+        if self.is_integer() {
+            Value::from(x.round() as i64)
+        } else {
+            serde_json::Number::from_f64(x)
+                .map(Value::Number)
+                .unwrap_or(Value::Null)
+        }
+    }
+}
+
+/// 一组参数取值 (参数路径 -> 值)
+pub type ParamSet = BTreeMap<String, Value>;
+
+/// 参数空间：固定参数 + 待优化参数
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParamSpace {
+    pub base: Value,
+    pub ranges: BTreeMap<String, ParamRange>,
+}
+
+impl ParamSpace {
+    pub fn new(base: Value) -> Self {
+        Self {
+            base,
+            ranges: BTreeMap::new(),
+        }
+    }
+
+    /// 从 `Strategy.config` 读取：`key` 字段为待优化参数，其余为固定参数
+    pub fn from_config(config: &Value, key: &str) -> Result<Self> {
+        let mut base = config.clone();
+        let ranges = match base.as_object_mut().and_then(|o| o.remove(key)) {
+            Some(raw) => serde_json::from_value(raw)?,
+            None => BTreeMap::new(),
+        };
+        let space = Self { base, ranges };
+        space.validate()?;
+        Ok(space)
+    }
+
+    pub fn with_range(mut self, path: impl Into<String>, range: ParamRange) -> Self {
+        self.ranges.insert(path.into(), range);
+        self
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        for (path, range) in &self.ranges {
+            range.validate(path)?;
+        }
+        Ok(())
+    }
+
+    /// 全部网格组合
+    pub fn grid(&self) -> Result<Vec<ParamSet>> {
+        let mut combos = vec![ParamSet::new()];
+        for (path, range) in &self.ranges {
+            let values = range.grid(path)?;
+            if combos.len() * values.len() > MAX_GRID {
+                bail!(
+                    "Grid has more than {} combinations, use random or bayesian search",
+                    MAX_GRID
+                );
+            }
+            combos = combos
+                .into_iter()
+                .flat_map(|combo| {
+                    values.iter().map(move |v| {
+                        let mut next = combo.clone();
+                        next.insert(path.clone(), v.clone());
+                        next
+                    })
+                })
+                .collect();
+        }
+        Ok(combos)
+    }
+
+    /// 随机采样一组参数
+    pub fn sample(&self, rng: &mut StdRng) -> ParamSet {
+        self.ranges
+            .iter()
+            .map(|(path, range)| (path.clone(), range.sample(rng)))
+            .collect()
+    }
+
+    /// 把参数取值写入固定参数，得到传给策略工厂的完整参数
+    pub fn params(&self, set: &ParamSet) -> Value {
+        let mut params = match &self.base {
+            Value::Null => Value::Object(Map::new()),
+            other => other.clone(),
+        };
+        for (path, value) in set {
+            set_path(&mut params, path, value.clone());
+        }
+        params
+    }
+}
+
+/// 按 `a.b.c` 路径写入 JSON (中间对象不存在时创建)
+fn set_path(target: &mut Value, path: &str, value: Value) {
+    let mut current = target;
+    let mut parts = path.split('.').peekable();
+    while let Some(part) = parts.next() {
+        if !current.is_object() {
+            *current = Value::Object(Map::new());
+        }
+        let object = current.as_object_mut().expect("object");
+        if parts.peek().is_none() {
+            object.insert(part.to_string(), value);
+            return;
+        }
+        current = object.entry(part).or_insert(Value::Object(Map::new()));
+    }
+}
+
+// =========================================================================
+// 2. 搜索方式与目标
+// =========================================================================
+
+/// 搜索方式
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum Search {
+    Grid,
+    Random {
+        trials: usize,
+        seed: u64,
+    },
+    /// TPE：先随机探索 `initial` 次，之后在表现最好的试验附近采样
+    Bayesian {
+        trials: usize,
+        initial: usize,
+        seed: u64,
+    },
+}
+
+/// 排序目标 (越大越好)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Objective {
+    #[default]
+    Sharpe,
+    TotalReturn,
+    AnnualizedReturn,
+    /// 年化收益 / 最大回撤
+    Calmar,
+    /// 最大回撤取负 (回撤越小越好)
+    MaxDrawdown,
+    WinRate,
+}
+
+impl Objective {
+    pub fn score(&self, metrics: &BacktestMetrics) -> f64 {
+        match self {
+            Self::Sharpe => metrics.sharpe,
+            Self::TotalReturn => metrics.total_return,
+            Self::AnnualizedReturn => metrics.annualized_return,
+            Self::Calmar if metrics.max_drawdown > 0.0 => {
+                metrics.annualized_return / metrics.max_drawdown
+            }
+            Self::Calmar => metrics.annualized_return,
+            Self::MaxDrawdown => -metrics.max_drawdown,
+            Self::WinRate => metrics.win_rate.unwrap_or(0.0),
+        }
+    }
+}
+
+// =========================================================================
+// 3. 试验与报告
+// =========================================================================
+
+/// 单次试验 (一组参数的一次回测)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trial {
+    pub id: usize,
+    pub params: ParamSet,
+    /// 目标得分 (参数非法时为 None)
+    pub score: Option<f64>,
+    pub metrics: Option<BacktestMetrics>,
+    pub error: Option<String>,
+    /// 周期收益率 (用于夏普显著性检验)
+    #[serde(skip)]
+    pub returns: Vec<f64>,
+}
+
+/// 一次优化的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptimizationReport {
+    pub strategy: String,
+    pub objective: Objective,
+    /// 按得分从高到低排序
+    pub trials: Vec<Trial>,
+    /// 最优试验的 Deflated Sharpe Ratio (有效试验不足或收益序列过短时为 None)
+    pub deflated_sharpe: Option<f64>,
+}
+
+impl OptimizationReport {
+    pub fn best(&self) -> Option<&Trial> {
+        self.trials.first().filter(|t| t.score.is_some())
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// 每个试验一行：排名、参数 (JSON)、得分和主要指标
+    pub fn write_trials_csv<W: Write>(&self, writer: W) -> Result<()> {
+        let mut wtr = csv::Writer::from_writer(writer);
+        wtr.write_record([
+            "rank",
+            "params",
+            "score",
+            "total_return",
+            "sharpe",
+            "max_drawdown",
+            "trade_count",
+            "error",
+        ])?;
+        for (rank, trial) in self.trials.iter().enumerate() {
+            let metric = |f: fn(&BacktestMetrics) -> String| {
+                trial.metrics.as_ref().map(f).unwrap_or_default()
+            };
+            wtr.write_record([
+                (rank + 1).to_string(),
+                serde_json::to_string(&trial.params)?,
+                trial.score.map(|s| s.to_string()).unwrap_or_default(),
+                metric(|m| m.total_return.to_string()),
+                metric(|m| m.sharpe.to_string()),
+                metric(|m| m.max_drawdown.to_string()),
+                metric(|m| m.trade_count.to_string()),
+                trial.error.clone().unwrap_or_default(),
+            ])?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+}
+
+/// 滚动前推切分 (按 K 线日期计数)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalkForward {
+    /// 样本内长度
+    pub train: usize,
+    /// 样本外长度 (也是每次向前滚动的步长)
+    pub test: usize,
+    /// true: 样本内起点固定、窗口逐步扩大；false: 固定长度滚动窗口
+    #[serde(default)]
+    pub anchored: bool,
+}
+
+/// 单个前推窗口的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkForwardFold {
+    pub fold: usize,
+    pub train_start: NaiveDate,
+    pub train_end: NaiveDate,
+    pub test_start: NaiveDate,
+    pub test_end: NaiveDate,
+    /// 样本内最优参数
+    pub params: ParamSet,
+    pub in_sample_score: f64,
+    pub in_sample_deflated_sharpe: Option<f64>,
+    pub out_of_sample_score: f64,
+    pub out_of_sample: BacktestMetrics,
+    pub trials: usize,
+}
+
+/// 滚动前推报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkForwardReport {
+    pub strategy: String,
+    pub objective: Objective,
+    pub config: WalkForward,
+    pub folds: Vec<WalkForwardFold>,
+    pub mean_in_sample_score: f64,
+    pub mean_out_of_sample_score: f64,
+    /// 样本外 / 样本内平均得分 (样本内得分非正时为 None)，明显低于 1 说明过拟合
+    pub efficiency: Option<f64>,
+    /// 各窗口样本外收益复利后的总收益
+    pub out_of_sample_return: f64,
+}
+
+impl WalkForwardReport {
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+// =========================================================================
+// 4. 优化器
+// =========================================================================
+
+/// 参数优化器
+pub struct Optimizer {
+    registry: StrategyRegistry,
+    class_name: String,
+    space: ParamSpace,
+    backtest: BacktestConfig,
+    objective: Objective,
+    search: Search,
+    threads: usize,
+}
+
+impl Optimizer {
+    pub fn new(
+        registry: StrategyRegistry,
+        class_name: impl Into<String>,
+        space: ParamSpace,
+        backtest: BacktestConfig,
+    ) -> Self {
+        Self {
+            registry,
+            class_name: class_name.into(),
+            space,
+            backtest,
+            objective: Objective::default(),
+            search: Search::Grid,
+            threads: std::thread::available_parallelism().map_or(1, |n| n.get()),
+        }
+    }
+
+    pub fn with_objective(mut self, objective: Objective) -> Self {
+        self.objective = objective;
+        self
+    }
+
+    pub fn with_search(mut self, search: Search) -> Self {
+        self.search = search;
+        self
+    }
+
+    /// 并行回测的线程数 (默认 CPU 核数)
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// 在全部 K 线上搜索参数
+    pub fn optimize(&self, bars: &[MarketBar]) -> Result<OptimizationReport> {
+        self.space.validate()?;
+        let trials = match &self.search {
+            Search::Grid => self.evaluate(self.space.grid()?, 0, bars),
+            Search::Random { trials, seed } => {
+                let mut rng = StdRng::seed_from_u64(*seed);
+                let mut seen = HashSet::new();
+                let mut sets = Vec::new();
+                for _ in 0..trials.saturating_mul(10) {
+                    if sets.len() >= *trials {
+                        break;
+                    }
+                    let set = self.space.sample(&mut rng);
+                    if seen.insert(key(&set)) {
+                        sets.push(set);
+                    }
+                }
+                self.evaluate(sets, 0, bars)
+            }
+            Search::Bayesian {
+                trials,
+                initial,
+                seed,
+            } => self.bayesian(*trials, *initial, *seed, bars),
+        };
+        Ok(self.report(trials))
+    }
+
+    /// 滚动前推：每个窗口在样本内优化，用最优参数在紧随其后的样本外区间回测
+    pub fn walk_forward(
+        &self,
+        bars: &[MarketBar],
+        config: WalkForward,
+    ) -> Result<WalkForwardReport> {
+        if config.train == 0 || config.test == 0 {
+            bail!("Walk-forward train and test lengths must be > 0");
+        }
+        let dates: Vec<NaiveDate> = bars
+            .iter()
+            .map(|b| b.start_time)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        if dates.len() < config.train + config.test {
+            bail!(
+                "Walk-forward needs at least {} bar dates, got {}",
+                config.train + config.test,
+                dates.len()
+            );
+        }
+        let slice = |from: NaiveDate, to: NaiveDate| -> Vec<MarketBar> {
+            bars.iter()
+                .filter(|b| b.start_time >= from && b.start_time <= to)
+                .cloned()
+                .collect()
+        };
+
+        let mut folds = Vec::new();
+        let mut train_end = config.train;
+        while train_end + config.test <= dates.len() {
+            let train_start = if config.anchored {
+                0
+            } else {
+                train_end - config.train
+            };
+            let test_end = train_end + config.test;
+            let (is_from, is_to) = (dates[train_start], dates[train_end - 1]);
+            let (oos_from, oos_to) = (dates[train_end], dates[test_end - 1]);
+
+            let train = slice(is_from, is_to);
+            let optimized = self.optimize(&train)?;
+            let Some(best) = optimized.best() else {
+                bail!(
+                    "No valid parameters in walk-forward fold {} ({} ~ {})",
+                    folds.len() + 1,
+                    is_from,
+                    is_to
+                );
+            };
+            let test = self.run(&best.params, &train, &slice(oos_from, oos_to))?;
+            folds.push(WalkForwardFold {
+                fold: folds.len() + 1,
+                train_start: is_from,
+                train_end: is_to,
+                test_start: oos_from,
+                test_end: oos_to,
+                params: best.params.clone(),
+                in_sample_score: best.score.unwrap_or_default(),
+                in_sample_deflated_sharpe: optimized.deflated_sharpe,
+                out_of_sample_score: self.objective.score(&test.metrics),
+                out_of_sample: test.metrics,
+                trials: optimized.trials.len(),
+            });
+            train_end += config.test;
+        }
+
+        let n = folds.len() as f64;
+        let mean_in_sample_score = folds.iter().map(|f| f.in_sample_score).sum::<f64>() / n;
+        let mean_out_of_sample_score = folds.iter().map(|f| f.out_of_sample_score).sum::<f64>() / n;
+        Ok(WalkForwardReport {
+            strategy: self.class_name.clone(),
+            objective: self.objective,
+            config,
+            efficiency: (mean_in_sample_score > 0.0)
+                .then(|| mean_out_of_sample_score / mean_in_sample_score),
+            out_of_sample_return: folds
+                .iter()
+                .map(|f| 1.0 + f.out_of_sample.total_return)
+                .product::<f64>()
+                - 1.0,
+            mean_in_sample_score,
+            mean_out_of_sample_score,
+            folds,
+        })
+    }
+
+    /// 用一组参数回测 (warmup 只用于预热策略)
+    fn run(
+        &self,
+        set: &ParamSet,
+        warmup: &[MarketBar],
+        bars: &[MarketBar],
+    ) -> Result<BacktestReport> {
+        let mut strategy = self
+            .registry
+            .create(&self.class_name, &self.space.params(set))?;
+        Backtester::new(self.backtest.clone()).run_with_warmup(strategy.as_mut(), warmup, bars)
+    }
+
+    fn trial(&self, id: usize, set: ParamSet, bars: &[MarketBar]) -> Trial {
+        match self.run(&set, &[], bars) {
+            Ok(report) => Trial {
+                id,
+                params: set,
+                score: Some(self.objective.score(&report.metrics)).filter(|s| s.is_finite()),
+                returns: period_returns(&report),
+                metrics: Some(report.metrics),
+                error: None,
+            },
+            Err(e) => Trial {
+                id,
+                params: set,
+                score: None,
+                metrics: None,
+                error: Some(format!("{:#}", e)),
+                returns: Vec::new(),
+            },
+        }
+    }
+
+    /// 多线程回测一批参数 (试验编号从 `first_id` 开始，结果保持输入顺序)
+    fn evaluate(&self, sets: Vec<ParamSet>, first_id: usize, bars: &[MarketBar]) -> Vec<Trial> {
+        let next = AtomicUsize::new(0);
+        let results: Mutex<Vec<Option<Trial>>> = Mutex::new(vec![None; sets.len()]);
+        std::thread::scope(|scope| {
+            for _ in 0..self.threads.min(sets.len()) {
+                scope.spawn(|| loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(set) = sets.get(i) else {
+                        break;
+                    };
+                    let trial = self.trial(first_id + i, set.clone(), bars);
+                    results.lock().unwrap()[i] = Some(trial);
+                });
+            }
+        });
+        results
+            .into_inner()
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// TPE 搜索：每轮按线程数并行评估一批候选
+    fn bayesian(&self, trials: usize, initial: usize, seed: u64, bars: &[MarketBar]) -> Vec<Trial> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut done: Vec<Trial> = Vec::new();
+        let mut seen = HashSet::new();
+        let mut attempts = 0;
+        while done.len() < trials && attempts < trials.saturating_mul(10) {
+            let batch_size = self.threads.min(trials - done.len());
+            let mut batch = Vec::new();
+            while batch.len() < batch_size && attempts < trials.saturating_mul(10) {
+                attempts += 1;
+                let valid = done.iter().filter(|t| t.score.is_some()).count();
+                let set = if done.len() + batch.len() < initial || valid < 2 {
+                    self.space.sample(&mut rng)
+                } else {
+                    self.propose(&done, &seen, &mut rng)
+                };
+                if seen.insert(key(&set)) {
+                    batch.push(set);
+                }
+            }
+            let first_id = done.len();
+            done.extend(self.evaluate(batch, first_id, bars));
+        }
+        done
+    }
+
+    /// 在“好”试验附近采样若干候选，取未试过的候选中 l(x) / g(x) 最大者
+    fn propose(&self, done: &[Trial], seen: &HashSet<String>, rng: &mut StdRng) -> ParamSet {
+        let mut scored: Vec<&Trial> = done.iter().filter(|t| t.score.is_some()).collect();
+        scored.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
+        let n_good = ((scored.len() as f64 * TPE_GAMMA).ceil() as usize).clamp(1, scored.len() - 1);
+        let (good, bad) = scored.split_at(n_good);
+
+        let mut best: Option<(f64, ParamSet)> = None;
+        for _ in 0..TPE_CANDIDATES {
+            let anchor = good[rng.gen_range(0..good.len())];
+            let candidate: ParamSet = self
+                .space
+                .ranges
+                .iter()
+                .map(|(path, range)| {
+                    let value = perturb(range, anchor.params.get(path), rng);
+                    (path.clone(), value)
+                })
+                .collect();
+            if seen.contains(&key(&candidate)) {
+                continue;
+            }
+            let ratio: f64 = self
+                .space
+                .ranges
+                .iter()
+                .map(|(path, range)| {
+                    let x = &candidate[path];
+                    density(range, x, good, path).ln() - density(range, x, bad, path).ln()
+                })
+                .sum();
+            if best.as_ref().is_none_or(|(r, _)| ratio > *r) {
+                best = Some((ratio, candidate));
+            }
+        }
+        best.map(|(_, set)| set)
+            .unwrap_or_else(|| self.space.sample(rng))
+    }
+
+    fn report(&self, mut trials: Vec<Trial>) -> OptimizationReport {
+        trials.sort_by(|a, b| match (a.score, b.score) {
+            (Some(x), Some(y)) => y.partial_cmp(&x).unwrap().then(a.id.cmp(&b.id)),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => a.id.cmp(&b.id),
+        });
+        let sharpes: Vec<f64> = trials
+            .iter()
+            .filter(|t| t.score.is_some())
+            .filter_map(|t| sharpe(&t.returns))
+            .collect();
+        let deflated_sharpe = trials
+            .first()
+            .filter(|t| t.score.is_some())
+            .and_then(|best| deflated_sharpe(&best.returns, &sharpes));
+        OptimizationReport {
+            strategy: self.class_name.clone(),
+            objective: self.objective,
+            trials,
+            deflated_sharpe,
+        }
+    }
+}
+
+fn key(set: &ParamSet) -> String {
+    serde_json::to_string(set).unwrap_or_default()
+}
+
+/// 权益曲线的周期收益率
+fn period_returns(report: &BacktestReport) -> Vec<f64> {
+    let mut prev = report.config.initial_cash.to_f64().unwrap_or(0.0);
+    let mut returns = Vec::with_capacity(report.equity_curve.len());
+    for point in &report.equity_curve {
+        let equity = point.equity.to_f64().unwrap_or(0.0);
+        if prev > 0.0 {
+            returns.push(equity / prev - 1.0);
+        }
+        prev = equity;
+    }
+    returns
+}
+
+/// 以锚点为中心的随机扰动 (离散参数 75% 概率沿用锚点)
+fn perturb(range: &ParamRange, anchor: Option<&Value>, rng: &mut StdRng) -> Value {
+    match (range, anchor) {
+        (ParamRange::Choice { .. }, Some(value)) if rng.gen_bool(0.75) => value.clone(),
+        (ParamRange::Range { min, max, step }, Some(value)) => {
+            let Some(center) = value.as_f64() else {
+                return range.sample(rng);
+            };
+            let x = center + gaussian(rng) * bandwidth(*min, *max);
+            range.value(range.snap(x, *step))
+        }
+        _ => range.sample(rng),
+    }
+}
+
+fn bandwidth(min: f64, max: f64) -> f64 {
+    ((max - min) / 5.0).max(f64::EPSILON)
+}
+
+/// 一组试验在 x 处的 Parzen 密度 (数值参数用高斯核，离散参数用加一平滑的频率)
+fn density(range: &ParamRange, x: &Value, trials: &[&Trial], path: &str) -> f64 {
+    let n = trials.len() as f64;
+    match range {
+        ParamRange::Choice { values } => {
+            let hits = trials
+                .iter()
+                .filter(|t| t.params.get(path) == Some(x))
+                .count() as f64;
+            (hits + 1.0) / (n + values.len() as f64)
+        }
+        ParamRange::Range { min, max, .. } => {
+            let x = x.as_f64().unwrap_or(*min);
+            let h = bandwidth(*min, *max);
+            let sum: f64 = trials
+                .iter()
+                .filter_map(|t| t.params.get(path).and_then(Value::as_f64))
+                .map(|c| (-0.5 * ((x - c) / h).powi(2)).exp())
+                .sum();
+            // 加一个覆盖整个区间的宽核，避免密度为 0
+            (sum + 1.0 / (max - min + 1.0)) / (n + 1.0)
+        }
+    }
+}
+
+/// 标准正态随机数 (Box-Muller)
+fn gaussian(rng: &mut StdRng) -> f64 {
+    let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+    let u2: f64 = rng.gen_range(0.0..1.0);
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+// =========================================================================
+// 5. 夏普显著性
+// =========================================================================
+
+/// 周期夏普 (不年化)
+pub fn sharpe(returns: &[f64]) -> Option<f64> {
+    let (mean, variance) = moments(returns)?;
+    (variance > 0.0).then(|| mean / variance.sqrt())
+}
+
+fn moments(values: &[f64]) -> Option<(f64, f64)> {
+    if values.len() < 2 {
+        return None;
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    Some((mean, variance))
+}
+
+/// Probabilistic Sharpe Ratio：真实夏普超过 `benchmark` (周期夏普) 的概率，考虑偏度和峰度
+pub fn probabilistic_sharpe(returns: &[f64], benchmark: f64) -> Option<f64> {
+    let sr = sharpe(returns)?;
+    let n = returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / n;
+    let m2 = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n;
+    let skew = returns.iter().map(|r| (r - mean).powi(3)).sum::<f64>() / n / m2.powf(1.5);
+    let kurt = returns.iter().map(|r| (r - mean).powi(4)).sum::<f64>() / n / m2.powi(2);
+    let denom = 1.0 - skew * sr + (kurt - 1.0) / 4.0 * sr * sr;
+    if denom <= 0.0 {
+        return None;
+    }
+    Some(normal_cdf(
+        (sr - benchmark) * (n - 1.0).sqrt() / denom.sqrt(),
+    ))
+}
+
+/// Deflated Sharpe Ratio (Bailey & López de Prado, 2014)
+///
+/// 以 N 次试验夏普的期望最大值作为基准计算 PSR：
+/// `SR0 = sqrt(Var[SR]) * ((1 - γ) Φ⁻¹(1 - 1/N) + γ Φ⁻¹(1 - 1/(N e)))`，γ 为欧拉常数。
+/// 只有一次试验时退化为基准为 0 的 PSR。
+pub fn deflated_sharpe(returns: &[f64], trial_sharpes: &[f64]) -> Option<f64> {
+    const EULER_GAMMA: f64 = 0.577_215_664_901_532_9;
+    let benchmark = match moments(trial_sharpes) {
+        Some((_, variance)) => {
+            let n = trial_sharpes.len() as f64;
+            variance.sqrt()
+                * ((1.0 - EULER_GAMMA) * normal_inv(1.0 - 1.0 / n)
+                    + EULER_GAMMA * normal_inv(1.0 - 1.0 / (n * std::f64::consts::E)))
+        }
+        None => 0.0,
+    };
+    probabilistic_sharpe(returns, benchmark)
+}
+
+/// 标准正态分布函数
+pub fn normal_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x / std::f64::consts::SQRT_2)
+}
+
+/// 互补误差函数 (Numerical Recipes erfcc，相对误差 < 1.2e-7)
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let r = t * poly.exp();
+    if x >= 0.0 {
+        r
+    } else {
+        2.0 - r
+    }
+}
+
+/// 标准正态分位数 (Acklam 有理逼近，相对误差 < 1.2e-9)
+pub fn normal_inv(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const LOW: f64 = 0.02425;
+
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
@@ -0,0 +1,777 @@
+//! 多策略组合回测 (Portfolio Backtest)
+//!
+//! 多个策略 (sleeve) 在同一个账户上运行，与实盘引擎的资金模型一致：
+//!
+//! * **共享资金**: 所有策略看到同一份现金和净值，买入受组合现金约束 (资金争用)
+//! * **跨策略轧差**: 同一标的同一开盘的下单意图先在策略之间对冲，只有净额进入市场成交；
+//!   对冲部分按开盘价内部成交，净额的滑点和手续费由净额方向上的策略按数量分摊
+//! * **归因**: 每个策略有独立的虚拟持仓和资金流水，策略盈亏之和等于组合盈亏
+//! * **组合风控**: 总敞口、单标的权重、单笔名义价值、回撤熔断 (触发后只允许卖出)
+//! * **日历对齐**: 时间轴为各交易所交易日的并集，按交易所日历丢弃休市日的 K 线；
+//!   休市标的沿用最近收盘价估值，订单挂到该标的下一个开盘成交
+//! * **按交易所区分标的**: 持仓、价格按 (交易所, 标的) 记账，不同交易所的同名标的互不轧差
+//!
+//! 混合美股与加密货币时，组合按自然日计净值，年化周期数应设为 365。
+
+use crate::backtest::{compute_metrics, BacktestConfig, BacktestMetrics, Backtester, Book};
+use crate::backtest::{EquityPoint, Trade};
+use crate::traits::{OrderIntent, StrategyContext, TradingStrategy};
+use anyhow::{bail, Result};
+use chrono::NaiveDate;
+use quant_core::calendar::TradingCalendar;
+use quant_core::enums::{Exchange, Side};
+use quant_core::market::MarketBar;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
+
+// =========================================================================
+// 1. 配置与结果
+// =========================================================================
+
+/// 组合内的标的：不同交易所的同名标的分开记账
+type Listing = (Exchange, String);
+
+/// 组合级风控限额 (None 表示不限制)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PortfolioLimits {
+    /// 持仓总市值 / 净值上限 (1.0 = 满仓)
+    pub max_gross_exposure: Option<Decimal>,
+    /// 单一标的市值 / 净值上限
+    pub max_symbol_weight: Option<Decimal>,
+    /// 单笔 (轧差后) 买单最大名义价值
+    pub max_order_notional: Option<Decimal>,
+    /// 组合回撤达到该比例后停止开仓，直到回测结束只允许卖出 (0.2 = 20%)
+    pub max_drawdown: Option<Decimal>,
+}
+
+/// 组合中的一个策略
+pub struct Sleeve {
+    /// 策略实例名 (组合内唯一，用于归因)
+    pub name: String,
+    pub strategy: Box<dyn TradingStrategy>,
+    /// 策略接收行情的标的 (为空表示全部标的)
+    pub symbols: Vec<String>,
+    /// 策略接收行情的交易所 (None 表示全部交易所)
+    pub exchange: Option<Exchange>,
+}
+
+impl Sleeve {
+    pub fn new(
+        name: impl Into<String>,
+        strategy: Box<dyn TradingStrategy>,
+        symbols: Vec<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            strategy,
+            symbols,
+            exchange: None,
+        }
+    }
+
+    /// 只接收某个交易所的行情，下单也只路由到该交易所
+    pub fn with_exchange(mut self, exchange: Exchange) -> Self {
+        self.exchange = Some(exchange);
+        self
+    }
+
+    fn receives(&self, bar: &MarketBar) -> bool {
+        self.exchange.is_none_or(|e| e == bar.exchange)
+            && (self.symbols.is_empty() || self.symbols.contains(&bar.symbol.to_string()))
+    }
+}
+
+/// 策略累计盈亏快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PnlPoint {
+    pub date: NaiveDate,
+    pub pnl: Decimal,
+}
+
+/// 单个策略的归因结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SleeveReport {
+    pub name: String,
+    /// 策略逻辑类名
+    pub strategy: String,
+    pub symbols: Vec<String>,
+    /// 总盈亏 (已实现 + 持仓浮动，已扣除分摊的滑点和手续费)
+    pub pnl: Decimal,
+    pub realized_pnl: Decimal,
+    pub fees: Decimal,
+    /// 盈亏占组合初始资金的比例
+    pub contribution: f64,
+    /// 盈利的平仓交易占比 (没有平仓时为 None)
+    pub win_rate: Option<f64>,
+    /// 期末虚拟持仓
+    pub positions: BTreeMap<String, Decimal>,
+    /// 策略视角的成交 (对冲部分按开盘价、无费用)
+    pub trades: Vec<Trade>,
+    pub pnl_curve: Vec<PnlPoint>,
+}
+
+/// 组合风控触发记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskBreach {
+    pub date: NaiveDate,
+    /// 触发的规则 (cash / max_gross_exposure / max_symbol_weight / max_order_notional / max_drawdown)
+    pub rule: String,
+    pub symbol: Option<String>,
+    pub message: String,
+}
+
+/// 组合回测报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioReport {
+    pub config: BacktestConfig,
+    pub limits: PortfolioLimits,
+    pub metrics: BacktestMetrics,
+    /// 轧差后实际进入市场的成交
+    pub trades: Vec<Trade>,
+    pub equity_curve: Vec<EquityPoint>,
+    pub sleeves: Vec<SleeveReport>,
+    /// 策略间内部对冲的名义价值 (省下了这部分的滑点和手续费)
+    pub crossed_notional: Decimal,
+    pub risk_events: Vec<RiskBreach>,
+    /// 落在交易所休市日而被丢弃的 K 线数
+    pub skipped_bars: usize,
+}
+
+#[derive(Serialize)]
+struct AttributionRow<'a> {
+    date: NaiveDate,
+    sleeve: &'a str,
+    pnl: Decimal,
+}
+
+impl PortfolioReport {
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn write_trades_csv<W: Write>(&self, writer: W) -> Result<()> {
+        let mut wtr = csv::Writer::from_writer(writer);
+        for trade in &self.trades {
+            wtr.serialize(trade)?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    pub fn write_equity_csv<W: Write>(&self, writer: W) -> Result<()> {
+        let mut wtr = csv::Writer::from_writer(writer);
+        for point in &self.equity_curve {
+            wtr.serialize(point)?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    /// 每个策略每个周期的累计盈亏 (date, sleeve, pnl)
+    pub fn write_attribution_csv<W: Write>(&self, writer: W) -> Result<()> {
+        let mut wtr = csv::Writer::from_writer(writer);
+        for sleeve in &self.sleeves {
+            for point in &sleeve.pnl_curve {
+                wtr.serialize(AttributionRow {
+                    date: point.date,
+                    sleeve: &sleeve.name,
+                    pnl: point.pnl,
+                })?;
+            }
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+}
+
+// =========================================================================
+// 2. 策略账本
+// =========================================================================
+
+/// 单个策略的虚拟持仓和资金流水 (不持有现金，现金在组合账户中)
+#[derive(Debug, Default)]
+struct Ledger {
+    cash_flow: Decimal,
+    positions: HashMap<Listing, Decimal>,
+    avg_cost: HashMap<Listing, Decimal>,
+    realized: Decimal,
+    fees: Decimal,
+    trades: Vec<Trade>,
+    pnl_curve: Vec<PnlPoint>,
+}
+
+impl Ledger {
+    fn position(&self, listing: &Listing) -> Decimal {
+        self.positions
+            .get(listing)
+            .copied()
+            .unwrap_or(Decimal::ZERO)
+    }
+
+    /// 策略视角的持仓 (每个策略的同名标的只来自一个交易所)
+    fn symbol_positions(&self) -> HashMap<String, Decimal> {
+        self.positions
+            .iter()
+            .map(|((_, symbol), qty)| (symbol.clone(), *qty))
+            .collect()
+    }
+
+    fn pnl(&self, marks: &HashMap<Listing, Decimal>) -> Decimal {
+        self.cash_flow
+            + self
+                .positions
+                .iter()
+                .map(|(listing, qty)| *qty * marks.get(listing).copied().unwrap_or(Decimal::ZERO))
+                .sum::<Decimal>()
+    }
+
+    /// 按实际分到的数量记账 (`quantity` 正数买入，负数卖出)
+    fn fill(
+        &mut self,
+        date: NaiveDate,
+        fill: &SleeveFill,
+        quantity: Decimal,
+        price: Decimal,
+        fee: Decimal,
+    ) {
+        let buy = quantity > Decimal::ZERO;
+        let quantity = quantity.abs();
+        let position = self.position(&fill.listing);
+        let avg_cost = self
+            .avg_cost
+            .get(&fill.listing)
+            .copied()
+            .unwrap_or(Decimal::ZERO);
+        let (side, realized_pnl) = if buy {
+            let cost = quantity * price + fee;
+            let new_position = position + quantity;
+            self.avg_cost.insert(
+                fill.listing.clone(),
+                (avg_cost * position + cost) / new_position,
+            );
+            self.positions.insert(fill.listing.clone(), new_position);
+            self.cash_flow -= cost;
+            (Side::Buy, None)
+        } else {
+            let realized = (price - avg_cost) * quantity - fee;
+            let remaining = position - quantity;
+            if remaining.is_zero() {
+                self.positions.remove(&fill.listing);
+                self.avg_cost.remove(&fill.listing);
+            } else {
+                self.positions.insert(fill.listing.clone(), remaining);
+            }
+            self.cash_flow += quantity * price - fee;
+            self.realized += realized;
+            (Side::Sell, Some(realized))
+        };
+        self.fees += fee;
+        self.trades.push(Trade {
+            date,
+            symbol: fill.listing.1.clone(),
+            side,
+            quantity,
+            price,
+            fee,
+            realized_pnl,
+            reason: fill.reason.clone(),
+        });
+    }
+}
+
+/// 某个策略在一次开盘撮合中的净下单 (正数买入，负数卖出)
+#[derive(Debug)]
+struct SleeveFill {
+    sleeve: usize,
+    listing: Listing,
+    quantity: Decimal,
+    reason: String,
+}
+
+// =========================================================================
+// 3. 组合回测器
+// =========================================================================
+
+/// 多策略、多标的、共享账户的回测器
+///
+/// 撮合规则与 [`Backtester`] 相同：收盘后下单，下一根 K 线开盘成交，不支持做空
+/// (每个策略的卖出不超过自己的虚拟持仓)。同一开盘先撮合净卖出的标的，释放的现金可用于买入。
+pub struct PortfolioBacktester {
+    config: BacktestConfig,
+    limits: PortfolioLimits,
+    calendars: HashMap<Exchange, TradingCalendar>,
+}
+
+impl PortfolioBacktester {
+    pub fn new(config: BacktestConfig) -> Self {
+        Self {
+            config,
+            limits: PortfolioLimits::default(),
+            calendars: HashMap::new(),
+        }
+    }
+
+    pub fn with_limits(mut self, limits: PortfolioLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// 覆盖某个交易所的日历 (默认 [`TradingCalendar::for_exchange`])
+    pub fn with_calendar(mut self, exchange: Exchange, calendar: TradingCalendar) -> Self {
+        self.calendars.insert(exchange, calendar);
+        self
+    }
+
+    fn is_trading_day(&self, exchange: Exchange, date: NaiveDate) -> bool {
+        match self.calendars.get(&exchange) {
+            Some(calendar) => calendar.is_trading_day(date),
+            None => TradingCalendar::for_exchange(exchange).is_trading_day(date),
+        }
+    }
+
+    pub fn run(&self, sleeves: &mut [Sleeve], bars: &[MarketBar]) -> Result<PortfolioReport> {
+        if sleeves.is_empty() {
+            bail!("Portfolio backtest needs at least one strategy");
+        }
+        let mut names = HashSet::new();
+        for sleeve in sleeves.iter() {
+            if !names.insert(sleeve.name.as_str()) {
+                bail!("Duplicate strategy name in portfolio: {}", sleeve.name);
+            }
+        }
+
+        // 1. 按交易所日历过滤 K 线，时间轴为交易日并集
+        let mut sorted: Vec<&MarketBar> = Vec::with_capacity(bars.len());
+        let mut skipped_bars = 0;
+        for bar in bars {
+            if self.is_trading_day(bar.exchange, bar.start_time) {
+                sorted.push(bar);
+            } else {
+                skipped_bars += 1;
+            }
+        }
+        sorted.sort_by_key(|b| (b.start_time, b.symbol.to_string(), b.exchange.to_string()));
+
+        // 策略下单只有标的代码，按它收到的行情路由到交易所；同名标的来自多个交易所时无法路由
+        let mut routes: Vec<HashMap<String, Exchange>> = vec![HashMap::new(); sleeves.len()];
+        for bar in &sorted {
+            for (index, sleeve) in sleeves.iter().enumerate() {
+                if !sleeve.receives(bar) {
+                    continue;
+                }
+                let symbol = bar.symbol.to_string();
+                match routes[index].insert(symbol.clone(), bar.exchange) {
+                    Some(previous) if previous != bar.exchange => bail!(
+                        "Strategy {} receives {} from both {} and {}, restrict it to one exchange",
+                        sleeve.name,
+                        symbol,
+                        previous,
+                        bar.exchange
+                    ),
+                    _ => {}
+                }
+            }
+        }
+
+        let executor = Backtester::new(self.config.clone());
+        let mut book: Book<Listing> = Book {
+            cash: self.config.initial_cash,
+            ..Default::default()
+        };
+        let mut ledgers: Vec<Ledger> = sleeves.iter().map(|_| Ledger::default()).collect();
+        let mut pending: Vec<(usize, Listing, OrderIntent)> = Vec::new();
+        let mut trades = Vec::new();
+        let mut equity_curve = Vec::new();
+        let mut risk_events = Vec::new();
+        let mut crossed_notional = Decimal::ZERO;
+        let mut peak = self.config.initial_cash;
+        let mut halted = false;
+
+        let mut i = 0;
+        while i < sorted.len() {
+            let date = sorted[i].start_time;
+            let mut j = i;
+            while j < sorted.len() && sorted[j].start_time == date {
+                j += 1;
+            }
+            let day = &sorted[i..j];
+
+            // 2. 开盘：按开盘价估值，轧差撮合上一周期的下单意图
+            let opens: HashMap<Listing, Decimal> = day
+                .iter()
+                .map(|b| ((b.exchange, b.symbol.to_string()), b.open.0))
+                .collect();
+            for (listing, open) in &opens {
+                book.last_close.insert(listing.clone(), *open);
+            }
+            let (ready, waiting): (Vec<_>, Vec<_>) = pending
+                .drain(..)
+                .partition(|(_, listing, _)| opens.contains_key(listing));
+            pending = waiting;
+
+            let mut batches = net_by_sleeve(ready, &ledgers);
+            // 先卖后买：净卖出的标的先成交
+            batches.sort_by_key(|((exchange, symbol), fills)| {
+                let net: Decimal = fills.iter().map(|f| f.quantity).sum();
+                (net > Decimal::ZERO, symbol.clone(), exchange.to_string())
+            });
+            for (listing, mut fills) in batches {
+                let open = opens[&listing];
+                if halted {
+                    if fills.iter().any(|f| f.quantity > Decimal::ZERO) {
+                        risk_events.push(RiskBreach {
+                            date,
+                            rule: "max_drawdown".to_string(),
+                            symbol: Some(listing.1.clone()),
+                            message: "drawdown limit reached, buy orders rejected".to_string(),
+                        });
+                    }
+                    fills.retain(|f| f.quantity < Decimal::ZERO);
+                } else {
+                    self.limit_buys(&book, date, &listing, open, &mut fills, &mut risk_events);
+                }
+                crossed_notional += self.settle(
+                    &executor,
+                    &mut book,
+                    &mut ledgers,
+                    date,
+                    &listing,
+                    open,
+                    &fills,
+                    &mut trades,
+                );
+            }
+
+            // 3. 收盘：更新价格并驱动各策略
+            for bar in day {
+                book.last_close
+                    .insert((bar.exchange, bar.symbol.to_string()), bar.close.0);
+            }
+            let equity = book.cash + book.market_value();
+            for (index, sleeve) in sleeves.iter_mut().enumerate() {
+                let mut ctx = StrategyContext {
+                    date: Some(date),
+                    cash: book.cash,
+                    equity,
+                    positions: ledgers[index].symbol_positions(),
+                    orders: Vec::new(),
+                };
+                for bar in day {
+                    if sleeve.receives(bar) {
+                        sleeve.strategy.on_bar(&mut ctx, bar);
+                    }
+                }
+                // 没收到过行情的标的没有开盘价，无法撮合
+                pending.extend(ctx.take_orders().into_iter().filter_map(|o| {
+                    let exchange = *routes[index].get(&o.symbol)?;
+                    Some((index, (exchange, o.symbol.clone()), o))
+                }));
+            }
+
+            // 4. 记录净值、归因和回撤熔断
+            let market_value = book.market_value();
+            equity_curve.push(EquityPoint {
+                date,
+                cash: book.cash,
+                market_value,
+                equity: book.cash + market_value,
+            });
+            for ledger in ledgers.iter_mut() {
+                let pnl = ledger.pnl(&book.last_close);
+                ledger.pnl_curve.push(PnlPoint { date, pnl });
+            }
+            peak = peak.max(equity);
+            if let Some(max) = self.limits.max_drawdown {
+                if !halted && peak > Decimal::ZERO && (peak - equity) / peak >= max {
+                    halted = true;
+                    risk_events.push(RiskBreach {
+                        date,
+                        rule: "max_drawdown".to_string(),
+                        symbol: None,
+                        message: format!(
+                            "equity {} is {:.2}% below peak {}, new positions halted",
+                            equity.round_dp(2),
+                            ((peak - equity) / peak * Decimal::ONE_HUNDRED).round_dp(2),
+                            peak.round_dp(2)
+                        ),
+                    });
+                }
+            }
+            i = j;
+        }
+
+        let metrics = compute_metrics(
+            &equity_curve,
+            &trades,
+            self.config.initial_cash,
+            self.config.periods_per_year,
+        );
+        let initial = self.config.initial_cash.to_f64().unwrap_or(0.0);
+        let sleeves = sleeves
+            .iter()
+            .zip(ledgers)
+            .map(|(sleeve, ledger)| {
+                let pnl = ledger.pnl(&book.last_close);
+                let closed: Vec<Decimal> = ledger
+                    .trades
+                    .iter()
+                    .filter_map(|t| t.realized_pnl)
+                    .collect();
+                SleeveReport {
+                    name: sleeve.name.clone(),
+                    strategy: sleeve.strategy.name().to_string(),
+                    symbols: sleeve.symbols.clone(),
+                    pnl,
+                    realized_pnl: ledger.realized,
+                    fees: ledger.fees,
+                    contribution: if initial > 0.0 {
+                        pnl.to_f64().unwrap_or(0.0) / initial
+                    } else {
+                        0.0
+                    },
+                    win_rate: if closed.is_empty() {
+                        None
+                    } else {
+                        Some(
+                            closed.iter().filter(|p| **p > Decimal::ZERO).count() as f64
+                                / closed.len() as f64,
+                        )
+                    },
+                    positions: ledger.symbol_positions().into_iter().collect(),
+                    trades: ledger.trades,
+                    pnl_curve: ledger.pnl_curve,
+                }
+            })
+            .collect();
+
+        Ok(PortfolioReport {
+            config: self.config.clone(),
+            limits: self.limits.clone(),
+            metrics,
+            trades,
+            equity_curve,
+            sleeves,
+            crossed_notional,
+            risk_events,
+            skipped_bars,
+        })
+    }
+
+    /// 按现金和组合限额压缩买单，使净买入量不超过可承受的上限
+    fn limit_buys(
+        &self,
+        book: &Book<Listing>,
+        date: NaiveDate,
+        listing: &Listing,
+        open: Decimal,
+        fills: &mut [SleeveFill],
+        risk_events: &mut Vec<RiskBreach>,
+    ) {
+        let buys: Decimal = fills
+            .iter()
+            .filter(|f| f.quantity > Decimal::ZERO)
+            .map(|f| f.quantity)
+            .sum();
+        let sells: Decimal = fills
+            .iter()
+            .filter(|f| f.quantity < Decimal::ZERO)
+            .map(|f| -f.quantity)
+            .sum();
+        if buys <= sells || open <= Decimal::ZERO {
+            return;
+        }
+
+        let price = open * (Decimal::ONE + self.config.slippage_bps / Decimal::from(10_000));
+        let equity = book.cash + book.market_value();
+        let mut caps = vec![(
+            "cash",
+            book.cash / (price * (Decimal::ONE + self.config.fee_rate)),
+        )];
+        if let Some(max) = self.limits.max_gross_exposure {
+            caps.push((
+                "max_gross_exposure",
+                (max * equity - book.market_value()) / open,
+            ));
+        }
+        if let Some(max) = self.limits.max_symbol_weight {
+            let held = book
+                .positions
+                .get(listing)
+                .copied()
+                .unwrap_or(Decimal::ZERO);
+            caps.push(("max_symbol_weight", (max * equity - held * open) / open));
+        }
+        if let Some(max) = self.limits.max_order_notional {
+            caps.push(("max_order_notional", max / price));
+        }
+        let (rule, cap) = caps
+            .into_iter()
+            .min_by_key(|(_, cap)| *cap)
+            .expect("cash cap always present");
+        let cap = round_down(cap.max(Decimal::ZERO));
+        if buys - sells <= cap {
+            return;
+        }
+
+        let factor = (cap + sells) / buys;
+        for fill in fills.iter_mut().filter(|f| f.quantity > Decimal::ZERO) {
+            fill.quantity = round_down(fill.quantity * factor);
+        }
+        risk_events.push(RiskBreach {
+            date,
+            rule: rule.to_string(),
+            symbol: Some(listing.1.clone()),
+            message: format!(
+                "net buy {} reduced to {} ({} buy orders scaled by {})",
+                buys - sells,
+                cap,
+                fills.iter().filter(|f| f.quantity > Decimal::ZERO).count(),
+                factor.round_dp(4)
+            ),
+        });
+    }
+
+    /// 净额进入市场，对冲部分按开盘价内部成交；返回内部对冲的名义价值
+    ///
+    /// 市场只成交了部分净额时 (现金 / 持仓不足)，净额方向上的策略按下单量比例分摊实际成交量。
+    #[allow(clippy::too_many_arguments)]
+    fn settle(
+        &self,
+        executor: &Backtester,
+        book: &mut Book<Listing>,
+        ledgers: &mut [Ledger],
+        date: NaiveDate,
+        listing: &Listing,
+        open: Decimal,
+        fills: &[SleeveFill],
+        trades: &mut Vec<Trade>,
+    ) -> Decimal {
+        let buys: Decimal = fills
+            .iter()
+            .filter(|f| f.quantity > Decimal::ZERO)
+            .map(|f| f.quantity)
+            .sum();
+        let sells: Decimal = fills
+            .iter()
+            .filter(|f| f.quantity < Decimal::ZERO)
+            .map(|f| -f.quantity)
+            .sum();
+        let net = buys - sells;
+        let crossed = buys.min(sells);
+
+        // 净额方向上的策略分摊市场成交的滑点和手续费
+        let mut filled = Decimal::ZERO;
+        let mut slippage = Decimal::ZERO;
+        let mut fee = Decimal::ZERO;
+        if !net.is_zero() {
+            let intent = OrderIntent {
+                symbol: listing.1.clone(),
+                side: if net > Decimal::ZERO {
+                    Side::Buy
+                } else {
+                    Side::Sell
+                },
+                quantity: net.abs(),
+                reason: format!("net of {} orders", fills.len()),
+            };
+            if let Some(trade) = executor.execute(book, listing, date, &intent, open) {
+                filled = trade.quantity;
+                slippage = (trade.price - open).abs() * trade.quantity;
+                fee = trade.fee;
+                trades.push(trade);
+            }
+        }
+
+        // 净额方向实际到手的数量 = 内部对冲量 + 市场成交量
+        let on_net_side = |f: &SleeveFill| {
+            !net.is_zero()
+                && !f.quantity.is_zero()
+                && (f.quantity > Decimal::ZERO) == (net > Decimal::ZERO)
+        };
+        let received = crossed + filled;
+        let wanted: Vec<Decimal> = fills
+            .iter()
+            .filter(|f| on_net_side(f))
+            .map(|f| f.quantity.abs())
+            .collect();
+        let mut shares = pro_rata(&wanted, received).into_iter();
+
+        for fill in fills.iter().filter(|f| !f.quantity.is_zero()) {
+            let (quantity, price, fill_fee) = if on_net_side(fill) {
+                let quantity = shares.next().unwrap_or(Decimal::ZERO);
+                if quantity.is_zero() {
+                    continue;
+                }
+                let per_unit = slippage / received;
+                if fill.quantity > Decimal::ZERO {
+                    (quantity, open + per_unit, fee * quantity / received)
+                } else {
+                    (-quantity, open - per_unit, fee * quantity / received)
+                }
+            } else {
+                (fill.quantity, open, Decimal::ZERO)
+            };
+            ledgers[fill.sleeve].fill(date, fill, quantity, price, fill_fee);
+        }
+        crossed * open
+    }
+}
+
+/// 把待撮合的下单意图按 (标的, 策略) 合并为净下单，卖出不超过策略的虚拟持仓
+fn net_by_sleeve(
+    intents: Vec<(usize, Listing, OrderIntent)>,
+    ledgers: &[Ledger],
+) -> Vec<(Listing, Vec<SleeveFill>)> {
+    let mut grouped: HashMap<Listing, Vec<SleeveFill>> = HashMap::new();
+    for (sleeve, listing, intent) in intents {
+        let signed = match intent.side {
+            Side::Buy => intent.quantity,
+            Side::Sell => -intent.quantity,
+        };
+        let fills = grouped.entry(listing.clone()).or_default();
+        match fills.iter_mut().find(|f| f.sleeve == sleeve) {
+            Some(fill) => fill.quantity += signed,
+            None => fills.push(SleeveFill {
+                sleeve,
+                listing,
+                quantity: signed,
+                reason: intent.reason,
+            }),
+        }
+    }
+    for fills in grouped.values_mut() {
+        for fill in fills.iter_mut() {
+            let held = ledgers[fill.sleeve].position(&fill.listing);
+            if fill.quantity < -held {
+                fill.quantity = -held;
+            }
+        }
+        fills.retain(|f| !f.quantity.is_zero());
+    }
+    grouped.into_iter().filter(|(_, f)| !f.is_empty()).collect()
+}
+
+fn round_down(value: Decimal) -> Decimal {
+    value.round_dp_with_strategy(8, RoundingStrategy::ToZero)
+}
+
+/// 把 `total` 按 `weights` 比例拆分 (向下取整，零头给最后一份)，各份之和恰好等于 `total`
+fn pro_rata(weights: &[Decimal], total: Decimal) -> Vec<Decimal> {
+    let sum: Decimal = weights.iter().sum();
+    if sum.is_zero() {
+        return vec![Decimal::ZERO; weights.len()];
+    }
+    let mut shares: Vec<Decimal> = weights
+        .iter()
+        .map(|w| round_down(*w * total / sum))
+        .collect();
+    let allocated: Decimal = shares.iter().sum();
+    if let Some(last) = shares.last_mut() {
+        *last += total - allocated;
+    }
+    shares
+}
@@ -0,0 +1,55 @@
+use crate::builtin::{BuyAndHold, SmaCross};
+use crate::traits::TradingStrategy;
+use anyhow::{anyhow, Context, Result};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// 策略工厂：根据 JSON 参数构建策略实例
+pub type StrategyFactory = fn(&Value) -> Result<Box<dyn TradingStrategy>>;
+
+/// 策略注册表 (class_name -> 工厂)
+///
+/// 引擎、回测和 CLI 通过 `Strategy.class_name` + `Strategy.config` 实例化策略。
+#[derive(Clone, Default)]
+pub struct StrategyRegistry {
+    factories: BTreeMap<String, StrategyFactory>,
+}
+
+impl StrategyRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 包含全部内置策略的注册表
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        registry.register(BuyAndHold::CLASS_NAME, |params| {
+            let params = serde_json::from_value(params.clone())?;
+            Ok(Box::new(BuyAndHold::new(params)))
+        });
+        registry.register(SmaCross::CLASS_NAME, |params| {
+            let params = serde_json::from_value(params.clone())?;
+            Ok(Box::new(SmaCross::new(params)?))
+        });
+        registry
+    }
+
+    pub fn register(&mut self, class_name: impl Into<String>, factory: StrategyFactory) {
+        self.factories.insert(class_name.into(), factory);
+    }
+
+    pub fn create(&self, class_name: &str, params: &Value) -> Result<Box<dyn TradingStrategy>> {
+        let factory = self.factories.get(class_name).ok_or_else(|| {
+            anyhow!(
+                "Unknown strategy class '{}', available: {:?}",
+                class_name,
+                self.class_names()
+            )
+        })?;
+        factory(params).with_context(|| format!("Invalid params for strategy {}", class_name))
+    }
+
+    pub fn class_names(&self) -> Vec<&str> {
+        self.factories.keys().map(|k| k.as_str()).collect()
+    }
+}
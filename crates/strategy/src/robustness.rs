@@ -0,0 +1,406 @@
+//! 回测稳健性分析 (Monte Carlo / Bootstrap)
+//!
+//! 单条回测路径只是历史的一种排列，这里基于回测报告模拟出多条“可能发生”的路径：
+//!
+//! * **交易自助采样 (trade bootstrap)**: 对已平仓交易的盈亏有放回抽样，重新拼出权益路径。
+//!   期末未平仓的浮动盈亏作为最后一步计入，保证原始顺序下的期末权益与回测一致；
+//!   路径的时间单位是“笔交易”。
+//! * **收益重排 (return shuffle)**: 打乱周期收益率的顺序。期末权益不变，回撤与恢复时间改变。
+//! * **成本扰动 (cost perturbation)**: 每次模拟在区间内抽取手续费倍数和额外滑点，
+//!   按成交明细把额外成本从成交日起计入权益曲线 (不重新撮合，忽略成本对可买数量的影响)。
+//!
+//! 每种模拟输出期末权益、总收益、最大回撤和恢复时间的分布及置信区间。
+
+use crate::backtest::BacktestReport;
+use anyhow::{bail, Result};
+use quant_core::enums::Side;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+// =========================================================================
+// 1. 配置与结果
+// =========================================================================
+
+/// 稳健性分析参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RobustnessConfig {
+    /// 每种方法的模拟次数
+    pub simulations: usize,
+    /// 置信水平 (0.95 = 取 2.5% ~ 97.5% 分位数)
+    pub confidence: f64,
+    pub seed: u64,
+    /// 手续费倍数区间 [min, max] (1.0 = 回测时的费率)
+    pub fee_multiplier: (f64, f64),
+    /// 额外滑点区间 [min, max] (bps，叠加在回测滑点之上，买卖双向不利)
+    pub extra_slippage_bps: (f64, f64),
+}
+
+impl Default for RobustnessConfig {
+    fn default() -> Self {
+        Self {
+            simulations: 1000,
+            confidence: 0.95,
+            seed: 42,
+            fee_multiplier: (0.5, 2.0),
+            extra_slippage_bps: (0.0, 10.0),
+        }
+    }
+}
+
+impl RobustnessConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.simulations == 0 {
+            bail!("simulations must be positive");
+        }
+        if !(self.confidence > 0.0 && self.confidence < 1.0) {
+            bail!("confidence must be in (0, 1), got {}", self.confidence);
+        }
+        let (lo, hi) = self.fee_multiplier;
+        if lo < 0.0 || lo > hi {
+            bail!("invalid fee multiplier range [{}, {}]", lo, hi);
+        }
+        let (lo, hi) = self.extra_slippage_bps;
+        if lo > hi {
+            bail!("invalid extra slippage range [{}, {}]", lo, hi);
+        }
+        Ok(())
+    }
+}
+
+/// 单条权益路径的统计
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PathStats {
+    pub final_equity: f64,
+    pub total_return: f64,
+    /// 最大回撤 (正数，0.2 = 20%)
+    pub max_drawdown: f64,
+    /// 最大回撤从前高到重新站上前高经过的周期数 (未恢复为 None，无回撤为 0)
+    pub time_to_recover: Option<usize>,
+}
+
+/// 一组模拟值的分布
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Distribution {
+    pub mean: f64,
+    pub std: f64,
+    pub min: f64,
+    pub max: f64,
+    pub median: f64,
+    /// 置信区间下界
+    pub lower: f64,
+    /// 置信区间上界
+    pub upper: f64,
+}
+
+impl Distribution {
+    /// 样本为空时返回 None
+    pub fn from_samples(samples: &[f64], confidence: f64) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let n = sorted.len() as f64;
+        let mean = sorted.iter().sum::<f64>() / n;
+        let std = if sorted.len() > 1 {
+            (sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+        let alpha = (1.0 - confidence) / 2.0;
+        Some(Self {
+            mean,
+            std,
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+            median: percentile(&sorted, 0.5),
+            lower: percentile(&sorted, alpha),
+            upper: percentile(&sorted, 1.0 - alpha),
+        })
+    }
+}
+
+/// 一种模拟方法的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationSummary {
+    pub simulations: usize,
+    pub final_equity: Distribution,
+    pub total_return: Distribution,
+    pub max_drawdown: Distribution,
+    /// 只统计恢复了的路径 (全部未恢复时为 None)
+    pub time_to_recover: Option<Distribution>,
+    /// 期末仍未从最大回撤中恢复的路径占比
+    pub unrecovered_ratio: f64,
+    /// 期末亏损 (权益低于初始资金) 的路径占比
+    pub loss_probability: f64,
+}
+
+impl SimulationSummary {
+    fn from_paths(paths: &[PathStats], confidence: f64) -> Option<Self> {
+        let collect = |f: fn(&PathStats) -> f64| paths.iter().map(f).collect::<Vec<_>>();
+        let final_equity = Distribution::from_samples(&collect(|p| p.final_equity), confidence)?;
+        let total_return = Distribution::from_samples(&collect(|p| p.total_return), confidence)?;
+        let max_drawdown = Distribution::from_samples(&collect(|p| p.max_drawdown), confidence)?;
+        let recovered: Vec<f64> = paths
+            .iter()
+            .filter_map(|p| p.time_to_recover.map(|t| t as f64))
+            .collect();
+        let n = paths.len() as f64;
+        Some(Self {
+            simulations: paths.len(),
+            final_equity,
+            total_return,
+            max_drawdown,
+            time_to_recover: Distribution::from_samples(&recovered, confidence),
+            unrecovered_ratio: (paths.len() - recovered.len()) as f64 / n,
+            loss_probability: paths.iter().filter(|p| p.total_return < 0.0).count() as f64 / n,
+        })
+    }
+}
+
+/// 稳健性分析报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RobustnessReport {
+    pub strategy: String,
+    pub config: RobustnessConfig,
+    pub initial_cash: f64,
+    /// 原始回测路径
+    pub baseline: PathStats,
+    /// 没有已平仓交易时为 None
+    pub trade_bootstrap: Option<SimulationSummary>,
+    /// 权益曲线少于 2 个周期时为 None
+    pub return_shuffle: Option<SimulationSummary>,
+    /// 没有成交时为 None
+    pub cost_perturbation: Option<SimulationSummary>,
+}
+
+impl RobustnessReport {
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+// =========================================================================
+// 2. 模拟
+// =========================================================================
+
+/// 对回测报告运行全部三种模拟
+pub fn analyze(report: &BacktestReport, config: &RobustnessConfig) -> Result<RobustnessReport> {
+    config.validate()?;
+    let initial = report.config.initial_cash.to_f64().unwrap_or(0.0);
+    if initial <= 0.0 {
+        bail!("initial cash must be positive");
+    }
+    let equities: Vec<f64> = report
+        .equity_curve
+        .iter()
+        .map(|p| p.equity.to_f64().unwrap_or(0.0))
+        .collect();
+
+    // 三种方法使用独立的随机流，调整其中一种的模拟次数不影响其他结果
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let trade_bootstrap = trade_bootstrap(report, initial, config, &mut rng);
+    let mut rng = StdRng::seed_from_u64(config.seed.wrapping_add(1));
+    let return_shuffle = return_shuffle(&equities, initial, config, &mut rng);
+    let mut rng = StdRng::seed_from_u64(config.seed.wrapping_add(2));
+    let cost_perturbation = cost_perturbation(report, &equities, initial, config, &mut rng);
+
+    Ok(RobustnessReport {
+        strategy: report.strategy.clone(),
+        config: config.clone(),
+        initial_cash: initial,
+        baseline: path_stats(initial, &equities),
+        trade_bootstrap,
+        return_shuffle,
+        cost_perturbation,
+    })
+}
+
+/// 对已平仓交易盈亏有放回抽样
+fn trade_bootstrap(
+    report: &BacktestReport,
+    initial: f64,
+    config: &RobustnessConfig,
+    rng: &mut StdRng,
+) -> Option<SimulationSummary> {
+    let pnls: Vec<f64> = report
+        .trades
+        .iter()
+        .filter_map(|t| t.realized_pnl.and_then(|p| p.to_f64()))
+        .collect();
+    if pnls.is_empty() {
+        return None;
+    }
+    let final_equity = report.metrics.final_equity.to_f64().unwrap_or(initial);
+    let unrealized = final_equity - initial - pnls.iter().sum::<f64>();
+
+    let mut path = Vec::with_capacity(pnls.len() + 1);
+    let paths: Vec<PathStats> = (0..config.simulations)
+        .map(|_| {
+            path.clear();
+            let mut equity = initial;
+            for _ in 0..pnls.len() {
+                equity += pnls[rng.gen_range(0..pnls.len())];
+                path.push(equity);
+            }
+            if unrealized != 0.0 {
+                path.push(equity + unrealized);
+            }
+            path_stats(initial, &path)
+        })
+        .collect();
+    SimulationSummary::from_paths(&paths, config.confidence)
+}
+
+/// 打乱周期收益率顺序
+fn return_shuffle(
+    equities: &[f64],
+    initial: f64,
+    config: &RobustnessConfig,
+    rng: &mut StdRng,
+) -> Option<SimulationSummary> {
+    if equities.len() < 2 {
+        return None;
+    }
+    let mut returns = Vec::with_capacity(equities.len());
+    let mut prev = initial;
+    for &equity in equities {
+        returns.push(if prev > 0.0 { equity / prev - 1.0 } else { 0.0 });
+        prev = equity;
+    }
+
+    let mut path = Vec::with_capacity(returns.len());
+    let paths: Vec<PathStats> = (0..config.simulations)
+        .map(|_| {
+            returns.shuffle(rng);
+            path.clear();
+            let mut equity = initial;
+            for r in &returns {
+                equity *= 1.0 + r;
+                path.push(equity);
+            }
+            path_stats(initial, &path)
+        })
+        .collect();
+    SimulationSummary::from_paths(&paths, config.confidence)
+}
+
+/// 按抽样的手续费倍数和额外滑点重新计算权益曲线
+fn cost_perturbation(
+    report: &BacktestReport,
+    equities: &[f64],
+    initial: f64,
+    config: &RobustnessConfig,
+    rng: &mut StdRng,
+) -> Option<SimulationSummary> {
+    if report.trades.is_empty() || equities.is_empty() {
+        return None;
+    }
+    let slippage = report.config.slippage_bps.to_f64().unwrap_or(0.0) / 10_000.0;
+    // 每笔成交：(所在权益点下标，手续费，不含滑点的名义成交额)
+    let fills: Vec<(usize, f64, f64)> = report
+        .trades
+        .iter()
+        .map(|t| {
+            let index = report
+                .equity_curve
+                .partition_point(|p| p.date < t.date)
+                .min(equities.len() - 1);
+            let fee = t.fee.to_f64().unwrap_or(0.0);
+            let price = t.price.to_f64().unwrap_or(0.0);
+            let open = match t.side {
+                Side::Buy => price / (1.0 + slippage),
+                Side::Sell => price / (1.0 - slippage),
+            };
+            (index, fee, open * t.quantity.to_f64().unwrap_or(0.0))
+        })
+        .collect();
+
+    let mut extra = vec![0.0; equities.len()];
+    let mut path = Vec::with_capacity(equities.len());
+    let paths: Vec<PathStats> = (0..config.simulations)
+        .map(|_| {
+            let multiplier = sample(config.fee_multiplier, rng);
+            let bps = sample(config.extra_slippage_bps, rng) / 10_000.0;
+            extra.iter_mut().for_each(|x| *x = 0.0);
+            for &(index, fee, notional) in &fills {
+                extra[index] += fee * (multiplier - 1.0) + notional * bps;
+            }
+            path.clear();
+            let mut cost = 0.0;
+            for (equity, x) in equities.iter().zip(&extra) {
+                cost += x;
+                path.push(equity - cost);
+            }
+            path_stats(initial, &path)
+        })
+        .collect();
+    SimulationSummary::from_paths(&paths, config.confidence)
+}
+
+fn sample((lo, hi): (f64, f64), rng: &mut StdRng) -> f64 {
+    if hi > lo {
+        rng.gen_range(lo..=hi)
+    } else {
+        lo
+    }
+}
+
+// =========================================================================
+// 3. 路径统计
+// =========================================================================
+
+/// 计算权益路径 (不含初始资金) 的期末权益、最大回撤和恢复时间
+pub fn path_stats(initial: f64, equities: &[f64]) -> PathStats {
+    let final_equity = equities.last().copied().unwrap_or(initial);
+
+    // 下标 0 是初始资金，与 compute_metrics 一致以初始资金作为第一个高点
+    let mut peak = initial;
+    let mut peak_at = 0;
+    let mut max_drawdown: f64 = 0.0;
+    // 最大回撤所在的 (前高, 前高下标, 谷底下标)
+    let mut worst = (initial, 0, 0);
+    for (i, &equity) in equities.iter().enumerate() {
+        if equity > peak {
+            peak = equity;
+            peak_at = i + 1;
+        }
+        if peak > 0.0 {
+            let drawdown = (peak - equity) / peak;
+            if drawdown > max_drawdown {
+                max_drawdown = drawdown;
+                worst = (peak, peak_at, i + 1);
+            }
+        }
+    }
+
+    let time_to_recover = if max_drawdown == 0.0 {
+        Some(0)
+    } else {
+        let (high, start, trough) = worst;
+        equities
+            .iter()
+            .enumerate()
+            .skip(trough)
+            .find(|(_, &e)| e >= high)
+            .map(|(i, _)| i + 1 - start)
+    };
+
+    PathStats {
+        final_equity,
+        total_return: final_equity / initial - 1.0,
+        max_drawdown,
+        time_to_recover,
+    }
+}
+
+/// 已排序样本的分位数 (线性插值)
+fn percentile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lo = pos.floor() as usize;
+    let hi = pos.ceil() as usize;
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+}
@@ -0,0 +1,570 @@
+//! 仓位管理与组合构建 (Position Sizing & Portfolio Construction)
+//!
+//! 把方向性观点 (`View`: 标的 + 方向 + 强度) 转换为目标持仓数量：
+//!
+//! * **FixedNotional**: 固定名义金额，`数量 = 金额 / 价格`
+//! * **FixedFractional**: 固定净值比例，`数量 = 净值 * 比例 / 价格`
+//! * **Atr**: 按 ATR 止损距离定风险，`数量 = 净值 * 单笔风险比例 / (ATR * 倍数)`
+//! * **VolatilityTarget**: 按收益率标准差 (年化) 定杠杆，`权重 = 目标波动率 / 实现波动率`
+//! * **Kelly**: 凯利公式 `f = p - (1 - p) / b`，并以 `cap` 封顶 (负值不开仓)
+//!
+//! 基础数量再乘以观点强度、受单标的仓位上限约束并按最小交易单位向下取整，
+//! 与当前持仓相减得到需要交易的数量。
+//!
+//! 跨策略的资金分配见 `allocate`：等权、波动率倒数或风险平价 (等风险贡献)。
+
+use crate::traits::StrategyContext;
+use anyhow::{bail, Result};
+use quant_core::account::{Asset, Position};
+use quant_core::enums::Side;
+use quant_core::market::MarketBar;
+use quant_core::primitive::{CurrencyPair, Quantity};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+// =========================================================================
+// 1. 账户快照
+// =========================================================================
+
+/// 定量使用的账户快照 (以 `quote` 计价)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccountSnapshot {
+    /// 计价币种 (如 "USDT")
+    pub quote: String,
+    /// 账户净值
+    pub nav: Decimal,
+    /// 计价币种余额
+    pub cash: Decimal,
+    /// 标的 -> 有符号持仓 (多头为正、空头为负)
+    pub positions: HashMap<String, Decimal>,
+}
+
+impl AccountSnapshot {
+    /// 只有现金的账户
+    pub fn new(quote: impl Into<String>, cash: Decimal) -> Self {
+        Self {
+            quote: quote.into().to_uppercase(),
+            nav: cash,
+            cash,
+            positions: HashMap::new(),
+        }
+    }
+
+    /// 从资产余额和持仓构建快照
+    ///
+    /// `prices` 为币种 -> 计价币种价格 (如 "BTC" -> 30000)。
+    /// 净值 = 各币种余额按价格折算之和 + 合约持仓的未实现盈亏；
+    /// 现货持仓即基础币种余额，记为 `BASE/QUOTE` 的多头持仓；同名合约持仓优先。
+    pub fn from_account(
+        assets: &[Asset],
+        positions: &[Position],
+        quote: &str,
+        prices: &HashMap<String, Decimal>,
+    ) -> Result<Self> {
+        let quote = quote.to_uppercase();
+        let mut snapshot = Self::new(quote.clone(), Decimal::ZERO);
+        for asset in assets {
+            let currency = asset.currency.to_uppercase();
+            let balance = asset.total();
+            if currency == quote {
+                snapshot.cash += balance;
+                snapshot.nav += balance;
+                continue;
+            }
+            if !balance.is_zero() {
+                let Some(price) = prices.get(&currency) else {
+                    bail!("No {} price for {} to value the account", quote, currency);
+                };
+                snapshot.nav += balance * price;
+            }
+            *snapshot
+                .positions
+                .entry(CurrencyPair::new(&currency, &quote).to_string())
+                .or_default() += balance;
+        }
+        let mut derivatives: HashMap<String, Decimal> = HashMap::new();
+        for position in positions {
+            let signed = match position.side {
+                Side::Buy => position.quantity,
+                Side::Sell => -position.quantity,
+            };
+            *derivatives.entry(position.symbol.to_string()).or_default() += signed;
+            snapshot.nav += position.unrealized_pnl.unwrap_or_default();
+        }
+        snapshot.positions.extend(derivatives);
+        Ok(snapshot)
+    }
+
+    /// 当前有符号持仓 (无持仓返回 0)
+    pub fn position(&self, symbol: &str) -> Decimal {
+        self.positions.get(symbol).copied().unwrap_or_default()
+    }
+
+    /// 把净值替换为分配给某个策略的资金 (持仓不变)
+    pub fn with_budget(mut self, capital: Decimal) -> Self {
+        self.nav = capital;
+        self
+    }
+}
+
+impl From<&StrategyContext> for AccountSnapshot {
+    fn from(ctx: &StrategyContext) -> Self {
+        Self {
+            quote: String::new(),
+            nav: ctx.equity,
+            cash: ctx.cash,
+            positions: ctx.positions.clone(),
+        }
+    }
+}
+
+// =========================================================================
+// 2. 观点与定量方法
+// =========================================================================
+
+/// 方向性观点
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct View {
+    pub symbol: String,
+    pub side: Side,
+    /// 观点强度 [0, 1]，按比例缩放目标仓位 (0 表示平仓)
+    pub strength: Decimal,
+}
+
+impl View {
+    pub fn new(symbol: impl Into<String>, side: Side) -> Self {
+        Self {
+            symbol: symbol.into(),
+            side,
+            strength: Decimal::ONE,
+        }
+    }
+
+    pub fn long(symbol: impl Into<String>) -> Self {
+        Self::new(symbol, Side::Buy)
+    }
+
+    pub fn short(symbol: impl Into<String>) -> Self {
+        Self::new(symbol, Side::Sell)
+    }
+
+    pub fn with_strength(mut self, strength: Decimal) -> Self {
+        self.strength = strength.clamp(Decimal::ZERO, Decimal::ONE);
+        self
+    }
+}
+
+/// 定量方法 (可直接写在 `Strategy.config` 中)
+///
+/// ```toml
+/// method = "atr"
+/// risk_fraction = 0.01
+/// period = 14
+/// multiple = 2
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum SizingMethod {
+    /// 固定名义金额 (计价币种)
+    FixedNotional { notional: Decimal },
+    /// 固定净值比例
+    FixedFractional { fraction: Decimal },
+    /// ATR 风险定量：价格反向波动 `multiple` 个 ATR 时亏损 `risk_fraction` 的净值
+    Atr {
+        risk_fraction: Decimal,
+        period: usize,
+        multiple: Decimal,
+    },
+    /// 波动率目标：按最近 `period` 个对数收益率的年化标准差调整权重
+    VolatilityTarget {
+        target_vol: f64,
+        period: usize,
+        periods_per_year: f64,
+    },
+    /// 封顶凯利：`win_rate` 胜率，`payoff_ratio` 平均盈亏比，`cap` 净值比例上限
+    Kelly {
+        win_rate: Decimal,
+        payoff_ratio: Decimal,
+        cap: Decimal,
+    },
+}
+
+impl SizingMethod {
+    /// 需要的最少 K 线数
+    pub fn required_bars(&self) -> usize {
+        match self {
+            Self::Atr { period, .. } | Self::VolatilityTarget { period, .. } => period + 1,
+            _ => 0,
+        }
+    }
+
+    /// 满仓观点下的目标数量 (绝对值)
+    pub fn base_quantity(
+        &self,
+        nav: Decimal,
+        price: Decimal,
+        bars: &[MarketBar],
+    ) -> Result<Decimal> {
+        if price <= Decimal::ZERO {
+            bail!("Cannot size a position at non-positive price {}", price);
+        }
+        let quantity = match self {
+            Self::FixedNotional { notional } => *notional / price,
+            Self::FixedFractional { fraction } => nav * fraction / price,
+            Self::Atr {
+                risk_fraction,
+                period,
+                multiple,
+            } => {
+                let stop = average_true_range(bars, *period)? * multiple;
+                if stop <= Decimal::ZERO {
+                    bail!("ATR is zero, cannot size by risk");
+                }
+                nav * risk_fraction / stop
+            }
+            Self::VolatilityTarget {
+                target_vol,
+                period,
+                periods_per_year,
+            } => {
+                let vol = realized_volatility(bars, *period, *periods_per_year)?;
+                if vol <= 0.0 {
+                    bail!("Realized volatility is zero, cannot target volatility");
+                }
+                nav * to_decimal(target_vol / vol)? / price
+            }
+            Self::Kelly {
+                win_rate,
+                payoff_ratio,
+                cap,
+            } => {
+                if *payoff_ratio <= Decimal::ZERO {
+                    bail!("Kelly payoff ratio must be positive, got {}", payoff_ratio);
+                }
+                if *cap <= Decimal::ZERO || *cap > Decimal::ONE {
+                    bail!("Kelly cap must be in (0, 1], got {}", cap);
+                }
+                let kelly = *win_rate - (Decimal::ONE - win_rate) / payoff_ratio;
+                nav * kelly.clamp(Decimal::ZERO, *cap) / price
+            }
+        };
+        Ok(quantity.max(Decimal::ZERO))
+    }
+}
+
+// =========================================================================
+// 3. 定量器
+// =========================================================================
+
+/// 仓位定量器：定量方法 + 仓位上限 + 最小交易单位
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionSizer {
+    #[serde(flatten)]
+    pub method: SizingMethod,
+    /// 单标的持仓市值占净值的上限
+    #[serde(default)]
+    pub max_position_fraction: Option<Decimal>,
+    /// 最小交易单位 (目标数量向下取整)
+    #[serde(default)]
+    pub lot_size: Option<Decimal>,
+}
+
+impl PositionSizer {
+    pub fn new(method: SizingMethod) -> Self {
+        Self {
+            method,
+            max_position_fraction: None,
+            lot_size: None,
+        }
+    }
+
+    /// 从 `Strategy.config` 的某个字段读取 (如 `{"sizing": {"method": "kelly", ...}}`)
+    pub fn from_config(config: &serde_json::Value, key: &str) -> Result<Option<Self>> {
+        match config.get(key) {
+            None | Some(serde_json::Value::Null) => Ok(None),
+            Some(value) => Ok(Some(serde_json::from_value(value.clone())?)),
+        }
+    }
+
+    pub fn with_max_position_fraction(mut self, fraction: Decimal) -> Self {
+        self.max_position_fraction = Some(fraction);
+        self
+    }
+
+    pub fn with_lot_size(mut self, lot_size: Decimal) -> Self {
+        self.lot_size = Some(lot_size);
+        self
+    }
+
+    /// 观点对应的目标持仓 (有符号)
+    pub fn target_position(
+        &self,
+        account: &AccountSnapshot,
+        view: &View,
+        price: Decimal,
+        bars: &[MarketBar],
+    ) -> Result<Decimal> {
+        if view.strength.is_zero() {
+            return Ok(Decimal::ZERO);
+        }
+        let mut quantity = self.method.base_quantity(account.nav, price, bars)? * view.strength;
+        if let Some(fraction) = self.max_position_fraction {
+            quantity = quantity.min((account.nav * fraction / price).max(Decimal::ZERO));
+        }
+        if let Some(lot) = self.lot_size.filter(|l| *l > Decimal::ZERO) {
+            quantity = (quantity / lot).floor() * lot;
+        }
+        Ok(match view.side {
+            Side::Buy => quantity,
+            Side::Sell => -quantity,
+        })
+    }
+
+    /// 从当前持仓调整到目标持仓需要交易的方向和数量 (无需交易返回 None)
+    pub fn order_for(
+        &self,
+        account: &AccountSnapshot,
+        view: &View,
+        price: Decimal,
+        bars: &[MarketBar],
+    ) -> Result<Option<(Side, Quantity)>> {
+        let target = self.target_position(account, view, price, bars)?;
+        let delta = target - account.position(&view.symbol);
+        Ok(if delta > Decimal::ZERO {
+            Some((Side::Buy, Quantity(delta)))
+        } else if delta < Decimal::ZERO {
+            Some((Side::Sell, Quantity(-delta)))
+        } else {
+            None
+        })
+    }
+}
+
+impl StrategyContext {
+    /// 按定量器把观点转换为目标持仓并下单
+    pub fn order_view(
+        &mut self,
+        sizer: &PositionSizer,
+        view: &View,
+        price: Decimal,
+        bars: &[MarketBar],
+        reason: impl Into<String>,
+    ) -> Result<()> {
+        let target = sizer.target_position(&AccountSnapshot::from(&*self), view, price, bars)?;
+        self.order_target(view.symbol.clone(), target, reason);
+        Ok(())
+    }
+}
+
+// =========================================================================
+// 4. 波动率
+// =========================================================================
+
+/// 最近 `period` 根 K 线的平均真实波幅 (简单平均，需要 period + 1 根 K 线)
+pub fn average_true_range(bars: &[MarketBar], period: usize) -> Result<Decimal> {
+    if period == 0 || bars.len() < period + 1 {
+        bail!(
+            "ATR({}) needs {} bars, got {}",
+            period,
+            period + 1,
+            bars.len()
+        );
+    }
+    let window = &bars[bars.len() - period - 1..];
+    let sum: Decimal = window
+        .windows(2)
+        .map(|w| {
+            let (prev, bar) = (w[0].close.0, &w[1]);
+            (bar.high.0 - bar.low.0)
+                .max((bar.high.0 - prev).abs())
+                .max((bar.low.0 - prev).abs())
+        })
+        .sum();
+    Ok(sum / Decimal::from(period))
+}
+
+/// 最近 `period` 个对数收益率的年化标准差 (需要 period + 1 根 K 线)
+pub fn realized_volatility(
+    bars: &[MarketBar],
+    period: usize,
+    periods_per_year: f64,
+) -> Result<f64> {
+    if period < 2 || bars.len() < period + 1 {
+        bail!(
+            "Volatility over {} returns needs {} bars, got {}",
+            period,
+            period + 1,
+            bars.len()
+        );
+    }
+    let closes: Vec<f64> = bars[bars.len() - period - 1..]
+        .iter()
+        .map(|b| b.close.0.to_f64().unwrap_or(0.0))
+        .collect();
+    if closes.iter().any(|c| *c <= 0.0) {
+        bail!("Volatility needs positive close prices");
+    }
+    let returns: Vec<f64> = closes.windows(2).map(|w| (w[1] / w[0]).ln()).collect();
+    Ok(stdev(&returns) * periods_per_year.sqrt())
+}
+
+/// 样本标准差
+fn stdev(values: &[f64]) -> f64 {
+    let n = values.len() as f64;
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let mean = values.iter().sum::<f64>() / n;
+    (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+}
+
+fn to_decimal(value: f64) -> Result<Decimal> {
+    Decimal::from_f64(value).ok_or_else(|| anyhow::anyhow!("Cannot convert {} to decimal", value))
+}
+
+// =========================================================================
+// 5. 跨策略资金分配
+// =========================================================================
+
+/// 资金分配方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AllocationMethod {
+    EqualWeight,
+    /// 权重与波动率成反比 (忽略相关性)
+    InverseVolatility,
+    /// 等风险贡献 (考虑协方差)
+    RiskParity,
+}
+
+/// 单个策略的分配结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Allocation {
+    pub strategy_uuid: String,
+    pub weight: f64,
+    /// 分配资金 = 净值 * 权重
+    pub capital: Decimal,
+    /// 单期收益率标准差
+    pub volatility: f64,
+    /// 组合风险贡献占比 (合计为 1)
+    pub risk_contribution: f64,
+}
+
+/// 按各策略的收益率序列在策略间分配资金
+///
+/// 序列按尾部对齐到最短长度 (至少 2 期)。
+pub fn allocate(
+    nav: Decimal,
+    returns: &BTreeMap<String, Vec<f64>>,
+    method: AllocationMethod,
+) -> Result<Vec<Allocation>> {
+    let len = returns.values().map(Vec::len).min().unwrap_or(0);
+    if returns.is_empty() {
+        return Ok(Vec::new());
+    }
+    if len < 2 {
+        bail!(
+            "Allocation needs at least 2 aligned returns per strategy, got {}",
+            len
+        );
+    }
+    let series: Vec<&[f64]> = returns.values().map(|r| &r[r.len() - len..]).collect();
+    let cov = covariance(&series);
+    let n = series.len();
+    let vols: Vec<f64> = (0..n).map(|i| cov[i][i].sqrt()).collect();
+    if method != AllocationMethod::EqualWeight {
+        if let Some((uuid, _)) = returns.keys().zip(&vols).find(|(_, v)| **v <= 0.0) {
+            bail!("Strategy {} has zero return volatility", uuid);
+        }
+    }
+
+    let weights = match method {
+        AllocationMethod::EqualWeight => vec![1.0 / n as f64; n],
+        AllocationMethod::InverseVolatility => normalize(vols.iter().map(|v| 1.0 / v).collect()),
+        AllocationMethod::RiskParity => risk_parity(&cov, &vols),
+    };
+    let contributions = risk_contributions(&cov, &weights);
+    returns
+        .keys()
+        .enumerate()
+        .map(|(i, uuid)| {
+            Ok(Allocation {
+                strategy_uuid: uuid.clone(),
+                weight: weights[i],
+                capital: (nav * to_decimal(weights[i])?).round_dp(8),
+                volatility: vols[i],
+                risk_contribution: contributions[i],
+            })
+        })
+        .collect()
+}
+
+/// 样本协方差矩阵
+fn covariance(series: &[&[f64]]) -> Vec<Vec<f64>> {
+    let len = series[0].len() as f64;
+    let means: Vec<f64> = series.iter().map(|s| s.iter().sum::<f64>() / len).collect();
+    let n = series.len();
+    let mut cov = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in i..n {
+            let c = series[i]
+                .iter()
+                .zip(series[j])
+                .map(|(a, b)| (a - means[i]) * (b - means[j]))
+                .sum::<f64>()
+                / (len - 1.0);
+            cov[i][j] = c;
+            cov[j][i] = c;
+        }
+    }
+    cov
+}
+
+fn normalize(weights: Vec<f64>) -> Vec<f64> {
+    let total: f64 = weights.iter().sum();
+    weights.into_iter().map(|w| w / total).collect()
+}
+
+/// 等风险贡献权重：循环坐标下降求解 `min ½wᵀΣw - Σ ln(w_i) / n`，再归一化
+fn risk_parity(cov: &[Vec<f64>], vols: &[f64]) -> Vec<f64> {
+    const MAX_SWEEPS: usize = 1000;
+    const TOLERANCE: f64 = 1e-12;
+
+    let n = cov.len();
+    let budget = 1.0 / n as f64;
+    let mut w = normalize(vols.iter().map(|v| 1.0 / v).collect());
+    for _ in 0..MAX_SWEEPS {
+        let mut change: f64 = 0.0;
+        for i in 0..n {
+            let c: f64 = (0..n).filter(|j| *j != i).map(|j| cov[i][j] * w[j]).sum();
+            let a = cov[i][i];
+            let next = (-c + (c * c + 4.0 * a * budget).sqrt()) / (2.0 * a);
+            change = change.max((next - w[i]).abs());
+            w[i] = next;
+        }
+        if change < TOLERANCE {
+            break;
+        }
+    }
+    normalize(w)
+}
+
+/// 各资产的风险贡献占比 `w_i (Σw)_i / wᵀΣw`
+fn risk_contributions(cov: &[Vec<f64>], weights: &[f64]) -> Vec<f64> {
+    let n = weights.len();
+    let marginal: Vec<f64> = (0..n)
+        .map(|i| (0..n).map(|j| cov[i][j] * weights[j]).sum())
+        .collect();
+    let total: f64 = (0..n).map(|i| weights[i] * marginal[i]).sum();
+    (0..n)
+        .map(|i| {
+            if total > 0.0 {
+                weights[i] * marginal[i] / total
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
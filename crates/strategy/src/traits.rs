@@ -0,0 +1,104 @@
+use chrono::NaiveDate;
+use quant_core::enums::Side;
+use quant_core::market::MarketBar;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// =========================================================================
+// 策略接口 (回测 / 模拟盘 / 实盘共用)
+// =========================================================================
+
+/// 交易策略
+///
+/// 策略只负责根据行情产出下单意图，撮合、资金和持仓由运行环境 (回测器或 OMS) 负责。
+pub trait TradingStrategy: Send {
+    /// 策略逻辑类名 (对应 `Strategy.class_name`)
+    fn name(&self) -> &str;
+
+    /// 每根 K 线收盘后调用
+    fn on_bar(&mut self, ctx: &mut StrategyContext, bar: &MarketBar);
+}
+
+/// 策略发出的下单意图 (市价，下一根 K 线开盘成交)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderIntent {
+    pub symbol: String,
+    pub side: Side,
+    pub quantity: Decimal,
+    /// 下单原因 (记录到成交明细)
+    pub reason: String,
+}
+
+/// 策略运行上下文：只读的账户快照 + 待提交的下单意图
+#[derive(Debug, Clone, Default)]
+pub struct StrategyContext {
+    /// 当前 K 线日期
+    pub date: Option<NaiveDate>,
+    pub cash: Decimal,
+    /// 账户净值 (现金 + 持仓市值)
+    pub equity: Decimal,
+    pub positions: HashMap<String, Decimal>,
+    pub(crate) orders: Vec<OrderIntent>,
+}
+
+impl StrategyContext {
+    pub fn new(cash: Decimal) -> Self {
+        Self {
+            cash,
+            equity: cash,
+            ..Default::default()
+        }
+    }
+
+    /// 当前持仓数量 (无持仓返回 0)
+    pub fn position(&self, symbol: &str) -> Decimal {
+        self.positions.get(symbol).copied().unwrap_or(Decimal::ZERO)
+    }
+
+    pub fn buy(&mut self, symbol: impl Into<String>, quantity: Decimal, reason: impl Into<String>) {
+        self.submit(symbol.into(), Side::Buy, quantity, reason.into());
+    }
+
+    pub fn sell(
+        &mut self,
+        symbol: impl Into<String>,
+        quantity: Decimal,
+        reason: impl Into<String>,
+    ) {
+        self.submit(symbol.into(), Side::Sell, quantity, reason.into());
+    }
+
+    /// 调整到目标持仓：自动计算买卖方向和数量
+    pub fn order_target(
+        &mut self,
+        symbol: impl Into<String>,
+        target: Decimal,
+        reason: impl Into<String>,
+    ) {
+        let symbol = symbol.into();
+        let delta = target - self.position(&symbol);
+        if delta > Decimal::ZERO {
+            self.submit(symbol, Side::Buy, delta, reason.into());
+        } else if delta < Decimal::ZERO {
+            self.submit(symbol, Side::Sell, -delta, reason.into());
+        }
+    }
+
+    /// 取出本轮产生的下单意图
+    pub fn take_orders(&mut self) -> Vec<OrderIntent> {
+        std::mem::take(&mut self.orders)
+    }
+
+    fn submit(&mut self, symbol: String, side: Side, quantity: Decimal, reason: String) {
+        if quantity <= Decimal::ZERO {
+            return;
+        }
+        self.orders.push(OrderIntent {
+            symbol,
+            side,
+            quantity,
+            reason,
+        });
+    }
+}
@@ -0,0 +1,156 @@
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use quant_core::enums::{BarPeriod, Exchange, Side};
+    use quant_core::market::MarketBar;
+    use quant_core::primitive::{Price, Quantity};
+    use quant_strategy::backtest::{BacktestConfig, Backtester};
+    use quant_strategy::registry::StrategyRegistry;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    // =========================================================================
+    // 1. Mock 数据
+    // =========================================================================
+
+    /// 按收盘价序列生成日线，开盘价 = 前一日收盘价
+    fn mock_bars(symbol: &str, closes: &[Decimal]) -> Vec<MarketBar> {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let mut prev = closes[0];
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, close)| {
+                let open = prev;
+                prev = *close;
+                MarketBar::new(
+                    Exchange::Binance,
+                    symbol,
+                    BarPeriod::D1,
+                    21,
+                    Price(open),
+                    Price(open.max(*close)),
+                    Price(open.min(*close)),
+                    Price(*close),
+                    Quantity(dec!(1000)),
+                    start + chrono::Duration::days(i as i64),
+                )
+                .expect("Failed to create mock bar")
+            })
+            .collect()
+    }
+
+    fn no_cost_config() -> BacktestConfig {
+        BacktestConfig {
+            initial_cash: dec!(1000),
+            fee_rate: Decimal::ZERO,
+            slippage_bps: Decimal::ZERO,
+            periods_per_year: 252,
+        }
+    }
+
+    // =========================================================================
+    // 2. 测试用例
+    // =========================================================================
+
+    #[test]
+    fn test_buy_and_hold_fills_at_next_open() -> anyhow::Result<()> {
+        let bars = mock_bars("BTC/USDT", &[dec!(100), dec!(110), dec!(121)]);
+        let mut strategy =
+            StrategyRegistry::with_builtins().create("BuyAndHold", &json!({ "quantity": 5 }))?;
+
+        let report = Backtester::new(no_cost_config()).run(strategy.as_mut(), &bars)?;
+
+        // 第一天收盘下单，第二天开盘 (100) 成交
+        assert_eq!(report.trades.len(), 1);
+        let trade = &report.trades[0];
+        assert_eq!(trade.side, Side::Buy);
+        assert_eq!(trade.price, dec!(100));
+        assert_eq!(trade.date, NaiveDate::from_ymd_opt(2024, 1, 2).unwrap());
+
+        // 最终权益 = 500 现金 + 5 * 121
+        assert_eq!(report.metrics.final_equity, dec!(1105));
+        assert!((report.metrics.total_return - 0.105).abs() < 1e-9);
+        assert_eq!(report.metrics.max_drawdown, 0.0);
+        assert_eq!(report.equity_curve.len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fees_slippage_and_cash_limit() -> anyhow::Result<()> {
+        let bars = mock_bars("BTC/USDT", &[dec!(100), dec!(100), dec!(100)]);
+        let config = BacktestConfig {
+            initial_cash: dec!(1000),
+            fee_rate: dec!(0.01),
+            slippage_bps: dec!(100),
+            periods_per_year: 252,
+        };
+        // 想买 100 个，但现金只够 1000 / (101 * 1.01)
+        let mut strategy =
+            StrategyRegistry::with_builtins().create("BuyAndHold", &json!({ "quantity": 100 }))?;
+
+        let report = Backtester::new(config).run(strategy.as_mut(), &bars)?;
+        let trade = &report.trades[0];
+        assert_eq!(trade.price, dec!(101));
+        assert!(trade.quantity < dec!(9.81) && trade.quantity > dec!(9.80));
+        assert!(report.equity_curve.last().unwrap().cash >= Decimal::ZERO);
+        assert!(report.metrics.total_fees > Decimal::ZERO);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sma_cross_round_trip_and_drawdown() -> anyhow::Result<()> {
+        // 先涨后跌：金叉买入，死叉卖出
+        let closes: Vec<Decimal> = [10, 10, 10, 11, 12, 13, 14, 15, 14, 12, 10, 8, 7, 6, 6, 6]
+            .iter()
+            .map(|c| Decimal::from(*c))
+            .collect();
+        let bars = mock_bars("ETH/USDT", &closes);
+        let mut strategy = StrategyRegistry::with_builtins()
+            .create("SmaCross", &json!({ "fast": 2, "slow": 3, "quantity": 10 }))?;
+
+        let report = Backtester::new(no_cost_config()).run(strategy.as_mut(), &bars)?;
+
+        let sells: Vec<_> = report
+            .trades
+            .iter()
+            .filter(|t| t.side == Side::Sell)
+            .collect();
+        assert!(!sells.is_empty(), "death cross should close the position");
+        assert!(sells[0].realized_pnl.is_some());
+        assert!(report.metrics.win_rate.is_some());
+        assert!(report.metrics.max_drawdown > 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_registry_rejects_unknown_and_invalid() {
+        let registry = StrategyRegistry::with_builtins();
+        assert!(registry.create("Nope", &json!({})).is_err());
+        assert!(registry
+            .create("SmaCross", &json!({ "fast": 5, "slow": 3, "quantity": 1 }))
+            .is_err());
+        assert_eq!(registry.class_names(), vec!["BuyAndHold", "SmaCross"]);
+    }
+
+    #[test]
+    fn test_report_export() -> anyhow::Result<()> {
+        let bars = mock_bars("BTC/USDT", &[dec!(100), dec!(110), dec!(121)]);
+        let mut strategy =
+            StrategyRegistry::with_builtins().create("BuyAndHold", &json!({ "quantity": 1 }))?;
+        let report = Backtester::new(no_cost_config()).run(strategy.as_mut(), &bars)?;
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()?)?;
+        assert_eq!(json["strategy"], "BuyAndHold");
+
+        let mut equity = Vec::new();
+        report.write_equity_csv(&mut equity)?;
+        assert_eq!(String::from_utf8(equity)?.lines().count(), 4);
+
+        let mut trades = Vec::new();
+        report.write_trades_csv(&mut trades)?;
+        assert!(String::from_utf8(trades)?.starts_with("date,symbol,side"));
+        Ok(())
+    }
+}
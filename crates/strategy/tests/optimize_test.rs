@@ -0,0 +1,300 @@
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use chrono::NaiveDate;
+    use quant_core::enums::{BarPeriod, Exchange};
+    use quant_core::market::MarketBar;
+    use quant_core::primitive::{Price, Quantity};
+    use quant_strategy::backtest::BacktestConfig;
+    use quant_strategy::optimize::{
+        deflated_sharpe, normal_cdf, normal_inv, probabilistic_sharpe, Objective, Optimizer,
+        ParamRange, ParamSpace, Search, WalkForward,
+    };
+    use quant_strategy::registry::StrategyRegistry;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use rust_decimal::prelude::FromPrimitive;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    // =========================================================================
+    // 1. Mock 数据
+    // =========================================================================
+
+    /// 带趋势的正弦价格，均线交叉策略在其上有明显的参数敏感性
+    fn mock_bars(days: usize) -> Vec<MarketBar> {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        (0..days)
+            .map(|i| {
+                let x = i as f64;
+                let close = Decimal::from_f64(100.0 + 10.0 * (x / 6.0).sin() + 0.1 * x)
+                    .unwrap()
+                    .round_dp(2);
+                MarketBar::new(
+                    Exchange::Binance,
+                    "BTC/USDT",
+                    BarPeriod::D1,
+                    21,
+                    Price(close),
+                    Price(close),
+                    Price(close),
+                    Price(close),
+                    Quantity(dec!(1000)),
+                    start + chrono::Duration::days(i as i64),
+                )
+                .expect("Failed to create mock bar")
+            })
+            .collect()
+    }
+
+    fn sma_space() -> Result<ParamSpace> {
+        ParamSpace::from_config(
+            &json!({
+                "quantity": 10,
+                "search_space": {
+                    "fast": { "min": 2, "max": 8, "step": 2 },
+                    "slow": { "values": [6, 12, 20] }
+                }
+            }),
+            "search_space",
+        )
+    }
+
+    fn optimizer(space: ParamSpace) -> Optimizer {
+        Optimizer::new(
+            StrategyRegistry::with_builtins(),
+            "SmaCross",
+            space,
+            BacktestConfig {
+                initial_cash: dec!(10000),
+                fee_rate: Decimal::ZERO,
+                slippage_bps: Decimal::ZERO,
+                periods_per_year: 365,
+            },
+        )
+    }
+
+    // =========================================================================
+    // 2. 参数空间
+    // =========================================================================
+
+    #[test]
+    fn test_param_space_from_config() -> Result<()> {
+        let space = sma_space()?;
+        assert_eq!(space.base, json!({ "quantity": 10 }));
+
+        let grid = space.grid()?;
+        assert_eq!(grid.len(), 12);
+        assert_eq!(grid[0]["fast"], json!(2));
+        assert_eq!(grid[11]["fast"], json!(8));
+        assert_eq!(grid[11]["slow"], json!(20));
+        assert_eq!(
+            space.params(&grid[0]),
+            json!({ "quantity": 10, "fast": 2, "slow": 6 })
+        );
+
+        // 嵌套路径 + 连续区间 (只能随机采样)
+        let nested = ParamSpace::new(json!({ "sizing": { "method": "kelly" } })).with_range(
+            "sizing.cap",
+            ParamRange::Range {
+                min: 0.1,
+                max: 0.5,
+                step: None,
+            },
+        );
+        assert!(nested.grid().is_err());
+        let mut rng = StdRng::seed_from_u64(7);
+        let set = nested.sample(&mut rng);
+        let cap = set["sizing.cap"].as_f64().unwrap();
+        assert!((0.1..=0.5).contains(&cap));
+        assert_eq!(nested.params(&set)["sizing"]["method"], json!("kelly"));
+        assert_eq!(nested.params(&set)["sizing"]["cap"], json!(cap));
+
+        assert!(ParamSpace::from_config(
+            &json!({ "search_space": { "fast": { "min": 5, "max": 1 } } }),
+            "search_space"
+        )
+        .is_err());
+        Ok(())
+    }
+
+    // =========================================================================
+    // 3. 搜索
+    // =========================================================================
+
+    #[test]
+    fn test_grid_search_ranks_trials_in_parallel() -> Result<()> {
+        let bars = mock_bars(120);
+        let report = optimizer(sma_space()?).with_threads(4).optimize(&bars)?;
+        assert_eq!(report.trials.len(), 12);
+        assert_eq!(report.objective, Objective::Sharpe);
+
+        // 有效试验按得分降序，fast >= slow 的非法组合排在最后
+        let scores: Vec<f64> = report.trials.iter().filter_map(|t| t.score).collect();
+        assert_eq!(scores.len(), 10);
+        assert!(scores.windows(2).all(|w| w[0] >= w[1]));
+        for trial in &report.trials[10..] {
+            assert!(trial.score.is_none());
+            assert!(trial.error.as_deref().unwrap().contains("fast < slow"));
+        }
+        let best = report.best().unwrap();
+        assert!(best.metrics.as_ref().unwrap().trade_count > 0);
+        let dsr = report.deflated_sharpe.unwrap();
+        assert!((0.0..=1.0).contains(&dsr));
+
+        // 单线程结果一致
+        let serial = optimizer(sma_space()?).with_threads(1).optimize(&bars)?;
+        assert_eq!(serial.best().unwrap().params, best.params);
+
+        let mut csv = Vec::new();
+        report.write_trials_csv(&mut csv)?;
+        assert_eq!(String::from_utf8(csv)?.lines().count(), 13);
+        Ok(())
+    }
+
+    #[test]
+    fn test_random_and_bayesian_search() -> Result<()> {
+        let bars = mock_bars(120);
+        let space = ParamSpace::new(json!({ "quantity": 10 }))
+            .with_range(
+                "fast",
+                ParamRange::Range {
+                    min: 2.0,
+                    max: 10.0,
+                    step: Some(1.0),
+                },
+            )
+            .with_range(
+                "slow",
+                ParamRange::Range {
+                    min: 12.0,
+                    max: 40.0,
+                    step: Some(1.0),
+                },
+            );
+
+        let random = optimizer(space.clone())
+            .with_search(Search::Random {
+                trials: 15,
+                seed: 1,
+            })
+            .with_objective(Objective::TotalReturn)
+            .optimize(&bars)?;
+        assert_eq!(random.trials.len(), 15);
+        let again = optimizer(space.clone())
+            .with_search(Search::Random {
+                trials: 15,
+                seed: 1,
+            })
+            .with_objective(Objective::TotalReturn)
+            .optimize(&bars)?;
+        assert_eq!(random.best().unwrap().params, again.best().unwrap().params);
+
+        let bayesian = optimizer(space)
+            .with_search(Search::Bayesian {
+                trials: 20,
+                initial: 8,
+                seed: 3,
+            })
+            .with_threads(2)
+            .optimize(&bars)?;
+        assert_eq!(bayesian.trials.len(), 20);
+        let mut ids: Vec<usize> = bayesian.trials.iter().map(|t| t.id).collect();
+        ids.sort();
+        assert_eq!(ids, (0..20).collect::<Vec<_>>());
+        let unique: std::collections::HashSet<String> = bayesian
+            .trials
+            .iter()
+            .map(|t| serde_json::to_string(&t.params).unwrap())
+            .collect();
+        assert_eq!(unique.len(), 20);
+        for trial in &bayesian.trials {
+            let fast = trial.params["fast"].as_i64().unwrap();
+            assert!((2..=10).contains(&fast));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_walk_forward_folds() -> Result<()> {
+        let bars = mock_bars(120);
+        let rolling = optimizer(sma_space()?).walk_forward(
+            &bars,
+            WalkForward {
+                train: 60,
+                test: 20,
+                anchored: false,
+            },
+        )?;
+        assert_eq!(rolling.folds.len(), 3);
+        let first = &rolling.folds[0];
+        assert_eq!(
+            first.train_start,
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()
+        );
+        assert_eq!(
+            first.test_start,
+            first.train_end + chrono::Duration::days(1)
+        );
+        assert_eq!(
+            first.test_end,
+            first.test_start + chrono::Duration::days(19)
+        );
+        assert_eq!(first.trials, 12);
+        assert_eq!(
+            rolling.folds[1].train_start,
+            first.train_start + chrono::Duration::days(20)
+        );
+        assert!(rolling.out_of_sample_return.is_finite());
+
+        let anchored = optimizer(sma_space()?).walk_forward(
+            &bars,
+            WalkForward {
+                train: 60,
+                test: 30,
+                anchored: true,
+            },
+        )?;
+        assert_eq!(anchored.folds.len(), 2);
+        assert_eq!(anchored.folds[1].train_start, anchored.folds[0].train_start);
+
+        assert!(optimizer(sma_space()?)
+            .walk_forward(
+                &bars,
+                WalkForward {
+                    train: 100,
+                    test: 30,
+                    anchored: false,
+                },
+            )
+            .is_err());
+        Ok(())
+    }
+
+    // =========================================================================
+    // 4. 夏普显著性
+    // =========================================================================
+
+    #[test]
+    fn test_deflated_sharpe() {
+        assert!((normal_cdf(0.0) - 0.5).abs() < 1e-7);
+        assert!((normal_cdf(1.96) - 0.975).abs() < 1e-4);
+        assert!((normal_inv(0.975) - 1.959964).abs() < 1e-5);
+        assert!((normal_cdf(normal_inv(0.01)) - 0.01).abs() < 1e-6);
+
+        let returns: Vec<f64> = (0..250)
+            .map(|i| 0.001 + 0.01 * ((i as f64) * 1.7).sin())
+            .collect();
+        let psr = probabilistic_sharpe(&returns, 0.0).unwrap();
+        assert!(psr > 0.5);
+
+        // 试验越多、试验间夏普差异越大，显著性越低
+        let few = deflated_sharpe(&returns, &[0.05, 0.1]).unwrap();
+        let many: Vec<f64> = (0..200).map(|i| -0.2 + 0.002 * i as f64).collect();
+        let deflated = deflated_sharpe(&returns, &many).unwrap();
+        assert!(deflated < few);
+        assert!(few < psr);
+        assert_eq!(deflated_sharpe(&returns, &[]), Some(psr));
+    }
+}
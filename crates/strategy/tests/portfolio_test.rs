@@ -0,0 +1,294 @@
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use chrono::NaiveDate;
+    use quant_core::enums::{BarPeriod, Exchange, Side};
+    use quant_core::market::MarketBar;
+    use quant_core::primitive::{Price, Quantity};
+    use quant_strategy::backtest::BacktestConfig;
+    use quant_strategy::portfolio::{PortfolioBacktester, PortfolioLimits, Sleeve};
+    use quant_strategy::traits::{StrategyContext, TradingStrategy};
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use std::collections::HashMap;
+
+    // =========================================================================
+    // 1. Mock 数据
+    // =========================================================================
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, day).unwrap()
+    }
+
+    /// 开盘 = 收盘 = 指定价格的日线
+    fn bar(exchange: Exchange, symbol: &str, day: u32, price: Decimal) -> MarketBar {
+        MarketBar::new(
+            exchange,
+            symbol,
+            BarPeriod::D1,
+            21,
+            Price(price),
+            Price(price),
+            Price(price),
+            Price(price),
+            Quantity(dec!(1000)),
+            date(day),
+        )
+        .expect("Failed to create mock bar")
+    }
+
+    fn crypto_bars(prices: &[Decimal]) -> Vec<MarketBar> {
+        prices
+            .iter()
+            .enumerate()
+            .map(|(i, p)| bar(Exchange::Binance, "BTC/USDT", i as u32 + 1, *p))
+            .collect()
+    }
+
+    /// 按日期下单的脚本策略：在 `symbol` 的 K 线收盘时发出当天的订单
+    struct Scripted {
+        symbol: String,
+        orders: HashMap<NaiveDate, (Side, Decimal)>,
+    }
+
+    impl TradingStrategy for Scripted {
+        fn name(&self) -> &str {
+            "Scripted"
+        }
+
+        fn on_bar(&mut self, ctx: &mut StrategyContext, bar: &MarketBar) {
+            if bar.symbol.to_string() != self.symbol {
+                return;
+            }
+            match self.orders.get(&bar.start_time) {
+                Some((Side::Buy, qty)) => ctx.buy(&self.symbol, *qty, "script"),
+                Some((Side::Sell, qty)) => ctx.sell(&self.symbol, *qty, "script"),
+                None => {}
+            }
+        }
+    }
+
+    fn sleeve(name: &str, symbol: &str, orders: &[(u32, Side, Decimal)]) -> Sleeve {
+        Sleeve::new(
+            name,
+            Box::new(Scripted {
+                symbol: symbol.to_string(),
+                orders: orders
+                    .iter()
+                    .map(|(day, side, qty)| (date(*day), (*side, *qty)))
+                    .collect(),
+            }),
+            vec![symbol.to_string()],
+        )
+    }
+
+    fn config(cash: Decimal, fee_rate: Decimal, slippage_bps: Decimal) -> BacktestConfig {
+        BacktestConfig {
+            initial_cash: cash,
+            fee_rate,
+            slippage_bps,
+            periods_per_year: 365,
+        }
+    }
+
+    // =========================================================================
+    // 2. 测试用例
+    // =========================================================================
+
+    #[test]
+    fn test_netting_and_attribution() -> Result<()> {
+        let bars = crypto_bars(&[dec!(100), dec!(100), dec!(100), dec!(100)]);
+        let mut sleeves = vec![
+            sleeve(
+                "a",
+                "BTC/USDT",
+                &[(1, Side::Buy, dec!(10)), (2, Side::Sell, dec!(4))],
+            ),
+            sleeve("b", "BTC/USDT", &[(2, Side::Buy, dec!(10))]),
+        ];
+        let report = PortfolioBacktester::new(config(dec!(10000), dec!(0.01), dec!(100)))
+            .run(&mut sleeves, &bars)?;
+
+        // 第三天开盘：a 卖 4、b 买 10，只有净买入 6 进入市场
+        assert_eq!(report.trades.len(), 2);
+        let net = &report.trades[1];
+        assert_eq!(
+            (net.side, net.quantity, net.price),
+            (Side::Buy, dec!(6), dec!(101))
+        );
+        assert_eq!(net.fee, dec!(6.06));
+        assert_eq!(report.crossed_notional, dec!(400));
+
+        // 卖方按开盘价内部成交，净额方向 (买方) 承担滑点和手续费
+        let a = &report.sleeves[0];
+        let b = &report.sleeves[1];
+        let a_sell = &a.trades[1];
+        assert_eq!((a_sell.price, a_sell.fee), (dec!(100), Decimal::ZERO));
+        assert_eq!(
+            (b.trades[0].price, b.trades[0].fee),
+            (dec!(100.6), dec!(6.06))
+        );
+        assert_eq!(a.positions["BTC/USDT"], dec!(6));
+        assert_eq!(b.positions["BTC/USDT"], dec!(10));
+
+        // 策略盈亏之和 = 组合盈亏
+        assert_eq!(a.pnl, dec!(-20.1));
+        assert_eq!(b.pnl, dec!(-12.06));
+        assert_eq!(report.metrics.final_equity, dec!(9967.84));
+        assert_eq!(a.pnl + b.pnl, report.metrics.final_equity - dec!(10000));
+        assert_eq!(a.pnl_curve.len(), report.equity_curve.len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_shared_cash_and_symbol_weight() -> Result<()> {
+        let bars = crypto_bars(&[dec!(100), dec!(100), dec!(100)]);
+
+        // 两个策略各想买 8，但共享现金只够 10：按比例各成交 5
+        let mut sleeves = vec![
+            sleeve("a", "BTC/USDT", &[(1, Side::Buy, dec!(8))]),
+            sleeve("b", "BTC/USDT", &[(1, Side::Buy, dec!(8))]),
+        ];
+        let report = PortfolioBacktester::new(config(dec!(1000), Decimal::ZERO, Decimal::ZERO))
+            .run(&mut sleeves, &bars)?;
+        assert_eq!(report.trades.len(), 1);
+        assert_eq!(report.trades[0].quantity, dec!(10));
+        assert_eq!(report.sleeves[0].positions["BTC/USDT"], dec!(5));
+        assert_eq!(report.sleeves[1].positions["BTC/USDT"], dec!(5));
+        assert_eq!(report.risk_events.len(), 1);
+        assert_eq!(report.risk_events[0].rule, "cash");
+
+        // 单标的权重上限 30%：最多持有 3
+        let mut sleeves = vec![sleeve("a", "BTC/USDT", &[(1, Side::Buy, dec!(8))])];
+        let report = PortfolioBacktester::new(config(dec!(1000), Decimal::ZERO, Decimal::ZERO))
+            .with_limits(PortfolioLimits {
+                max_symbol_weight: Some(dec!(0.3)),
+                ..Default::default()
+            })
+            .run(&mut sleeves, &bars)?;
+        assert_eq!(report.trades[0].quantity, dec!(3));
+        assert_eq!(report.risk_events[0].rule, "max_symbol_weight");
+        Ok(())
+    }
+
+    #[test]
+    fn test_drawdown_halts_new_positions() -> Result<()> {
+        let bars = crypto_bars(&[dec!(100), dec!(100), dec!(60), dec!(60), dec!(60)]);
+        let mut sleeves = vec![
+            sleeve(
+                "a",
+                "BTC/USDT",
+                &[(1, Side::Buy, dec!(5)), (4, Side::Sell, dec!(5))],
+            ),
+            sleeve("b", "BTC/USDT", &[(3, Side::Buy, dec!(2))]),
+        ];
+        let report = PortfolioBacktester::new(config(dec!(1000), Decimal::ZERO, Decimal::ZERO))
+            .with_limits(PortfolioLimits {
+                max_drawdown: Some(dec!(0.2)),
+                ..Default::default()
+            })
+            .run(&mut sleeves, &bars)?;
+
+        // 第三天净值 800，回撤 20% 触发熔断：b 的买单被拒绝，a 的卖出照常成交
+        assert!(report.sleeves[1].trades.is_empty());
+        assert_eq!(report.sleeves[0].trades.len(), 2);
+        let rules: Vec<(&str, Option<&str>)> = report
+            .risk_events
+            .iter()
+            .map(|e| (e.rule.as_str(), e.symbol.as_deref()))
+            .collect();
+        assert_eq!(
+            rules,
+            vec![("max_drawdown", None), ("max_drawdown", Some("BTC/USDT"))]
+        );
+        assert_eq!(report.risk_events[0].date, date(3));
+        Ok(())
+    }
+
+    #[test]
+    fn test_calendar_alignment_across_exchanges() -> Result<()> {
+        // 2024-01-05 是周五：BTC 每天都有 K 线，AAPL 的周六 K 线是脏数据
+        let mut bars = vec![
+            bar(Exchange::Nasdaq, "AAPL/USD", 5, dec!(180)),
+            bar(Exchange::Nasdaq, "AAPL/USD", 6, dec!(999)),
+            bar(Exchange::Nasdaq, "AAPL/USD", 8, dec!(185)),
+        ];
+        for day in 5..=8 {
+            bars.push(bar(Exchange::Binance, "BTC/USDT", day, dec!(100)));
+        }
+        let mut sleeves = vec![
+            sleeve("equity", "AAPL/USD", &[(5, Side::Buy, dec!(1))]),
+            sleeve("crypto", "BTC/USDT", &[(5, Side::Buy, dec!(1))]),
+        ];
+        let report = PortfolioBacktester::new(config(dec!(1000), Decimal::ZERO, Decimal::ZERO))
+            .run(&mut sleeves, &bars)?;
+
+        assert_eq!(report.skipped_bars, 1);
+        assert_eq!(report.equity_curve.len(), 4);
+        // 周五的 AAPL 订单跳过周末，周一开盘成交；BTC 周六即成交
+        assert_eq!(report.sleeves[0].trades[0].date, date(8));
+        assert_eq!(report.sleeves[0].trades[0].price, dec!(185));
+        assert_eq!(report.sleeves[1].trades[0].date, date(6));
+        // 周末 AAPL 无持仓，净值只随 BTC 变动
+        assert_eq!(report.equity_curve[1].equity, dec!(1000));
+        assert_eq!(report.metrics.final_equity, dec!(1000));
+
+        let mut duplicate = vec![sleeve("x", "BTC/USDT", &[]), sleeve("x", "BTC/USDT", &[])];
+        assert!(
+            PortfolioBacktester::new(config(dec!(1000), Decimal::ZERO, Decimal::ZERO))
+                .run(&mut duplicate, &bars)
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_same_symbol_on_different_exchanges() -> Result<()> {
+        // 同名标的在两个交易所价格不同：分别记账、分别成交，不互相轧差
+        let mut bars = Vec::new();
+        for day in 1..=3 {
+            bars.push(bar(Exchange::Binance, "BTC/USDT", day, dec!(100)));
+            bars.push(bar(Exchange::Okx, "BTC/USDT", day, dec!(200)));
+        }
+        let mut sleeves = vec![
+            sleeve("binance", "BTC/USDT", &[(1, Side::Buy, dec!(2))])
+                .with_exchange(Exchange::Binance),
+            sleeve(
+                "okx",
+                "BTC/USDT",
+                &[(1, Side::Buy, dec!(1)), (2, Side::Sell, dec!(1))],
+            )
+            .with_exchange(Exchange::Okx),
+        ];
+        let report = PortfolioBacktester::new(config(dec!(1000), Decimal::ZERO, Decimal::ZERO))
+            .run(&mut sleeves, &bars)?;
+
+        let fills: Vec<(Side, Decimal, Decimal)> = report
+            .trades
+            .iter()
+            .map(|t| (t.side, t.quantity, t.price))
+            .collect();
+        assert_eq!(
+            fills,
+            vec![
+                (Side::Buy, dec!(2), dec!(100)),
+                (Side::Buy, dec!(1), dec!(200)),
+                (Side::Sell, dec!(1), dec!(200)),
+            ]
+        );
+        assert_eq!(report.crossed_notional, Decimal::ZERO);
+        assert_eq!(report.sleeves[0].positions["BTC/USDT"], dec!(2));
+        assert!(report.sleeves[1].positions.is_empty());
+        // 持仓按各自交易所的价格估值
+        assert_eq!(report.equity_curve[2].market_value, dec!(200));
+
+        // 不限定交易所时同一标的有两个来源，无法路由下单
+        let mut ambiguous = vec![sleeve("any", "BTC/USDT", &[])];
+        assert!(
+            PortfolioBacktester::new(config(dec!(1000), Decimal::ZERO, Decimal::ZERO))
+                .run(&mut ambiguous, &bars)
+                .is_err()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use chrono::NaiveDate;
+    use quant_core::enums::Side;
+    use quant_strategy::backtest::{
+        compute_metrics, BacktestConfig, BacktestReport, EquityPoint, Trade,
+    };
+    use quant_strategy::robustness::{analyze, path_stats, Distribution, RobustnessConfig};
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    // =========================================================================
+    // 1. Mock 数据
+    // =========================================================================
+
+    fn day(i: i64) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, 1).unwrap() + chrono::Duration::days(i)
+    }
+
+    fn trade(i: i64, side: Side, price: Decimal, pnl: Option<Decimal>) -> Trade {
+        Trade {
+            date: day(i),
+            symbol: "BTC/USDT".to_string(),
+            side,
+            quantity: dec!(10),
+            price,
+            fee: dec!(1),
+            realized_pnl: pnl,
+            reason: "mock".to_string(),
+        }
+    }
+
+    /// 两轮买卖：第一轮盈利 99，第二轮亏损 51，期末空仓
+    fn mock_report() -> BacktestReport {
+        let config = BacktestConfig {
+            initial_cash: dec!(1000),
+            fee_rate: dec!(0.001),
+            slippage_bps: Decimal::ZERO,
+            periods_per_year: 252,
+        };
+        let trades = vec![
+            trade(0, Side::Buy, dec!(100), None),
+            trade(2, Side::Sell, dec!(110), Some(dec!(98))),
+            trade(3, Side::Buy, dec!(110), None),
+            trade(5, Side::Sell, dec!(105), Some(dec!(-52))),
+        ];
+        let equity_curve: Vec<EquityPoint> = [999, 1050, 1098, 1097, 1020, 1046, 1046]
+            .iter()
+            .enumerate()
+            .map(|(i, e)| EquityPoint {
+                date: day(i as i64),
+                cash: Decimal::from(*e),
+                market_value: Decimal::ZERO,
+                equity: Decimal::from(*e),
+            })
+            .collect();
+        let metrics = compute_metrics(&equity_curve, &trades, config.initial_cash, 252);
+        BacktestReport {
+            strategy: "Mock".to_string(),
+            config,
+            metrics,
+            trades,
+            equity_curve,
+        }
+    }
+
+    fn small_config() -> RobustnessConfig {
+        RobustnessConfig {
+            simulations: 200,
+            ..Default::default()
+        }
+    }
+
+    // =========================================================================
+    // 2. 测试用例
+    // =========================================================================
+
+    #[test]
+    fn test_path_stats_drawdown_and_recovery() {
+        // 前高 120 (第 2 期)，谷底 90，第 5 期重新站上 120
+        let stats = path_stats(100.0, &[110.0, 120.0, 90.0, 100.0, 125.0]);
+        assert!((stats.max_drawdown - 0.25).abs() < 1e-12);
+        assert_eq!(stats.time_to_recover, Some(3));
+        assert!((stats.total_return - 0.25).abs() < 1e-12);
+
+        // 持平前高不算新的前高，恢复必须发生在谷底之后
+        let stats = path_stats(100.0, &[100.0, 80.0, 100.0]);
+        assert_eq!(stats.time_to_recover, Some(3));
+
+        let stats = path_stats(100.0, &[90.0, 95.0]);
+        assert_eq!(stats.time_to_recover, None);
+        assert_eq!(stats.final_equity, 95.0);
+
+        let stats = path_stats(100.0, &[101.0, 102.0]);
+        assert_eq!(stats.max_drawdown, 0.0);
+        assert_eq!(stats.time_to_recover, Some(0));
+    }
+
+    #[test]
+    fn test_distribution_confidence_interval() {
+        let samples: Vec<f64> = (0..=100).map(f64::from).collect();
+        let dist = Distribution::from_samples(&samples, 0.9).unwrap();
+        assert_eq!(dist.min, 0.0);
+        assert_eq!(dist.max, 100.0);
+        assert_eq!(dist.median, 50.0);
+        assert!((dist.mean - 50.0).abs() < 1e-12);
+        assert!((dist.lower - 5.0).abs() < 1e-9);
+        assert!((dist.upper - 95.0).abs() < 1e-9);
+
+        assert!(Distribution::from_samples(&[], 0.95).is_none());
+    }
+
+    #[test]
+    fn test_analyze_simulations() -> Result<()> {
+        let report = mock_report();
+        let result = analyze(&report, &small_config())?;
+        assert_eq!(result.baseline.final_equity, 1046.0);
+
+        // 收益重排不改变期末权益，回撤至少不小于 0
+        let shuffle = result.return_shuffle.as_ref().unwrap();
+        assert_eq!(shuffle.simulations, 200);
+        assert!((shuffle.final_equity.min - 1046.0).abs() < 1e-6);
+        assert!((shuffle.final_equity.max - 1046.0).abs() < 1e-6);
+        assert!(shuffle.max_drawdown.max >= result.baseline.max_drawdown);
+
+        // 两笔盈亏 (+98 / -52) 有放回抽样：期末权益只能是 1196 / 1046 / 896 三种
+        let bootstrap = result.trade_bootstrap.as_ref().unwrap();
+        for value in [bootstrap.final_equity.min, bootstrap.final_equity.max] {
+            assert!([1196.0, 1046.0, 896.0]
+                .iter()
+                .any(|v| (v - value).abs() < 1e-6));
+        }
+        assert!(bootstrap.loss_probability > 0.0 && bootstrap.loss_probability < 1.0);
+
+        // 成本只会增加：扰动后的期末权益不高于 “手续费减半、无额外滑点” 的上限
+        let costs = result.cost_perturbation.as_ref().unwrap();
+        assert!(costs.final_equity.max <= 1046.0 + 2.0 + 1e-6);
+        assert!(costs.final_equity.min < 1046.0);
+
+        // 同一种子结果可复现
+        let again = analyze(&report, &small_config())?;
+        assert_eq!(result.to_json()?, again.to_json()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cost_perturbation_identity_and_validation() -> Result<()> {
+        let report = mock_report();
+        let config = RobustnessConfig {
+            simulations: 10,
+            fee_multiplier: (1.0, 1.0),
+            extra_slippage_bps: (0.0, 0.0),
+            ..Default::default()
+        };
+        let costs = analyze(&report, &config)?.cost_perturbation.unwrap();
+        assert!((costs.final_equity.mean - 1046.0).abs() < 1e-9);
+        assert_eq!(costs.final_equity.std, 0.0);
+
+        // 手续费翻倍 + 10bps 额外滑点：4 笔各多 1 手续费，名义额合计 4250 * 0.001
+        let config = RobustnessConfig {
+            fee_multiplier: (2.0, 2.0),
+            extra_slippage_bps: (10.0, 10.0),
+            ..config
+        };
+        let costs = analyze(&report, &config)?.cost_perturbation.unwrap();
+        assert!((costs.final_equity.mean - (1046.0 - 4.0 - 4.25)).abs() < 1e-9);
+
+        let invalid = RobustnessConfig {
+            confidence: 1.0,
+            ..Default::default()
+        };
+        assert!(analyze(&report, &invalid).is_err());
+        let invalid = RobustnessConfig {
+            fee_multiplier: (2.0, 1.0),
+            ..Default::default()
+        };
+        assert!(analyze(&report, &invalid).is_err());
+        Ok(())
+    }
+}
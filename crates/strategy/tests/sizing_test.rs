@@ -0,0 +1,306 @@
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use chrono::NaiveDate;
+    use quant_core::account::{Asset, Position};
+    use quant_core::enums::{BarPeriod, Exchange, Side};
+    use quant_core::market::MarketBar;
+    use quant_core::primitive::{Price, Quantity};
+    use quant_strategy::sizing::{
+        allocate, average_true_range, realized_volatility, AccountSnapshot, AllocationMethod,
+        PositionSizer, SizingMethod, View,
+    };
+    use quant_strategy::StrategyContext;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+    use std::collections::{BTreeMap, HashMap};
+
+    // =========================================================================
+    // 1. Mock 数据
+    // =========================================================================
+
+    /// 按收盘价序列生成日线，最高 / 最低价为收盘价 ± `range`
+    fn mock_bars(closes: &[Decimal], range: Decimal) -> Vec<MarketBar> {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, close)| {
+                MarketBar::new(
+                    Exchange::Binance,
+                    "BTC/USDT",
+                    BarPeriod::D1,
+                    21,
+                    Price(*close),
+                    Price(*close + range),
+                    Price(*close - range),
+                    Price(*close),
+                    Quantity(dec!(1000)),
+                    start + chrono::Duration::days(i as i64),
+                )
+                .expect("Failed to create mock bar")
+            })
+            .collect()
+    }
+
+    fn cash_account() -> AccountSnapshot {
+        AccountSnapshot::new("USDT", dec!(10000))
+    }
+
+    // =========================================================================
+    // 2. 账户快照
+    // =========================================================================
+
+    #[test]
+    fn test_account_snapshot_from_assets_and_positions() -> Result<()> {
+        let mut usdt = Asset::new("main", Exchange::Binance, "USDT");
+        usdt.free = dec!(9000);
+        usdt.frozen = dec!(1000);
+        let mut btc = Asset::new("main", Exchange::Binance, "BTC");
+        btc.free = dec!(0.5);
+        let mut eth = Position::new("main", Exchange::Binance, "ETH/USDT", Side::Sell);
+        eth.quantity = dec!(2);
+        eth.unrealized_pnl = Some(dec!(-50));
+
+        let prices = HashMap::from([("BTC".to_string(), dec!(20000))]);
+        let snapshot =
+            AccountSnapshot::from_account(&[usdt.clone(), btc.clone()], &[eth], "usdt", &prices)?;
+        assert_eq!(snapshot.cash, dec!(10000));
+        assert_eq!(snapshot.nav, dec!(19950));
+        assert_eq!(snapshot.position("BTC/USDT"), dec!(0.5));
+        assert_eq!(snapshot.position("ETH/USDT"), dec!(-2));
+        assert_eq!(snapshot.position("SOL/USDT"), Decimal::ZERO);
+
+        // 无法估值的余额直接报错，避免低估净值
+        let err =
+            AccountSnapshot::from_account(&[usdt, btc], &[], "USDT", &HashMap::new()).unwrap_err();
+        assert!(err.to_string().contains("BTC"));
+        Ok(())
+    }
+
+    // =========================================================================
+    // 3. 定量方法
+    // =========================================================================
+
+    #[test]
+    fn test_fixed_sizing_with_strength_cap_and_lot() -> Result<()> {
+        let account = cash_account();
+        let view = View::long("BTC/USDT");
+
+        let notional = PositionSizer::new(SizingMethod::FixedNotional {
+            notional: dec!(1000),
+        });
+        assert_eq!(
+            notional.target_position(&account, &view, dec!(100), &[])?,
+            dec!(10)
+        );
+
+        let fractional = PositionSizer::new(SizingMethod::FixedFractional {
+            fraction: dec!(0.5),
+        });
+        assert_eq!(
+            fractional.target_position(&account, &View::short("BTC/USDT"), dec!(100), &[])?,
+            dec!(-50)
+        );
+
+        // 仓位上限 20% -> 20，强度 0.5 -> 25 被上限截断为 20，再按 3 取整 -> 18
+        let capped = fractional
+            .clone()
+            .with_max_position_fraction(dec!(0.2))
+            .with_lot_size(dec!(3));
+        let half = view.clone().with_strength(dec!(0.5));
+        assert_eq!(
+            capped.target_position(&account, &half, dec!(100), &[])?,
+            dec!(18)
+        );
+        assert_eq!(
+            capped.target_position(
+                &account,
+                &view.clone().with_strength(Decimal::ZERO),
+                dec!(100),
+                &[]
+            )?,
+            Decimal::ZERO
+        );
+
+        // 交易数量 = 目标 - 当前持仓
+        let mut holding = cash_account();
+        holding.positions.insert("BTC/USDT".to_string(), dec!(30));
+        assert_eq!(
+            capped.order_for(&holding, &view, dec!(100), &[])?,
+            Some((Side::Sell, Quantity(dec!(12))))
+        );
+        holding.positions.insert("BTC/USDT".to_string(), dec!(18));
+        assert_eq!(capped.order_for(&holding, &view, dec!(100), &[])?, None);
+
+        assert!(fractional
+            .target_position(&account, &view, Decimal::ZERO, &[])
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_atr_and_volatility_target_sizing() -> Result<()> {
+        let account = cash_account();
+        let view = View::long("BTC/USDT");
+
+        // 收盘价不变、振幅 ±1 -> 真实波幅恒为 2
+        let flat = mock_bars(&[dec!(100); 15], dec!(1));
+        assert_eq!(average_true_range(&flat, 14)?, dec!(2));
+        let atr = PositionSizer::new(SizingMethod::Atr {
+            risk_fraction: dec!(0.01),
+            period: 14,
+            multiple: dec!(2),
+        });
+        // 风险预算 100 / 止损距离 4 = 25
+        assert_eq!(
+            atr.target_position(&account, &view, dec!(100), &flat)?,
+            dec!(25)
+        );
+        assert!(atr
+            .target_position(&account, &view, dec!(100), &flat[..10])
+            .is_err());
+
+        let closes: Vec<Decimal> = (0..21)
+            .map(|i| if i % 2 == 0 { dec!(100) } else { dec!(110) })
+            .collect();
+        let swings = mock_bars(&closes, dec!(1));
+        let vol = realized_volatility(&swings, 20, 252.0)?;
+        assert!(vol > 0.0);
+        let target = PositionSizer::new(SizingMethod::VolatilityTarget {
+            target_vol: 0.2,
+            period: 20,
+            periods_per_year: 252.0,
+        });
+        let quantity = target.target_position(&account, &view, dec!(100), &swings)?;
+        let expected = 10000.0 * 0.2 / vol / 100.0;
+        assert!((quantity.to_string().parse::<f64>()? - expected).abs() < 1e-9);
+
+        // 没有波动无法定量
+        assert!(target
+            .target_position(
+                &account,
+                &view,
+                dec!(100),
+                &mock_bars(&[dec!(100); 21], dec!(1))
+            )
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_capped_kelly_sizing() -> Result<()> {
+        let account = cash_account();
+        let view = View::long("BTC/USDT");
+        let kelly = |win_rate, cap| {
+            PositionSizer::new(SizingMethod::Kelly {
+                win_rate,
+                payoff_ratio: dec!(1),
+                cap,
+            })
+        };
+
+        // f = 0.6 - 0.4 / 1 = 0.2
+        assert_eq!(
+            kelly(dec!(0.6), dec!(1)).target_position(&account, &view, dec!(100), &[])?,
+            dec!(20)
+        );
+        assert_eq!(
+            kelly(dec!(0.6), dec!(0.1)).target_position(&account, &view, dec!(100), &[])?,
+            dec!(10)
+        );
+        // 负期望不开仓
+        assert_eq!(
+            kelly(dec!(0.4), dec!(1)).order_for(&account, &view, dec!(100), &[])?,
+            None
+        );
+        // 封顶比例必须在 (0, 1] 内
+        for cap in [dec!(-0.1), dec!(0), dec!(1.5)] {
+            assert!(kelly(dec!(0.6), cap)
+                .target_position(&account, &view, dec!(100), &[])
+                .is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_sizer_from_strategy_config_and_context() -> Result<()> {
+        let config = json!({
+            "fast": 5,
+            "sizing": {
+                "method": "fixed_fractional",
+                "fraction": 0.5,
+                "lot_size": 1
+            }
+        });
+        let sizer = PositionSizer::from_config(&config, "sizing")?.unwrap();
+        assert_eq!(
+            sizer,
+            PositionSizer::new(SizingMethod::FixedFractional {
+                fraction: dec!(0.5)
+            })
+            .with_lot_size(dec!(1))
+        );
+        assert!(PositionSizer::from_config(&config, "missing")?.is_none());
+        assert!(
+            PositionSizer::from_config(&json!({"sizing": {"method": "martingale"}}), "sizing")
+                .is_err()
+        );
+
+        // 策略按上下文中的净值和持仓下单
+        let mut ctx = StrategyContext::new(dec!(1000));
+        ctx.positions.insert("BTC/USDT".to_string(), dec!(2));
+        ctx.order_view(&sizer, &View::long("BTC/USDT"), dec!(99), &[], "sized")?;
+        let orders = ctx.take_orders();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].side, Side::Buy);
+        assert_eq!(orders[0].quantity, dec!(3));
+        Ok(())
+    }
+
+    // =========================================================================
+    // 4. 跨策略资金分配
+    // =========================================================================
+
+    #[test]
+    fn test_allocation_methods() -> Result<()> {
+        // a 与 b 样本不相关，b 的波动率是 a 的两倍
+        let returns = BTreeMap::from([
+            ("a".to_string(), vec![0.01, -0.01, 0.01, -0.01]),
+            ("b".to_string(), vec![0.5, 0.02, 0.02, -0.02, -0.02]),
+        ]);
+
+        let equal = allocate(dec!(9000), &returns, AllocationMethod::EqualWeight)?;
+        assert_eq!(equal[0].capital, dec!(4500));
+
+        let inverse = allocate(dec!(9000), &returns, AllocationMethod::InverseVolatility)?;
+        assert_eq!(inverse[0].strategy_uuid, "a");
+        assert!((inverse[0].weight - 2.0 / 3.0).abs() < 1e-9);
+        assert!((inverse[1].capital - dec!(3000)).abs() < dec!(0.0001));
+
+        // 不相关时风险平价与波动率倒数一致
+        let parity = allocate(dec!(9000), &returns, AllocationMethod::RiskParity)?;
+        assert!((parity[0].weight - inverse[0].weight).abs() < 1e-6);
+        assert!((parity[0].risk_contribution - 0.5).abs() < 1e-6);
+
+        // 相关资产：风险贡献相等，且不同于波动率倒数
+        let correlated = BTreeMap::from([
+            ("a".to_string(), vec![0.01, -0.01, 0.01, -0.01]),
+            ("b".to_string(), vec![0.02, -0.02, -0.02, 0.02]),
+            ("c".to_string(), vec![0.015, -0.005, 0.01, -0.02]),
+        ]);
+        let parity = allocate(dec!(1000), &correlated, AllocationMethod::RiskParity)?;
+        let inverse = allocate(dec!(1000), &correlated, AllocationMethod::InverseVolatility)?;
+        assert!((parity.iter().map(|a| a.weight).sum::<f64>() - 1.0).abs() < 1e-9);
+        for allocation in &parity {
+            assert!((allocation.risk_contribution - 1.0 / 3.0).abs() < 1e-6);
+        }
+        assert!((parity[2].weight - inverse[2].weight).abs() > 1e-3);
+
+        assert!(allocate(dec!(1000), &BTreeMap::new(), AllocationMethod::RiskParity)?.is_empty());
+        let flat = BTreeMap::from([("a".to_string(), vec![0.0, 0.0, 0.0])]);
+        assert!(allocate(dec!(1000), &flat, AllocationMethod::RiskParity).is_err());
+        Ok(())
+    }
+}
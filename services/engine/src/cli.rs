@@ -0,0 +1,417 @@
+use chrono::NaiveDate;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use quant_core::enums::{BarPeriod, Exchange};
+use quant_storage::adjust::AdjustMode;
+use quant_storage::import::ImportArgs;
+use quant_storage::migrate::MigrateArgs;
+use quant_strategy::optimize::Objective;
+use quant_strategy::sizing::AllocationMethod;
+use rust_decimal::Decimal;
+use std::path::PathBuf;
+use std::str::FromStr;
+use uuid::Uuid;
+
+// =========================================================================
+// 命令行定义
+// =========================================================================
+
+/// Photon 量化交易引擎
+#[derive(Debug, Parser)]
+#[command(name = "quant-engine", version)]
+pub struct Cli {
+    /// 不带子命令时等同于 `paper`
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// 使用数据库中的历史 K 线回测策略
+    Backtest(BacktestArgs),
+    /// 参数优化 (网格 / 随机 / 贝叶斯搜索，可选滚动前推)
+    Optimize(OptimizeArgs),
+    /// 多策略组合回测 (共享账户、跨策略轧差、组合风控)
+    Portfolio(PortfolioArgs),
+    /// 对回测报告做 Monte Carlo / Bootstrap 稳健性分析
+    Robustness(RobustnessArgs),
+    /// 启动引擎 (模拟盘)
+    Paper,
+    /// 启动引擎 (实盘)
+    Live {
+        /// 确认以实盘模式运行
+        #[arg(long)]
+        yes: bool,
+    },
+    /// 导入 K 线 CSV / Parquet (文件或目录)
+    Import(ImportArgs),
+    /// 策略实例管理
+    #[command(subcommand)]
+    Strategy(StrategyCommand),
+    /// 查询订单
+    Orders(OrdersArgs),
+    /// 交易成本分析 (到达价滑点 / 执行落差 / 有效价差，按策略 / 标的 / 交易所汇总)
+    Tca(TcaArgs),
+    /// K 线数据质量扫描 (可选修复 / 隔离)
+    Quality(QualityArgs),
+    /// 执行数据库迁移 (建表 / 升级表结构)
+    Migrate(MigrateArgs),
+    /// 把数据库中的 K 线导出为本地 Parquet (供离线研究 / 回测)
+    Export(ExportArgs),
+    /// 在终端运行 Agent 任务
+    #[command(subcommand)]
+    Agent(AgentCommand),
+}
+
+#[derive(Debug, Args)]
+pub struct BacktestArgs {
+    /// 策略名 (config 中的 strategies[].name) 或策略类名
+    #[arg(long)]
+    pub strategy: String,
+    /// 策略参数 (JSON)，覆盖配置文件中的 params
+    #[arg(long)]
+    pub params: Option<String>,
+    /// 交易标的，可重复 (默认取配置中该策略的 symbols)
+    #[arg(long = "symbol")]
+    pub symbols: Vec<String>,
+    #[arg(long, default_value = "BINANCE", value_parser = parse_exchange)]
+    pub exchange: Exchange,
+    #[arg(long, default_value = "D1", value_parser = parse_period)]
+    pub period: BarPeriod,
+    /// 交易条件类型 (21 = 盘中交易)
+    #[arg(long, default_value_t = 21)]
+    pub trade_type: u8,
+    /// 开始日期 (YYYY-MM-DD)
+    #[arg(long)]
+    pub start: NaiveDate,
+    /// 结束日期 (YYYY-MM-DD，含)
+    #[arg(long)]
+    pub end: NaiveDate,
+    #[arg(long, default_value = "100000")]
+    pub cash: Decimal,
+    /// 手续费率 (0.001 = 10bps)
+    #[arg(long, default_value = "0.001")]
+    pub fee_rate: Decimal,
+    #[arg(long, default_value = "0")]
+    pub slippage_bps: Decimal,
+    /// 年化周期数 (股票日线 252，加密货币日线 365)
+    #[arg(long, default_value_t = 252)]
+    pub periods_per_year: u32,
+    /// 报告输出目录 (report.json / trades.csv / equity.csv)
+    #[arg(long, default_value = "reports/backtest")]
+    pub output: PathBuf,
+    /// 数据质量扫描发现错误时仍然回测
+    #[arg(long)]
+    pub allow_dirty: bool,
+    /// 复权方式 (none / backward / forward)，只对有公司行动的股票生效
+    #[arg(long, value_enum, default_value_t = AdjustMode::Backward)]
+    pub adjust: AdjustMode,
+    /// 从本地 Parquet 目录读取 K 线 (`export` 的输出)，不连接数据库，也不复权
+    #[arg(long)]
+    pub data_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct OptimizeArgs {
+    #[command(flatten)]
+    pub backtest: BacktestArgs,
+    /// 参数空间 (JSON)，默认取策略参数中的 `search_space` 字段
+    #[arg(long)]
+    pub space: Option<String>,
+    #[arg(long, value_enum, default_value_t = SearchMethod::Grid)]
+    pub search: SearchMethod,
+    /// 随机 / 贝叶斯搜索的试验次数
+    #[arg(long, default_value_t = 100)]
+    pub trials: usize,
+    /// 贝叶斯搜索前的随机探索次数
+    #[arg(long, default_value_t = 20)]
+    pub initial: usize,
+    #[arg(long, default_value_t = 42)]
+    pub seed: u64,
+    /// 排序目标 (sharpe / total_return / annualized_return / calmar / max_drawdown / win_rate)
+    #[arg(long, default_value = "sharpe", value_parser = parse_objective)]
+    pub objective: Objective,
+    /// 并行线程数 (默认 CPU 核数)
+    #[arg(long)]
+    pub threads: Option<usize>,
+    /// 滚动前推：样本内 K 线数 (需同时指定 --test)
+    #[arg(long, requires = "test")]
+    pub train: Option<usize>,
+    /// 滚动前推：样本外 K 线数 (也是滚动步长)
+    #[arg(long, requires = "train")]
+    pub test: Option<usize>,
+    /// 滚动前推使用扩张窗口 (样本内起点固定)
+    #[arg(long)]
+    pub anchored: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SearchMethod {
+    Grid,
+    Random,
+    Bayesian,
+}
+
+#[derive(Debug, Args)]
+pub struct PortfolioArgs {
+    /// 参与组合的策略实例 (config 中的 strategies[].name)，可重复 (默认全部启用的策略)
+    #[arg(long = "strategy")]
+    pub strategies: Vec<String>,
+    #[arg(long, default_value = "D1", value_parser = parse_period)]
+    pub period: BarPeriod,
+    /// 交易条件类型 (21 = 盘中交易)
+    #[arg(long, default_value_t = 21)]
+    pub trade_type: u8,
+    /// 开始日期 (YYYY-MM-DD)
+    #[arg(long)]
+    pub start: NaiveDate,
+    /// 结束日期 (YYYY-MM-DD，含)
+    #[arg(long)]
+    pub end: NaiveDate,
+    #[arg(long, default_value = "100000")]
+    pub cash: Decimal,
+    /// 手续费率 (0.001 = 10bps)
+    #[arg(long, default_value = "0.001")]
+    pub fee_rate: Decimal,
+    #[arg(long, default_value = "0")]
+    pub slippage_bps: Decimal,
+    /// 年化周期数 (组合按自然日计净值，含加密货币时用 365)
+    #[arg(long, default_value_t = 365)]
+    pub periods_per_year: u32,
+    /// 持仓总市值 / 净值上限
+    #[arg(long)]
+    pub max_gross_exposure: Option<Decimal>,
+    /// 单一标的市值 / 净值上限
+    #[arg(long)]
+    pub max_symbol_weight: Option<Decimal>,
+    /// 组合回撤达到该比例后停止开仓 (0.2 = 20%)
+    #[arg(long)]
+    pub max_drawdown: Option<Decimal>,
+    /// 按各策略的盈亏曲线分配资金 (equal_weight / inverse_volatility / risk_parity)，
+    /// 结果写入 allocations.json，可由 [signal.sizing].allocations 加载到实盘定量
+    #[arg(long, value_parser = parse_allocation)]
+    pub allocation: Option<AllocationMethod>,
+    /// 报告输出目录 (report.json / trades.csv / equity.csv / attribution.csv)
+    #[arg(long, default_value = "reports/portfolio")]
+    pub output: PathBuf,
+    /// 数据质量扫描发现错误时仍然回测
+    #[arg(long)]
+    pub allow_dirty: bool,
+    /// 复权方式 (none / backward / forward)，只对有公司行动的股票生效
+    #[arg(long, value_enum, default_value_t = AdjustMode::Backward)]
+    pub adjust: AdjustMode,
+    /// 从本地 Parquet 目录读取 K 线 (`export` 的输出)，不连接数据库，也不复权
+    #[arg(long)]
+    pub data_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct RobustnessArgs {
+    /// `backtest` 输出的 report.json
+    #[arg(long)]
+    pub report: PathBuf,
+    /// 结果输出路径 (默认与报告同目录的 robustness.json)
+    #[arg(long)]
+    pub output: Option<PathBuf>,
+    /// 每种方法的模拟次数
+    #[arg(long, default_value_t = 1000)]
+    pub simulations: usize,
+    /// 置信水平
+    #[arg(long, default_value_t = 0.95)]
+    pub confidence: f64,
+    #[arg(long, default_value_t = 42)]
+    pub seed: u64,
+    /// 手续费倍数区间 (MIN MAX)
+    #[arg(long, num_args = 2, value_names = ["MIN", "MAX"], default_values_t = [0.5, 2.0])]
+    pub fee_multiplier: Vec<f64>,
+    /// 额外滑点区间 (bps，MIN MAX)
+    #[arg(long, num_args = 2, value_names = ["MIN", "MAX"], default_values_t = [0.0, 10.0])]
+    pub extra_slippage_bps: Vec<f64>,
+}
+
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    /// Parquet 输出目录 (已存在的分区会被合并)
+    #[arg(long)]
+    pub out: PathBuf,
+    /// 只导出指定交易所
+    #[arg(long, value_parser = parse_exchange)]
+    pub exchange: Option<Exchange>,
+    /// 只导出指定标的 (e.g. BTC/USDT)
+    #[arg(long)]
+    pub symbol: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct QualityArgs {
+    /// 只扫描指定交易所
+    #[arg(long, value_parser = parse_exchange)]
+    pub exchange: Option<Exchange>,
+    /// 只扫描指定标的 (e.g. AAPL/USD)
+    #[arg(long)]
+    pub symbol: Option<String>,
+    /// 相邻收盘价涨跌幅阈值 (0.5 = ±50%)
+    #[arg(long, default_value_t = 0.5)]
+    pub max_jump: f64,
+    /// 不检测缺失交易日
+    #[arg(long)]
+    pub no_gaps: bool,
+    /// 修正可修复的 OHLC，其余错误 K 线移入隔离表
+    #[arg(long, conflicts_with = "quarantine")]
+    pub repair: bool,
+    /// 错误 K 线全部移入隔离表
+    #[arg(long)]
+    pub quarantine: bool,
+    /// 每条序列最多打印的异常数
+    #[arg(long, default_value_t = 10)]
+    pub show: usize,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum StrategyCommand {
+    /// 列出全部策略实例
+    List,
+    /// 启动策略 (状态置为 RUNNING；已停止的策略先经过 INITIALIZING)
+    Start {
+        uuid: Uuid,
+        /// 变更原因 (记入审计)
+        #[arg(long, default_value = "")]
+        reason: String,
+    },
+    /// 暂停策略
+    Pause {
+        uuid: Uuid,
+        #[arg(long, default_value = "")]
+        reason: String,
+    },
+    /// 停止策略
+    Stop {
+        uuid: Uuid,
+        #[arg(long, default_value = "")]
+        reason: String,
+    },
+    /// 最近信号的执行统计 (命中率 / 信号 -> 成交延迟)
+    Signals {
+        uuid: Uuid,
+        /// 统计最近的信号数
+        #[arg(long, default_value_t = 500)]
+        limit: i64,
+    },
+    /// 查看状态变更审计记录
+    Audit {
+        uuid: Uuid,
+        /// 最多显示的记录数
+        #[arg(long, default_value_t = 20)]
+        limit: i64,
+    },
+    /// 查看运行状态的历史版本
+    History {
+        uuid: Uuid,
+        /// 最多显示的版本数
+        #[arg(long, default_value_t = 20)]
+        limit: i64,
+    },
+    /// 把运行状态回滚到指定版本 (复制为新版本，重启策略后生效)
+    Rollback { uuid: Uuid, version: i64 },
+}
+
+#[derive(Debug, Args)]
+pub struct OrdersArgs {
+    /// 只看指定策略的订单
+    #[arg(long)]
+    pub strategy: Option<Uuid>,
+    /// 只看未终结订单
+    #[arg(long)]
+    pub open: bool,
+    /// 最近 N 天 (未指定 strategy/open 时生效)
+    #[arg(long, default_value_t = 1)]
+    pub days: i64,
+    /// 最多显示条数
+    #[arg(long, default_value_t = 50)]
+    pub limit: usize,
+}
+
+#[derive(Debug, Args)]
+pub struct TcaArgs {
+    /// 开始日期 (YYYY-MM-DD，按订单创建时间)
+    #[arg(long)]
+    pub start: NaiveDate,
+    /// 结束日期 (YYYY-MM-DD，含)
+    #[arg(long)]
+    pub end: NaiveDate,
+    /// 交易条件类型 (21 = 盘中交易)
+    #[arg(long, default_value_t = 21)]
+    pub trade_type: u8,
+    /// 输出格式 (json: report.json；csv: orders.csv + summary.csv)
+    #[arg(long, value_enum, default_value_t = TcaFormat::Json)]
+    pub format: TcaFormat,
+    /// 报告输出目录
+    #[arg(long, default_value = "reports/tca")]
+    pub output: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TcaFormat {
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AgentCommand {
+    /// 执行一次 Agent 任务并打印 JSON 结果
+    Run(AgentRunArgs),
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum AgentKind {
+    /// ManagerAgent：规划-执行-验证
+    Manager,
+    /// DebateHost：多角色辩论
+    Debate,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum LlmKind {
+    Qwen,
+    Gemini,
+}
+
+#[derive(Debug, Args)]
+pub struct AgentRunArgs {
+    #[arg(value_enum)]
+    pub kind: AgentKind,
+    /// 任务指令 / 辩论主题
+    #[arg(long)]
+    pub task: String,
+    #[arg(long, value_enum, default_value = "qwen")]
+    pub llm: LlmKind,
+    /// 期望的输出格式描述
+    #[arg(
+        long,
+        default_value = "A JSON object with fields: conclusion (string), reasons (array of string)"
+    )]
+    pub output_schema: String,
+    /// 辩论最大轮数
+    #[arg(long, default_value_t = 3)]
+    pub max_turns: usize,
+    /// Agent 上下文落盘目录
+    #[arg(long, default_value = "logs/agent")]
+    pub store: PathBuf,
+}
+
+fn parse_exchange(s: &str) -> Result<Exchange, String> {
+    Exchange::from_str(&s.to_uppercase()).map_err(|_| format!("unknown exchange: {}", s))
+}
+
+fn parse_objective(s: &str) -> Result<Objective, String> {
+    serde_json::from_value(serde_json::Value::String(s.to_lowercase()))
+        .map_err(|_| format!("unknown objective: {}", s))
+}
+
+fn parse_allocation(s: &str) -> Result<AllocationMethod, String> {
+    serde_json::from_value(serde_json::Value::String(s.to_lowercase()))
+        .map_err(|_| format!("unknown allocation method: {}", s))
+}
+
+fn parse_period(s: &str) -> Result<BarPeriod, String> {
+    BarPeriod::from_str(&s.to_uppercase()).map_err(|_| format!("unknown bar period: {}", s))
+}
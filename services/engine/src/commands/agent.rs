@@ -0,0 +1,50 @@
+use crate::cli::{AgentKind, AgentRunArgs, LlmKind};
+use anyhow::Result;
+use quant_agent::host::DebateHost;
+use quant_agent::llm::gemini3_flash::GeminiBackend;
+use quant_agent::llm::qwen3_flash::QwenBackend;
+use quant_agent::llm::ModelBackend;
+use quant_agent::manager::ManagerAgent;
+use quant_agent::personas::fundamental_skill::FundamentalSkill;
+use quant_agent::personas::macro_skill::MacroSkill;
+use quant_agent::personas::technical_skill::TechnicalSkill;
+use quant_agent::store::local::LocalFileStore;
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::info;
+
+fn backend(kind: LlmKind) -> Box<dyn ModelBackend> {
+    match kind {
+        LlmKind::Qwen => Box::new(QwenBackend::new()),
+        LlmKind::Gemini => Box::new(GeminiBackend::new()),
+    }
+}
+
+/// 在终端运行一次 ManagerAgent / DebateHost 任务
+pub async fn run(args: AgentRunArgs) -> Result<()> {
+    let store = Arc::new(LocalFileStore::new(&args.store));
+    let llm: Arc<dyn ModelBackend> = Arc::from(backend(args.llm));
+
+    info!("🤖 Running {:?} agent: {}", args.kind, args.task);
+    let result: Value = match args.kind {
+        AgentKind::Manager => {
+            let mut agent = ManagerAgent::new("CliManager", llm, store);
+            agent.register_skill(FundamentalSkill::new(backend(args.llm)));
+            agent.register_skill(MacroSkill::new(backend(args.llm)));
+            agent.register_skill(TechnicalSkill::new(backend(args.llm)));
+            agent.run_task(&args.task, &args.output_schema).await?
+        }
+        AgentKind::Debate => {
+            let host = DebateHost::builder("CliDebate", llm, store)
+                .register_skill(FundamentalSkill::new(backend(args.llm)))
+                .register_skill(MacroSkill::new(backend(args.llm)))
+                .register_skill(TechnicalSkill::new(backend(args.llm)))
+                .with_max_turns(args.max_turns)
+                .build();
+            host.run_debate(&args.task, &args.output_schema).await?
+        }
+    };
+
+    println!("{}", serde_json::to_string_pretty(&result)?);
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use crate::cli::BacktestArgs;
+use crate::config::EngineConfig;
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
+use quant_core::market::MarketBar;
+use quant_storage::adjust::{AdjustMode, AdjustedBarService};
+use quant_storage::quality::{scan_series, QualityRules};
+use quant_storage::repository::market_repo::BarSeriesKey;
+use quant_storage::store::ParquetMarketStore;
+use quant_strategy::backtest::{BacktestConfig, Backtester};
+use quant_strategy::registry::StrategyRegistry;
+use serde_json::Value;
+use std::fs::{self, File};
+use std::path::Path;
+use tracing::{info, warn};
+
+/// 从数据库 (或 --data-dir 指定的 Parquet 目录) 加载 K 线，运行回测并输出报告
+pub async fn run(args: BacktestArgs) -> Result<()> {
+    let (class_name, params, bars) = prepare(&args).await?;
+    let mut strategy = StrategyRegistry::with_builtins().create(&class_name, &params)?;
+
+    // 1. 回测
+    let backtester = Backtester::new(backtest_config(&args));
+    let report = backtester.run(strategy.as_mut(), &bars)?;
+
+    // 2. 输出报告
+    fs::create_dir_all(&args.output)
+        .with_context(|| format!("Failed to create {:?}", args.output))?;
+    fs::write(args.output.join("report.json"), report.to_json()?)?;
+    report.write_trades_csv(File::create(args.output.join("trades.csv"))?)?;
+    report.write_equity_csv(File::create(args.output.join("equity.csv"))?)?;
+
+    let m = &report.metrics;
+    info!(
+        "📊 {} | return {:.2}% | annualized {:.2}% | sharpe {:.2} | max drawdown {:.2}% | trades {} | fees {}",
+        report.strategy,
+        m.total_return * 100.0,
+        m.annualized_return * 100.0,
+        m.sharpe,
+        m.max_drawdown * 100.0,
+        m.trade_count,
+        m.total_fees
+    );
+    info!("📝 Report written to {:?}", args.output);
+    Ok(())
+}
+
+pub(crate) fn backtest_config(args: &BacktestArgs) -> BacktestConfig {
+    BacktestConfig {
+        initial_cash: args.cash,
+        fee_rate: args.fee_rate,
+        slippage_bps: args.slippage_bps,
+        periods_per_year: args.periods_per_year,
+    }
+}
+
+/// 解析策略类名和参数，并加载 (复权后的) 历史 K 线
+pub(crate) async fn prepare(args: &BacktestArgs) -> Result<(String, Value, Vec<MarketBar>)> {
+    // 1. 解析策略：优先匹配配置中的策略实例，否则按类名处理
+    let config = EngineConfig::load().unwrap_or_else(|e| {
+        warn!(
+            "Config not loaded ({:#}), treating --strategy as class name",
+            e
+        );
+        EngineConfig::default()
+    });
+    let instance = config.strategies.iter().find(|s| s.name == args.strategy);
+    let class_name = instance
+        .map(|s| s.class_name.clone())
+        .unwrap_or_else(|| args.strategy.clone());
+    let params: Value = match &args.params {
+        Some(raw) => serde_json::from_str(raw).context("--params is not valid JSON")?,
+        None => instance.map(|s| s.params.clone()).unwrap_or(Value::Null),
+    };
+    let symbols = if args.symbols.is_empty() {
+        instance.map(|s| s.symbols.clone()).unwrap_or_default()
+    } else {
+        args.symbols.clone()
+    };
+    if symbols.is_empty() {
+        bail!("No symbols to backtest, pass --symbol");
+    }
+
+    // 2. 加载历史 K 线 (按 --adjust 复权，原始数据不变)
+    let keys: Vec<BarSeriesKey> = symbols
+        .iter()
+        .map(|symbol| BarSeriesKey {
+            exchange: args.exchange,
+            symbol: symbol.clone(),
+            bar_period: args.period,
+            trade_type: args.trade_type,
+        })
+        .collect();
+    let bars = load_bars(
+        &keys,
+        args.start,
+        args.end,
+        args.adjust,
+        args.data_dir.as_deref(),
+        args.allow_dirty,
+    )
+    .await?;
+    if bars.is_empty() {
+        bail!(
+            "No bars found for {:?} on {} between {} and {}",
+            symbols,
+            args.exchange,
+            args.start,
+            args.end
+        );
+    }
+
+    Ok((class_name, params, bars))
+}
+
+/// 按序列加载 (复权后的) K 线并做数据质量检查，有 Error 级别异常时拒绝 (除非 `allow_dirty`)
+pub(crate) async fn load_bars(
+    keys: &[BarSeriesKey],
+    start: NaiveDate,
+    end: NaiveDate,
+    adjust: AdjustMode,
+    data_dir: Option<&Path>,
+    allow_dirty: bool,
+) -> Result<Vec<MarketBar>> {
+    let storage;
+    let files;
+    let service = match data_dir {
+        Some(dir) => {
+            info!("📂 Reading bars from {:?} (no database)", dir);
+            files = ParquetMarketStore::open(dir)?;
+            AdjustedBarService::without_actions(&files)
+        }
+        None => {
+            storage = super::connect_storage().await?;
+            AdjustedBarService::new(storage.market_store(), storage.corporate_actions())
+        }
+    };
+    let mut bars = Vec::new();
+    for key in keys {
+        let loaded = service.load(key, start, end, adjust).await?;
+        info!("📈 Loaded {} bars for {}", loaded.len(), key.symbol);
+
+        // 数据质量检查：有 Error 级别异常时拒绝回测 (除非 --allow-dirty)
+        let quality = scan_series(key, &loaded, &QualityRules::default());
+        if !quality.is_clean() {
+            let message = format!(
+                "{} has {} data errors (first on {}), run `quant-engine quality --symbol {}`",
+                key,
+                quality.errors(),
+                quality
+                    .first_error()
+                    .map(|a| a.date.to_string())
+                    .unwrap_or_default(),
+                key.symbol
+            );
+            if !allow_dirty {
+                bail!("{} (pass --allow-dirty to backtest anyway)", message);
+            }
+            warn!("⚠️ {}", message);
+        } else if quality.warnings() > 0 {
+            warn!("⚠️ {}: {} data warnings", key, quality.warnings());
+        }
+        bars.extend(loaded);
+    }
+    Ok(bars)
+}
@@ -0,0 +1,24 @@
+use crate::cli::ExportArgs;
+use anyhow::Result;
+use quant_storage::store::parquet::{self, ParquetMarketStore};
+use quant_storage::Storage;
+use tracing::info;
+
+/// 把 MySQL market_bar 中的 K 线序列导出为分区 Parquet
+pub async fn run(storage: &Storage, args: ExportArgs) -> Result<()> {
+    let target = ParquetMarketStore::open(&args.out)?;
+    let exchange = args.exchange.map(|e| e.to_string());
+    info!("📤 Exporting bars to {:?}", target.root());
+    let summary = parquet::export_bars(
+        storage.market(),
+        &target,
+        exchange.as_deref(),
+        args.symbol.as_deref(),
+    )
+    .await?;
+    info!(
+        "✅ Exported {} bars in {} series",
+        summary.bars, summary.series
+    );
+    Ok(())
+}
@@ -0,0 +1,9 @@
+use anyhow::Result;
+use quant_storage::import::{self, ImportArgs};
+use quant_storage::Storage;
+
+/// 导入 K 线到 market_bar 表 (导入后自动做数据质量扫描)
+pub async fn run(storage: &Storage, args: ImportArgs) -> Result<()> {
+    import::run(storage, &args).await?;
+    Ok(())
+}
@@ -0,0 +1,8 @@
+use anyhow::Result;
+use quant_storage::migrate::{self, MigrateArgs};
+use quant_storage::Storage;
+
+/// 对 `[storage]` 配置的数据库执行数据库迁移 (--status 只打印状态)
+pub async fn run(storage: &Storage, args: MigrateArgs) -> Result<()> {
+    migrate::run(storage, &args).await
+}
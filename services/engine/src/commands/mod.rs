@@ -0,0 +1,26 @@
+pub mod agent;
+pub mod backtest;
+pub mod export;
+pub mod import;
+pub mod migrate;
+pub mod optimize;
+pub mod orders;
+pub mod portfolio;
+pub mod quality;
+pub mod robustness;
+pub mod run;
+pub mod strategy;
+pub mod tca;
+
+use crate::config::EngineConfig;
+use anyhow::Result;
+use quant_storage::Storage;
+
+/// 按 `[storage]` 配置连接存储 (配置加载失败时只用环境变量)
+pub async fn connect_storage() -> Result<Storage> {
+    let config = EngineConfig::load()
+        .map(|c| c.storage)
+        .unwrap_or_default()
+        .with_env_fallback();
+    Storage::connect(&config).await
+}
@@ -0,0 +1,135 @@
+use super::backtest::{backtest_config, prepare};
+use crate::cli::{OptimizeArgs, SearchMethod};
+use anyhow::{bail, Context, Result};
+use quant_strategy::optimize::{
+    OptimizationReport, Optimizer, ParamRange, ParamSpace, Search, WalkForward,
+};
+use quant_strategy::registry::StrategyRegistry;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use tracing::info;
+
+/// 策略参数中描述参数空间的字段
+const SPACE_KEY: &str = "search_space";
+
+/// 加载 K 线后搜索参数，输出排名 (可选滚动前推评估样本外表现)
+pub async fn run(args: OptimizeArgs) -> Result<()> {
+    let (class_name, params, bars) = prepare(&args.backtest).await?;
+
+    // 1. 参数空间：--space 覆盖策略参数中的 search_space
+    let mut space = ParamSpace::from_config(&params, SPACE_KEY)?;
+    if let Some(raw) = &args.space {
+        let ranges: BTreeMap<String, ParamRange> =
+            serde_json::from_str(raw).context("--space is not a valid parameter space")?;
+        space.ranges = ranges;
+        space.validate()?;
+    }
+    if space.ranges.is_empty() {
+        bail!(
+            "No parameters to optimize, pass --space or add `{}` to the strategy params",
+            SPACE_KEY
+        );
+    }
+
+    let search = match args.search {
+        SearchMethod::Grid => Search::Grid,
+        SearchMethod::Random => Search::Random {
+            trials: args.trials,
+            seed: args.seed,
+        },
+        SearchMethod::Bayesian => Search::Bayesian {
+            trials: args.trials,
+            initial: args.initial,
+            seed: args.seed,
+        },
+    };
+    let mut optimizer = Optimizer::new(
+        StrategyRegistry::with_builtins(),
+        class_name.clone(),
+        space,
+        backtest_config(&args.backtest),
+    )
+    .with_objective(args.objective)
+    .with_search(search);
+    if let Some(threads) = args.threads {
+        optimizer = optimizer.with_threads(threads);
+    }
+
+    let output = &args.backtest.output;
+    fs::create_dir_all(output).with_context(|| format!("Failed to create {:?}", output))?;
+
+    // 2. 全样本优化
+    let report = optimizer.optimize(&bars)?;
+    fs::write(output.join("optimize.json"), report.to_json()?)?;
+    report.write_trials_csv(File::create(output.join("trials.csv"))?)?;
+    log_report(&report);
+
+    // 3. 滚动前推
+    if let (Some(train), Some(test)) = (args.train, args.test) {
+        let walk = optimizer.walk_forward(
+            &bars,
+            WalkForward {
+                train,
+                test,
+                anchored: args.anchored,
+            },
+        )?;
+        fs::write(output.join("walk_forward.json"), walk.to_json()?)?;
+        for fold in &walk.folds {
+            info!(
+                "🔁 Fold {} | IS {} ~ {} score {:.3} | OOS {} ~ {} score {:.3} return {:.2}% | {}",
+                fold.fold,
+                fold.train_start,
+                fold.train_end,
+                fold.in_sample_score,
+                fold.test_start,
+                fold.test_end,
+                fold.out_of_sample_score,
+                fold.out_of_sample.total_return * 100.0,
+                serde_json::to_string(&fold.params)?
+            );
+        }
+        info!(
+            "🔁 Walk-forward {} folds | IS {:.3} | OOS {:.3} | efficiency {} | OOS return {:.2}%",
+            walk.folds.len(),
+            walk.mean_in_sample_score,
+            walk.mean_out_of_sample_score,
+            walk.efficiency
+                .map(|e| format!("{:.2}", e))
+                .unwrap_or_else(|| "-".to_string()),
+            walk.out_of_sample_return * 100.0
+        );
+    }
+    info!("📝 Optimization written to {:?}", output);
+    Ok(())
+}
+
+fn log_report(report: &OptimizationReport) {
+    let valid = report.trials.iter().filter(|t| t.score.is_some()).count();
+    info!(
+        "🧪 {} trials ({} valid) ranked by {:?}",
+        report.trials.len(),
+        valid,
+        report.objective
+    );
+    for (rank, trial) in report.trials.iter().take(5).enumerate() {
+        let Some(metrics) = &trial.metrics else {
+            continue;
+        };
+        info!(
+            "#{} score {:.3} | return {:.2}% | sharpe {:.2} | max drawdown {:.2}% | {}",
+            rank + 1,
+            trial.score.unwrap_or_default(),
+            metrics.total_return * 100.0,
+            metrics.sharpe,
+            metrics.max_drawdown * 100.0,
+            serde_json::to_string(&trial.params).unwrap_or_default()
+        );
+    }
+    if let Some(dsr) = report.deflated_sharpe {
+        info!(
+            "📉 Deflated Sharpe of best trial: {:.3} (probability the edge survives {} trials)",
+            dsr, valid
+        );
+    }
+}
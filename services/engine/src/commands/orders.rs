@@ -0,0 +1,47 @@
+use crate::cli::OrdersArgs;
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use quant_storage::Storage;
+
+/// 查询订单并以表格输出
+pub async fn run(storage: &Storage, args: OrdersArgs) -> Result<()> {
+    let repo = storage.orders();
+    let mut orders = match args.strategy {
+        Some(uuid) => repo.find_by_strategy(uuid).await?,
+        None if args.open => repo.find_open_orders().await?,
+        None => {
+            let end = Utc::now();
+            repo.find_by_range(end - Duration::days(args.days), end)
+                .await?
+        }
+    };
+    if args.open {
+        orders.retain(|o| o.status.is_open());
+    }
+
+    println!(
+        "{:<36}  {:<10}  {:<9}  {:<4}  {:<6}  {:<16}  {:>14}  {:>14}  {:>14}  CREATED",
+        "ORDER", "SYMBOL", "EXCHANGE", "SIDE", "TYPE", "STATUS", "PRICE", "QTY", "FILLED"
+    );
+    for o in orders.iter().take(args.limit) {
+        println!(
+            "{:<36}  {:<10}  {:<9}  {:<4}  {:<6}  {:<16}  {:>14}  {:>14}  {:>14}  {}",
+            o.uuid,
+            o.symbol.to_string(),
+            o.exchange.to_string(),
+            o.side.to_string(),
+            o.order_type.to_string(),
+            o.status.to_string(),
+            o.price
+                .map(|p| p.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            o.quantity.to_string(),
+            o.filled_quantity.to_string(),
+            o.gmt_create.format("%Y-%m-%d %H:%M:%S")
+        );
+    }
+    if orders.len() > args.limit {
+        println!("... {} more (use --limit)", orders.len() - args.limit);
+    }
+    Ok(())
+}
@@ -0,0 +1,192 @@
+use super::backtest::load_bars;
+use crate::cli::PortfolioArgs;
+use crate::config::EngineConfig;
+use anyhow::{bail, Context, Result};
+use quant_storage::repository::market_repo::BarSeriesKey;
+use quant_strategy::backtest::BacktestConfig;
+use quant_strategy::portfolio::{PortfolioBacktester, PortfolioLimits, Sleeve};
+use quant_strategy::registry::StrategyRegistry;
+use quant_strategy::sizing::allocate;
+use rust_decimal::prelude::ToPrimitive;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use tracing::{info, warn};
+
+/// 按配置中的策略实例组建组合，在共享账户上回测并输出组合报告和策略归因
+pub async fn run(args: PortfolioArgs) -> Result<()> {
+    let config = EngineConfig::load()?;
+
+    // 1. 选择策略实例 (默认全部启用的策略)
+    let instances: Vec<_> = if args.strategies.is_empty() {
+        config.strategies.iter().filter(|s| s.enabled).collect()
+    } else {
+        args.strategies
+            .iter()
+            .map(|name| {
+                config
+                    .strategies
+                    .iter()
+                    .find(|s| &s.name == name)
+                    .with_context(|| format!("Strategy {} not found in config", name))
+            })
+            .collect::<Result<_>>()?
+    };
+    if instances.is_empty() {
+        bail!("No strategies to backtest, pass --strategy or enable strategies in config");
+    }
+
+    let registry = StrategyRegistry::with_builtins();
+    let mut sleeves = Vec::with_capacity(instances.len());
+    let mut keys: Vec<BarSeriesKey> = Vec::new();
+    for instance in &instances {
+        if instance.symbols.is_empty() {
+            bail!("Strategy {} has no symbols", instance.name);
+        }
+        // 标的所在交易所：优先 symbols 配置，否则取策略账户的交易所
+        let account_exchange = config
+            .accounts
+            .iter()
+            .find(|a| a.name == instance.account)
+            .map(|a| a.exchange);
+        let mut exchanges = Vec::new();
+        for symbol in &instance.symbols {
+            let exchange = config
+                .symbols
+                .iter()
+                .find(|s| &s.symbol == symbol)
+                .map(|s| s.exchange)
+                .or(account_exchange)
+                .with_context(|| format!("Unknown exchange for symbol {}", symbol))?;
+            if !exchanges.contains(&exchange) {
+                exchanges.push(exchange);
+            }
+            let key = BarSeriesKey {
+                exchange,
+                symbol: symbol.clone(),
+                bar_period: args.period,
+                trade_type: args.trade_type,
+            };
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+        let strategy = registry.create(&instance.class_name, &instance.params)?;
+        let mut sleeve = Sleeve::new(instance.name.clone(), strategy, instance.symbols.clone());
+        // 标的都在同一交易所时只接收该交易所的行情，避免与其他策略的同名标的混淆
+        if let [exchange] = exchanges[..] {
+            sleeve = sleeve.with_exchange(exchange);
+        }
+        sleeves.push(sleeve);
+    }
+
+    // 2. 加载所有标的的 K 线 (跨交易所，按各自日历对齐)
+    let bars = load_bars(
+        &keys,
+        args.start,
+        args.end,
+        args.adjust,
+        args.data_dir.as_deref(),
+        args.allow_dirty,
+    )
+    .await?;
+    if bars.is_empty() {
+        bail!(
+            "No bars found for {} series between {} and {}",
+            keys.len(),
+            args.start,
+            args.end
+        );
+    }
+
+    // 3. 回测：单笔名义价值限额沿用实盘的 [risk] 配置
+    let backtester = PortfolioBacktester::new(BacktestConfig {
+        initial_cash: args.cash,
+        fee_rate: args.fee_rate,
+        slippage_bps: args.slippage_bps,
+        periods_per_year: args.periods_per_year,
+    })
+    .with_limits(PortfolioLimits {
+        max_gross_exposure: args.max_gross_exposure,
+        max_symbol_weight: args.max_symbol_weight,
+        max_order_notional: config.risk.max_order_notional,
+        max_drawdown: args.max_drawdown,
+    });
+    let report = backtester.run(&mut sleeves, &bars)?;
+
+    // 4. 输出报告
+    fs::create_dir_all(&args.output)
+        .with_context(|| format!("Failed to create {:?}", args.output))?;
+    fs::write(args.output.join("report.json"), report.to_json()?)?;
+    report.write_trades_csv(File::create(args.output.join("trades.csv"))?)?;
+    report.write_equity_csv(File::create(args.output.join("equity.csv"))?)?;
+    report.write_attribution_csv(File::create(args.output.join("attribution.csv"))?)?;
+
+    let m = &report.metrics;
+    info!(
+        "📊 Portfolio of {} | return {:.2}% | annualized {:.2}% | sharpe {:.2} | max drawdown {:.2}% | trades {} | fees {} | crossed {}",
+        report.sleeves.len(),
+        m.total_return * 100.0,
+        m.annualized_return * 100.0,
+        m.sharpe,
+        m.max_drawdown * 100.0,
+        m.trade_count,
+        m.total_fees,
+        report.crossed_notional.round_dp(2)
+    );
+    for sleeve in &report.sleeves {
+        info!(
+            "🧩 {} ({}) | pnl {} | contribution {:.2}% | trades {} | fees {}",
+            sleeve.name,
+            sleeve.strategy,
+            sleeve.pnl.round_dp(2),
+            sleeve.contribution * 100.0,
+            sleeve.trades.len(),
+            sleeve.fees.round_dp(2)
+        );
+    }
+    // 5. 可选：按各策略的盈亏曲线在策略间分配资金，供实盘定量使用
+    if let Some(method) = args.allocation {
+        let mut returns = BTreeMap::new();
+        for (instance, sleeve) in instances.iter().zip(&report.sleeves) {
+            let Some(uuid) = &instance.uuid else {
+                warn!(
+                    "Strategy {} has no uuid, skipped in allocation",
+                    instance.name
+                );
+                continue;
+            };
+            // 每期盈亏变化 / 初始资金 作为策略收益率
+            let series: Vec<f64> = sleeve
+                .pnl_curve
+                .windows(2)
+                .map(|w| ((w[1].pnl - w[0].pnl) / args.cash).to_f64().unwrap_or(0.0))
+                .collect();
+            returns.insert(uuid.clone(), series);
+        }
+        let allocations = allocate(args.cash, &returns, method)?;
+        fs::write(
+            args.output.join("allocations.json"),
+            serde_json::to_string_pretty(&allocations)?,
+        )?;
+        for allocation in &allocations {
+            info!(
+                "💰 {} | weight {:.2}% | capital {} | risk contribution {:.2}%",
+                allocation.strategy_uuid,
+                allocation.weight * 100.0,
+                allocation.capital,
+                allocation.risk_contribution * 100.0
+            );
+        }
+    }
+    if !report.risk_events.is_empty() {
+        info!("🛡️ {} portfolio risk events", report.risk_events.len());
+    }
+    if report.skipped_bars > 0 {
+        info!(
+            "📅 Skipped {} bars on exchange holidays",
+            report.skipped_bars
+        );
+    }
+    info!("📝 Report written to {:?}", args.output);
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use crate::cli::QualityArgs;
+use anyhow::Result;
+use quant_storage::quality::{QualityAction, QualityRules, QualityScanner, Severity};
+use quant_storage::Storage;
+
+/// 扫描 market_bar 中的 K 线序列并打印异常
+pub async fn run(storage: &Storage, args: QualityArgs) -> Result<()> {
+    let market = storage.market();
+    let exchange = args.exchange.map(|e| e.to_string());
+    let series = market
+        .find_series(exchange.as_deref(), args.symbol.as_deref())
+        .await?;
+    if series.is_empty() {
+        println!("No bar series matched");
+        return Ok(());
+    }
+
+    let action = if args.repair {
+        QualityAction::Repair
+    } else if args.quarantine {
+        QualityAction::Quarantine
+    } else {
+        QualityAction::Report
+    };
+    let scanner = QualityScanner::new(market, storage.quality())
+        .with_rules(QualityRules {
+            max_jump: args.max_jump,
+            check_gaps: !args.no_gaps,
+            calendar: None,
+        })
+        .with_action(action);
+    let reports = scanner.scan_all(&series).await?;
+
+    println!(
+        "{:<40}  {:>7}  {:<10}  {:<10}  {:>6}  {:>8}",
+        "SERIES", "BARS", "FIRST", "LAST", "ERRORS", "WARNINGS"
+    );
+    for report in &reports {
+        let date = |d: Option<chrono::NaiveDate>| d.map(|d| d.to_string()).unwrap_or_default();
+        println!(
+            "{:<40}  {:>7}  {:<10}  {:<10}  {:>6}  {:>8}",
+            report.key.to_string(),
+            report.bars,
+            date(report.first_date),
+            date(report.last_date),
+            report.errors(),
+            report.warnings()
+        );
+        // 先打印 Error，再打印 Warning
+        let mut anomalies: Vec<_> = report.anomalies.iter().collect();
+        anomalies.sort_by_key(|a| (a.kind.severity() != Severity::Error, a.date));
+        for anomaly in anomalies.iter().take(args.show) {
+            println!(
+                "    {} {:?} {:?}",
+                anomaly.date,
+                anomaly.kind.severity(),
+                anomaly.kind
+            );
+        }
+        if anomalies.len() > args.show {
+            println!("    ... {} more", anomalies.len() - args.show);
+        }
+    }
+
+    let dirty = reports.iter().filter(|r| !r.is_clean()).count();
+    println!("{} series scanned, {} with errors", reports.len(), dirty);
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use crate::cli::RobustnessArgs;
+use anyhow::{Context, Result};
+use quant_strategy::backtest::BacktestReport;
+use quant_strategy::robustness::{self, RobustnessConfig, SimulationSummary};
+use std::fs;
+use tracing::info;
+
+/// 读取回测报告，运行 Monte Carlo / Bootstrap 模拟并输出 JSON
+pub fn run(args: RobustnessArgs) -> Result<()> {
+    let raw = fs::read_to_string(&args.report)
+        .with_context(|| format!("Failed to read {:?}", args.report))?;
+    let report: BacktestReport =
+        serde_json::from_str(&raw).context("--report is not a backtest report")?;
+
+    let config = RobustnessConfig {
+        simulations: args.simulations,
+        confidence: args.confidence,
+        seed: args.seed,
+        fee_multiplier: (args.fee_multiplier[0], args.fee_multiplier[1]),
+        extra_slippage_bps: (args.extra_slippage_bps[0], args.extra_slippage_bps[1]),
+    };
+    let result = robustness::analyze(&report, &config)?;
+
+    let output = args
+        .output
+        .unwrap_or_else(|| args.report.with_file_name("robustness.json"));
+    fs::write(&output, result.to_json()?)
+        .with_context(|| format!("Failed to write {:?}", output))?;
+
+    let base = &result.baseline;
+    info!(
+        "📊 {} baseline | return {:.2}% | max drawdown {:.2}% | recover {}",
+        result.strategy,
+        base.total_return * 100.0,
+        base.max_drawdown * 100.0,
+        base.time_to_recover
+            .map(|t| t.to_string())
+            .unwrap_or_else(|| "-".to_string())
+    );
+    for (name, summary) in [
+        ("trade bootstrap", &result.trade_bootstrap),
+        ("return shuffle", &result.return_shuffle),
+        ("cost perturbation", &result.cost_perturbation),
+    ] {
+        match summary {
+            Some(summary) => log_summary(name, summary, config.confidence),
+            None => info!("🎲 {}: skipped (not enough trades / equity points)", name),
+        }
+    }
+    info!("📝 Robustness report written to {:?}", output);
+    Ok(())
+}
+
+fn log_summary(name: &str, s: &SimulationSummary, confidence: f64) {
+    info!(
+        "🎲 {} x{} | return {:.2}% [{:.2}%, {:.2}%] | max drawdown {:.2}% [{:.2}%, {:.2}%] @ {:.0}% | P(loss) {:.1}% | unrecovered {:.1}%",
+        name,
+        s.simulations,
+        s.total_return.median * 100.0,
+        s.total_return.lower * 100.0,
+        s.total_return.upper * 100.0,
+        s.max_drawdown.median * 100.0,
+        s.max_drawdown.lower * 100.0,
+        s.max_drawdown.upper * 100.0,
+        confidence * 100.0,
+        s.loss_probability * 100.0,
+        s.unrecovered_ratio * 100.0
+    );
+}
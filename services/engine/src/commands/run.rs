@@ -0,0 +1,341 @@
+use crate::config::{EngineConfig, TradingMode};
+use crate::ha::{self, LeaderElector, SyncScope};
+use crate::health;
+use crate::subsystem::Supervisor;
+use crate::subsystems::risk::OrderLimits;
+use crate::subsystems::{
+    AgentScheduler, FeedSubsystem, OmsSubsystem, RiskSubsystem, SignalSubsystem, StrategySubsystem,
+};
+use anyhow::{bail, Context, Result};
+use quant_core::bus::{EventBus, JsonlRecorder};
+use quant_core::enums::Exchange;
+use quant_execution::signal::SignalProcessor;
+use quant_execution::sizing::PortfolioSizer;
+use quant_storage::lock::DistributedLock;
+use quant_storage::migrate;
+use quant_storage::redis::RedisService;
+use quant_storage::store::{AccountStore, MemoryStrategyStore, StrategyStore};
+use quant_storage::stream::{self, EventStream};
+use quant_storage::Storage;
+use quant_strategy::sizing::{Allocation, PositionSizer};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::signal;
+use tokio::sync::Mutex;
+use tracing::{error, info, warn};
+
+/// 启动完整引擎 (paper / live)，直到收到 Ctrl+C
+pub async fn run(mut config: EngineConfig, mode: TradingMode) -> Result<()> {
+    config.engine.mode = mode;
+    config.validate()?;
+    info!("🏁 Running in {:?} mode", mode);
+    info!(
+        "⚙️ Loaded config: {} accounts, {} symbols, {} strategies, {} agent schedules",
+        config.accounts.len(),
+        config.symbols.len(),
+        config.strategies.len(),
+        config.agents.len()
+    );
+
+    // 1. 存储 (连接失败会直接退出)
+    let storage = if config.engine.storage_enabled {
+        let storage = Storage::connect(&config.storage.clone().with_env_fallback()).await?;
+        if config.engine.auto_migrate {
+            migrate::run_migrations(storage.pool()).await?;
+            info!("🗄️ Database migrations applied.");
+        }
+        info!("📦 Storage module initialized.");
+        Some(storage)
+    } else {
+        None
+    };
+
+    // 2. 事件总线 (可选：录制全部事件用于回放)
+    let bus = EventBus::new();
+    if let Some(path) = &config.engine.event_record_path {
+        let recorder = JsonlRecorder::open(path).context("Failed to open event recorder")?;
+        bus.add_sink(Arc::new(recorder));
+        info!("🎞️ Recording events to {}", path);
+    }
+
+    // 3. 跨进程桥接 (可选)：出站 Sink 需要在任何事件发布之前挂上
+    let mut bridge_tasks = Vec::new();
+    let bridge_stream = if config.bridge.enabled {
+        let redis_url = config
+            .storage
+            .clone()
+            .with_env_fallback()
+            .redis_url
+            .context("[bridge] requires Redis: set [storage].redis_url or REDIS_URL")?;
+        let stream = EventStream::new(RedisService::new(&redis_url)?, config.bridge.stream.clone());
+        if !config.bridge.publish.is_empty() {
+            bridge_tasks.push(stream::bridge_outbound(
+                &bus,
+                stream.clone(),
+                config.bridge.node.clone(),
+                config.bridge.publish.clone(),
+            ));
+        }
+        Some(stream)
+    } else {
+        None
+    };
+
+    // 4. 高可用 (可选)：只有主节点运行策略 / OMS
+    let elector = if config.ha.enabled {
+        let redis_url = config
+            .storage
+            .clone()
+            .with_env_fallback()
+            .redis_url
+            .context("[ha] requires Redis: set [storage].redis_url or REDIS_URL")?;
+        let key = config.ha.lock_key(&config.accounts);
+        Some(LeaderElector::new(
+            RedisService::new(&redis_url)?,
+            &config.ha,
+            key,
+        ))
+    } else {
+        None
+    };
+    let leadership = elector.as_ref().map(LeaderElector::leadership);
+    // 主节点的订单 / 策略 / 缓存写入带上本任期的 fencing token
+    let storage = match &leadership {
+        Some(leadership) => storage.map(|s| s.with_fencing(leadership.fencing())),
+        None => storage,
+    };
+    let sync_scope = SyncScope {
+        accounts: config.accounts.iter().map(|a| a.name.clone()).collect(),
+        strategies: std::iter::once(None)
+            .chain(
+                config
+                    .strategies
+                    .iter()
+                    .map(|s| s.uuid.clone())
+                    .filter(Option::is_some),
+            )
+            .collect(),
+    };
+
+    // 5. 按依赖顺序注册子系统：消费者先订阅，生产者最后启动
+    let order_store = storage.as_ref().map(Storage::order_store);
+    let strategy_store = storage
+        .as_ref()
+        .map(|s| Arc::new(s.strategies().clone()) as Arc<dyn StrategyStore>);
+    let mut supervisor = Supervisor::new(
+        bus.clone(),
+        Duration::from_secs(config.engine.shutdown_timeout_secs),
+    );
+    supervisor.register(RiskSubsystem::new(config.risk.clone()));
+    let mut oms = OmsSubsystem::new(order_store).with_mode(mode);
+    if let Some(storage) = &storage {
+        oms = oms.with_fills(storage.fill_store());
+    }
+    if let Some(cache) = storage.as_ref().and_then(Storage::cache) {
+        oms = oms.with_cache(cache.clone());
+        info!("🔥 Redis hot cache enabled.");
+    }
+    if let Some(leadership) = &leadership {
+        oms = oms.with_leadership(leadership.clone());
+    }
+    supervisor.register(oms);
+    let mut signals = signal_subsystem(
+        &config,
+        strategy_store.clone(),
+        storage.as_ref().map(Storage::account_store),
+    )?;
+    let mut strategies = StrategySubsystem::new(config.strategies.clone(), strategy_store);
+    if let Some(leadership) = &leadership {
+        signals = signals.with_leadership(leadership.clone());
+        strategies = strategies.with_leadership(leadership.clone());
+    }
+    supervisor.register(signals);
+    supervisor.register(strategies);
+    supervisor.register(AgentScheduler::new(config.agents.clone()));
+    supervisor.register(FeedSubsystem::new(
+        config.symbols.clone(),
+        config.engine.heartbeat_secs,
+    ));
+    let supervisor = Arc::new(Mutex::new(supervisor));
+    let health_server =
+        health::serve(&config.engine.health_addr, supervisor.clone(), leadership).await?;
+
+    // 6. 备节点在这里待命 (期间保持缓存热度)，成为主节点后先对账再交易
+    let mut lease = None;
+    let mut stop = Stop::Signal;
+    let standby = match &elector {
+        Some(elector) => {
+            let warmer = storage.clone().map(|storage| {
+                ha::keep_warm(
+                    storage,
+                    sync_scope.clone(),
+                    Duration::from_secs(config.ha.warm_interval_secs),
+                )
+            });
+            let elected = tokio::select! {
+                lock = elector.campaign() => Some(lock),
+                signal = signal::ctrl_c() => {
+                    signal.context("Failed to listen for Ctrl+C")?;
+                    None
+                }
+            };
+            if let Some(warmer) = warmer {
+                warmer.abort();
+            }
+            if let Some(lock) = &elected {
+                if let Err(e) = ha::reconcile(lock, storage.as_ref(), &sync_scope).await {
+                    elector.step_down();
+                    return Err(e);
+                }
+            }
+            lease = elected;
+            lease.is_none()
+        }
+        None => false,
+    };
+
+    if !standby {
+        supervisor.lock().await.start_all().await?;
+
+        // 入站桥接在订阅者就绪后再开始消费
+        if let Some(stream) = bridge_stream.filter(|_| !config.bridge.subscribe.is_empty()) {
+            bridge_tasks.push(
+                stream::bridge_inbound(
+                    bus.clone(),
+                    stream,
+                    config.bridge.node.clone(),
+                    config.bridge.subscribe.clone(),
+                )
+                .await?,
+            );
+        }
+        if config.bridge.enabled {
+            info!(
+                "🌉 Bridging events via {} as node {}",
+                config.bridge.stream.stream, config.bridge.node
+            );
+        }
+        info!("🚀 Photon Engine is running. Press Ctrl+C to stop.");
+
+        // 7. 等待停机信号 (或主节点租约丢失)
+        stop = tokio::select! {
+            signal = signal::ctrl_c() => {
+                signal.context("Failed to listen for Ctrl+C")?;
+                Stop::Signal
+            }
+            _ = lease_lost(lease.as_ref()) => Stop::LeaseLost,
+        };
+    }
+    match stop {
+        Stop::Signal => warn!("🛑 Shutdown signal received, stopping subsystems..."),
+        Stop::LeaseLost => {
+            // 先降级再停机：OMS 看到自己不是主节点就不会撤掉新主节点接管的挂单
+            if let Some(elector) = &elector {
+                elector.step_down();
+            }
+            error!("🔀 Leader lease lost, stopping trading subsystems...");
+        }
+    }
+
+    // 8. 优雅停机：逆序停止子系统 (OMS 撤单、策略落盘)，最后冲刷总线
+    health_server.abort();
+    supervisor.lock().await.shutdown_all().await;
+    for task in bridge_tasks {
+        task.abort();
+    }
+    if let Err(e) = bus.close() {
+        error!("Failed to flush event bus: {:?}", e);
+    }
+    // 主动释放租约，备节点无需等待过期即可接管
+    if let Some(lock) = lease {
+        if let Err(e) = lock.release().await {
+            warn!("Failed to release leader lock: {:?}", e);
+        }
+    }
+    if let Some(storage) = &storage {
+        storage.close().await;
+    }
+    if stop == Stop::LeaseLost {
+        bail!("Leader lease lost; restart the engine to rejoin as standby");
+    }
+    info!("👋 Photon Engine Shutdown Complete.");
+    Ok(())
+}
+
+/// 主循环退出原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stop {
+    Signal,
+    LeaseLost,
+}
+
+/// 未开启高可用时永不返回
+async fn lease_lost(lease: Option<&DistributedLock>) {
+    match lease {
+        Some(lock) => lock.lost().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// 信号管道：按策略所属账户路由交易所，订单限额作为事前检查；未启用存储时只在内存跟踪
+fn signal_subsystem(
+    config: &EngineConfig,
+    store: Option<Arc<dyn StrategyStore>>,
+    accounts: Option<Arc<dyn AccountStore>>,
+) -> Result<SignalSubsystem> {
+    let store = store.unwrap_or_else(|| Arc::new(MemoryStrategyStore::new()));
+    let default_exchange = config
+        .accounts
+        .first()
+        .map(|a| a.exchange)
+        .unwrap_or(Exchange::Binance);
+    let mut processor =
+        SignalProcessor::new(store, default_exchange).with_check(OrderLimits(config.risk.clone()));
+    for strategy in &config.strategies {
+        let account = config.accounts.iter().find(|a| a.name == strategy.account);
+        if let (Some(uuid), Some(account)) = (&strategy.uuid, account) {
+            processor = processor.with_route(uuid.clone(), account.exchange);
+        }
+    }
+    if let Some(ttl) = config.signal.ttl_secs {
+        processor = processor.with_default_ttl(chrono::Duration::seconds(ttl as i64));
+    }
+    let Some(sizing) = &config.signal.sizing else {
+        return Ok(SignalSubsystem::new(
+            processor,
+            Duration::from_secs(config.signal.expire_interval_secs),
+        ));
+    };
+
+    // 未指定数量的信号按账户净值定量，策略可在 params.sizing 中覆盖
+    let mut sizer = PortfolioSizer::new(sizing.quote.clone(), sizing.sizer.clone());
+    for strategy in &config.strategies {
+        if let (Some(uuid), Some(custom)) = (
+            &strategy.uuid,
+            PositionSizer::from_config(&strategy.params, "sizing")?,
+        ) {
+            sizer = sizer.with_strategy(uuid.clone(), custom);
+        }
+    }
+    if let Some(path) = &sizing.allocations {
+        let allocations: Vec<Allocation> = serde_json::from_str(
+            &std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read allocations {:?}", path))?,
+        )
+        .with_context(|| format!("Invalid allocations file {:?}", path))?;
+        info!(
+            "💰 Loaded capital allocations for {} strategies",
+            allocations.len()
+        );
+        sizer.set_allocations(&allocations);
+    }
+    if accounts.is_none() {
+        warn!("⚖️ No storage configured, signal sizing runs without account balances");
+    }
+    let account_names = config.accounts.iter().map(|a| a.name.clone()).collect();
+    Ok(SignalSubsystem::new(
+        processor.with_sizer(sizer.clone()),
+        Duration::from_secs(config.signal.expire_interval_secs),
+    )
+    .with_sizing(sizer, accounts, account_names))
+}
@@ -0,0 +1,150 @@
+use crate::cli::StrategyCommand;
+use anyhow::{anyhow, Result};
+use quant_core::enums::{StatusActor, StrategyStatus};
+use quant_execution::signal::SignalStats;
+use quant_storage::repository::strategy_repo::StrategyRepository;
+use quant_storage::Storage;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+/// 策略实例管理 (直接读写 strategy 表，运行中的引擎在下次加载时生效)
+pub async fn run(storage: &Storage, cmd: StrategyCommand) -> Result<()> {
+    let repo = storage.strategies();
+    let (uuid, status, reason) = match cmd {
+        StrategyCommand::List => {
+            let strategies = repo.find_all().await?;
+            println!(
+                "{:<36}  {:<20}  {:<16}  {:<12}  MODIFIED",
+                "UUID", "NAME", "CLASS", "STATUS"
+            );
+            for s in strategies {
+                println!(
+                    "{:<36}  {:<20}  {:<16}  {:<12}  {}",
+                    s.uuid,
+                    s.name,
+                    s.class_name,
+                    s.status.to_string(),
+                    s.gmt_modified.format("%Y-%m-%d %H:%M:%S")
+                );
+            }
+            return Ok(());
+        }
+        StrategyCommand::History { uuid, limit } => {
+            let versions = repo.list_state_versions(uuid, limit).await?;
+            let current = repo.load_state(uuid).await?.map(|s| s.version);
+            println!("{:<8}  {:<7}  {:<19}  STATE", "VERSION", "SCHEMA", "SAVED");
+            for state in versions {
+                let marker = if Some(state.version) == current {
+                    "*"
+                } else {
+                    ""
+                };
+                println!(
+                    "{:<8}  {:<7}  {:<19}  {}",
+                    format!("{}{}", state.version, marker),
+                    state.schema_version,
+                    state.gmt_create.format("%Y-%m-%d %H:%M:%S"),
+                    state.state_data
+                );
+            }
+            return Ok(());
+        }
+        StrategyCommand::Rollback { uuid, version } => {
+            let new_version = repo.rollback_state(uuid, version).await?;
+            println!(
+                "Strategy {} state rolled back to v{} (saved as v{})",
+                uuid, version, new_version
+            );
+            return Ok(());
+        }
+        StrategyCommand::Signals { uuid, limit } => {
+            let signals = repo.find_signals_by_strategy(uuid, limit).await?;
+            let Some(stats) = SignalStats::by_strategy(&signals).pop() else {
+                println!("Strategy {} has no signals", uuid);
+                return Ok(());
+            };
+            let ms = |v: Option<i64>| v.map_or("-".to_string(), |v| format!("{}ms", v));
+            println!(
+                "signals: {}  pending: {}  rejected: {}  expired: {}  executed: {}",
+                stats.total, stats.pending, stats.rejected, stats.expired, stats.executed
+            );
+            println!(
+                "hit rate: {} ({} / {} submitted)",
+                stats.hit_rate.map_or("-".to_string(), |r| format!(
+                    "{:.1}%",
+                    r * Decimal::from(100)
+                )),
+                stats.filled,
+                stats.submitted
+            );
+            println!(
+                "signal -> first fill: avg {}  median {}  max {}",
+                ms(stats.avg_fill_latency_ms),
+                ms(stats.median_fill_latency_ms),
+                ms(stats.max_fill_latency_ms)
+            );
+            println!(
+                "signal -> executed:   avg {}",
+                ms(stats.avg_execution_latency_ms)
+            );
+            return Ok(());
+        }
+        StrategyCommand::Audit { uuid, limit } => {
+            let changes = repo.find_status_history(uuid, limit).await?;
+            println!(
+                "{:<19}  {:<12}  {:<12}  {:<6}  REASON",
+                "TIME", "FROM", "TO", "ACTOR"
+            );
+            for c in changes {
+                println!(
+                    "{:<19}  {:<12}  {:<12}  {:<6}  {}",
+                    c.gmt_create.format("%Y-%m-%d %H:%M:%S"),
+                    c.from_status.to_string(),
+                    c.to_status.to_string(),
+                    c.actor.to_string(),
+                    c.reason
+                );
+            }
+            return Ok(());
+        }
+        StrategyCommand::Start { uuid, reason } => (uuid, StrategyStatus::Running, reason),
+        StrategyCommand::Pause { uuid, reason } => (uuid, StrategyStatus::Paused, reason),
+        StrategyCommand::Stop { uuid, reason } => (uuid, StrategyStatus::Stopped, reason),
+    };
+
+    set_status(repo, uuid, status, &reason).await
+}
+
+async fn set_status(
+    repo: &StrategyRepository,
+    uuid: Uuid,
+    status: StrategyStatus,
+    reason: &str,
+) -> Result<()> {
+    let strategy = repo
+        .find_by_uuid(uuid)
+        .await?
+        .ok_or_else(|| anyhow!("Strategy {} not found", uuid))?;
+    // 未运行过 / 已停止的策略不能直接进入 RUNNING，先经过 INITIALIZING
+    if status == StrategyStatus::Running
+        && !strategy.status.can_transition_to(status)
+        && strategy
+            .status
+            .can_transition_to(StrategyStatus::Initializing)
+    {
+        repo.update_status(
+            uuid,
+            StrategyStatus::Initializing,
+            StatusActor::User,
+            reason,
+        )
+        .await?;
+    }
+    repo.update_status(uuid, status, StatusActor::User, reason)
+        .await?;
+    println!(
+        "Strategy {} ({}): {} -> {}",
+        strategy.name, uuid, strategy.status, status
+    );
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use crate::cli::{TcaArgs, TcaFormat};
+use anyhow::{bail, Context, Result};
+use chrono::{Duration, NaiveTime};
+use quant_execution::tca::{TcaAnalyzer, TcaConfig};
+use quant_storage::Storage;
+use std::fs::{self, File};
+use tracing::info;
+
+/// 对区间内创建的订单做交易成本分析，按 --format 输出报告
+pub async fn run(storage: &Storage, args: TcaArgs) -> Result<()> {
+    if args.end < args.start {
+        bail!("--end {} is before --start {}", args.end, args.start);
+    }
+    let start = args.start.and_time(NaiveTime::MIN).and_utc();
+    let end = args.end.and_time(NaiveTime::MIN).and_utc() + Duration::days(1);
+
+    let analyzer = TcaAnalyzer::new(TcaConfig {
+        trade_type: args.trade_type,
+    });
+    let report = analyzer
+        .run(
+            storage.orders(),
+            storage.fills(),
+            storage.market(),
+            start,
+            end,
+        )
+        .await?;
+
+    fs::create_dir_all(&args.output)
+        .with_context(|| format!("Failed to create {:?}", args.output))?;
+    match args.format {
+        TcaFormat::Json => fs::write(args.output.join("report.json"), report.to_json()?)?,
+        TcaFormat::Csv => {
+            report.write_orders_csv(File::create(args.output.join("orders.csv"))?)?;
+            report.write_summary_csv(File::create(args.output.join("summary.csv"))?)?;
+        }
+    }
+
+    for s in &report.by_strategy {
+        info!(
+            "📊 {} | orders {} (filled {}) | slippage {} bps | shortfall {} bps | fees {}",
+            s.key,
+            s.order_count,
+            s.filled_order_count,
+            s.arrival_slippage_bps
+                .map(|v| v.round_dp(2).to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            s.implementation_shortfall_bps
+                .map(|v| v.round_dp(2).to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            s.fees
+        );
+    }
+    info!(
+        "📝 TCA of {} orders written to {:?}",
+        report.orders.len(),
+        args.output
+    );
+    Ok(())
+}
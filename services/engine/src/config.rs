@@ -0,0 +1,416 @@
+use ::config::{Config, Environment, File};
+use anyhow::{bail, Context, Result};
+use quant_core::enums::{BarPeriod, Exchange};
+use quant_core::event::Topic;
+use quant_storage::repository::common::StorageConfig;
+use quant_storage::stream::StreamConfig;
+use quant_strategy::registry::StrategyRegistry;
+use quant_strategy::sizing::PositionSizer;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::env;
+use std::path::PathBuf;
+
+// =========================================================================
+// 引擎配置 (分层加载)
+// =========================================================================
+//
+// 加载顺序 (后者覆盖前者):
+//   1. {PHOTON_CONFIG_DIR}/default.toml    基础配置 (必需)
+//   2. {PHOTON_CONFIG_DIR}/{PHOTON_ENV}.toml 环境配置 (可选, e.g. prod.toml)
+//   3. 环境变量 PHOTON__*                   (e.g. PHOTON__ENGINE__HEALTH_ADDR)
+//
+// PHOTON_CONFIG_DIR 默认为 "config"，PHOTON_ENV 默认为 "dev"。
+
+/// 引擎完整配置
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct EngineConfig {
+    #[serde(default)]
+    pub engine: EngineSection,
+    #[serde(default)]
+    pub storage: StorageConfig,
+    #[serde(default)]
+    pub accounts: Vec<AccountConfig>,
+    #[serde(default)]
+    pub exchanges: Vec<ExchangeConfig>,
+    #[serde(default)]
+    pub symbols: Vec<SymbolConfig>,
+    #[serde(default)]
+    pub strategies: Vec<StrategyConfig>,
+    #[serde(default)]
+    pub risk: RiskConfig,
+    #[serde(default)]
+    pub signal: SignalConfig,
+    #[serde(default)]
+    pub agents: Vec<AgentScheduleConfig>,
+    #[serde(default)]
+    pub bridge: BridgeConfig,
+    #[serde(default)]
+    pub ha: HaConfig,
+}
+
+/// 运行模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TradingMode {
+    /// 模拟盘：订单只在本地撮合，不会发往交易所
+    #[default]
+    Paper,
+    /// 实盘
+    Live,
+}
+
+/// 引擎自身参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EngineSection {
+    /// 运行模式 (由 CLI 子命令决定，配置文件中的值会被覆盖)
+    pub mode: TradingMode,
+    /// 健康检查 HTTP 监听地址 (GET /health)
+    pub health_addr: String,
+    /// 是否连接 MySQL (关闭后 OMS 只做内存撤单，策略状态不落库)
+    pub storage_enabled: bool,
+    /// 启动时自动执行数据库迁移 (生产环境建议关闭，改用 `migrate` 子命令)
+    pub auto_migrate: bool,
+    /// 行情心跳间隔 (秒)
+    pub heartbeat_secs: u64,
+    /// 每个子系统停机的最长等待时间 (秒)
+    pub shutdown_timeout_secs: u64,
+    /// 事件录制文件 (JSONL)，为空则不录制
+    pub event_record_path: Option<String>,
+}
+
+impl Default for EngineSection {
+    fn default() -> Self {
+        Self {
+            mode: TradingMode::Paper,
+            health_addr: "127.0.0.1:8089".to_string(),
+            storage_enabled: true,
+            auto_migrate: false,
+            heartbeat_secs: 5,
+            shutdown_timeout_secs: 10,
+            event_record_path: None,
+        }
+    }
+}
+
+/// 跨进程事件桥接 (Redis Streams)
+///
+/// 快路径 (交易) 和慢路径 (Agent) 拆成独立进程时，双方连接同一个流，
+/// 各自用不同的 `node` 和消费组，按主题选择要发出 / 接收的事件。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BridgeConfig {
+    pub enabled: bool,
+    /// 本进程的节点名 (自己发出的事件不会再被自己消费)
+    pub node: String,
+    /// 转发到 Redis 的本地事件主题
+    pub publish: Vec<Topic>,
+    /// 从 Redis 接收并发布到本地总线的事件主题
+    pub subscribe: Vec<Topic>,
+    pub stream: StreamConfig,
+}
+
+impl Default for BridgeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            node: "engine".to_string(),
+            publish: Vec::new(),
+            subscribe: Vec::new(),
+            stream: StreamConfig::default(),
+        }
+    }
+}
+
+/// 高可用 (主备) 部署
+///
+/// 同一组账户启动两个引擎实例，通过 Redis 租约选主：只有主节点运行策略和 OMS，
+/// 备节点保持缓存热度，主节点租约过期后接管 (先对账再交易)。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HaConfig {
+    pub enabled: bool,
+    /// 本实例的节点名 (出现在健康检查和日志中)
+    pub node: String,
+    /// 选主使用的锁 Key，为空时按账户名生成 (photon:leader:{accounts})
+    pub lock_key: Option<String>,
+    /// 租约时长 (毫秒)：主节点失联后最多这么久备节点开始接管
+    pub lease_ms: u64,
+    /// 备节点抢锁间隔 (毫秒)
+    pub retry_ms: u64,
+    /// 备节点刷新缓存的间隔 (秒)
+    pub warm_interval_secs: u64,
+}
+
+impl Default for HaConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            node: format!("engine-{}", std::process::id()),
+            lock_key: None,
+            lease_ms: 5_000,
+            retry_ms: 500,
+            warm_interval_secs: 10,
+        }
+    }
+}
+
+impl HaConfig {
+    /// 同一组账户的主备实例必须得到相同的 Key
+    pub fn lock_key(&self, accounts: &[AccountConfig]) -> String {
+        if let Some(key) = &self.lock_key {
+            return key.clone();
+        }
+        let mut names: Vec<&str> = accounts.iter().map(|a| a.name.as_str()).collect();
+        names.sort_unstable();
+        format!("photon:leader:{}", names.join("+"))
+    }
+}
+
+/// 交易账户
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountConfig {
+    pub name: String,
+    pub exchange: Exchange,
+    /// 存放 API Key 的环境变量名 (密钥本身不写入配置文件)
+    #[serde(default)]
+    pub api_key_env: Option<String>,
+    #[serde(default)]
+    pub api_secret_env: Option<String>,
+}
+
+/// 交易所连接
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExchangeConfig {
+    pub exchange: Exchange,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub rest_url: Option<String>,
+    #[serde(default)]
+    pub ws_url: Option<String>,
+}
+
+/// 订阅的交易标的
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolConfig {
+    pub exchange: Exchange,
+    /// 标的 (e.g. "BTC/USDT")
+    pub symbol: String,
+    #[serde(default)]
+    pub period: BarPeriod,
+}
+
+/// 策略实例
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyConfig {
+    pub name: String,
+    /// 策略逻辑类名
+    pub class_name: String,
+    /// 下单账户 (对应 accounts[].name)
+    pub account: String,
+    /// 交易标的 (需要出现在 symbols 中)
+    #[serde(default)]
+    pub symbols: Vec<String>,
+    /// 已落库策略的业务 UUID，配置后停机时会保存运行状态
+    #[serde(default)]
+    pub uuid: Option<String>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// 策略静态参数 (原样传给策略)
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// 全局风控限额 (None 表示不限制)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct RiskConfig {
+    /// 单笔订单最大名义价值
+    pub max_order_notional: Option<Decimal>,
+    /// 单笔订单最大数量
+    pub max_order_quantity: Option<Decimal>,
+    /// 同时挂单数量上限
+    pub max_open_orders: Option<usize>,
+}
+
+/// 信号 -> 订单管道
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SignalConfig {
+    /// 信号默认存活时间 (秒)，None 表示不过期 (信号自带 expires_at 时以信号为准)
+    pub ttl_secs: Option<u64>,
+    /// 过期扫描间隔 (秒)
+    pub expire_interval_secs: u64,
+    /// 信号未指定数量时的定量方式 (不配置则拒绝未定量的信号)
+    pub sizing: Option<SizingConfig>,
+}
+
+impl Default for SignalConfig {
+    fn default() -> Self {
+        Self {
+            ttl_secs: Some(300),
+            expire_interval_secs: 1,
+            sizing: None,
+        }
+    }
+}
+
+/// 信号定量：按账户净值 / 持仓 / 最近 K 线计算数量
+///
+/// 策略可在 `params.sizing` 中覆盖定量方式。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SizingConfig {
+    /// 净值计价币种
+    #[serde(default = "default_quote")]
+    pub quote: String,
+    /// `portfolio --allocation` 输出的 allocations.json：配置了分配的策略按分配资金代替账户净值定量
+    #[serde(default)]
+    pub allocations: Option<PathBuf>,
+    #[serde(flatten)]
+    pub sizer: PositionSizer,
+}
+
+fn default_quote() -> String {
+    "USDT".to_string()
+}
+
+/// Agent 定时调度
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentScheduleConfig {
+    pub name: String,
+    /// 调度间隔 (秒)
+    pub interval_secs: u64,
+    /// 交给 Agent 的任务描述
+    #[serde(default)]
+    pub query: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl EngineConfig {
+    /// 按默认规则分层加载配置
+    pub fn load() -> Result<Self> {
+        let dir = env::var("PHOTON_CONFIG_DIR").unwrap_or_else(|_| "config".to_string());
+        let profile = env::var("PHOTON_ENV").unwrap_or_else(|_| "dev".to_string());
+        Self::load_from(&dir, &profile)
+    }
+
+    pub fn load_from(dir: &str, profile: &str) -> Result<Self> {
+        let settings = Config::builder()
+            .add_source(File::with_name(&format!("{}/default", dir)))
+            .add_source(File::with_name(&format!("{}/{}", dir, profile)).required(false))
+            .add_source(Environment::with_prefix("PHOTON").separator("__"))
+            .build()
+            .with_context(|| format!("Failed to load config from '{}'", dir))?;
+
+        let config: EngineConfig = settings
+            .try_deserialize()
+            .context("Invalid engine config")?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// 校验配置之间的引用关系，启动前尽早失败
+    pub fn validate(&self) -> Result<()> {
+        let enabled_exchanges: HashSet<Exchange> = self
+            .exchanges
+            .iter()
+            .filter(|e| e.enabled)
+            .map(|e| e.exchange)
+            .collect();
+
+        for symbol in &self.symbols {
+            if !enabled_exchanges.contains(&symbol.exchange) {
+                bail!(
+                    "Symbol {} references exchange {} which is not enabled",
+                    symbol.symbol,
+                    symbol.exchange
+                );
+            }
+        }
+
+        let accounts: HashSet<&str> = self.accounts.iter().map(|a| a.name.as_str()).collect();
+        let symbols: HashSet<&str> = self.symbols.iter().map(|s| s.symbol.as_str()).collect();
+        let registry = StrategyRegistry::with_builtins();
+        let mut names = HashSet::new();
+        for strategy in self.strategies.iter().filter(|s| s.enabled) {
+            if !names.insert(strategy.name.as_str()) {
+                bail!("Duplicate strategy name: {}", strategy.name);
+            }
+            if !accounts.contains(strategy.account.as_str()) {
+                bail!(
+                    "Strategy {} references unknown account {}",
+                    strategy.name,
+                    strategy.account
+                );
+            }
+            if let Some(missing) = strategy
+                .symbols
+                .iter()
+                .find(|s| !symbols.contains(s.as_str()))
+            {
+                bail!(
+                    "Strategy {} trades {} which is not in [[symbols]]",
+                    strategy.name,
+                    missing
+                );
+            }
+            // 类名必须已注册，参数必须能构建出策略实例
+            registry
+                .create(&strategy.class_name, &strategy.params)
+                .with_context(|| format!("Strategy {} cannot be built", strategy.name))?;
+        }
+
+        if self.engine.mode == TradingMode::Live {
+            for account in &self.accounts {
+                for var in [&account.api_key_env, &account.api_secret_env]
+                    .into_iter()
+                    .flatten()
+                {
+                    if env::var(var).is_err() {
+                        bail!(
+                            "Live mode requires env {} for account {}",
+                            var,
+                            account.name
+                        );
+                    }
+                }
+            }
+        }
+
+        for agent in &self.agents {
+            if agent.enabled && agent.interval_secs == 0 {
+                bail!("Agent schedule {} must have interval_secs > 0", agent.name);
+            }
+        }
+
+        if self.bridge.enabled && self.bridge.publish.is_empty() && self.bridge.subscribe.is_empty()
+        {
+            bail!("[bridge] is enabled but has no publish / subscribe topics");
+        }
+
+        if self.ha.enabled && (self.ha.retry_ms == 0 || self.ha.retry_ms >= self.ha.lease_ms) {
+            bail!("[ha] retry_ms must be > 0 and shorter than lease_ms");
+        }
+        if self.signal.expire_interval_secs == 0 {
+            bail!("[signal] expire_interval_secs must be > 0");
+        }
+        for strategy in &self.strategies {
+            PositionSizer::from_config(&strategy.params, "sizing").with_context(|| {
+                format!("Strategy {} has an invalid params.sizing", strategy.name)
+            })?;
+        }
+        if self.ha.enabled && self.ha.warm_interval_secs == 0 {
+            bail!("[ha] warm_interval_secs must be > 0");
+        }
+        Ok(())
+    }
+}
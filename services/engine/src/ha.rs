@@ -0,0 +1,267 @@
+use crate::config::HaConfig;
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use quant_core::oms::Order;
+use quant_storage::lock::{DistributedLock, Fencing, LockOptions};
+use quant_storage::redis::RedisService;
+use quant_storage::Storage;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tracing::{debug, info, warn};
+
+// =========================================================================
+// 高可用：Redis 租约选主 + 热备接管
+// =========================================================================
+//
+// 同一组账户的两个引擎实例竞争同一把分布式锁 (`DistributedLock`)：
+//   - 主节点 (Leader)  持锁，运行策略 / OMS；看门狗判定租约丢失的同时失去主节点身份，
+//                      信号 / OMS / 策略的写入都先检查 `Leadership::is_leader`，不等停机流程
+//   - 备节点 (Standby) 每 retry_ms 抢一次锁，期间定期用数据库状态刷新热点缓存
+//
+// 主节点进程崩溃后最多一个租约 (lease_ms) 锁自动过期，备节点拿到锁后先对账
+// (数据库 -> 缓存)，确认自己仍是最新持有者 (fencing token 未变) 才开始交易。
+// 当选后本任期的 Fence 写入 `Leadership::fencing`，存储层据此拒绝旧主节点的迟到写入。
+
+/// 节点角色
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Role {
+    Standby,
+    Leader,
+}
+
+/// 选主状态 (健康检查端点原样输出)
+#[derive(Debug, Clone, Serialize)]
+pub struct LeaderStatus {
+    pub node: String,
+    pub role: Role,
+    pub lock_key: String,
+    /// 成为主节点时分配的 fencing token (备节点为 None)
+    pub fencing_token: Option<u64>,
+    /// 进入当前角色的时间
+    pub since: DateTime<Utc>,
+}
+
+/// 选主状态的只读句柄，可在子系统 / 健康检查之间共享
+#[derive(Clone)]
+pub struct Leadership {
+    status: Arc<RwLock<LeaderStatus>>,
+    /// 主节点租约的持有状态 (看门狗维护)
+    lease: Arc<RwLock<Option<watch::Receiver<bool>>>>,
+    /// 最近一次当选的 Fence (与 `Storage::with_fencing` 共享)
+    fencing: Fencing,
+}
+
+impl Leadership {
+    pub fn status(&self) -> LeaderStatus {
+        self.status.read().unwrap().clone()
+    }
+
+    /// 是主节点且租约仍有效；租约一旦丢失立即返回 false
+    pub fn is_leader(&self) -> bool {
+        self.status.read().unwrap().role == Role::Leader
+            && self
+                .lease
+                .read()
+                .unwrap()
+                .as_ref()
+                .is_some_and(|held| *held.borrow())
+    }
+
+    /// 受保护写入使用的 Fence；卸任后保留，迟到的写入仍按旧任期校验
+    pub fn fencing(&self) -> Fencing {
+        self.fencing.clone()
+    }
+
+    fn set(&self, role: Role, lock: Option<&DistributedLock>) {
+        *self.lease.write().unwrap() = lock.map(DistributedLock::watch_held);
+        if let Some(lock) = lock {
+            self.fencing.set(lock.fence());
+        }
+        let mut status = self.status.write().unwrap();
+        status.role = role;
+        status.fencing_token = lock.map(DistributedLock::fencing_token);
+        status.since = Utc::now();
+    }
+}
+
+/// 选主器
+pub struct LeaderElector {
+    redis: RedisService,
+    key: String,
+    options: LockOptions,
+    retry_interval: Duration,
+    leadership: Leadership,
+}
+
+impl LeaderElector {
+    pub fn new(redis: RedisService, config: &HaConfig, key: String) -> Self {
+        let leadership = Leadership {
+            status: Arc::new(RwLock::new(LeaderStatus {
+                node: config.node.clone(),
+                role: Role::Standby,
+                lock_key: key.clone(),
+                fencing_token: None,
+                since: Utc::now(),
+            })),
+            lease: Arc::new(RwLock::new(None)),
+            fencing: Fencing::new(),
+        };
+        Self {
+            redis,
+            key,
+            options: LockOptions::with_ttl(Duration::from_millis(config.lease_ms)),
+            retry_interval: Duration::from_millis(config.retry_ms),
+            leadership,
+        }
+    }
+
+    pub fn leadership(&self) -> Leadership {
+        self.leadership.clone()
+    }
+
+    /// 一直竞选直到成为主节点 (Redis 暂时不可用时继续重试)
+    pub async fn campaign(&self) -> DistributedLock {
+        info!(
+            "⏳ Standing by as {}, waiting for leader lock {}",
+            self.leadership.status().node,
+            self.key
+        );
+        loop {
+            match DistributedLock::try_acquire(&self.redis, &self.key, &self.options).await {
+                Ok(Some(lock)) => {
+                    self.leadership.set(Role::Leader, Some(&lock));
+                    info!(
+                        "👑 Elected leader for {} (fencing token {})",
+                        self.key,
+                        lock.fencing_token()
+                    );
+                    return lock;
+                }
+                Ok(None) => debug!("Leader lock {} is held by another node", self.key),
+                Err(e) => warn!("Leader election for {} failed: {:?}", self.key, e),
+            }
+            sleep(self.retry_interval).await;
+        }
+    }
+
+    /// 失去租约 / 主动让位后回到备节点状态
+    pub fn step_down(&self) {
+        self.leadership.set(Role::Standby, None);
+    }
+}
+
+// =========================================================================
+// 缓存保温与接管对账
+// =========================================================================
+
+/// 需要同步到缓存的范围
+#[derive(Debug, Clone, Default)]
+pub struct SyncScope {
+    pub accounts: Vec<String>,
+    /// 已知的策略分组 (None 为手工单)；数据库中没有挂单的分组也会被清空
+    pub strategies: Vec<Option<String>>,
+}
+
+/// 一次同步的结果
+#[derive(Debug, Clone, Default)]
+pub struct SyncReport {
+    /// 数据库中的未完结订单数
+    pub open_orders: usize,
+    /// 缓存中有、数据库中已不是挂单的订单数
+    pub stale: usize,
+    /// 数据库中有、缓存中缺失的挂单数
+    pub missing: usize,
+    pub accounts: usize,
+}
+
+/// 用数据库 (事实来源) 覆盖热点缓存中的挂单和账户快照
+pub async fn sync_cache(storage: &Storage, scope: &SyncScope) -> Result<SyncReport> {
+    let orders = storage.orders().find_open_orders().await?;
+    let mut report = SyncReport {
+        open_orders: orders.len(),
+        ..SyncReport::default()
+    };
+    let Some(cache) = storage.cache() else {
+        return Ok(report);
+    };
+
+    let mut groups: HashMap<Option<String>, Vec<Order>> = scope
+        .strategies
+        .iter()
+        .map(|s| (s.clone(), Vec::new()))
+        .collect();
+    for order in orders {
+        groups
+            .entry(order.strategy_uuid.clone())
+            .or_default()
+            .push(order);
+    }
+    for (strategy_uuid, orders) in &groups {
+        let cached: HashSet<String> = cache
+            .open_orders(strategy_uuid.as_deref())
+            .await?
+            .into_iter()
+            .map(|o| o.uuid)
+            .collect();
+        let expected: HashSet<String> = orders.iter().map(|o| o.uuid.clone()).collect();
+        report.stale += cached.difference(&expected).count();
+        report.missing += expected.difference(&cached).count();
+        cache
+            .set_open_orders(strategy_uuid.as_deref(), orders)
+            .await?;
+    }
+
+    let accounts = storage.accounts();
+    for account in &scope.accounts {
+        let assets = accounts.find_assets_by_account(account).await?;
+        cache.set_assets(account, &assets).await?;
+        let positions = accounts.find_positions_by_account(account).await?;
+        cache.set_positions(account, &positions).await?;
+        report.accounts += 1;
+    }
+    Ok(report)
+}
+
+/// 备节点后台保温：按固定间隔同步缓存，成为主节点后 abort
+pub fn keep_warm(storage: Storage, scope: SyncScope, interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match sync_cache(&storage, &scope).await {
+                Ok(report) => debug!(
+                    "🔥 Standby cache refreshed: {} open orders, {} accounts",
+                    report.open_orders, report.accounts
+                ),
+                Err(e) => warn!("Standby cache refresh failed: {:?}", e),
+            }
+            sleep(interval).await;
+        }
+    })
+}
+
+/// 接管前对账：同步缓存后再确认锁仍由自己持有，否则拒绝开始交易
+pub async fn reconcile(
+    lock: &DistributedLock,
+    storage: Option<&Storage>,
+    scope: &SyncScope,
+) -> Result<()> {
+    if let Some(storage) = storage {
+        let report = sync_cache(storage, scope).await?;
+        info!(
+            "🧾 Reconciled before trading: {} open orders ({} stale / {} missing in cache), {} accounts",
+            report.open_orders, report.stale, report.missing, report.accounts
+        );
+    }
+    if !lock.is_current().await? {
+        bail!(
+            "Leader lock {} was taken over during reconciliation",
+            lock.key()
+        );
+    }
+    Ok(())
+}
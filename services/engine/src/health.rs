@@ -0,0 +1,89 @@
+use crate::ha::{Leadership, Role};
+use crate::subsystem::{HealthReport, Supervisor};
+use anyhow::{Context, Result};
+use serde_json::json;
+use std::sync::{Arc, Mutex as StdMutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+// =========================================================================
+// 健康检查端点 (极简 HTTP，仅支持 GET /health)
+// =========================================================================
+
+/// 启动健康检查服务
+///
+/// 全部子系统健康时返回 200，否则返回 503，响应体为各子系统状态 JSON。
+/// 开启高可用时附带选主状态 (`ha`)；备节点的子系统尚未启动，只要在待命就返回 200。
+/// 启动 / 停机期间编排器被占用，不等待锁，直接返回最近一次的状态快照。
+pub async fn serve(
+    addr: &str,
+    supervisor: Arc<Mutex<Supervisor>>,
+    leadership: Option<Leadership>,
+) -> Result<JoinHandle<()>> {
+    let listener = TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("Failed to bind health endpoint on {}", addr))?;
+    info!("🩺 Health endpoint listening on http://{}/health", addr);
+    let snapshot = Arc::new(StdMutex::new(supervisor.lock().await.health()));
+
+    Ok(tokio::spawn(async move {
+        loop {
+            let (mut socket, peer) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    warn!("Health endpoint accept failed: {:?}", e);
+                    continue;
+                }
+            };
+
+            let supervisor = supervisor.clone();
+            let snapshot = snapshot.clone();
+            let leadership = leadership.clone();
+            tokio::spawn(async move {
+                let mut buf = [0u8; 1024];
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+
+                let (status, body) = if request.starts_with("GET /health") {
+                    let reports = latest_health(&supervisor, &snapshot);
+                    let ha = leadership.as_ref().map(Leadership::status);
+                    let standby = ha.as_ref().is_some_and(|s| s.role == Role::Standby);
+                    let ok = standby || reports.iter().all(|r| r.health.is_ok());
+                    let body = json!({ "ok": ok, "ha": ha, "subsystems": reports }).to_string();
+                    if ok {
+                        ("200 OK", body)
+                    } else {
+                        ("503 Service Unavailable", body)
+                    }
+                } else {
+                    ("404 Not Found", json!({ "error": "not found" }).to_string())
+                };
+
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                if let Err(e) = socket.write_all(response.as_bytes()).await {
+                    warn!("Health endpoint write to {} failed: {:?}", peer, e);
+                }
+            });
+        }
+    }))
+}
+
+/// 编排器空闲时刷新快照，正在启动 / 停机时沿用上一次的快照
+fn latest_health(
+    supervisor: &Mutex<Supervisor>,
+    snapshot: &StdMutex<Vec<HealthReport>>,
+) -> Vec<HealthReport> {
+    let mut snapshot = snapshot.lock().unwrap();
+    if let Ok(supervisor) = supervisor.try_lock() {
+        *snapshot = supervisor.health();
+    }
+    snapshot.clone()
+}
@@ -1,15 +1,19 @@
-use anyhow::Context;
+mod cli;
+mod commands;
+mod config;
+mod ha;
+mod health;
+mod subsystem;
+mod subsystems;
+
+use anyhow::bail;
+use clap::Parser;
 use dotenvy::dotenv;
-use std::env;
-use std::sync::Arc;
-use tokio::signal;
-use tokio::sync::broadcast;
-use tracing::{error, info, warn};
+use tracing::info;
 
-use quant_core::enums::Side;
 // 引入内部模块
-use quant_core::oms::Order;
-use quant_core::primitive::{Price, Quantity};
+use crate::cli::{AgentCommand, Cli, Command};
+use crate::config::{EngineConfig, TradingMode};
 
 // =========================================================================
 // 1. 日志配置 (输出到控制台 + 文件)
@@ -46,59 +50,49 @@ fn init_logging() -> tracing_appender::non_blocking::WorkerGuard {
 }
 
 // =========================================================================
-// 2. 模拟一个简单的事件循环 (这是未来的核心)
-// =========================================================================
-async fn run_event_loop() {
-    info!("🚀 Event Loop Started...");
-
-    let (tx, _rx) = broadcast::channel::<String>(1000);
-
-    // 1. 行情任务
-    let tx_clone = tx.clone();
-    tokio::spawn(async move {
-        loop {
-            tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-            info!("📡 [Mock] Feed received a heartbeat...");
-        }
-    });
-
-    // 2. 策略任务
-    tokio::spawn(async move {
-        tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-        info!("💡 Strategy triggered! Placing a test order...");
-        // ... 之前的逻辑
-    });
-
-    // ✅ 新增：让这个函数永远等待，不要退出！
-    // std::future::pending() 会创建一个永远不会完成的 Future
-    std::future::pending::<()>().await;
-}
-
-// =========================================================================
-// 3. 主入口 (Main Entry)
+// 2. 主入口 (Main Entry)
 // =========================================================================
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // A. 加载配置与日志
+    // A. 解析命令行、加载 .env 与日志
+    let cli = Cli::parse();
     dotenv().ok(); // 读取 .env 文件
     let _log_guard = init_logging(); // 初始化日志，_guard 不能丢
 
     info!("Starting Photon Quant Engine ⚡️");
 
-    info!("📦 Storage module initialized.");
-
-    // D. 启动主逻辑
-    // 使用 tokio::select! 监听系统信号，实现优雅停机
-    tokio::select! {
-        _ = run_event_loop() => {
-            error!("Event loop exited unexpectedly!");
+    // B. 分发子命令 (默认启动模拟盘)
+    match cli.command.unwrap_or(Command::Paper) {
+        Command::Paper => commands::run::run(EngineConfig::load()?, TradingMode::Paper).await,
+        Command::Live { yes } => {
+            if !yes {
+                bail!("Refusing to start live trading without --yes");
+            }
+            commands::run::run(EngineConfig::load()?, TradingMode::Live).await
+        }
+        Command::Backtest(args) => commands::backtest::run(args).await,
+        Command::Optimize(args) => commands::optimize::run(args).await,
+        Command::Portfolio(args) => commands::portfolio::run(args).await,
+        Command::Robustness(args) => commands::robustness::run(args),
+        Command::Import(args) => {
+            commands::import::run(&commands::connect_storage().await?, args).await
+        }
+        Command::Quality(args) => {
+            commands::quality::run(&commands::connect_storage().await?, args).await
+        }
+        Command::Migrate(args) => {
+            commands::migrate::run(&commands::connect_storage().await?, args).await
         }
-        _ = signal::ctrl_c() => {
-            warn!("🛑 Ctrl+C received! Shutting down gracefully...");
+        Command::Export(args) => {
+            commands::export::run(&commands::connect_storage().await?, args).await
         }
+        Command::Strategy(cmd) => {
+            commands::strategy::run(&commands::connect_storage().await?, cmd).await
+        }
+        Command::Orders(args) => {
+            commands::orders::run(&commands::connect_storage().await?, args).await
+        }
+        Command::Tca(args) => commands::tca::run(&commands::connect_storage().await?, args).await,
+        Command::Agent(AgentCommand::Run(args)) => commands::agent::run(args).await,
     }
-
-    // E. 清理工作 (如果有)
-    info!("👋 Photon Engine Shutdown Complete.");
-    Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,127 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use quant_core::bus::EventBus;
+use serde::Serialize;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+// =========================================================================
+// 1. 子系统抽象
+// =========================================================================
+
+/// 子系统健康状态
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(
+    tag = "status",
+    content = "detail",
+    rename_all = "SCREAMING_SNAKE_CASE"
+)]
+pub enum Health {
+    /// 尚未启动
+    Starting,
+    Healthy,
+    /// 可用但有异常 (e.g. 事件积压)
+    Degraded(String),
+    Unhealthy(String),
+}
+
+impl Health {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Health::Healthy | Health::Degraded(_))
+    }
+}
+
+/// 引擎子系统 (Feed / Strategy / Risk / OMS / Agent)
+///
+/// 生命周期：`start` -> 运行期间周期性 `health` -> `shutdown`。
+/// `start` 只负责订阅总线并拉起后台任务，必须尽快返回。
+#[async_trait]
+pub trait Subsystem: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn start(&mut self, bus: &EventBus) -> Result<()>;
+
+    fn health(&self) -> Health;
+
+    /// 停止后台任务并落盘状态
+    async fn shutdown(&mut self) -> Result<()>;
+}
+
+// =========================================================================
+// 2. 子系统编排 (按依赖顺序启动，逆序停机)
+// =========================================================================
+
+/// 单个子系统的健康报告
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    pub name: &'static str,
+    #[serde(flatten)]
+    pub health: Health,
+}
+
+pub struct Supervisor {
+    bus: EventBus,
+    subsystems: Vec<Box<dyn Subsystem>>,
+    /// 已成功启动的子系统数量 (前缀)
+    started: usize,
+    shutdown_timeout: Duration,
+}
+
+impl Supervisor {
+    pub fn new(bus: EventBus, shutdown_timeout: Duration) -> Self {
+        Self {
+            bus,
+            subsystems: Vec::new(),
+            started: 0,
+            shutdown_timeout,
+        }
+    }
+
+    /// 注册子系统：注册顺序即启动顺序，下游 (消费者) 应先于上游 (生产者) 注册
+    pub fn register(&mut self, subsystem: impl Subsystem + 'static) {
+        self.subsystems.push(Box::new(subsystem));
+    }
+
+    /// 依次启动；任何一个失败都会停掉已启动的子系统并返回错误
+    pub async fn start_all(&mut self) -> Result<()> {
+        while self.started < self.subsystems.len() {
+            let subsystem = &mut self.subsystems[self.started];
+            let name = subsystem.name();
+            if let Err(e) = subsystem.start(&self.bus).await {
+                error!("❌ Subsystem [{}] failed to start: {:?}", name, e);
+                self.shutdown_all().await;
+                return Err(e.context(format!("Subsystem {} failed to start", name)));
+            }
+            info!("✅ Subsystem [{}] started", name);
+            self.started += 1;
+        }
+        Ok(())
+    }
+
+    pub fn health(&self) -> Vec<HealthReport> {
+        self.subsystems
+            .iter()
+            .map(|s| HealthReport {
+                name: s.name(),
+                health: s.health(),
+            })
+            .collect()
+    }
+
+    /// 逆序停机：先停生产者 (Feed)，最后停 OMS，保证撤单时总线仍可用
+    pub async fn shutdown_all(&mut self) {
+        while self.started > 0 {
+            self.started -= 1;
+            let subsystem = &mut self.subsystems[self.started];
+            let name = subsystem.name();
+            match tokio::time::timeout(self.shutdown_timeout, subsystem.shutdown()).await {
+                Ok(Ok(())) => info!("🛑 Subsystem [{}] stopped", name),
+                Ok(Err(e)) => error!("Subsystem [{}] shutdown failed: {:?}", name, e),
+                Err(_) => warn!(
+                    "Subsystem [{}] shutdown timed out after {:?}",
+                    name, self.shutdown_timeout
+                ),
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use crate::config::AgentScheduleConfig;
+use crate::subsystem::{Health, Subsystem};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use quant_core::bus::EventBus;
+use quant_core::event::{Event, TimerEvent};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::info;
+
+/// Agent 调度器
+///
+/// 按配置的间隔发布 `agent:<name>` 定时事件，由订阅方触发对应的 Agent 运行 (慢路径)。
+pub struct AgentScheduler {
+    schedules: Vec<AgentScheduleConfig>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl AgentScheduler {
+    pub fn new(schedules: Vec<AgentScheduleConfig>) -> Self {
+        Self {
+            schedules: schedules.into_iter().filter(|s| s.enabled).collect(),
+            tasks: Vec::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Subsystem for AgentScheduler {
+    fn name(&self) -> &'static str {
+        "agent"
+    }
+
+    async fn start(&mut self, bus: &EventBus) -> Result<()> {
+        for schedule in &self.schedules {
+            info!(
+                "🤖 Agent [{}] scheduled every {}s",
+                schedule.name, schedule.interval_secs
+            );
+            let bus = bus.clone();
+            let name = format!("agent:{}", schedule.name);
+            let interval = Duration::from_secs(schedule.interval_secs);
+            self.tasks.push(tokio::spawn(async move {
+                let mut ticker = tokio::time::interval(interval);
+                ticker.tick().await;
+                loop {
+                    ticker.tick().await;
+                    bus.publish(
+                        "agent_scheduler",
+                        Event::Timer(TimerEvent {
+                            name: name.clone(),
+                            fired_at: Utc::now(),
+                        }),
+                    )
+                    .await;
+                }
+            }));
+        }
+        Ok(())
+    }
+
+    fn health(&self) -> Health {
+        if self.tasks.iter().any(|t| t.is_finished()) {
+            return Health::Unhealthy("agent schedule exited unexpectedly".to_string());
+        }
+        Health::Healthy
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        for task in self.tasks.drain(..) {
+            task.abort();
+        }
+        Ok(())
+    }
+}
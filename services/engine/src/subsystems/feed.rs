@@ -0,0 +1,76 @@
+use super::task_health;
+use crate::config::SymbolConfig;
+use crate::subsystem::{Health, Subsystem};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use quant_core::bus::EventBus;
+use quant_core::event::{Event, TimerEvent};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::info;
+
+/// 行情子系统
+///
+/// 交易所连接器接入前，只按固定间隔发布心跳定时器，驱动下游的定时逻辑。
+pub struct FeedSubsystem {
+    symbols: Vec<SymbolConfig>,
+    heartbeat: Duration,
+    task: Option<JoinHandle<()>>,
+}
+
+impl FeedSubsystem {
+    pub fn new(symbols: Vec<SymbolConfig>, heartbeat_secs: u64) -> Self {
+        Self {
+            symbols,
+            heartbeat: Duration::from_secs(heartbeat_secs.max(1)),
+            task: None,
+        }
+    }
+}
+
+#[async_trait]
+impl Subsystem for FeedSubsystem {
+    fn name(&self) -> &'static str {
+        "feed"
+    }
+
+    async fn start(&mut self, bus: &EventBus) -> Result<()> {
+        for symbol in &self.symbols {
+            info!(
+                "📡 Feed subscribed {} {} {}",
+                symbol.exchange, symbol.symbol, symbol.period
+            );
+        }
+
+        let bus = bus.clone();
+        let heartbeat = self.heartbeat;
+        self.task = Some(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(heartbeat);
+            ticker.tick().await; // 第一次 tick 立即返回，跳过
+            loop {
+                ticker.tick().await;
+                bus.publish(
+                    "feed",
+                    Event::Timer(TimerEvent {
+                        name: "heartbeat".to_string(),
+                        fired_at: Utc::now(),
+                    }),
+                )
+                .await;
+            }
+        }));
+        Ok(())
+    }
+
+    fn health(&self) -> Health {
+        task_health("feed", &self.task)
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+        Ok(())
+    }
+}
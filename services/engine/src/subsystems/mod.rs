@@ -0,0 +1,39 @@
+pub mod agent;
+pub mod feed;
+pub mod oms;
+pub mod risk;
+pub mod signal;
+pub mod strategy;
+
+pub use agent::AgentScheduler;
+pub use feed::FeedSubsystem;
+pub use oms::OmsSubsystem;
+pub use risk::RiskSubsystem;
+pub use signal::SignalSubsystem;
+pub use strategy::StrategySubsystem;
+
+use crate::subsystem::Health;
+use quant_core::bus::EventBus;
+use tokio::task::JoinHandle;
+
+/// 后台任务存活检查：任务意外退出视为不健康
+pub(crate) fn task_health(name: &str, task: &Option<JoinHandle<()>>) -> Health {
+    match task {
+        None => Health::Starting,
+        Some(handle) if handle.is_finished() => {
+            Health::Unhealthy(format!("{} task exited unexpectedly", name))
+        }
+        Some(_) => Health::Healthy,
+    }
+}
+
+/// 订阅者积压检查：出现丢弃或积压超过 1 秒时降级
+pub(crate) fn subscriber_health(bus: &EventBus, subscriber: &str) -> Health {
+    match bus.stats().into_iter().find(|s| s.name == subscriber) {
+        Some(stats) if stats.dropped > 0 || stats.lag_ms > 1000 => Health::Degraded(format!(
+            "{} dropped={} lag={}ms",
+            subscriber, stats.dropped, stats.lag_ms
+        )),
+        _ => Health::Healthy,
+    }
+}
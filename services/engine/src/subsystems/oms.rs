@@ -0,0 +1,455 @@
+use super::{subscriber_health, task_health};
+use crate::config::TradingMode;
+use crate::ha::Leadership;
+use crate::subsystem::{Health, Subsystem};
+use anyhow::Result;
+use async_trait::async_trait;
+use quant_core::bus::{BackpressurePolicy, EventBus, SubscribeOptions};
+use quant_core::enums::OrderStatus;
+use quant_core::event::{CancelRequest, Event, RiskEvent, RiskLevel, Topic};
+use quant_core::oms::Order;
+use quant_storage::cache::HotCache;
+use quant_storage::store::{FillStore, OrderStore};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+const SUBSCRIBER: &str = "oms";
+
+/// 订单管理子系统
+///
+/// 跟踪所有未终结订单；风控拦截的订单标记为 Rejected 并移出挂单，收到撤单请求的挂单标记为 Canceled；
+/// 模拟盘停机时撤销全部挂单并把撤单状态写回数据库。
+/// 实盘在交易所连接器接入前不能真正撤单，停机时只报告挂单、不改写状态。
+/// 配置了热点缓存时，订单事件同步写入 Redis，策略 / Agent 直接读缓存；
+/// 配置了成交存储时，成交回报逐笔落库 (TCA 的数据来源)。
+pub struct OmsSubsystem {
+    /// 订单存储 (None 表示不落库，只做内存撤单)
+    store: Option<Arc<dyn OrderStore>>,
+    fills: Option<Arc<dyn FillStore>>,
+    mode: TradingMode,
+    cache: Option<HotCache>,
+    leadership: Option<Leadership>,
+    open_orders: Arc<Mutex<HashMap<String, Order>>>,
+    bus: Option<EventBus>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl OmsSubsystem {
+    pub fn new(store: Option<Arc<dyn OrderStore>>) -> Self {
+        Self {
+            store,
+            fills: None,
+            mode: TradingMode::Paper,
+            cache: None,
+            leadership: None,
+            open_orders: Arc::new(Mutex::new(HashMap::new())),
+            bus: None,
+            task: None,
+        }
+    }
+
+    /// 运行模式：实盘停机时不在本地标记撤单
+    pub fn with_mode(mut self, mode: TradingMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_fills(mut self, fills: Arc<dyn FillStore>) -> Self {
+        self.fills = Some(fills);
+        self
+    }
+
+    pub fn with_cache(mut self, cache: HotCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// 高可用部署：失去主节点身份后不再写缓存，停机也不撤单 (挂单已交给新的主节点)
+    pub fn with_leadership(mut self, leadership: Leadership) -> Self {
+        self.leadership = Some(leadership);
+        self
+    }
+
+    /// 用数据库中的未完结订单重建缓存 (清掉上次进程遗留的陈旧条目)
+    async fn warm_cache(&self, cache: &HotCache) -> Result<()> {
+        let Some(store) = &self.store else {
+            return Ok(());
+        };
+        let strategies = cache
+            .replace_open_orders(&store.find_open_orders().await?)
+            .await?;
+        info!("🔥 Cached open orders for {} strategies", strategies);
+        Ok(())
+    }
+
+    /// 汇总需要撤销的订单：内存中跟踪的 + 数据库中遗留的 (上次异常退出)
+    async fn collect_open_orders(&self) -> Result<Vec<Order>> {
+        let mut orders = self.open_orders.lock().unwrap().clone();
+        if let Some(store) = &self.store {
+            for order in store.find_open_orders().await? {
+                orders.entry(order.uuid.clone()).or_insert(order);
+            }
+        }
+        Ok(orders.into_values().collect())
+    }
+}
+
+/// 把 OMS 本地终结的订单状态写回数据库
+async fn write_status(store: &dyn OrderStore, order: &Order) -> Result<()> {
+    store
+        .update_status(
+            Uuid::from_str(&order.uuid)?,
+            order.status,
+            None,
+            order.filled_quantity.0,
+            order.average_price.map(|p| p.0),
+            order.fee,
+        )
+        .await
+}
+
+#[async_trait]
+impl Subsystem for OmsSubsystem {
+    fn name(&self) -> &'static str {
+        "oms"
+    }
+
+    async fn start(&mut self, bus: &EventBus) -> Result<()> {
+        let sub = bus.subscribe(
+            SubscribeOptions::new(SUBSCRIBER)
+                .topics([Topic::Order, Topic::Fill, Topic::Risk])
+                .policy(BackpressurePolicy::Block),
+        );
+        if let Some(cache) = &self.cache {
+            if let Err(e) = self.warm_cache(cache).await {
+                warn!("Failed to warm order cache: {:?}", e);
+            }
+        }
+        let open_orders = self.open_orders.clone();
+        let store = self.store.clone();
+        let fills = self.fills.clone();
+        let cache = self.cache.clone();
+        let leadership = self.leadership.clone();
+        let mode = self.mode;
+        let publisher = bus.clone();
+        self.task = Some(tokio::spawn(async move {
+            // 被风控拦截的订单：之后到达的状态更新不再放回挂单
+            let mut rejected: HashSet<String> = HashSet::new();
+            while let Some(envelope) = sub.recv().await {
+                let order = match &envelope.event {
+                    Event::Order(order) => {
+                        let mut open = open_orders.lock().unwrap();
+                        if order.status.is_open() {
+                            if rejected.contains(&order.uuid) {
+                                continue;
+                            }
+                            open.insert(order.uuid.clone(), order.clone());
+                        } else {
+                            open.remove(&order.uuid);
+                            rejected.remove(&order.uuid);
+                        }
+                        order.clone()
+                    }
+                    Event::Risk(RiskEvent {
+                        level: RiskLevel::Reject,
+                        order_uuid: Some(uuid),
+                        rule,
+                        message,
+                        ..
+                    }) => {
+                        rejected.insert(uuid.clone());
+                        let Some(mut order) = open_orders.lock().unwrap().remove(uuid) else {
+                            continue;
+                        };
+                        warn!("🚫 Order {} rejected by {}: {}", uuid, rule, message);
+                        order.status = OrderStatus::Rejected;
+                        order
+                    }
+                    Event::Fill(fill) => {
+                        if leadership.as_ref().is_some_and(|l| !l.is_leader()) {
+                            warn!(
+                                "🔀 Not the leader anymore, skipping fill {} of order {}",
+                                fill.uuid, fill.order_uuid
+                            );
+                        } else if let Some(fills) = &fills {
+                            if let Err(e) = fills.insert(fill).await {
+                                error!("Failed to record fill {}: {:?}", fill.uuid, e);
+                            }
+                        }
+                        continue;
+                    }
+                    Event::CancelOrder(CancelRequest { order_uuid, reason }) => {
+                        let mut open = open_orders.lock().unwrap();
+                        if !open.contains_key(order_uuid) {
+                            continue;
+                        }
+                        // 与停机撤单一致：实盘没有交易所连接器时不在本地改写状态
+                        if mode == TradingMode::Live {
+                            warn!(
+                                "⚠️ Order {} is still working at the exchange, cancel it manually ({})",
+                                order_uuid, reason
+                            );
+                            continue;
+                        }
+                        let Some(mut order) = open.remove(order_uuid) else {
+                            continue;
+                        };
+                        info!("🧹 Canceling order {}: {}", order_uuid, reason);
+                        order.status = OrderStatus::Canceled;
+                        order
+                    }
+                    _ => continue,
+                };
+                if leadership.as_ref().is_some_and(|l| !l.is_leader()) {
+                    warn!(
+                        "🔀 Not the leader anymore, skipping writes for order {}",
+                        order.uuid
+                    );
+                    continue;
+                }
+                // 本地终结的订单 (风控拦截 / 撤单) 由 OMS 负责落库
+                let local = matches!(envelope.event, Event::Risk(_) | Event::CancelOrder(_));
+                if local {
+                    if let Some(store) = &store {
+                        if let Err(e) = write_status(store.as_ref(), &order).await {
+                            error!("Failed to update order {}: {:?}", order.uuid, e);
+                        }
+                    }
+                }
+                if let Some(cache) = &cache {
+                    if let Err(e) = cache.put_order(&order).await {
+                        warn!("Failed to cache order {}: {:?}", order.uuid, e);
+                    }
+                }
+                // 撤单结果通知风控等订阅者；OMS 自己也订阅订单主题，异步发布避免阻塞自身
+                if local && order.status == OrderStatus::Canceled {
+                    let publisher = publisher.clone();
+                    tokio::spawn(async move {
+                        publisher.publish(SUBSCRIBER, Event::Order(order)).await;
+                    });
+                }
+            }
+        }));
+        self.bus = Some(bus.clone());
+        Ok(())
+    }
+
+    fn health(&self) -> Health {
+        match (&self.bus, task_health(SUBSCRIBER, &self.task)) {
+            (Some(bus), Health::Healthy) => subscriber_health(bus, SUBSCRIBER),
+            (_, health) => health,
+        }
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+
+        if let Some(leadership) = self.leadership.as_ref().filter(|l| !l.is_leader()) {
+            warn!(
+                "🔀 Not the leader anymore ({}), leaving open orders to the new leader",
+                leadership.status().node
+            );
+            self.open_orders.lock().unwrap().clear();
+            return Ok(());
+        }
+
+        // TODO: 交易所连接器接入后，这里先调用交易所撤单接口，再更新本地状态
+        let orders = self.collect_open_orders().await?;
+        if self.mode == TradingMode::Live {
+            // 没有交易所连接器时本地写 Canceled 会让数据库与交易所不一致
+            if !orders.is_empty() {
+                warn!(
+                    "⚠️ {} open orders are still working at the exchange, cancel them manually:",
+                    orders.len()
+                );
+                for order in &orders {
+                    warn!(
+                        "  {} {} {:?} {} (filled {}) status {:?}",
+                        order.uuid,
+                        order.symbol,
+                        order.side,
+                        order.quantity,
+                        order.filled_quantity,
+                        order.status
+                    );
+                }
+            }
+            self.open_orders.lock().unwrap().clear();
+            return Ok(());
+        }
+        info!("🧹 Canceling {} open orders", orders.len());
+        for mut order in orders {
+            if let Some(store) = &self.store {
+                let uuid = Uuid::from_str(&order.uuid)?;
+                if let Err(e) = store
+                    .update_status(
+                        uuid,
+                        OrderStatus::Canceled,
+                        None,
+                        order.filled_quantity.0,
+                        order.average_price.map(|p| p.0),
+                        order.fee,
+                    )
+                    .await
+                {
+                    error!("Failed to cancel order {}: {:?}", order.uuid, e);
+                    continue;
+                }
+            }
+            order.status = OrderStatus::Canceled;
+            if let Some(bus) = &self.bus {
+                bus.publish(SUBSCRIBER, Event::Order(order)).await;
+            }
+        }
+        self.open_orders.lock().unwrap().clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RiskConfig;
+    use crate::subsystems::RiskSubsystem;
+    use chrono::Utc;
+    use quant_core::enums::{Exchange, Side};
+    use quant_core::oms::Fill;
+    use quant_core::primitive::{Price, Quantity};
+    use quant_storage::store::MemoryFillStore;
+    use rust_decimal::Decimal;
+    use std::time::Duration;
+
+    // =========================================================================
+    // 风控拦截的订单不进入挂单
+    // =========================================================================
+    #[tokio::test]
+    async fn test_risk_rejected_order_never_stays_open() {
+        let bus = EventBus::new();
+        let mut risk = RiskSubsystem::new(RiskConfig {
+            max_order_quantity: Some(Decimal::ONE),
+            ..Default::default()
+        });
+        let mut oms = OmsSubsystem::new(None);
+        risk.start(&bus).await.unwrap();
+        oms.start(&bus).await.unwrap();
+
+        let big = Order::new_market(
+            "BTC/USDT",
+            Exchange::Binance,
+            None,
+            Side::Buy,
+            Quantity(Decimal::from(5)),
+        );
+        let small = Order::new_market(
+            "BTC/USDT",
+            Exchange::Binance,
+            None,
+            Side::Buy,
+            Quantity(Decimal::new(5, 1)),
+        );
+        bus.publish("test", Event::Order(big.clone())).await;
+        bus.publish("test", Event::Order(small.clone())).await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // 被拦截订单的后续状态更新也不能把它放回挂单
+        let mut update = big.clone();
+        update.status = OrderStatus::New;
+        bus.publish("test", Event::Order(update)).await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        {
+            let open = oms.open_orders.lock().unwrap();
+            assert!(
+                !open.contains_key(&big.uuid),
+                "rejected order must leave the book"
+            );
+            assert!(open.contains_key(&small.uuid));
+        }
+        oms.shutdown().await.unwrap();
+        risk.shutdown().await.unwrap();
+    }
+
+    // =========================================================================
+    // 成交回报落库
+    // =========================================================================
+    #[tokio::test]
+    async fn test_fills_are_recorded() {
+        let bus = EventBus::new();
+        let fills = Arc::new(MemoryFillStore::new());
+        let mut oms = OmsSubsystem::new(None).with_fills(fills.clone());
+        oms.start(&bus).await.unwrap();
+
+        let order = Order::new_market(
+            "BTC/USDT",
+            Exchange::Binance,
+            None,
+            Side::Buy,
+            Quantity(Decimal::ONE),
+        );
+        let fill = Fill::new(
+            &order,
+            Price(Decimal::from(100)),
+            Quantity(Decimal::ONE),
+            Decimal::ZERO,
+            Utc::now(),
+        );
+        bus.publish("test", Event::Fill(fill.clone())).await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let recorded = fills
+            .find_by_order(Uuid::from_str(&order.uuid).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].uuid, fill.uuid);
+        oms.shutdown().await.unwrap();
+    }
+
+    // =========================================================================
+    // 撤单请求
+    // =========================================================================
+    #[tokio::test]
+    async fn test_cancel_request_cancels_open_order() {
+        let bus = EventBus::new();
+        let mut oms = OmsSubsystem::new(None);
+        oms.start(&bus).await.unwrap();
+        let orders = bus.subscribe(SubscribeOptions::new("observer").topics([Topic::Order]));
+
+        let order = Order::new_market(
+            "BTC/USDT",
+            Exchange::Binance,
+            None,
+            Side::Buy,
+            Quantity(Decimal::ONE),
+        );
+        bus.publish("test", Event::Order(order.clone())).await;
+        bus.publish(
+            "test",
+            Event::CancelOrder(CancelRequest {
+                order_uuid: order.uuid.clone(),
+                reason: "signal expired".to_string(),
+            }),
+        )
+        .await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert!(oms.open_orders.lock().unwrap().is_empty());
+        // 撤单结果重新发布到订单主题
+        let mut canceled = None;
+        while let Some(envelope) = orders.try_recv() {
+            if let Event::Order(o) = &envelope.event {
+                if o.status == OrderStatus::Canceled {
+                    canceled = Some(o.uuid.clone());
+                }
+            }
+        }
+        assert_eq!(canceled, Some(order.uuid));
+        oms.shutdown().await.unwrap();
+    }
+}
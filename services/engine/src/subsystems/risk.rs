@@ -0,0 +1,157 @@
+use super::{subscriber_health, task_health};
+use crate::config::RiskConfig;
+use crate::subsystem::{Health, Subsystem};
+use anyhow::Result;
+use async_trait::async_trait;
+use quant_core::bus::{BackpressurePolicy, EventBus, SubscribeOptions};
+use quant_core::enums::OrderStatus;
+use quant_core::event::{Event, RiskEvent, RiskLevel, Topic};
+use quant_core::oms::Order;
+use quant_core::strategy::Signal;
+use quant_execution::signal::PreTradeCheck;
+use std::collections::HashSet;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+const SUBSCRIBER: &str = "risk";
+
+/// 风控子系统
+///
+/// 对新建订单做事前检查，超限时发布带订单 UUID 的 `RiskLevel::Reject` 事件：
+/// OMS 据此把订单标记为 Rejected 并移出挂单，信号子系统把父信号转入 RiskRejected。
+pub struct RiskSubsystem {
+    limits: RiskConfig,
+    bus: Option<EventBus>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl RiskSubsystem {
+    pub fn new(limits: RiskConfig) -> Self {
+        Self {
+            limits,
+            bus: None,
+            task: None,
+        }
+    }
+}
+
+/// 检查单笔订单，返回触发的规则和原因
+fn check_order(
+    limits: &RiskConfig,
+    order: &Order,
+    open_orders: usize,
+) -> Option<(&'static str, String)> {
+    if let (Some(max), Some(price)) = (limits.max_order_notional, order.price) {
+        let notional = price * order.quantity;
+        if notional > max {
+            return Some((
+                "max_order_notional",
+                format!("order notional {} exceeds {}", notional, max),
+            ));
+        }
+    }
+    if let Some(max) = limits.max_order_quantity {
+        if order.quantity.0 > max {
+            return Some((
+                "max_order_quantity",
+                format!("order quantity {} exceeds {}", order.quantity, max),
+            ));
+        }
+    }
+    if let Some(max) = limits.max_open_orders {
+        if open_orders >= max {
+            return Some((
+                "max_open_orders",
+                format!("{} open orders, limit is {}", open_orders, max),
+            ));
+        }
+    }
+    None
+}
+
+/// 把单笔订单限额作为信号管道的事前检查 (信号直接进入 RiskRejected，不会发出订单)
+///
+/// 挂单数量上限依赖实时挂单，仍由风控子系统在订单事件上检查。
+pub struct OrderLimits(pub RiskConfig);
+
+impl PreTradeCheck for OrderLimits {
+    fn name(&self) -> &str {
+        "order_limits"
+    }
+
+    fn check(&self, _signal: &Signal, order: &Order) -> std::result::Result<(), String> {
+        let limits = RiskConfig {
+            max_open_orders: None,
+            ..self.0.clone()
+        };
+        match check_order(&limits, order, 0) {
+            Some((rule, message)) => Err(format!("{} ({})", message, rule)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl Subsystem for RiskSubsystem {
+    fn name(&self) -> &'static str {
+        "risk"
+    }
+
+    async fn start(&mut self, bus: &EventBus) -> Result<()> {
+        // 风控不能漏看订单，使用阻塞背压
+        let sub = bus.subscribe(
+            SubscribeOptions::new(SUBSCRIBER)
+                .topics([Topic::Order])
+                .policy(BackpressurePolicy::Block),
+        );
+        let limits = self.limits.clone();
+        let publisher = bus.clone();
+        self.task = Some(tokio::spawn(async move {
+            let mut open: HashSet<String> = HashSet::new();
+            while let Some(envelope) = sub.recv().await {
+                let Event::Order(order) = &envelope.event else {
+                    continue;
+                };
+                if order.status == OrderStatus::Created && !open.contains(&order.uuid) {
+                    if let Some((rule, message)) = check_order(&limits, order, open.len()) {
+                        warn!("🚫 Risk reject order {}: {}", order.uuid, message);
+                        publisher
+                            .publish(
+                                SUBSCRIBER,
+                                Event::Risk(RiskEvent {
+                                    level: RiskLevel::Reject,
+                                    rule: rule.to_string(),
+                                    strategy_uuid: order.strategy_uuid.clone(),
+                                    order_uuid: Some(order.uuid.clone()),
+                                    message,
+                                }),
+                            )
+                            .await;
+                        continue;
+                    }
+                }
+                if order.status.is_open() {
+                    open.insert(order.uuid.clone());
+                } else {
+                    open.remove(&order.uuid);
+                }
+            }
+        }));
+        self.bus = Some(bus.clone());
+        Ok(())
+    }
+
+    fn health(&self) -> Health {
+        match (&self.bus, task_health(SUBSCRIBER, &self.task)) {
+            (Some(bus), Health::Healthy) => subscriber_health(bus, SUBSCRIBER),
+            (_, health) => health,
+        }
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,285 @@
+use super::{subscriber_health, task_health};
+use crate::ha::Leadership;
+use crate::subsystem::{Health, Subsystem};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use quant_core::bus::{BackpressurePolicy, EventBus, SubscribeOptions};
+use quant_core::event::{CancelRequest, Event, RiskEvent, RiskLevel, Topic};
+use quant_core::strategy::Signal;
+use quant_execution::signal::{SignalOutcome, SignalProcessor};
+use quant_execution::sizing::PortfolioSizer;
+use quant_storage::store::AccountStore;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::interval;
+use tracing::{debug, error, info, warn};
+
+const SUBSCRIBER: &str = "signal";
+
+/// 信号子系统
+///
+/// 消费策略 / Agent 发布的信号，定量后生成订单发布到总线 (风控 / OMS 接手)；
+/// 按成交回报推进信号生命周期，订单被运行时风控拦截时父信号转入 RiskRejected；
+/// 定期让超时未成交的信号过期，并请求 OMS 撤销其子订单。
+pub struct SignalSubsystem {
+    processor: Arc<SignalProcessor>,
+    expire_interval: Duration,
+    sizing: Option<Arc<Sizing>>,
+    leadership: Option<Leadership>,
+    bus: Option<EventBus>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl SignalSubsystem {
+    pub fn new(processor: SignalProcessor, expire_interval: Duration) -> Self {
+        Self {
+            processor: Arc::new(processor),
+            expire_interval,
+            sizing: None,
+            leadership: None,
+            bus: None,
+            task: None,
+        }
+    }
+
+    /// 维护定量器的行情和账户数据 (定量器需已通过 `with_sizer` 交给处理器)
+    pub fn with_sizing(
+        mut self,
+        sizer: PortfolioSizer,
+        accounts: Option<Arc<dyn AccountStore>>,
+        account_names: Vec<String>,
+    ) -> Self {
+        self.sizing = Some(Arc::new(Sizing {
+            sizer,
+            accounts,
+            account_names,
+        }));
+        self
+    }
+
+    /// 高可用部署：失去主节点身份后不再把信号转成订单
+    pub fn with_leadership(mut self, leadership: Leadership) -> Self {
+        self.leadership = Some(leadership);
+        self
+    }
+}
+
+/// 信号定量器及其账户数据来源
+struct Sizing {
+    sizer: PortfolioSizer,
+    accounts: Option<Arc<dyn AccountStore>>,
+    account_names: Vec<String>,
+}
+
+impl Sizing {
+    /// 重新加载全部账户的余额和持仓 (多账户合并计算净值)
+    async fn refresh(&self) -> Result<()> {
+        let Some(store) = &self.accounts else {
+            return Ok(());
+        };
+        let mut assets = Vec::new();
+        let mut positions = Vec::new();
+        for name in &self.account_names {
+            assets.extend(store.find_assets_by_account(name).await?);
+            positions.extend(store.find_positions_by_account(name).await?);
+        }
+        self.sizer.set_account(assets, positions);
+        Ok(())
+    }
+}
+
+/// 处理一个信号并把结果发布到总线
+async fn handle_signal(
+    processor: &SignalProcessor,
+    leadership: Option<&Leadership>,
+    bus: &EventBus,
+    signal: Signal,
+) {
+    // 租约丢失到停机之间不再下单，交易已交给新的主节点
+    if leadership.is_some_and(|l| !l.is_leader()) {
+        warn!("🔀 Not the leader anymore, dropping signal {}", signal.uuid);
+        return;
+    }
+    let signal_uuid = signal.uuid.clone();
+    let strategy_uuid = signal.strategy_uuid.clone();
+    match processor.process(signal).await {
+        Ok(SignalOutcome::Submitted(order)) => {
+            info!(
+                "📨 Signal {} -> order {} ({} {} {})",
+                signal_uuid, order.uuid, order.side, order.quantity, order.symbol
+            );
+            bus.publish(SUBSCRIBER, Event::Order(*order)).await;
+        }
+        Ok(SignalOutcome::Rejected(message)) => {
+            warn!("🚫 Signal {} rejected: {}", signal_uuid, message);
+            // 本子系统也订阅风控事件：异步发布，避免队列写满时阻塞自己的消费任务
+            let bus = bus.clone();
+            tokio::spawn(async move {
+                bus.publish(
+                    SUBSCRIBER,
+                    Event::Risk(RiskEvent {
+                        level: RiskLevel::Reject,
+                        rule: "signal".to_string(),
+                        strategy_uuid: Some(strategy_uuid),
+                        order_uuid: None,
+                        message,
+                    }),
+                )
+                .await;
+            });
+        }
+        Ok(SignalOutcome::Expired) => warn!("⌛ Signal {} expired before processing", signal_uuid),
+        Err(e) => error!("Failed to process signal {}: {:?}", signal_uuid, e),
+    }
+}
+
+async fn handle_event(
+    processor: &SignalProcessor,
+    sizing: Option<&Sizing>,
+    leadership: Option<&Leadership>,
+    bus: &EventBus,
+    event: &Event,
+) {
+    match event {
+        Event::Signal(signal) => handle_signal(processor, leadership, bus, signal.clone()).await,
+        Event::Fill(fill) => {
+            match processor.on_fill(fill).await {
+                Ok(Some(signal)) => debug!(
+                    "Signal {} {}: filled {} / {:?}",
+                    signal.uuid, signal.status, signal.filled_quantity, signal.quantity
+                ),
+                Ok(None) => {}
+                Err(e) => error!("Failed to apply fill {} to signal: {:?}", fill.uuid, e),
+            }
+            if let Some(sizing) = sizing {
+                if let Err(e) = sizing.refresh().await {
+                    warn!("Failed to refresh accounts for sizing: {:?}", e);
+                }
+            }
+        }
+        Event::Risk(RiskEvent {
+            level: RiskLevel::Reject,
+            order_uuid: Some(order_uuid),
+            rule,
+            message,
+            ..
+        }) => match processor
+            .on_order_rejected(order_uuid, format!("{}: {}", rule, message))
+            .await
+        {
+            Ok(Some(signal)) => warn!(
+                "🚫 Signal {} rejected with order {}: {}",
+                signal.uuid, order_uuid, message
+            ),
+            Ok(None) => {}
+            Err(e) => error!("Failed to reject signal of order {}: {:?}", order_uuid, e),
+        },
+        Event::MarketData(bar) => {
+            if let Some(sizing) = sizing {
+                sizing.sizer.on_bar(bar);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[async_trait]
+impl Subsystem for SignalSubsystem {
+    fn name(&self) -> &'static str {
+        "signal"
+    }
+
+    async fn start(&mut self, bus: &EventBus) -> Result<()> {
+        // 信号、成交和风控拦截都不能丢，使用阻塞背压；定量需要行情
+        let mut topics = vec![Topic::Signal, Topic::Fill, Topic::Risk];
+        if let Some(sizing) = &self.sizing {
+            topics.push(Topic::MarketData);
+            sizing.refresh().await?;
+        }
+        let sub = bus.subscribe(
+            SubscribeOptions::new(SUBSCRIBER)
+                .topics(topics)
+                .policy(BackpressurePolicy::Block),
+        );
+        // 上次运行遗留的信号：已提交的继续跟踪成交，未处理的重新处理
+        let pending = self.processor.recover().await?;
+        if self.processor.tracked() > 0 || !pending.is_empty() {
+            info!(
+                "📨 Recovered {} submitted signals, {} pending",
+                self.processor.tracked(),
+                pending.len()
+            );
+        }
+
+        let processor = self.processor.clone();
+        let sizing = self.sizing.clone();
+        let leadership = self.leadership.clone();
+        let publisher = bus.clone();
+        let mut ticker = interval(self.expire_interval);
+        self.task = Some(tokio::spawn(async move {
+            for signal in pending {
+                handle_signal(&processor, leadership.as_ref(), &publisher, signal).await;
+            }
+            loop {
+                tokio::select! {
+                    envelope = sub.recv() => {
+                        let Some(envelope) = envelope else {
+                            break;
+                        };
+                        handle_event(
+                            &processor,
+                            sizing.as_deref(),
+                            leadership.as_ref(),
+                            &publisher,
+                            &envelope.event,
+                        )
+                        .await;
+                    }
+                    _ = ticker.tick() => match processor.expire_due(Utc::now()).await {
+                        Ok(expired) => {
+                            for signal in expired {
+                                warn!(
+                                    "⌛ Signal {} expired ({}), canceling orders {:?}",
+                                    signal.uuid,
+                                    signal.status_reason.as_deref().unwrap_or_default(),
+                                    signal.order_uuids
+                                );
+                                // OMS 只撤销仍在挂单中的订单，已终结的请求会被忽略
+                                for order_uuid in &signal.order_uuids {
+                                    publisher
+                                        .publish(
+                                            SUBSCRIBER,
+                                            Event::CancelOrder(CancelRequest {
+                                                order_uuid: order_uuid.clone(),
+                                                reason: format!("signal {} expired", signal.uuid),
+                                            }),
+                                        )
+                                        .await;
+                                }
+                            }
+                        }
+                        Err(e) => error!("Failed to expire signals: {:?}", e),
+                    },
+                }
+            }
+        }));
+        self.bus = Some(bus.clone());
+        Ok(())
+    }
+
+    fn health(&self) -> Health {
+        match (&self.bus, task_health(SUBSCRIBER, &self.task)) {
+            (Some(bus), Health::Healthy) => subscriber_health(bus, SUBSCRIBER),
+            (_, health) => health,
+        }
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+        Ok(())
+    }
+}
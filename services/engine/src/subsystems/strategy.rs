@@ -0,0 +1,240 @@
+use super::subscriber_health;
+use crate::config::StrategyConfig;
+use crate::ha::Leadership;
+use crate::subsystem::{Health, Subsystem};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use quant_core::bus::{BackpressurePolicy, EventBus, SubscribeOptions};
+use quant_core::enums::{Side, StatusActor, StrategyStatus};
+use quant_core::event::{Event, Topic};
+use quant_core::primitive::{CurrencyPair, Quantity};
+use quant_core::strategy::Signal;
+use quant_storage::store::StrategyStore;
+use quant_strategy::registry::StrategyRegistry;
+use quant_strategy::traits::StrategyContext;
+use serde_json::json;
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+/// 引擎落盘的运行状态结构版本 (结构变化时递增，并为旧版本提供 `StateMigration`)
+const STATE_SCHEMA_VERSION: i32 = 1;
+
+/// 单个策略实例的运行时
+struct StrategyRunner {
+    config: StrategyConfig,
+    subscriber: String,
+    processed: Arc<AtomicU64>,
+    task: JoinHandle<()>,
+}
+
+/// 策略子系统：为每个启用的策略实例构建策略对象并拉起一个事件消费任务
+///
+/// 行情驱动 `on_bar`，产出的下单意图以 `Signal` 事件发布 (由信号子系统定量、风控和下单)。
+pub struct StrategySubsystem {
+    configs: Vec<StrategyConfig>,
+    /// 策略存储 (None 表示停机时不落盘)
+    store: Option<Arc<dyn StrategyStore>>,
+    leadership: Option<Leadership>,
+    bus: Option<EventBus>,
+    runners: Vec<StrategyRunner>,
+}
+
+impl StrategySubsystem {
+    pub fn new(configs: Vec<StrategyConfig>, store: Option<Arc<dyn StrategyStore>>) -> Self {
+        Self {
+            configs: configs.into_iter().filter(|s| s.enabled).collect(),
+            store,
+            leadership: None,
+            bus: None,
+            runners: Vec::new(),
+        }
+    }
+
+    /// 高可用部署：失去主节点身份后停机不落盘 (避免覆盖新主节点的状态)
+    pub fn with_leadership(mut self, leadership: Leadership) -> Self {
+        self.leadership = Some(leadership);
+        self
+    }
+
+    /// 停机时落盘运行状态并把策略标记为 Stopped (记入审计)
+    async fn persist(store: &dyn StrategyStore, runner: &StrategyRunner) -> Result<()> {
+        let Some(uuid) = &runner.config.uuid else {
+            return Ok(());
+        };
+        let uuid = Uuid::from_str(uuid)?;
+        let state = json!({
+            "events_processed": runner.processed.load(Ordering::Relaxed),
+            "stopped_at": Utc::now(),
+        });
+        store.save_state(uuid, STATE_SCHEMA_VERSION, &state).await?;
+        // 未登记 / 已经停止的策略不改状态
+        let Some(strategy) = store.find_by_uuid(uuid).await? else {
+            return Ok(());
+        };
+        if strategy.status.can_transition_to(StrategyStatus::Stopped) {
+            store
+                .update_status(
+                    uuid,
+                    StrategyStatus::Stopped,
+                    StatusActor::System,
+                    "engine shutdown",
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Subsystem for StrategySubsystem {
+    fn name(&self) -> &'static str {
+        "strategy"
+    }
+
+    async fn start(&mut self, bus: &EventBus) -> Result<()> {
+        let registry = StrategyRegistry::with_builtins();
+        for config in &self.configs {
+            let mut strategy = registry
+                .create(&config.class_name, &config.params)
+                .with_context(|| format!("Strategy {} cannot be built", config.name))?;
+            let subscriber = format!("strategy:{}", config.name);
+            let sub = bus.subscribe(
+                SubscribeOptions::new(subscriber.clone())
+                    .topics([Topic::MarketData, Topic::Fill, Topic::Timer])
+                    .policy(BackpressurePolicy::DropOldest),
+            );
+            let symbols: HashSet<String> = config.symbols.iter().cloned().collect();
+            let processed = Arc::new(AtomicU64::new(0));
+            let counter = processed.clone();
+            let name = config.name.clone();
+            // 未登记到数据库的策略用实例名归属信号
+            let strategy_uuid = config.uuid.clone().unwrap_or_else(|| config.name.clone());
+            let publisher = bus.clone();
+
+            let task = tokio::spawn(async move {
+                // 实盘资金由信号子系统定量，策略上下文只维护按成交回报累计的持仓
+                let mut ctx = StrategyContext::default();
+                while let Some(envelope) = sub.recv().await {
+                    match &envelope.event {
+                        // 只处理本策略交易标的的行情
+                        Event::MarketData(bar) => {
+                            if !symbols.contains(&bar.symbol.to_string()) {
+                                continue;
+                            }
+                            ctx.date = Some(bar.start_time);
+                            strategy.on_bar(&mut ctx, bar);
+                        }
+                        // 只累计归属本策略的成交
+                        Event::Fill(fill) => {
+                            if fill.strategy_uuid.as_deref() != Some(strategy_uuid.as_str()) {
+                                continue;
+                            }
+                            let delta = match fill.side {
+                                Side::Buy => fill.quantity.0,
+                                Side::Sell => -fill.quantity.0,
+                            };
+                            *ctx.positions.entry(fill.symbol.to_string()).or_default() += delta;
+                        }
+                        _ => {}
+                    }
+                    counter.fetch_add(1, Ordering::Relaxed);
+                    debug!(
+                        "💡 Strategy [{}] received #{} {} from {}",
+                        name,
+                        envelope.seq,
+                        envelope.topic(),
+                        envelope.source
+                    );
+
+                    for intent in ctx.take_orders() {
+                        if CurrencyPair::from_str(&intent.symbol).is_err() {
+                            warn!(
+                                "Strategy [{}] emitted an invalid symbol {}, order dropped",
+                                name, intent.symbol
+                            );
+                            continue;
+                        }
+                        let signal = Signal::new_market(
+                            strategy_uuid.clone(),
+                            intent.symbol,
+                            intent.side,
+                            Quantity(intent.quantity),
+                            intent.reason,
+                        );
+                        info!(
+                            "💡 Strategy [{}] signal {} {:?} {} {}",
+                            name, signal.uuid, signal.side, signal.symbol, intent.quantity
+                        );
+                        publisher
+                            .publish(format!("strategy:{}", name), Event::Signal(signal))
+                            .await;
+                    }
+                }
+            });
+
+            info!(
+                "🧠 Strategy [{}] ({}) started on account {} for {:?}",
+                config.name, config.class_name, config.account, config.symbols
+            );
+            self.runners.push(StrategyRunner {
+                config: config.clone(),
+                subscriber,
+                processed,
+                task,
+            });
+        }
+        self.bus = Some(bus.clone());
+        Ok(())
+    }
+
+    fn health(&self) -> Health {
+        let Some(bus) = &self.bus else {
+            return Health::Starting;
+        };
+        for runner in &self.runners {
+            if runner.task.is_finished() {
+                return Health::Unhealthy(format!(
+                    "strategy {} exited unexpectedly",
+                    runner.config.name
+                ));
+            }
+            let health = subscriber_health(bus, &runner.subscriber);
+            if health != Health::Healthy {
+                return health;
+            }
+        }
+        Health::Healthy
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        let store = match &self.leadership {
+            Some(leadership) if !leadership.is_leader() => {
+                warn!(
+                    "🔀 Not the leader anymore ({}), skipping strategy state persistence",
+                    leadership.status().node
+                );
+                None
+            }
+            _ => self.store.as_ref(),
+        };
+        for runner in &self.runners {
+            runner.task.abort();
+            if let Some(store) = store {
+                if let Err(e) = Self::persist(store.as_ref(), runner).await {
+                    error!(
+                        "Failed to persist strategy [{}] state: {:?}",
+                        runner.config.name, e
+                    );
+                }
+            }
+        }
+        self.runners.clear();
+        Ok(())
+    }
+}